@@ -5,15 +5,24 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Template;
 use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
 use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash};
 
+// Withdrawals are not benchmarked here, as they need a proof accepted by the runtime's verifier.
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	deposit {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		T::Currency::make_free_balance_be(&caller, Bounded::max_value());
+		// Clear the first byte, so that the commitment is a field element.
+		let mut commitment = T::Hashing::hash_of(&0u32);
+		commitment.as_mut()[0] = 0;
+	}: _(RawOrigin::Signed(caller), commitment)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(
+			T::Currency::free_balance(&Pallet::<T>::account_id()),
+			T::Denomination::get()
+		);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -1,8 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Edit this file to define custom logic or remove it if it is not needed.
-/// Learn more about FRAME and the core library of Substrate FRAME pallets:
-/// <https://docs.substrate.io/reference/frame-pallets/>
+//! A minimal shielded pool.
+//!
+//! Accounts deposit a fixed [`Config::Denomination`] together with a note commitment, which is
+//! appended to an incremental Merkle tree hashed with Poseidon. Anyone knowing the secrets of a
+//! note can later withdraw the denomination to any account with a Groth16 proof that:
+//!
+//! - the commitment of the note is in the tree under a recent root,
+//! - the nullifier hash is derived from the secrets of the note,
+//! - the proof is bound to the recipient.
+//!
+//! The public inputs of the proof are, in this order, the root, the nullifier hash and the
+//! Keccak-256 hash of the SCALE encoded recipient shifted right by 8 bits. Every nullifier hash is
+//! accepted once, so each note is withdrawn once, without revealing which deposit it was.
+//!
+//! Proofs are verified by `pallet-groth16-verifier` with the BN254 host functions, and the tree
+//! is kept by `pallet-incremental-merkle-tree`.
 pub use pallet::*;
 
 #[cfg(test)]
@@ -16,8 +29,20 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use codec::Encode;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement},
+		PalletId,
+	};
 	use frame_system::pallet_prelude::*;
+	use pallet_groth16_verifier::{hash_to_public_input, public_input, Proof, ProofVerifier};
+	use pallet_incremental_merkle_tree::AppendOnlyTree;
+	use sp_runtime::traits::AccountIdConversion;
+	use sp_std::prelude::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
@@ -28,75 +53,124 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency deposited in the pool.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The identifier of the account holding the deposits.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The amount of every deposit and withdrawal.
+		#[pallet::constant]
+		type Denomination: Get<BalanceOf<Self>>;
+
+		/// The tree of note commitments, which must be hashed with Poseidon.
+		type Tree: AppendOnlyTree<Self::Hash>;
+
+		/// The identifier of a verifying key of the [`Config::Verifier`].
+		type VerifyingKeyId: Parameter;
+
+		/// The verifier of the withdrawal proofs.
+		type Verifier: ProofVerifier<Self::VerifyingKeyId>;
+
+		/// The verifying key of the withdrawal circuit.
+		#[pallet::constant]
+		type VerifyingKey: Get<Self::VerifyingKeyId>;
 	}
 
-	// The pallet's runtime storage items.
-	// https://docs.substrate.io/main-docs/build/runtime-storage/
+	// The nullifier hashes of the withdrawn notes.
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
-	// Learn more about declaring storage items:
-	// https://docs.substrate.io/main-docs/build/runtime-storage/#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	#[pallet::getter(fn is_spent)]
+	pub type Nullifiers<T: Config> = StorageMap<_, Identity, T::Hash, bool, ValueQuery>;
 
-	// Pallets use events to inform users when important changes are made.
-	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored { something: u32, who: T::AccountId },
+		/// A note was deposited and its commitment inserted in the tree at `index`.
+		Deposited { commitment: T::Hash, index: u64 },
+		/// A note was withdrawn to `recipient`.
+		Withdrawn { recipient: T::AccountId, nullifier_hash: T::Hash },
 	}
 
-	// Errors inform users that something went wrong.
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Error names should be descriptive.
-		NoneValue,
-		/// Errors should have helpful documentation associated with them.
-		StorageOverflow,
+		/// The commitment or nullifier hash is not the encoding of a field element.
+		InvalidFieldElement,
+		/// The root is not one of the recent roots of the tree.
+		UnknownRoot,
+		/// The note was already withdrawn.
+		NoteSpent,
 	}
 
-	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-	// These functions materialize as "extrinsics", which are often compared to transactions.
-	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://docs.substrate.io/main-docs/build/origins/
+		/// Deposit [`Config::Denomination`] with the note commitment `commitment`.
+		#[pallet::weight(
+			Weight::from_ref_time(10_000)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+				.saturating_add(T::Tree::insert_leaf_weight())
+		)]
+		pub fn deposit(origin: OriginFor<T>, commitment: T::Hash) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(public_input(commitment.as_ref()).is_some(), Error::<T>::InvalidFieldElement);
 
-			// Update storage.
-			<Something<T>>::put(something);
+			T::Currency::transfer(
+				&who,
+				&Self::account_id(),
+				T::Denomination::get(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			let index = T::Tree::insert_leaf(commitment)?;
 
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored { something, who });
-			// Return a successful DispatchResultWithPostInfo
+			Self::deposit_event(Event::Deposited { commitment, index });
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match <Something<T>>::get() {
-				// Return an error if the value has not been set.
-				None => return Err(Error::<T>::NoneValue.into()),
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
-					Ok(())
-				},
-			}
+		/// Withdraw [`Config::Denomination`] to `recipient` with `proof` of a note in the tree
+		/// under `root` with the nullifier hash `nullifier_hash`.
+		///
+		/// The signer can be a relayer unrelated to the note.
+		#[pallet::weight(
+			Weight::from_ref_time(10_000)
+				.saturating_add(T::DbWeight::get().reads_writes(4, 3))
+				.saturating_add(T::Verifier::verify_proof_weight(3))
+		)]
+		pub fn withdraw(
+			origin: OriginFor<T>,
+			root: T::Hash,
+			nullifier_hash: T::Hash,
+			recipient: T::AccountId,
+			proof: Box<Proof>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(T::Tree::is_known_root(&root), Error::<T>::UnknownRoot);
+			ensure!(!Self::is_spent(nullifier_hash), Error::<T>::NoteSpent);
+
+			let public_inputs = [
+				public_input(root.as_ref()).ok_or(Error::<T>::InvalidFieldElement)?,
+				public_input(nullifier_hash.as_ref()).ok_or(Error::<T>::InvalidFieldElement)?,
+				hash_to_public_input(&recipient.encode()),
+			];
+			T::Verifier::verify_proof(&T::VerifyingKey::get(), &proof, &public_inputs)?;
+
+			<Nullifiers<T>>::insert(nullifier_hash, true);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&recipient,
+				T::Denomination::get(),
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Self::deposit_event(Event::Withdrawn { recipient, nullifier_hash });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the deposits.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
 		}
 	}
 }
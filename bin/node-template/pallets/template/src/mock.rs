@@ -1,11 +1,24 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use ark_bn254::G1Affine;
+use ark_ec::AffineRepr;
+use frame_support::{
+	dispatch::DispatchResult,
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Hooks},
+	weights::Weight,
+	PalletId,
+};
 use frame_system as system;
+use pallet_groth16_verifier::{Proof, ProofVerifier, PublicInput};
+use sp_ark_scale::ArkScale;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
 };
+use sp_smt::{FieldNodeHasher, Poseidon};
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,6 +31,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
+		MerkleTree: pallet_incremental_merkle_tree,
 		TemplateModule: pallet_template,
 	}
 );
@@ -40,7 +55,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +64,86 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_incremental_merkle_tree::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Hasher = FieldNodeHasher<Poseidon>;
+	type Depth = ConstU32<3>;
+	type MaxLeavesPerBlock = ConstU32<3>;
+	type RootHistorySize = ConstU32<2>;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	// The public inputs of the proofs checked by `MockVerifier`.
+	pub static VERIFIED_INPUTS: RefCell<Vec<Vec<PublicInput>>> = RefCell::new(Vec::new());
+}
+
+// The proof accepted by `MockVerifier`.
+pub fn valid_proof() -> Box<Proof> {
+	Box::new(ArkScale(ark_groth16::Proof {
+		a: G1Affine::generator(),
+		b: Default::default(),
+		c: Default::default(),
+	}))
+}
+
+// A verifier accepting `valid_proof` for any public inputs, and recording them.
+pub struct MockVerifier;
+
+impl ProofVerifier<u32> for MockVerifier {
+	fn verify_proof(_key_id: &u32, proof: &Proof, public_inputs: &[PublicInput]) -> DispatchResult {
+		VERIFIED_INPUTS.with(|inputs| inputs.borrow_mut().push(public_inputs.to_vec()));
+		if *proof != *valid_proof() {
+			return Err(DispatchError::Other("invalid proof"))
+		}
+		Ok(())
+	}
+
+	fn verify_proof_weight(_public_inputs: u32) -> Weight {
+		Weight::zero()
+	}
+}
+
+parameter_types! {
+	pub const ShieldedPoolPalletId: PalletId = PalletId(*b"py/shpol");
+}
+
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PalletId = ShieldedPoolPalletId;
+	type Denomination = ConstU64<10>;
+	type Tree = MerkleTree;
+	type VerifyingKeyId = u32;
+	type Verifier = MockVerifier;
+	type VerifyingKey = ConstU32<0>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// Finalize the current block, inserting the deposited commitments, and start the next one.
+pub fn next_block() {
+	MerkleTree::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
 }
@@ -1,23 +1,110 @@
-use crate::{mock::*, Error};
+use crate::{mock::*, Error, Event};
+use codec::Encode;
 use frame_support::{assert_noop, assert_ok};
+use pallet_groth16_verifier::hash_to_public_input;
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+fn commitment(i: u64) -> H256 {
+	H256::from_low_u64_be(i + 1)
+}
+
+fn deposit_note(who: u64, i: u64) {
+	assert_ok!(TemplateModule::deposit(RuntimeOrigin::signed(who), commitment(i)));
+	System::assert_last_event(Event::Deposited { commitment: commitment(i), index: i }.into());
+}
+
+#[test]
+fn deposits_insert_commitments() {
+	new_test_ext().execute_with(|| {
+		deposit_note(1, 0);
+		deposit_note(2, 1);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::free_balance(TemplateModule::account_id()), 20);
+
+		next_block();
+		assert_eq!(MerkleTree::tree().len(), 2);
+
+		// Commitments must be field elements.
+		assert_noop!(
+			TemplateModule::deposit(RuntimeOrigin::signed(1), H256::repeat_byte(0xff)),
+			Error::<Test>::InvalidFieldElement
+		);
+		// The depositor must be able to pay the denomination.
+		assert_noop!(
+			TemplateModule::deposit(RuntimeOrigin::signed(3), commitment(2)),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn withdrawals_spend_notes() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		deposit_note(1, 0);
+		next_block();
+		let root = MerkleTree::root();
+		let nullifier_hash = H256::from_low_u64_be(0x4e);
+
+		// Anyone can submit the withdrawal to the recipient.
+		assert_ok!(TemplateModule::withdraw(
+			RuntimeOrigin::signed(2),
+			root,
+			nullifier_hash,
+			3,
+			valid_proof()
+		));
+		System::assert_last_event(Event::Withdrawn { recipient: 3, nullifier_hash }.into());
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(TemplateModule::account_id()), 0);
+		assert!(TemplateModule::is_spent(nullifier_hash));
+
+		// The proof is bound to the root, the nullifier hash and the recipient.
+		let public_inputs = VERIFIED_INPUTS.with(|inputs| inputs.borrow().last().cloned().unwrap());
+		assert_eq!(
+			public_inputs[0].0,
+			pallet_groth16_verifier::public_input(root.as_bytes()).unwrap().0
+		);
+		assert_eq!(public_inputs[1].0, ark_bn254::Fr::from(0x4eu8));
+		assert_eq!(public_inputs[2], hash_to_public_input(&3u64.encode()));
+
+		// Notes are withdrawn once.
+		assert_noop!(
+			TemplateModule::withdraw(
+				RuntimeOrigin::signed(2),
+				root,
+				nullifier_hash,
+				3,
+				valid_proof()
+			),
+			Error::<Test>::NoteSpent
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn withdrawals_are_checked() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		deposit_note(1, 0);
+		next_block();
+		let root = MerkleTree::root();
+		let withdraw = |root, nullifier_hash, proof| {
+			TemplateModule::withdraw(RuntimeOrigin::signed(2), root, nullifier_hash, 3, proof)
+		};
+
+		let mut invalid_proof = valid_proof();
+		invalid_proof.0.a = Default::default();
+		assert_noop!(
+			withdraw(root, H256::from_low_u64_be(1), invalid_proof),
+			DispatchError::Other("invalid proof")
+		);
+		assert_noop!(
+			withdraw(H256::from_low_u64_be(1), H256::from_low_u64_be(1), valid_proof()),
+			Error::<Test>::UnknownRoot
+		);
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			withdraw(root, H256::repeat_byte(0xff), valid_proof()),
+			Error::<Test>::InvalidFieldElement
 		);
 	});
 }
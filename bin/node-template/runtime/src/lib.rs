@@ -35,7 +35,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -273,9 +273,42 @@ impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 }
 
+impl pallet_groth16_verifier::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type VerifyingKeyId = u32;
+	type RegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxPublicInputs = ConstU32<8>;
+	type WeightInfo = pallet_groth16_verifier::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_incremental_merkle_tree::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	/// The Poseidon hash of circomlib, so that membership can be proven in circom circuits.
+	type Hasher = sp_smt::FieldNodeHasher<sp_smt::Poseidon>;
+	type Depth = ConstU32<20>;
+	type MaxLeavesPerBlock = ConstU32<64>;
+	type RootHistorySize = ConstU32<30>;
+	type WeightInfo = pallet_incremental_merkle_tree::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const ShieldedPoolPalletId: PalletId = PalletId(*b"py/shpol");
+}
+
+/// The identifier of the verifying key of the withdrawal circuit, registered with
+/// `Groth16Verifier::register_vk`.
+pub const WITHDRAWAL_KEY_ID: u32 = 0;
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PalletId = ShieldedPoolPalletId;
+	type Denomination = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type Tree = IncrementalMerkleTree;
+	type VerifyingKeyId = u32;
+	type Verifier = Groth16Verifier;
+	type VerifyingKey = ConstU32<WITHDRAWAL_KEY_ID>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -294,6 +327,8 @@ construct_runtime!(
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		Groth16Verifier: pallet_groth16_verifier,
+		IncrementalMerkleTree: pallet_incremental_merkle_tree,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
 	}
@@ -341,6 +376,8 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_groth16_verifier, Groth16Verifier]
+		[pallet_incremental_merkle_tree, IncrementalMerkleTree]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
 	);
@@ -494,6 +531,31 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_groth16_verifier_rpc_runtime_api::ArkworksApi<Block, u32> for Runtime {
+		fn verify_groth16(
+			vk_id: u32,
+			proof: pallet_groth16_verifier::Proof,
+			public_inputs: Vec<pallet_groth16_verifier::PublicInput>,
+		) -> sp_runtime::DispatchResult {
+			use pallet_groth16_verifier::ProofVerifier;
+			Groth16Verifier::verify_proof(&vk_id, &proof, &public_inputs)
+		}
+
+		fn pairing_check(
+			g1: Vec<pallet_groth16_verifier::G1Affine>,
+			g2: Vec<pallet_groth16_verifier::G2Affine>,
+		) -> Option<bool> {
+			pallet_groth16_verifier::pairing_check(&g1, &g2)
+		}
+
+		fn msm(
+			bases: Vec<pallet_groth16_verifier::G1Affine>,
+			scalars: Vec<pallet_groth16_verifier::PublicInput>,
+		) -> Option<pallet_groth16_verifier::G1Affine> {
+			pallet_groth16_verifier::msm(&bases, &scalars)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
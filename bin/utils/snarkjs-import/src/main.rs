@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Conversion of snarkjs Groth16 files into the SCALE encoding of the `sp-ark-scale` types.
+
+use std::{fs, path::PathBuf};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use codec::Encode;
+
+use sp_ark_scale::{
+	groth16::BoundedVerifyingKey,
+	snarkjs::{self, SnarkjsCurve},
+};
+use sp_core::ConstU32;
+
+/// A utility to convert the Groth16 files exported by snarkjs into the call data of the
+/// verifying key, proof and public inputs.
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+struct SnarkjsImport {
+	#[command(subcommand)]
+	file: File,
+	/// The curve the files were exported for.
+	#[arg(long, value_enum, default_value_t = Curve::Bn128, global = true)]
+	curve: Curve,
+}
+
+/// The snarkjs file to convert, each printed as hex of its SCALE encoding.
+#[derive(Subcommand)]
+#[command(rename_all = "kebab-case")]
+enum File {
+	/// Convert `verification_key.json` into a `BoundedVerifyingKey`.
+	VerifyingKey {
+		/// The path of the verifying key.
+		path: PathBuf,
+	},
+	/// Convert `proof.json` into a `Proof`.
+	Proof {
+		/// The path of the proof.
+		path: PathBuf,
+	},
+	/// Convert `public.json` into a vector of scalar field elements.
+	PublicInputs {
+		/// The path of the public inputs.
+		path: PathBuf,
+	},
+}
+
+/// The curves snarkjs exports files for, named as in the files.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Curve {
+	/// BN254.
+	Bn128,
+	/// BLS12-381.
+	Bls12381,
+}
+
+/// The SCALE encoding of `file` for the pairing engine `E`.
+fn encode<E: SnarkjsCurve>(file: &File) -> Result<Vec<u8>, String> {
+	let read = |path: &PathBuf| {
+		fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+	};
+	let invalid = |e: snarkjs::Error| format!("Invalid snarkjs file: {:?}", e);
+
+	Ok(match file {
+		File::VerifyingKey { path } => {
+			let key = snarkjs::verifying_key_from_json::<E>(&read(path)?).map_err(invalid)?;
+			// The bound does not change the encoding.
+			BoundedVerifyingKey::<E, ConstU32<{ u32::MAX }>>::try_from(key.0)
+				.map_err(|_| "The verifying key has too many points".to_string())?
+				.encode()
+		},
+		File::Proof { path } =>
+			snarkjs::proof_from_json::<E>(&read(path)?).map_err(invalid)?.encode(),
+		File::PublicInputs { path } =>
+			snarkjs::public_inputs_from_json::<E>(&read(path)?).map_err(invalid)?.encode(),
+	})
+}
+
+fn main() -> Result<(), String> {
+	let SnarkjsImport { file, curve } = SnarkjsImport::parse();
+
+	let encoded = match curve {
+		Curve::Bn128 => encode::<ark_bn254::Bn254>(&file)?,
+		Curve::Bls12381 => encode::<ark_bls12_381::Bls12_381>(&file)?,
+	};
+	println!("{}", array_bytes::bytes2hex("0x", &encoded));
+	Ok(())
+}
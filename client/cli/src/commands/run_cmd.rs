@@ -253,6 +253,14 @@ pub struct RunCmd {
 	#[arg(long, default_value_t = 2)]
 	pub runtime_cache_size: u8,
 
+	/// The number of threads the cryptographic host functions use for parallel multi-scalar
+	/// multiplications and pairings.
+	///
+	/// By default there is one per CPU. Only has an effect if the node is built with the
+	/// `parallel` feature of `sp-io`.
+	#[arg(long)]
+	pub crypto_exec_threads: Option<usize>,
+
 	/// Run a temporary node.
 	///
 	/// A temporary directory will be created to store the configuration and will be deleted
@@ -481,6 +489,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.runtime_cache_size)
 	}
 
+	fn crypto_exec_threads(&self) -> Result<Option<usize>> {
+		Ok(self.crypto_exec_threads)
+	}
+
 	fn base_path(&self) -> Result<Option<BasePath>> {
 		Ok(if self.tmp {
 			Some(BasePath::new_temp_dir()?)
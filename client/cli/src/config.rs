@@ -468,6 +468,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(2)
 	}
 
+	/// Get the number of threads of the cryptographic host functions
+	///
+	/// By default this is `None`, one per CPU.
+	fn crypto_exec_threads(&self) -> Result<Option<usize>> {
+		Ok(Default::default())
+	}
+
 	/// Activate or not the automatic announcing of blocks after import
 	///
 	/// By default this is `false`.
@@ -649,6 +656,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 
 		logger.init()?;
 
+		if let Some(threads) = self.crypto_exec_threads()? {
+			sp_io::set_crypto_threads(threads);
+		}
+
 		if let Some(new_limit) = fdlimit::raise_fd_limit() {
 			if new_limit < RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT {
 				warn!(
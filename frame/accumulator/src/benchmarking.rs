@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the accumulator pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+
+#[cfg(test)]
+use crate::Pallet as Accumulator;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+/// Set the fixture public key with `value` as the accumulator of epoch zero.
+fn initialize<T: Config>(value: G1Point) {
+	PublicKey::<T>::put(fixtures::public_key());
+	InitialValue::<T>::put(fixtures::initial_value());
+	KeyEpoch::<T>::put(0);
+	CurrentEpoch::<T>::put(0);
+	Accumulators::<T>::insert(0, value);
+}
+
+benchmarks! {
+	set_public_key {
+		let call = Call::<T>::set_public_key {
+			public_key: fixtures::public_key(),
+			value: fixtures::initial_value(),
+		};
+		let origin = T::ManagerOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::PublicKeySet { epoch: 0 }.into());
+	}
+
+	add {
+		initialize::<T>(fixtures::initial_value());
+		let element = fixtures::element();
+		let call = Call::<T>::add { element, value: fixtures::added_value() };
+		let origin = T::ManagerOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::ElementAdded { element, epoch: 1 }.into());
+	}
+
+	remove {
+		initialize::<T>(fixtures::added_value());
+		let element = fixtures::element();
+		let call = Call::<T>::remove { element, value: fixtures::initial_value() };
+		let origin = T::ManagerOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::ElementRemoved { element, epoch: 1 }.into());
+	}
+
+	verify_membership {
+		let caller: T::AccountId = whitelisted_caller();
+		initialize::<T>(fixtures::added_value());
+		let element = fixtures::element();
+	}: _(RawOrigin::Signed(caller.clone()), 0, element, fixtures::initial_value())
+	verify {
+		assert_last_event::<T>(
+			Event::MembershipVerified { who: caller, epoch: 0, element }.into()
+		);
+	}
+
+	verify_non_membership {
+		let caller: T::AccountId = whitelisted_caller();
+		initialize::<T>(fixtures::added_value());
+		let element = fixtures::non_member();
+		let mut d = [0; 32];
+		d[31] = 1;
+	}: _(RawOrigin::Signed(caller.clone()), 0, element, fixtures::initial_value(), d)
+	verify {
+		assert_last_event::<T>(
+			Event::NonMembershipVerified { who: caller, epoch: 0, element }.into()
+		);
+	}
+
+	impl_benchmark_test_suite!(Accumulator, crate::mock::new_test_ext(), crate::mock::Test);
+}
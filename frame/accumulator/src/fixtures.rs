@@ -0,0 +1,74 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Accumulator values shared by the tests and benchmarks.
+//!
+//! All fixtures are computed for the secret `s = 0x5ec2e7` and start from the initial value
+//! `V₀ = G1`.
+
+use crate::{Element, G1Point, G2Point};
+
+/// The secret of all fixtures.
+#[cfg(test)]
+pub const SECRET: u64 = 0x5ec2e7;
+
+/// The public key `s·G2`.
+pub fn public_key() -> G2Point {
+	array_bytes::hex2array_unchecked(
+		"\
+24ec0e1fcd86c20b9a029a91a09cd4eeda2ce22abc5a437d1eca99681555375a\
+09c815b14c630574e91416616f6d1d5c05e5a7a35f64f018d1da0b24185fb997\
+27bb2760de644cf125cf43bd55806cfc7e3d3a4adafe58516f4027ad14618abf\
+07d0998610415ace0cd42474a71496122cdf1f9d7dd8f020e598a0d892e80ed4\
+",
+	)
+}
+
+/// The initial value `V₀`, the generator `(1, 2)` of G1.
+pub fn initial_value() -> G1Point {
+	let mut point = [0; 64];
+	point[31] = 1;
+	point[63] = 2;
+	point
+}
+
+/// The element accumulated by [`added_value`].
+pub fn element() -> Element {
+	let mut element = [0; 32];
+	element[31] = 2;
+	element
+}
+
+/// The value `V₁ = (2 + s)·V₀` after adding [`element`], for which `V₀` is the membership
+/// witness of [`element`].
+///
+/// Since `V₁ = (1 + s)·V₀ + 1·V₀`, `V₀` with `d = 1` is also the non-membership witness of `1`.
+pub fn added_value() -> G1Point {
+	array_bytes::hex2array_unchecked(
+		"\
+1c75a3f8814a54a76e0ce9186160b59626076f25048b40f807549df43b4740f0\
+2e904cb9a14f37d1bb6e9899a97e90575436248142ec2a02a2b6ab91c4b54412\
+",
+	)
+}
+
+/// An element not accumulated by [`added_value`].
+pub fn non_member() -> Element {
+	let mut element = [0; 32];
+	element[31] = 1;
+	element
+}
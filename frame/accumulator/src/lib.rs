@@ -0,0 +1,460 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Accumulator Pallet
+//!
+//! Maintains a pairing-based universal accumulator over BN254, following Nguyen's construction.
+//!
+//! The accumulator of a set `X` is the G1 point `V = Π(x + s)·V₀` for a secret `s` only known to
+//! [`Config::ManagerOrigin`], who publishes `s·G2` and the initial value `V₀` through
+//! [`Pallet::set_public_key`]. The manager adds and removes elements through [`Pallet::add`] and
+//! [`Pallet::remove`] by submitting the updated value, which is checked to be `(x + s)` times the
+//! previous one when adding `x`, and the previous one divided by `(x + s)` when removing it.
+//!
+//! The pallet does not track the accumulated set: these checks only show that an update changes
+//! the value by a single factor. Since the manager knows `s`, it can just as well divide out an
+//! element that was never added, so the value on chain reflects the added and removed elements
+//! only as long as the manager follows the protocol.
+//!
+//! Every update starts a new epoch and the values of the last [`Config::HistoryDepth`] epochs are
+//! kept, so that witnesses computed off-chain against any of them can be verified:
+//!
+//! - a membership witness for `x` is the point `W` such that `V = (x + s)·W`,
+//! - a non-membership witness for `x` is the point `W` with a scalar `d ≠ 0` such that `V = (x +
+//!   s)·W + d·V₀`.
+//!
+//! Each check is a single call to the `bn254_ec_pairing` host function. Witnesses can be verified
+//! through [`Pallet::verify_membership`] and [`Pallet::verify_non_membership`], or by other
+//! pallets through [`Pallet::check_membership`] and [`Pallet::check_non_membership`].
+//!
+//! Points and scalars use the encoding of the EIP-196 and EIP-197 precompiles: big-endian
+//! integers of 32 bytes, G1 points as `x` followed by `y` and G2 points with the coefficients of
+//! each coordinate ordered as `c1` followed by `c0`.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod fixtures;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// An encoded G1 point.
+pub type G1Point = [u8; 64];
+/// An encoded G2 point.
+pub type G2Point = [u8; 128];
+/// An accumulated element, a big-endian integer smaller than the group order.
+pub type Element = [u8; 32];
+/// The index of an accumulator value.
+pub type EpochIndex = u32;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::ArithmeticError;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to set the public key and to update the accumulator.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of epochs whose accumulator values are kept.
+		///
+		/// Must be larger than zero.
+		#[pallet::constant]
+		type HistoryDepth: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The public key `s·G2` of the accumulator.
+	#[pallet::storage]
+	#[pallet::getter(fn public_key)]
+	pub type PublicKey<T> = StorageValue<_, G2Point>;
+
+	/// The initial accumulator value `V₀` set along with [`PublicKey`].
+	#[pallet::storage]
+	#[pallet::getter(fn initial_value)]
+	pub type InitialValue<T> = StorageValue<_, G1Point>;
+
+	/// The epoch [`PublicKey`] was set in.
+	///
+	/// Values of earlier epochs were accumulated under a different key and are not used anymore.
+	#[pallet::storage]
+	#[pallet::getter(fn key_epoch)]
+	pub type KeyEpoch<T> = StorageValue<_, EpochIndex>;
+
+	/// The epoch of the current accumulator value.
+	#[pallet::storage]
+	#[pallet::getter(fn current_epoch)]
+	pub type CurrentEpoch<T> = StorageValue<_, EpochIndex>;
+
+	/// The accumulator values of the last [`Config::HistoryDepth`] epochs.
+	#[pallet::storage]
+	pub type Accumulators<T> = StorageMap<_, Twox64Concat, EpochIndex, G1Point>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new public key and initial accumulator value were set.
+		PublicKeySet { epoch: EpochIndex },
+		/// An element was added to the accumulator.
+		ElementAdded { element: Element, epoch: EpochIndex },
+		/// An element was removed from the accumulator.
+		ElementRemoved { element: Element, epoch: EpochIndex },
+		/// A membership witness was verified.
+		MembershipVerified { who: T::AccountId, epoch: EpochIndex, element: Element },
+		/// A non-membership witness was verified.
+		NonMembershipVerified { who: T::AccountId, epoch: EpochIndex, element: Element },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A point is not a valid encoding, not on the curve or the point at infinity.
+		InvalidPoint,
+		/// The element is not smaller than the group order.
+		InvalidElement,
+		/// The public key has not been set yet.
+		NotInitialized,
+		/// There is no accumulator value for the epoch under the current public key.
+		UnknownEpoch,
+		/// The new accumulator value does not match the added or removed element.
+		InvalidUpdate,
+		/// The witness did not verify.
+		InvalidWitness,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::HistoryDepth::get() > 0, "`HistoryDepth` must be larger than zero");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the `public_key` `s·G2` and the initial accumulator `value`, starting a new
+		/// epoch.
+		///
+		/// The values of all previous epochs become unusable.
+		///
+		/// The dispatch origin for this call must be [`Config::ManagerOrigin`].
+		#[pallet::weight(T::WeightInfo::set_public_key())]
+		pub fn set_public_key(
+			origin: OriginFor<T>,
+			public_key: G2Point,
+			value: G1Point,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			// An empty pairing input only succeeds if the G2 point is valid.
+			ensure!(
+				public_key != [0; 128] &&
+					sp_io::crypto::bn254_ec_pairing(&[&[0; 64][..], &public_key].concat())
+						.is_some(),
+				Error::<T>::InvalidPoint,
+			);
+			ensure_valid_g1::<T>(&value)?;
+
+			let epoch = Self::new_epoch(value)?;
+			PublicKey::<T>::put(public_key);
+			InitialValue::<T>::put(value);
+			KeyEpoch::<T>::put(epoch);
+
+			Self::deposit_event(Event::PublicKeySet { epoch });
+			Ok(())
+		}
+
+		/// Add `element` to the accumulator, whose new `value` must be `(element + s)` times the
+		/// current one.
+		///
+		/// The dispatch origin for this call must be [`Config::ManagerOrigin`].
+		#[pallet::weight(T::WeightInfo::add())]
+		pub fn add(origin: OriginFor<T>, element: Element, value: G1Point) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			let (public_key, current) = Self::current()?;
+			ensure_valid_element::<T>(&element)?;
+			ensure_valid_g1::<T>(&value)?;
+			ensure!(
+				is_multiple::<T>(&public_key, &value, &current, &element)?,
+				Error::<T>::InvalidUpdate,
+			);
+
+			let epoch = Self::new_epoch(value)?;
+			Self::deposit_event(Event::ElementAdded { element, epoch });
+			Ok(())
+		}
+
+		/// Remove `element` from the accumulator, whose current value must be `(element + s)`
+		/// times the new `value`.
+		///
+		/// The dispatch origin for this call must be [`Config::ManagerOrigin`].
+		#[pallet::weight(T::WeightInfo::remove())]
+		pub fn remove(origin: OriginFor<T>, element: Element, value: G1Point) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			let (public_key, current) = Self::current()?;
+			ensure_valid_element::<T>(&element)?;
+			ensure_valid_g1::<T>(&value)?;
+			ensure!(
+				is_multiple::<T>(&public_key, &current, &value, &element)?,
+				Error::<T>::InvalidUpdate,
+			);
+
+			let epoch = Self::new_epoch(value)?;
+			Self::deposit_event(Event::ElementRemoved { element, epoch });
+			Ok(())
+		}
+
+		/// Verify the membership `witness` of `element` in the accumulator value of `epoch`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::verify_membership())]
+		pub fn verify_membership(
+			origin: OriginFor<T>,
+			epoch: EpochIndex,
+			element: Element,
+			witness: G1Point,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::check_membership(epoch, &element, &witness)?;
+
+			Self::deposit_event(Event::MembershipVerified { who, epoch, element });
+			Ok(())
+		}
+
+		/// Verify the non-membership `witness` of `element` in the accumulator value of `epoch`,
+		/// where `d` is the non-zero remainder of the division by `element + s`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::verify_non_membership())]
+		pub fn verify_non_membership(
+			origin: OriginFor<T>,
+			epoch: EpochIndex,
+			element: Element,
+			witness: G1Point,
+			d: Element,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::check_non_membership(epoch, &element, &witness, &d)?;
+
+			Self::deposit_event(Event::NonMembershipVerified { who, epoch, element });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The accumulator value of `epoch`, if it is kept and was accumulated under the current
+		/// public key.
+		pub fn accumulator(epoch: EpochIndex) -> Option<G1Point> {
+			if epoch < KeyEpoch::<T>::get()? {
+				return None
+			}
+			Accumulators::<T>::get(epoch)
+		}
+
+		/// Check that `witness` proves the membership of `element` in the accumulator value of
+		/// `epoch`.
+		pub fn check_membership(
+			epoch: EpochIndex,
+			element: &Element,
+			witness: &G1Point,
+		) -> DispatchResult {
+			let public_key = PublicKey::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let value = Self::accumulator(epoch).ok_or(Error::<T>::UnknownEpoch)?;
+			ensure_valid_element::<T>(element)?;
+
+			ensure!(
+				is_multiple::<T>(&public_key, &value, witness, element)?,
+				Error::<T>::InvalidWitness,
+			);
+			Ok(())
+		}
+
+		/// Check that `witness` and `d` prove that `element` is not a member of the accumulator
+		/// value of `epoch`.
+		pub fn check_non_membership(
+			epoch: EpochIndex,
+			element: &Element,
+			witness: &G1Point,
+			d: &Element,
+		) -> DispatchResult {
+			let public_key = PublicKey::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let initial = InitialValue::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let value = Self::accumulator(epoch).ok_or(Error::<T>::UnknownEpoch)?;
+			ensure_valid_element::<T>(element)?;
+			// With `d = 0` this would be a membership witness.
+			ensure!(*d != [0; 32] && is_scalar(d), Error::<T>::InvalidWitness);
+
+			// `V - d·V₀`, the value the witness must divide.
+			let minus_d = g1_mul::<T>(&initial, &negate_scalar(d))?;
+			let quotient = g1_add::<T>(&value, &minus_d)?;
+			ensure!(
+				is_multiple::<T>(&public_key, &quotient, witness, element)?,
+				Error::<T>::InvalidWitness,
+			);
+			Ok(())
+		}
+
+		/// The public key and the current accumulator value.
+		fn current() -> Result<(G2Point, G1Point), DispatchError> {
+			let public_key = PublicKey::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let epoch = CurrentEpoch::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let value = Accumulators::<T>::get(epoch).ok_or(Error::<T>::NotInitialized)?;
+			Ok((public_key, value))
+		}
+
+		/// Store `value` as the accumulator of a new epoch and prune the oldest kept one.
+		fn new_epoch(value: G1Point) -> Result<EpochIndex, DispatchError> {
+			let epoch = match CurrentEpoch::<T>::get() {
+				Some(current) => current.checked_add(1).ok_or(ArithmeticError::Overflow)?,
+				None => 0,
+			};
+
+			CurrentEpoch::<T>::put(epoch);
+			Accumulators::<T>::insert(epoch, value);
+			if let Some(expired) = epoch.checked_sub(T::HistoryDepth::get()) {
+				Accumulators::<T>::remove(expired);
+			}
+			Ok(epoch)
+		}
+	}
+}
+
+/// The base field modulus.
+const FIELD_MODULUS: [u8; 32] = [
+	0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+	0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// The order of G1 and G2.
+const GROUP_ORDER: [u8; 32] = [
+	0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+	0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// The generator of G2.
+const G2_GENERATOR: G2Point = [
+	0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
+	0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12, 0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3, 0x12, 0xc2,
+	0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76, 0x42, 0x6a, 0x00, 0x66, 0x5e, 0x5c, 0x44, 0x79,
+	0x67, 0x43, 0x22, 0xd4, 0xf7, 0x5e, 0xda, 0xdd, 0x46, 0xde, 0xbd, 0x5c, 0xd9, 0x92, 0xf6, 0xed,
+	0x09, 0x06, 0x89, 0xd0, 0x58, 0x5f, 0xf0, 0x75, 0xec, 0x9e, 0x99, 0xad, 0x69, 0x0c, 0x33, 0x95,
+	0xbc, 0x4b, 0x31, 0x33, 0x70, 0xb3, 0x8e, 0xf3, 0x55, 0xac, 0xda, 0xdc, 0xd1, 0x22, 0x97, 0x5b,
+	0x12, 0xc8, 0x5e, 0xa5, 0xdb, 0x8c, 0x6d, 0xeb, 0x4a, 0xab, 0x71, 0x80, 0x8d, 0xcb, 0x40, 0x8f,
+	0xe3, 0xd1, 0xe7, 0x69, 0x0c, 0x43, 0xd3, 0x7b, 0x4c, 0xe6, 0xcc, 0x01, 0x66, 0xfa, 0x7d, 0xaa,
+];
+
+/// Subtract the big-endian integer `b` from `a`, which must not be smaller.
+fn sub(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+	let mut result = [0; 32];
+	let mut borrow = false;
+	for i in (0..32).rev() {
+		let (digit, underflow) = a[i].overflowing_sub(b[i]);
+		let (digit, underflow_borrow) = digit.overflowing_sub(borrow.into());
+		result[i] = digit;
+		borrow = underflow || underflow_borrow;
+	}
+	result
+}
+
+/// Whether `scalar` is smaller than the group order.
+fn is_scalar(scalar: &[u8; 32]) -> bool {
+	scalar < &GROUP_ORDER
+}
+
+/// The scalar `-scalar`, which must be smaller than the group order.
+///
+/// Zero is mapped to the group order, which the `bn254_ec_mul` host function does not reduce but
+/// which multiplies every point to the point at infinity as well.
+fn negate_scalar(scalar: &[u8; 32]) -> [u8; 32] {
+	sub(&GROUP_ORDER, scalar)
+}
+
+/// The point `-point`, which must be valid.
+fn negate(point: &G1Point) -> G1Point {
+	let mut negated = *point;
+	if *point != [0; 64] {
+		let y = point[32..].try_into().expect("The slice is 32 bytes long; qed");
+		negated[32..].copy_from_slice(&sub(&FIELD_MODULUS, y));
+	}
+	negated
+}
+
+/// Ensure `element` is smaller than the group order.
+fn ensure_valid_element<T: Config>(element: &Element) -> Result<(), Error<T>> {
+	frame_support::ensure!(is_scalar(element), Error::<T>::InvalidElement);
+	Ok(())
+}
+
+/// Ensure `point` is a valid G1 point other than the point at infinity.
+fn ensure_valid_g1<T: Config>(point: &G1Point) -> Result<(), Error<T>> {
+	frame_support::ensure!(
+		*point != [0; 64] && sp_io::crypto::bn254_ec_add(point).is_some(),
+		Error::<T>::InvalidPoint,
+	);
+	Ok(())
+}
+
+/// `a + b` through the `bn254_ec_add` host function.
+fn g1_add<T: Config>(a: &G1Point, b: &G1Point) -> Result<G1Point, Error<T>> {
+	sp_io::crypto::bn254_ec_add(&[&a[..], &b[..]].concat()).ok_or(Error::<T>::InvalidPoint)
+}
+
+/// `scalar·point` through the `bn254_ec_mul` host function.
+fn g1_mul<T: Config>(point: &G1Point, scalar: &[u8; 32]) -> Result<G1Point, Error<T>> {
+	sp_io::crypto::bn254_ec_mul(&[&point[..], &scalar[..]].concat()).ok_or(Error::<T>::InvalidPoint)
+}
+
+/// Whether `a = (x + s)·b` for the secret `s` of `public_key`.
+///
+/// This holds if `e(a - x·b, G2) · e(-b, s·G2) = 1`, which is checked with a single pairing.
+fn is_multiple<T: Config>(
+	public_key: &G2Point,
+	a: &G1Point,
+	b: &G1Point,
+	x: &Element,
+) -> Result<bool, Error<T>> {
+	// Validates `b` before it is negated below.
+	let minus_xb = g1_mul::<T>(b, &negate_scalar(x))?;
+	let lhs = g1_add::<T>(a, &minus_xb)?;
+
+	let input = [&lhs[..], &G2_GENERATOR[..], &negate(b)[..], &public_key[..]].concat();
+	let result = sp_io::crypto::bn254_ec_pairing(&input).ok_or(Error::<T>::InvalidPoint)?;
+	Ok(result[31] == 1)
+}
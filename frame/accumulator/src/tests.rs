@@ -0,0 +1,399 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the accumulator pallet.
+
+use super::{
+	fixtures, Accumulators, Element, Error, Event, G1Point, G2Point, Pallet as Accumulator,
+	GROUP_ORDER,
+};
+use crate::mock::*;
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, One, PrimeField};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+fn g1(point: G1Affine) -> G1Point {
+	let mut bytes = [0; 64];
+	if let Some((x, y)) = point.xy() {
+		bytes[..32].copy_from_slice(&x.into_bigint().to_bytes_be());
+		bytes[32..].copy_from_slice(&y.into_bigint().to_bytes_be());
+	}
+	bytes
+}
+
+fn g2(point: G2Affine) -> G2Point {
+	let (x, y) = point.xy().unwrap();
+	[x.c1, x.c0, y.c1, y.c0]
+		.iter()
+		.flat_map(|c| c.into_bigint().to_bytes_be())
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+fn element(x: Fr) -> Element {
+	x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn secret() -> Fr {
+	Fr::from(fixtures::SECRET)
+}
+
+/// `f(s) = Π(x + s)` for the accumulated `elements`.
+fn product(elements: &[Fr]) -> Fr {
+	elements.iter().map(|x| *x + secret()).product()
+}
+
+/// Set the fixture public key and add `elements` one by one, returning the last epoch.
+fn accumulate(elements: &[Fr]) -> u32 {
+	accumulate_from(G1Affine::generator(), elements)
+}
+
+/// Set the fixture public key with the `initial` value and add `elements` one by one, returning
+/// the last epoch.
+fn accumulate_from(initial: G1Affine, elements: &[Fr]) -> u32 {
+	assert_ok!(Accumulator::<Test>::set_public_key(
+		RawOrigin::Root.into(),
+		fixtures::public_key(),
+		g1(initial),
+	));
+	for (i, x) in elements.iter().enumerate() {
+		let value = (initial * product(&elements[..=i])).into_affine();
+		assert_ok!(Accumulator::<Test>::add(RawOrigin::Root.into(), element(*x), g1(value)));
+	}
+	Accumulator::<Test>::current_epoch().unwrap()
+}
+
+#[test]
+fn fixtures_match_secret() {
+	assert_eq!(fixtures::public_key(), g2((G2Affine::generator() * secret()).into_affine()));
+	assert_eq!(fixtures::initial_value(), g1(G1Affine::generator()));
+	assert_eq!(
+		fixtures::added_value(),
+		g1((G1Affine::generator() * (Fr::from(2) + secret())).into_affine()),
+	);
+	assert_eq!(GROUP_ORDER[..], Fr::MODULUS.to_bytes_be());
+}
+
+#[test]
+fn set_public_key_works() {
+	new_test_ext().execute_with(|| {
+		let public_key = fixtures::public_key();
+		let value = fixtures::initial_value();
+
+		assert_noop!(
+			Accumulator::<Test>::set_public_key(RawOrigin::Signed(1).into(), public_key, value),
+			DispatchError::BadOrigin,
+		);
+		let mut off_curve = public_key;
+		off_curve[127] ^= 1;
+		for invalid in [[0; 128], off_curve] {
+			assert_noop!(
+				Accumulator::<Test>::set_public_key(RawOrigin::Root.into(), invalid, value),
+				Error::<Test>::InvalidPoint,
+			);
+		}
+		let mut off_curve = value;
+		off_curve[63] ^= 1;
+		for invalid in [[0; 64], off_curve] {
+			assert_noop!(
+				Accumulator::<Test>::set_public_key(RawOrigin::Root.into(), public_key, invalid),
+				Error::<Test>::InvalidPoint,
+			);
+		}
+
+		assert_ok!(Accumulator::<Test>::set_public_key(RawOrigin::Root.into(), public_key, value));
+		assert_eq!(Accumulator::<Test>::public_key(), Some(public_key));
+		assert_eq!(Accumulator::<Test>::initial_value(), Some(value));
+		assert_eq!(Accumulator::<Test>::key_epoch(), Some(0));
+		assert_eq!(Accumulator::<Test>::accumulator(0), Some(value));
+		System::assert_last_event(Event::PublicKeySet { epoch: 0 }.into());
+
+		// A new key starts a new epoch and invalidates the previous ones.
+		assert_ok!(Accumulator::<Test>::set_public_key(RawOrigin::Root.into(), public_key, value));
+		assert_eq!(Accumulator::<Test>::key_epoch(), Some(1));
+		assert_eq!(Accumulator::<Test>::accumulator(0), None);
+		assert_eq!(Accumulator::<Test>::accumulator(1), Some(value));
+	});
+}
+
+#[test]
+fn add_and_remove_work() {
+	new_test_ext().execute_with(|| {
+		let x = fixtures::element();
+		let (initial, added) = (fixtures::initial_value(), fixtures::added_value());
+
+		assert_noop!(
+			Accumulator::<Test>::add(RawOrigin::Root.into(), x, added),
+			Error::<Test>::NotInitialized,
+		);
+		accumulate(&[]);
+
+		assert_noop!(
+			Accumulator::<Test>::add(RawOrigin::Signed(1).into(), x, added),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Accumulator::<Test>::add(RawOrigin::Root.into(), GROUP_ORDER, added),
+			Error::<Test>::InvalidElement,
+		);
+		assert_noop!(
+			Accumulator::<Test>::add(RawOrigin::Root.into(), fixtures::non_member(), added),
+			Error::<Test>::InvalidUpdate,
+		);
+		// Removing requires the reverse relation.
+		assert_noop!(
+			Accumulator::<Test>::remove(RawOrigin::Root.into(), x, added),
+			Error::<Test>::InvalidUpdate,
+		);
+
+		assert_ok!(Accumulator::<Test>::add(RawOrigin::Root.into(), x, added));
+		assert_eq!(Accumulator::<Test>::current_epoch(), Some(1));
+		assert_eq!(Accumulator::<Test>::accumulator(1), Some(added));
+		System::assert_last_event(Event::ElementAdded { element: x, epoch: 1 }.into());
+
+		assert_noop!(
+			Accumulator::<Test>::remove(RawOrigin::Signed(1).into(), x, initial),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(Accumulator::<Test>::remove(RawOrigin::Root.into(), x, initial));
+		assert_eq!(Accumulator::<Test>::current_epoch(), Some(2));
+		assert_eq!(Accumulator::<Test>::accumulator(2), Some(initial));
+		System::assert_last_event(Event::ElementRemoved { element: x, epoch: 2 }.into());
+	});
+}
+
+#[test]
+fn membership_is_verified() {
+	new_test_ext().execute_with(|| {
+		let elements = [Fr::from(3), Fr::from(5), Fr::from(7)];
+		assert_noop!(
+			Accumulator::<Test>::verify_membership(
+				RawOrigin::Signed(1).into(),
+				0,
+				[0; 32],
+				[0; 64],
+			),
+			Error::<Test>::NotInitialized,
+		);
+		let epoch = accumulate(&elements);
+
+		for (i, x) in elements.iter().enumerate() {
+			let others = [&elements[..i], &elements[i + 1..]].concat();
+			let witness = g1((G1Affine::generator() * product(&others)).into_affine());
+
+			assert_ok!(Accumulator::<Test>::verify_membership(
+				RawOrigin::Signed(1).into(),
+				epoch,
+				element(*x),
+				witness,
+			));
+			System::assert_last_event(
+				Event::MembershipVerified { who: 1, epoch, element: element(*x) }.into(),
+			);
+
+			// The witness is only valid for this element and epoch.
+			assert_noop!(
+				Accumulator::<Test>::verify_membership(
+					RawOrigin::Signed(1).into(),
+					epoch,
+					element(*x + Fr::one()),
+					witness,
+				),
+				Error::<Test>::InvalidWitness,
+			);
+			assert_noop!(
+				Accumulator::<Test>::verify_membership(
+					RawOrigin::Signed(1).into(),
+					epoch - 1,
+					element(*x),
+					witness,
+				),
+				Error::<Test>::InvalidWitness,
+			);
+		}
+
+		let witness = fixtures::initial_value();
+		assert_noop!(
+			Accumulator::<Test>::verify_membership(RawOrigin::None.into(), epoch, [0; 32], witness),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Accumulator::<Test>::verify_membership(
+				RawOrigin::Signed(1).into(),
+				epoch + 1,
+				[0; 32],
+				witness,
+			),
+			Error::<Test>::UnknownEpoch,
+		);
+		assert_noop!(
+			Accumulator::<Test>::verify_membership(
+				RawOrigin::Signed(1).into(),
+				epoch,
+				GROUP_ORDER,
+				witness,
+			),
+			Error::<Test>::InvalidElement,
+		);
+		let mut off_curve = witness;
+		off_curve[63] ^= 1;
+		assert_noop!(
+			Accumulator::<Test>::verify_membership(
+				RawOrigin::Signed(1).into(),
+				epoch,
+				[0; 32],
+				off_curve,
+			),
+			Error::<Test>::InvalidPoint,
+		);
+	});
+}
+
+#[test]
+fn non_membership_is_verified() {
+	new_test_ext().execute_with(|| {
+		let elements = [Fr::from(3), Fr::from(5), Fr::from(7)];
+		let epoch = accumulate(&elements);
+
+		// `f(s) = q(s)·(y + s) + d` with `d = f(-y)`.
+		let y = Fr::from(4);
+		let d: Fr = elements.iter().map(|x| *x - y).product();
+		let q = (product(&elements) - d) * (y + secret()).inverse().unwrap();
+		let witness = g1((G1Affine::generator() * q).into_affine());
+
+		assert_ok!(Accumulator::<Test>::verify_non_membership(
+			RawOrigin::Signed(1).into(),
+			epoch,
+			element(y),
+			witness,
+			element(d),
+		));
+		System::assert_last_event(
+			Event::NonMembershipVerified { who: 1, epoch, element: element(y) }.into(),
+		);
+
+		assert_noop!(
+			Accumulator::<Test>::verify_non_membership(
+				RawOrigin::Signed(1).into(),
+				epoch,
+				element(y),
+				witness,
+				element(d + Fr::one()),
+			),
+			Error::<Test>::InvalidWitness,
+		);
+		assert_noop!(
+			Accumulator::<Test>::verify_non_membership(
+				RawOrigin::Signed(1).into(),
+				epoch,
+				element(y),
+				witness,
+				GROUP_ORDER,
+			),
+			Error::<Test>::InvalidWitness,
+		);
+
+		// A membership witness with `d = 0` must not pass as a non-membership witness.
+		let x = elements[0];
+		let witness = g1((G1Affine::generator() * product(&elements[1..])).into_affine());
+		assert_ok!(Accumulator::<Test>::check_membership(epoch, &element(x), &witness));
+		assert_noop!(
+			Accumulator::<Test>::verify_non_membership(
+				RawOrigin::Signed(1).into(),
+				epoch,
+				element(x),
+				witness,
+				[0; 32],
+			),
+			Error::<Test>::InvalidWitness,
+		);
+	});
+}
+
+#[test]
+fn non_membership_uses_initial_value() {
+	new_test_ext().execute_with(|| {
+		let initial = (G1Affine::generator() * Fr::from(11)).into_affine();
+		let elements = [Fr::from(3), Fr::from(5), Fr::from(7)];
+		let epoch = accumulate_from(initial, &elements);
+
+		// `V = f(s)·V₀ = q(s)·(y + s)·V₀ + d·V₀`.
+		let y = Fr::from(4);
+		let d: Fr = elements.iter().map(|x| *x - y).product();
+		let q = (product(&elements) - d) * (y + secret()).inverse().unwrap();
+		let witness = g1((initial * q).into_affine());
+		assert_ok!(Accumulator::<Test>::check_non_membership(
+			epoch,
+			&element(y),
+			&witness,
+			&element(d),
+		));
+
+		// A witness for `V = (y + s)·W + d·G1` does not verify unless `V₀ = G1`.
+		let q = (product(&elements) * Fr::from(11) - d) * (y + secret()).inverse().unwrap();
+		let witness = g1((G1Affine::generator() * q).into_affine());
+		assert_noop!(
+			Accumulator::<Test>::check_non_membership(epoch, &element(y), &witness, &element(d)),
+			Error::<Test>::InvalidWitness,
+		);
+	});
+}
+
+#[test]
+fn fixture_witnesses_verify() {
+	new_test_ext().execute_with(|| {
+		let epoch = accumulate(&[Fr::from(2)]);
+		assert_eq!(Accumulator::<Test>::accumulator(epoch), Some(fixtures::added_value()));
+
+		let mut one = [0; 32];
+		one[31] = 1;
+		assert_ok!(Accumulator::<Test>::check_membership(
+			epoch,
+			&fixtures::element(),
+			&fixtures::initial_value(),
+		));
+		assert_ok!(Accumulator::<Test>::check_non_membership(
+			epoch,
+			&fixtures::non_member(),
+			&fixtures::initial_value(),
+			&one,
+		));
+	});
+}
+
+#[test]
+fn history_is_pruned() {
+	new_test_ext().execute_with(|| {
+		let epoch = accumulate(&[Fr::from(3), Fr::from(5), Fr::from(7)]);
+		assert_eq!(epoch, 3);
+
+		// `HistoryDepth` is three in the mock.
+		assert!(!Accumulators::<Test>::contains_key(0));
+		assert_eq!(Accumulator::<Test>::accumulator(0), None);
+		for epoch in 1..=3 {
+			assert!(Accumulator::<Test>::accumulator(epoch).is_some());
+		}
+		assert_noop!(
+			Accumulator::<Test>::check_membership(0, &[0; 32], &fixtures::initial_value()),
+			Error::<Test>::UnknownEpoch,
+		);
+	});
+}
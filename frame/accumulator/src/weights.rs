@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Weights for pallet_accumulator
+//!
+//! These are conservative estimates until the weights are generated with the benchmark CLI:
+//!
+//! ./target/production/substrate benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_accumulator --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --template=./.maintain/frame-weight-template.hbs --output=./frame/accumulator/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_accumulator.
+pub trait WeightInfo {
+	fn set_public_key() -> Weight;
+	fn add() -> Weight;
+	fn remove() -> Weight;
+	fn verify_membership() -> Weight;
+	fn verify_non_membership() -> Weight;
+}
+
+/// Weights for pallet_accumulator using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Accumulator CurrentEpoch (r:1 w:1)
+	// Storage: Accumulator Accumulators (r:0 w:2)
+	// Storage: Accumulator PublicKey (r:0 w:1)
+	// Storage: Accumulator InitialValue (r:0 w:1)
+	// Storage: Accumulator KeyEpoch (r:0 w:1)
+	fn set_public_key() -> Weight {
+		Weight::from_ref_time(600_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator CurrentEpoch (r:1 w:1)
+	// Storage: Accumulator Accumulators (r:1 w:2)
+	fn add() -> Weight {
+		Weight::from_ref_time(1_200_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator CurrentEpoch (r:1 w:1)
+	// Storage: Accumulator Accumulators (r:1 w:2)
+	fn remove() -> Weight {
+		Weight::from_ref_time(1_200_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator KeyEpoch (r:1 w:0)
+	// Storage: Accumulator Accumulators (r:1 w:0)
+	fn verify_membership() -> Weight {
+		Weight::from_ref_time(1_200_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator InitialValue (r:1 w:0)
+	// Storage: Accumulator KeyEpoch (r:1 w:0)
+	// Storage: Accumulator Accumulators (r:1 w:0)
+	fn verify_non_membership() -> Weight {
+		Weight::from_ref_time(1_300_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Accumulator CurrentEpoch (r:1 w:1)
+	// Storage: Accumulator Accumulators (r:0 w:2)
+	// Storage: Accumulator PublicKey (r:0 w:1)
+	// Storage: Accumulator InitialValue (r:0 w:1)
+	// Storage: Accumulator KeyEpoch (r:0 w:1)
+	fn set_public_key() -> Weight {
+		Weight::from_ref_time(600_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator CurrentEpoch (r:1 w:1)
+	// Storage: Accumulator Accumulators (r:1 w:2)
+	fn add() -> Weight {
+		Weight::from_ref_time(1_200_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator CurrentEpoch (r:1 w:1)
+	// Storage: Accumulator Accumulators (r:1 w:2)
+	fn remove() -> Weight {
+		Weight::from_ref_time(1_200_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator KeyEpoch (r:1 w:0)
+	// Storage: Accumulator Accumulators (r:1 w:0)
+	fn verify_membership() -> Weight {
+		Weight::from_ref_time(1_200_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+	}
+	// Storage: Accumulator PublicKey (r:1 w:0)
+	// Storage: Accumulator InitialValue (r:1 w:0)
+	// Storage: Accumulator KeyEpoch (r:1 w:0)
+	// Storage: Accumulator Accumulators (r:1 w:0)
+	fn verify_non_membership() -> Weight {
+		Weight::from_ref_time(1_300_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+	}
+}
@@ -0,0 +1,177 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the BLS DKG pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	dispatch::UnfilteredDispatchable,
+	traits::{EnsureOrigin, Get},
+};
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+use sp_runtime::traits::Hash;
+
+#[cfg(test)]
+use crate::Pallet as BlsDkg;
+
+/// The generator of G1, the commitment to the coefficient one.
+const GENERATOR: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+
+/// The point at infinity of G1, the commitment to the coefficient zero.
+const INFINITY: &str = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+/// The hash to G2 of the message `[0; 32]` with [`DST`], which is its signature by the secret
+/// one.
+const SIGNATURE: &str = "b72f4d8dfacf216719bcebb8349312f6bce892587291ee672fc0c7b6d16f427002713a961d122633e2deda35e803e9c3\
+02b3fcbc9d36fbb5fdb644bd41ce296399a5ea115f0c6cd612ff7e8b08477993cf1a2412fd81bc07e7ecc7380cfd2876";
+
+/// The share one, of the secret one shared by the commitments of [`commitments`].
+const SHARE: Share = {
+	let mut share = [0; 32];
+	share[31] = 1;
+	share
+};
+
+/// The commitments to the constant polynomial one, with `threshold` coefficients.
+fn commitments<T: Config>(threshold: u32) -> BoundedVec<PublicKey, T::MaxParticipants> {
+	(0..threshold)
+		.map(|k| array_bytes::hex2array_unchecked(if k == 0 { GENERATOR } else { INFINITY }))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+/// A full size encrypted share for each of `n` participants.
+fn encrypted_shares<T: Config>(
+	n: u32,
+) -> BoundedVec<BoundedVec<u8, T::MaxEncryptedShareLength>, T::MaxParticipants> {
+	let share: BoundedVec<_, _> =
+		vec![0; T::MaxEncryptedShareLength::get() as usize].try_into().unwrap();
+	vec![share; n as usize].try_into().unwrap()
+}
+
+fn participants<T: Config>(n: u32) -> BoundedVec<T::AccountId, T::MaxParticipants> {
+	(0..n)
+		.map(|i| account("participant", i, 0))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+/// Start a ceremony among `n` participants, with a threshold of `n`.
+fn start<T: Config>(n: u32) -> BoundedVec<T::AccountId, T::MaxParticipants> {
+	let participants = participants::<T>(n);
+	let committee = Committee { participants: participants.clone(), threshold: n };
+	Ceremony::<T>::put((committee, System::<T>::block_number()));
+	participants
+}
+
+/// Deal the secret one for `dealer` in a ceremony with a threshold of `n`.
+fn insert_dealing<T: Config>(dealer: &T::AccountId, n: u32) {
+	let dealing =
+		Dealing { commitments: commitments::<T>(n), encrypted_shares: encrypted_shares::<T>(n) };
+	Dealings::<T>::insert(dealer, dealing);
+}
+
+/// Move on by `phases` phases.
+fn skip_phases<T: Config>(phases: u32) {
+	let now = System::<T>::block_number();
+	System::<T>::set_block_number(now + T::PhaseLength::get() * phases.into());
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	start_ceremony {
+		let n in 1 .. T::MaxParticipants::get();
+		let participants = participants::<T>(n);
+		let call = Call::<T>::start_ceremony { participants: participants.clone(), threshold: n };
+		let origin = T::StartOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::CeremonyStarted { participants, threshold: n }.into());
+	}
+
+	deal {
+		let n in 1 .. T::MaxParticipants::get();
+		let dealer = start::<T>(n)[0].clone();
+		let commitments = commitments::<T>(n);
+		let encrypted_shares = encrypted_shares::<T>(n);
+	}: _(RawOrigin::Signed(dealer.clone()), commitments, encrypted_shares)
+	verify {
+		assert_last_event::<T>(Event::Dealt { dealer }.into());
+	}
+
+	complain {
+		let participants = start::<T>(2);
+		let (dealer, complainer) = (participants[0].clone(), participants[1].clone());
+		insert_dealing::<T>(&dealer, 2);
+		skip_phases::<T>(1);
+	}: _(RawOrigin::Signed(complainer.clone()), dealer.clone())
+	verify {
+		assert_last_event::<T>(Event::Complained { dealer, complainer }.into());
+	}
+
+	justify {
+		let n in 2 .. T::MaxParticipants::get();
+		let participants = start::<T>(n);
+		let (dealer, complainer) = (participants[0].clone(), participants[1].clone());
+		insert_dealing::<T>(&dealer, n);
+		Complaints::<T>::insert(&dealer, &complainer, ());
+		skip_phases::<T>(2);
+	}: _(RawOrigin::Signed(dealer.clone()), complainer.clone(), SHARE)
+	verify {
+		assert_last_event::<T>(Event::Justified { dealer, complainer, share: SHARE }.into());
+	}
+
+	finalize {
+		let n in 1 .. T::MaxParticipants::get();
+		let caller: T::AccountId = whitelisted_caller();
+		for dealer in start::<T>(n) {
+			insert_dealing::<T>(&dealer, n);
+		}
+		skip_phases::<T>(3);
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert!(Pallet::<T>::group_public_key().is_some());
+	}
+
+	combine_signatures {
+		let n in 1 .. T::MaxParticipants::get();
+		let caller: T::AccountId = whitelisted_caller();
+		GroupCommittee::<T>::put(Committee { participants: participants::<T>(n), threshold: n });
+		GroupCommitments::<T>::put(commitments::<T>(n));
+		let message: BoundedVec<_, _> = vec![0; 32].try_into().unwrap();
+		let signature = array_bytes::hex2array_unchecked(SIGNATURE);
+		let partial_signatures: BoundedVec<_, _> =
+			(0..n).map(|position| (position, signature)).collect::<Vec<_>>().try_into().unwrap();
+	}: _(RawOrigin::Signed(caller), message.clone(), partial_signatures)
+	verify {
+		let message_hash = T::Hashing::hash(&message);
+		assert_last_event::<T>(Event::SignatureCombined { message_hash, signature }.into());
+	}
+
+	impl_benchmark_test_suite!(BlsDkg, crate::mock::new_test_ext(), crate::mock::Test);
+}
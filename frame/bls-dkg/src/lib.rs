@@ -0,0 +1,545 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # BLS DKG Pallet
+//!
+//! Generates a BLS12-381 threshold key among a committee of accounts with a distributed key
+//! generation, so that no one knows its secret, and verifies and combines signatures by the
+//! shares of the key. This is the basis of a randomness beacon.
+//!
+//! The key generation is the DKG of Pedersen, a joint Feldman secret sharing with complaints. A
+//! ceremony started with [`Pallet::start_ceremony`] runs in three phases of
+//! [`Config::PhaseLength`] blocks each:
+//!
+//! 1. Dealing: each participant deals a random secret with [`Pallet::deal`], publishing Feldman
+//!    commitments to the polynomial sharing it and the share of every participant, encrypted to
+//!    them off-chain.
+//! 2. Complaining: a participant whose share does not match the commitments of its dealer complains
+//!    with [`Pallet::complain`].
+//! 3. Justifying: a dealer answers each complaint against it by revealing the share with
+//!    [`Pallet::justify`], which is checked against its commitments.
+//!
+//! [`Pallet::finalize`] then disqualifies the dealers which did not deal or left a complaint
+//! unanswered, and derives the group key from the commitments of the others. The share of a
+//! participant is the sum of the shares it received from these dealers. Partial signatures by
+//! the shares can be verified with [`Pallet::verify_partial_signature`], and any threshold of
+//! them combined into a signature by the group key with [`Pallet::combine_signatures`].
+//!
+//! The participant at position `i` of the committee holds the share at index `i + 1`. Public keys
+//! and commitments are in G1 and signatures in G2, compressed in the zcash encoding. Messages are
+//! hashed to G2 with the [`DST`] domain separation tag. The curve operations run natively through
+//! the BLS12-381 threshold host functions of `sp_io::crypto`.
+//!
+//! Unlike the DKG of Gennaro et al., the DKG of Pedersen lets an adversary controlling some
+//! participants bias the distribution of the group key, though not learn its secret.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	traits::Get,
+	BoundedVec, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The domain separation tag messages are hashed to G2 with, that of the basic ciphersuite.
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// A compressed G1 public key or commitment.
+pub type PublicKey = [u8; 48];
+
+/// A compressed G2 signature.
+pub type Signature = [u8; 96];
+
+/// A 32-byte big-endian share of a secret.
+pub type Share = [u8; 32];
+
+/// The participants sharing a key and the number of them needed to sign.
+#[derive(Clone, Eq, PartialEq, RuntimeDebugNoBound, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Committee<T: Config> {
+	/// The participants, the one at position `i` holding the share at index `i + 1`.
+	pub participants: BoundedVec<T::AccountId, T::MaxParticipants>,
+	/// The number of shares needed to sign.
+	pub threshold: u32,
+}
+
+/// The dealing of a participant.
+#[derive(Clone, Eq, PartialEq, RuntimeDebugNoBound, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Dealing<T: Config> {
+	/// The coefficients of the polynomial sharing the secret of the dealer times the generator of
+	/// G1, from the constant term up.
+	pub commitments: BoundedVec<PublicKey, T::MaxParticipants>,
+	/// The share of each participant, encrypted to them.
+	pub encrypted_shares:
+		BoundedVec<BoundedVec<u8, T::MaxEncryptedShareLength>, T::MaxParticipants>,
+}
+
+/// The phase of a ceremony.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+enum Phase {
+	Dealing,
+	Complaining,
+	Justifying,
+	Finished,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Hash;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may start a ceremony.
+		type StartOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of participants of a ceremony.
+		///
+		/// Must be at most [`sp_io::BLS12_381_MAX_AGGREGATION`].
+		#[pallet::constant]
+		type MaxParticipants: Get<u32>;
+
+		/// The maximum length of an encrypted share.
+		#[pallet::constant]
+		type MaxEncryptedShareLength: Get<u32>;
+
+		/// The maximum length of a signed message.
+		#[pallet::constant]
+		type MaxMessageLength: Get<u32>;
+
+		/// The number of blocks of each phase of a ceremony.
+		#[pallet::constant]
+		type PhaseLength: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The committee of the running ceremony and the block it started at.
+	#[pallet::storage]
+	pub type Ceremony<T: Config> = StorageValue<_, (Committee<T>, T::BlockNumber)>;
+
+	/// The dealings of the running ceremony.
+	#[pallet::storage]
+	pub type Dealings<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Dealing<T>>;
+
+	/// The unanswered complaints of the running ceremony, by dealer and complainer.
+	#[pallet::storage]
+	pub type Complaints<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The committee sharing the group key.
+	#[pallet::storage]
+	pub type GroupCommittee<T: Config> = StorageValue<_, Committee<T>>;
+
+	/// The commitments to the polynomial sharing the group key, the first being the group key.
+	#[pallet::storage]
+	pub type GroupCommitments<T: Config> =
+		StorageValue<_, BoundedVec<PublicKey, T::MaxParticipants>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A ceremony among `participants` started.
+		CeremonyStarted {
+			participants: BoundedVec<T::AccountId, T::MaxParticipants>,
+			threshold: u32,
+		},
+		/// A participant dealt its secret.
+		Dealt { dealer: T::AccountId },
+		/// A participant complained about its share from a dealer.
+		Complained { dealer: T::AccountId, complainer: T::AccountId },
+		/// A dealer answered a complaint by revealing the share of the complainer.
+		Justified { dealer: T::AccountId, complainer: T::AccountId, share: Share },
+		/// A ceremony derived a group key from the dealings of `qualified`.
+		KeyGenerated {
+			public_key: PublicKey,
+			qualified: BoundedVec<T::AccountId, T::MaxParticipants>,
+		},
+		/// A ceremony failed, with fewer qualified dealers than the threshold.
+		KeyGenerationFailed { qualified: BoundedVec<T::AccountId, T::MaxParticipants> },
+		/// Partial signatures of the message with the given hash were combined into a signature by
+		/// the group key.
+		SignatureCombined { message_hash: T::Hash, signature: Signature },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A ceremony is already running.
+		CeremonyInProgress,
+		/// No ceremony is running.
+		NoCeremony,
+		/// The ceremony is not in the phase of the call.
+		WrongPhase,
+		/// The threshold is zero or more than the participants.
+		InvalidThreshold,
+		/// An account is a participant more than once.
+		DuplicateParticipant,
+		/// The account is not a participant of the ceremony.
+		NotParticipant,
+		/// The participant already dealt.
+		AlreadyDealt,
+		/// The number of commitments is not the threshold.
+		WrongNumberOfCommitments,
+		/// The number of encrypted shares is not the number of participants.
+		WrongNumberOfShares,
+		/// A commitment is not a valid point.
+		InvalidCommitment,
+		/// The dealer did not deal.
+		NoDealing,
+		/// The complaint is about the complainer's own dealing or was already made.
+		InvalidComplaint,
+		/// There is no such complaint.
+		NoComplaint,
+		/// The revealed share does not match the commitments of the dealer.
+		InvalidShare,
+		/// No group key was generated.
+		NoGroupKey,
+		/// There are fewer partial signatures than the threshold.
+		NotEnoughSignatures,
+		/// A partial signature is by an unknown or repeated index, or did not verify.
+		InvalidPartialSignature,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxParticipants::get() <= sp_io::BLS12_381_MAX_AGGREGATION,
+				"`MaxParticipants` must be at most `BLS12_381_MAX_AGGREGATION`",
+			);
+			assert!(!T::PhaseLength::get().is_zero(), "`PhaseLength` must not be zero");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Start a ceremony generating a key shared by `participants`, any `threshold` of which
+		/// can sign.
+		///
+		/// The dispatch origin for this call must be `StartOrigin`.
+		#[pallet::weight(T::WeightInfo::start_ceremony(participants.len() as u32))]
+		pub fn start_ceremony(
+			origin: OriginFor<T>,
+			participants: BoundedVec<T::AccountId, T::MaxParticipants>,
+			threshold: u32,
+		) -> DispatchResult {
+			T::StartOrigin::ensure_origin(origin)?;
+
+			ensure!(!Ceremony::<T>::exists(), Error::<T>::CeremonyInProgress);
+			ensure!(
+				threshold > 0 && threshold as usize <= participants.len(),
+				Error::<T>::InvalidThreshold,
+			);
+			let mut sorted = participants.to_vec();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == participants.len(), Error::<T>::DuplicateParticipant);
+
+			let committee = Committee { participants: participants.clone(), threshold };
+			Ceremony::<T>::put((committee, frame_system::Pallet::<T>::block_number()));
+
+			Self::deposit_event(Event::CeremonyStarted { participants, threshold });
+			Ok(())
+		}
+
+		/// Deal the secret of the caller, with `commitments` to the polynomial sharing it and the
+		/// share of each participant encrypted to them, in the order of the participants.
+		///
+		/// The share of the participant at position `i` is the evaluation of the polynomial at
+		/// `i + 1`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a participant, in the dealing
+		/// phase.
+		#[pallet::weight(T::WeightInfo::deal(encrypted_shares.len() as u32))]
+		pub fn deal(
+			origin: OriginFor<T>,
+			commitments: BoundedVec<PublicKey, T::MaxParticipants>,
+			encrypted_shares: BoundedVec<
+				BoundedVec<u8, T::MaxEncryptedShareLength>,
+				T::MaxParticipants,
+			>,
+		) -> DispatchResult {
+			let dealer = ensure_signed(origin)?;
+
+			let committee = Self::ceremony_in(Phase::Dealing)?;
+			ensure!(committee.participants.contains(&dealer), Error::<T>::NotParticipant);
+			ensure!(!Dealings::<T>::contains_key(&dealer), Error::<T>::AlreadyDealt);
+			ensure!(
+				commitments.len() == committee.threshold as usize,
+				Error::<T>::WrongNumberOfCommitments,
+			);
+			ensure!(
+				encrypted_shares.len() == committee.participants.len(),
+				Error::<T>::WrongNumberOfShares,
+			);
+			ensure!(
+				sp_io::crypto::bls12_381_threshold_public_key(commitments.to_vec(), 0).is_some(),
+				Error::<T>::InvalidCommitment,
+			);
+
+			Dealings::<T>::insert(&dealer, Dealing { commitments, encrypted_shares });
+
+			Self::deposit_event(Event::Dealt { dealer });
+			Ok(())
+		}
+
+		/// Complain that the share of the caller from `dealer` does not match its commitments.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a participant, in the
+		/// complaining phase.
+		#[pallet::weight(T::WeightInfo::complain())]
+		pub fn complain(origin: OriginFor<T>, dealer: T::AccountId) -> DispatchResult {
+			let complainer = ensure_signed(origin)?;
+
+			let committee = Self::ceremony_in(Phase::Complaining)?;
+			ensure!(committee.participants.contains(&complainer), Error::<T>::NotParticipant);
+			ensure!(Dealings::<T>::contains_key(&dealer), Error::<T>::NoDealing);
+			ensure!(
+				dealer != complainer && !Complaints::<T>::contains_key(&dealer, &complainer),
+				Error::<T>::InvalidComplaint,
+			);
+
+			Complaints::<T>::insert(&dealer, &complainer, ());
+
+			Self::deposit_event(Event::Complained { dealer, complainer });
+			Ok(())
+		}
+
+		/// Answer the complaint of `complainer` by revealing its `share` of the secret of the
+		/// caller.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the dealer complained about, in
+		/// the justifying phase.
+		#[pallet::weight(T::WeightInfo::justify(T::MaxParticipants::get()))]
+		pub fn justify(
+			origin: OriginFor<T>,
+			complainer: T::AccountId,
+			share: Share,
+		) -> DispatchResult {
+			let dealer = ensure_signed(origin)?;
+
+			let committee = Self::ceremony_in(Phase::Justifying)?;
+			ensure!(Complaints::<T>::contains_key(&dealer, &complainer), Error::<T>::NoComplaint);
+			let dealing = Dealings::<T>::get(&dealer).ok_or(Error::<T>::NoDealing)?;
+			let index =
+				Self::index_of(&committee, &complainer).ok_or(Error::<T>::NotParticipant)?;
+			ensure!(
+				sp_io::crypto::bls12_381_threshold_verify_share(
+					dealing.commitments.to_vec(),
+					index,
+					&share,
+				),
+				Error::<T>::InvalidShare,
+			);
+
+			Complaints::<T>::remove(&dealer, &complainer);
+
+			Self::deposit_event(Event::Justified { dealer, complainer, share });
+			Ok(())
+		}
+
+		/// Finish the running ceremony, deriving the group key from the dealings of the dealers
+		/// without unanswered complaints, if there are at least the threshold of them.
+		///
+		/// The dispatch origin for this call must be _Signed_, after the justifying phase.
+		#[pallet::weight(T::WeightInfo::finalize(T::MaxParticipants::get()))]
+		pub fn finalize(origin: OriginFor<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let committee = Self::ceremony_in(Phase::Finished)?;
+			let mut qualified = Vec::new();
+			let mut dealings = Vec::new();
+			for participant in committee.participants.iter() {
+				if let Some(dealing) = Dealings::<T>::get(participant) {
+					if Complaints::<T>::iter_prefix(participant).next().is_none() {
+						qualified.push(participant.clone());
+						dealings.push(dealing);
+					}
+				}
+			}
+			let qualified: BoundedVec<_, _> =
+				qualified.try_into().expect("Qualified dealers are participants; qed");
+
+			Ceremony::<T>::kill();
+			let _ = Dealings::<T>::clear(u32::MAX, None);
+			let _ = Complaints::<T>::clear(u32::MAX, None);
+
+			if qualified.len() < committee.threshold as usize {
+				Self::deposit_event(Event::KeyGenerationFailed { qualified });
+				return Ok(())
+			}
+
+			let commitments = (0..committee.threshold as usize)
+				.map(|k| {
+					sp_io::crypto::bls12_381_aggregate_g1(
+						dealings.iter().map(|dealing| dealing.commitments[k]).collect(),
+					)
+					.expect("Commitments were checked when dealt and are at most `MaxParticipants`; qed")
+				})
+				.collect::<Vec<_>>();
+			let public_key = commitments[0];
+			GroupCommitments::<T>::put(
+				BoundedVec::try_from(commitments).expect("There are `threshold` commitments; qed"),
+			);
+			GroupCommittee::<T>::put(committee);
+
+			Self::deposit_event(Event::KeyGenerated { public_key, qualified });
+			Ok(())
+		}
+
+		/// Combine the partial signatures of `message` by the shares at the given positions of
+		/// the group committee into a signature by the group key.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::combine_signatures(partial_signatures.len() as u32))]
+		pub fn combine_signatures(
+			origin: OriginFor<T>,
+			message: BoundedVec<u8, T::MaxMessageLength>,
+			partial_signatures: BoundedVec<(u32, Signature), T::MaxParticipants>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let signature = Self::combine(&message, &partial_signatures)?;
+
+			let message_hash = T::Hashing::hash(&message);
+			Self::deposit_event(Event::SignatureCombined { message_hash, signature });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The group key, once generated.
+	pub fn group_public_key() -> Option<PublicKey> {
+		GroupCommitments::<T>::get().and_then(|commitments| commitments.first().copied())
+	}
+
+	/// The public key of the share of the participant at `position` of the group committee.
+	pub fn share_public_key(position: u32) -> Option<PublicKey> {
+		let committee = GroupCommittee::<T>::get()?;
+		if position as usize >= committee.participants.len() {
+			return None
+		}
+		let commitments = GroupCommitments::<T>::get()?;
+		sp_io::crypto::bls12_381_threshold_public_key(commitments.to_vec(), position + 1)
+	}
+
+	/// Whether `signature` is a signature of `message` by the share of the participant at
+	/// `position` of the group committee.
+	pub fn verify_partial_signature(position: u32, message: &[u8], signature: &Signature) -> bool {
+		match Self::share_public_key(position) {
+			Some(public_key) =>
+				sp_io::crypto::bls12_381_verify(&public_key, message, signature, DST),
+			None => false,
+		}
+	}
+
+	/// Combine the partial signatures of `message` by the participants at the given positions
+	/// of the group committee into a signature by the group key.
+	///
+	/// Each partial signature is verified, and so is the result.
+	pub fn combine(
+		message: &[u8],
+		partial_signatures: &[(u32, Signature)],
+	) -> Result<Signature, DispatchError> {
+		let committee = GroupCommittee::<T>::get().ok_or(Error::<T>::NoGroupKey)?;
+		let public_key = Self::group_public_key().ok_or(Error::<T>::NoGroupKey)?;
+		ensure!(
+			partial_signatures.len() >= committee.threshold as usize,
+			Error::<T>::NotEnoughSignatures,
+		);
+		for (position, signature) in partial_signatures {
+			ensure!(
+				Self::verify_partial_signature(*position, message, signature),
+				Error::<T>::InvalidPartialSignature,
+			);
+		}
+
+		let (positions, signatures): (Vec<_>, Vec<_>) = partial_signatures.iter().copied().unzip();
+		let signature = sp_io::crypto::bls12_381_threshold_combine(
+			positions.into_iter().map(|position| position + 1).collect(),
+			signatures,
+		)
+		.filter(|signature| sp_io::crypto::bls12_381_verify(&public_key, message, signature, DST))
+		.ok_or(Error::<T>::InvalidPartialSignature)?;
+		Ok(signature)
+	}
+
+	/// The committee of the running ceremony, if it is in `phase`.
+	fn ceremony_in(phase: Phase) -> Result<Committee<T>, Error<T>> {
+		let (committee, start) = Ceremony::<T>::get().ok_or(Error::<T>::NoCeremony)?;
+		ensure!(Self::phase(start) == phase, Error::<T>::WrongPhase);
+		Ok(committee)
+	}
+
+	/// The phase of a ceremony started at `start`.
+	fn phase(start: T::BlockNumber) -> Phase {
+		let elapsed = frame_system::Pallet::<T>::block_number().saturating_sub(start);
+		let length = T::PhaseLength::get();
+		if elapsed < length {
+			Phase::Dealing
+		} else if elapsed < length.saturating_add(length) {
+			Phase::Complaining
+		} else if elapsed < length.saturating_mul(3u32.into()) {
+			Phase::Justifying
+		} else {
+			Phase::Finished
+		}
+	}
+
+	/// The share index of `who` in `committee`.
+	fn index_of(committee: &Committee<T>, who: &T::AccountId) -> Option<u32> {
+		committee
+			.participants
+			.iter()
+			.position(|participant| participant == who)
+			.map(|position| position as u32 + 1)
+	}
+}
@@ -0,0 +1,363 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the BLS DKG pallet.
+
+use super::{
+	Ceremony, Complaints, Dealings, Error, Event, GroupCommittee, Pallet as BlsDkg, PublicKey,
+	Share, Signature, DST,
+};
+use crate::mock::*;
+use ark_bls12_381::{g2, Fr, G1Affine, G2Projective};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+	AffineRepr,
+};
+use ark_ff::{field_hashers::DefaultFieldHasher, BigInteger, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_system::RawOrigin;
+use sha2::Sha256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+/// The polynomials dealt by the participants 1, 2 and 3, from the constant term up.
+const POLYNOMIALS: [[u64; 2]; 3] = [[3, 5], [7, 11], [13, 17]];
+
+fn compressed<const N: usize>(point: impl CanonicalSerialize) -> [u8; N] {
+	let mut bytes = [0; N];
+	point.serialize_compressed(&mut bytes[..]).unwrap();
+	bytes
+}
+
+fn public_key(secret: Fr) -> PublicKey {
+	compressed(G1Affine::generator() * secret)
+}
+
+fn commitments(polynomial: &[u64]) -> BoundedVec<PublicKey, MaxParticipants> {
+	polynomial
+		.iter()
+		.map(|coefficient| public_key(Fr::from(*coefficient)))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+/// The evaluation of `polynomial` at `index`.
+fn share(polynomial: &[u64], index: u64) -> Fr {
+	polynomial
+		.iter()
+		.rev()
+		.fold(Fr::from(0u8), |share, coefficient| share * Fr::from(index) + Fr::from(*coefficient))
+}
+
+fn scalar(share: Fr) -> Share {
+	share.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn sign(secret: Fr, message: &[u8]) -> Signature {
+	let hash = MapToCurveBasedHasher::<
+		G2Projective,
+		DefaultFieldHasher<Sha256, 128>,
+		WBMap<g2::Config>,
+	>::new(DST)
+	.unwrap()
+	.hash(message)
+	.unwrap();
+	compressed(hash * secret)
+}
+
+fn encrypted_shares(
+	n: usize,
+) -> BoundedVec<BoundedVec<u8, MaxEncryptedShareLength>, MaxParticipants> {
+	vec![b"encrypted share".to_vec().try_into().unwrap(); n].try_into().unwrap()
+}
+
+/// Start a ceremony among the accounts 1, 2 and 3.
+fn start(threshold: u32) {
+	assert_ok!(BlsDkg::<Test>::start_ceremony(
+		RawOrigin::Root.into(),
+		vec![1, 2, 3].try_into().unwrap(),
+		threshold,
+	));
+}
+
+/// Deal the polynomial of `dealer`.
+fn deal(dealer: u64) {
+	assert_ok!(BlsDkg::<Test>::deal(
+		RawOrigin::Signed(dealer).into(),
+		commitments(&POLYNOMIALS[dealer as usize - 1]),
+		encrypted_shares(3),
+	));
+}
+
+/// Move to the `phase`th phase of a ceremony started at block 1.
+fn run_to_phase(phase: u64) {
+	System::set_block_number(1 + 10 * phase);
+}
+
+#[test]
+fn starts_ceremonies() {
+	new_test_ext().execute_with(|| {
+		let start_with = |origin: RawOrigin<u64>, participants: Vec<u64>, threshold| {
+			BlsDkg::<Test>::start_ceremony(
+				origin.into(),
+				participants.try_into().unwrap(),
+				threshold,
+			)
+		};
+
+		assert_noop!(start_with(RawOrigin::Signed(1), vec![1, 2, 3], 2), DispatchError::BadOrigin);
+		assert_noop!(
+			start_with(RawOrigin::Root, vec![1, 2, 3], 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			start_with(RawOrigin::Root, vec![1, 2, 3], 4),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			start_with(RawOrigin::Root, vec![1, 2, 1], 2),
+			Error::<Test>::DuplicateParticipant,
+		);
+
+		start(2);
+		System::assert_last_event(
+			Event::CeremonyStarted {
+				participants: vec![1, 2, 3].try_into().unwrap(),
+				threshold: 2,
+			}
+			.into(),
+		);
+		assert_noop!(
+			start_with(RawOrigin::Root, vec![1, 2, 3], 2),
+			Error::<Test>::CeremonyInProgress,
+		);
+	});
+}
+
+#[test]
+fn deals() {
+	new_test_ext().execute_with(|| {
+		let deal_with = |dealer: u64, commitments, shares| {
+			BlsDkg::<Test>::deal(RawOrigin::Signed(dealer).into(), commitments, shares)
+		};
+
+		assert_noop!(
+			deal_with(1, commitments(&[3, 5]), encrypted_shares(3)),
+			Error::<Test>::NoCeremony,
+		);
+		start(2);
+		assert_noop!(
+			deal_with(4, commitments(&[3, 5]), encrypted_shares(3)),
+			Error::<Test>::NotParticipant,
+		);
+		assert_noop!(
+			deal_with(1, commitments(&[3, 5, 7]), encrypted_shares(3)),
+			Error::<Test>::WrongNumberOfCommitments,
+		);
+		assert_noop!(
+			deal_with(1, commitments(&[3, 5]), encrypted_shares(2)),
+			Error::<Test>::WrongNumberOfShares,
+		);
+		assert_noop!(
+			deal_with(1, vec![[0; 48], [0; 48]].try_into().unwrap(), encrypted_shares(3)),
+			Error::<Test>::InvalidCommitment,
+		);
+
+		deal(1);
+		System::assert_last_event(Event::Dealt { dealer: 1 }.into());
+		assert_eq!(Dealings::<Test>::get(1).unwrap().commitments, commitments(&POLYNOMIALS[0]));
+		assert_noop!(
+			deal_with(1, commitments(&[3, 5]), encrypted_shares(3)),
+			Error::<Test>::AlreadyDealt,
+		);
+
+		run_to_phase(1);
+		assert_noop!(
+			deal_with(2, commitments(&[3, 5]), encrypted_shares(3)),
+			Error::<Test>::WrongPhase,
+		);
+	});
+}
+
+#[test]
+fn handles_complaints() {
+	new_test_ext().execute_with(|| {
+		start(2);
+		deal(1);
+		deal(2);
+		assert_noop!(
+			BlsDkg::<Test>::complain(RawOrigin::Signed(2).into(), 1),
+			Error::<Test>::WrongPhase,
+		);
+
+		run_to_phase(1);
+		assert_ok!(BlsDkg::<Test>::complain(RawOrigin::Signed(2).into(), 1));
+		System::assert_last_event(Event::Complained { dealer: 1, complainer: 2 }.into());
+		assert_noop!(
+			BlsDkg::<Test>::complain(RawOrigin::Signed(2).into(), 1),
+			Error::<Test>::InvalidComplaint,
+		);
+		assert_noop!(
+			BlsDkg::<Test>::complain(RawOrigin::Signed(1).into(), 1),
+			Error::<Test>::InvalidComplaint,
+		);
+		assert_noop!(
+			BlsDkg::<Test>::complain(RawOrigin::Signed(1).into(), 3),
+			Error::<Test>::NoDealing,
+		);
+		assert_noop!(
+			BlsDkg::<Test>::complain(RawOrigin::Signed(4).into(), 1),
+			Error::<Test>::NotParticipant,
+		);
+
+		run_to_phase(2);
+		// Participant 2, at position 1, holds the share at index 2.
+		assert_noop!(
+			BlsDkg::<Test>::justify(
+				RawOrigin::Signed(1).into(),
+				2,
+				scalar(share(&POLYNOMIALS[0], 3)),
+			),
+			Error::<Test>::InvalidShare,
+		);
+		assert_noop!(
+			BlsDkg::<Test>::justify(
+				RawOrigin::Signed(2).into(),
+				1,
+				scalar(share(&POLYNOMIALS[1], 1)),
+			),
+			Error::<Test>::NoComplaint,
+		);
+		let share = scalar(share(&POLYNOMIALS[0], 2));
+		assert_ok!(BlsDkg::<Test>::justify(RawOrigin::Signed(1).into(), 2, share));
+		System::assert_last_event(Event::Justified { dealer: 1, complainer: 2, share }.into());
+		assert!(!Complaints::<Test>::contains_key(1, 2));
+	});
+}
+
+#[test]
+fn generates_keys_and_combines_signatures() {
+	new_test_ext().execute_with(|| {
+		start(2);
+		deal(1);
+		deal(2);
+		deal(3);
+		run_to_phase(1);
+		assert_ok!(BlsDkg::<Test>::complain(RawOrigin::Signed(3).into(), 2));
+		run_to_phase(2);
+		assert_noop!(
+			BlsDkg::<Test>::finalize(RawOrigin::Signed(4).into()),
+			Error::<Test>::WrongPhase
+		);
+
+		// The complaint about participant 2 is left unanswered, disqualifying it.
+		run_to_phase(3);
+		assert_ok!(BlsDkg::<Test>::finalize(RawOrigin::Signed(4).into()));
+		let secret = Fr::from(POLYNOMIALS[0][0] + POLYNOMIALS[2][0]);
+		System::assert_last_event(
+			Event::KeyGenerated {
+				public_key: public_key(secret),
+				qualified: vec![1, 3].try_into().unwrap(),
+			}
+			.into(),
+		);
+		assert_eq!(BlsDkg::<Test>::group_public_key(), Some(public_key(secret)));
+		assert_eq!(GroupCommittee::<Test>::get().unwrap().threshold, 2);
+		assert!(!Ceremony::<Test>::exists());
+		assert_eq!(Dealings::<Test>::iter().count(), 0);
+		assert_eq!(Complaints::<Test>::iter().count(), 0);
+
+		// The share of the participant at `position` is the sum of its shares from participants
+		// 1 and 3, at index `position + 1`.
+		let secret_share = |position: u64| {
+			share(&POLYNOMIALS[0], position + 1) + share(&POLYNOMIALS[2], position + 1)
+		};
+		for position in 0..3 {
+			assert_eq!(
+				BlsDkg::<Test>::share_public_key(position),
+				Some(public_key(secret_share(position as u64))),
+			);
+		}
+		assert_eq!(BlsDkg::<Test>::share_public_key(3), None);
+
+		let message = b"round 1";
+		let partial = |position: u32| (position, sign(secret_share(position as u64), message));
+		assert!(BlsDkg::<Test>::verify_partial_signature(0, message, &partial(0).1));
+		assert!(!BlsDkg::<Test>::verify_partial_signature(1, message, &partial(0).1));
+
+		for positions in [[0, 1], [2, 0], [1, 2]] {
+			assert_eq!(
+				BlsDkg::<Test>::combine(message, &positions.map(partial)),
+				Ok(sign(secret, message)),
+			);
+		}
+		assert_eq!(
+			BlsDkg::<Test>::combine(message, &[partial(0)]),
+			Err(Error::<Test>::NotEnoughSignatures.into())
+		);
+		assert_eq!(
+			BlsDkg::<Test>::combine(message, &[partial(0), (1, partial(0).1)]),
+			Err(Error::<Test>::InvalidPartialSignature.into()),
+		);
+		assert_eq!(
+			BlsDkg::<Test>::combine(message, &[partial(0), partial(0)]),
+			Err(Error::<Test>::InvalidPartialSignature.into()),
+		);
+
+		let message: BoundedVec<_, _> = message.to_vec().try_into().unwrap();
+		assert_ok!(BlsDkg::<Test>::combine_signatures(
+			RawOrigin::Signed(4).into(),
+			message.clone(),
+			vec![partial(2), partial(1)].try_into().unwrap(),
+		));
+		System::assert_last_event(
+			Event::SignatureCombined {
+				message_hash: BlakeTwo256::hash(&message),
+				signature: sign(secret, &message),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn fails_without_enough_qualified_dealers() {
+	new_test_ext().execute_with(|| {
+		start(2);
+		deal(1);
+		run_to_phase(3);
+
+		assert_ok!(BlsDkg::<Test>::finalize(RawOrigin::Signed(4).into()));
+		System::assert_last_event(
+			Event::KeyGenerationFailed { qualified: vec![1].try_into().unwrap() }.into(),
+		);
+		assert_eq!(BlsDkg::<Test>::group_public_key(), None);
+		assert!(!Ceremony::<Test>::exists());
+		assert_noop!(
+			BlsDkg::<Test>::combine_signatures(
+				RawOrigin::Signed(4).into(),
+				b"round 1".to_vec().try_into().unwrap(),
+				Default::default(),
+			),
+			Error::<Test>::NoGroupKey,
+		);
+	});
+}
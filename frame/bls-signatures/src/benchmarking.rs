@@ -0,0 +1,444 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the BLS signatures pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+use sp_runtime::traits::Hash;
+
+#[cfg(test)]
+use crate::Pallet as BlsSignatures;
+
+/// A public key with its proof of possession and signatures.
+pub struct Key {
+	public_key: &'static str,
+	proof_of_possession: &'static str,
+	signature: &'static str,
+	same_message_signature: &'static str,
+}
+
+// Public keys of the secret keys `i + 1`, their proofs of possession, and their signatures of
+// the messages `[i; 32]` and `[16; 32]`, generated for [`MinPk`] with:
+// ```
+// let secret = Fr::from(i + 1);
+// let public_key = G1Affine::generator() * secret;
+// let proof_of_possession = pop_hasher.hash(&compressed(public_key)).unwrap() * secret;
+// let signature = hasher.hash(&[i as u8; 32]).unwrap() * secret;
+// let same_message_signature = hasher.hash(&[16; 32]).unwrap() * secret;
+// ```
+// where `pop_hasher` hashes to G2 with the `BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` tag and
+// `hasher` with [`Variant::DST`].
+const MIN_PK_KEYS: [Key; 16] = [
+	Key {
+		public_key: "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+		proof_of_possession: "abd367bf7fe788f30632c5d7e92a9958da6164eea2f0cc2d4678a1bcc281f1bede7fc92f5624c84718da7c203f8f69cc\
+016b555c691666c80d48dbebdbb5985eff6618683e563660d926ab2e336376e011717f4d35754ba8cac2b33e0ab21f9a",
+		signature: "97502412bcfc3f1d88b71f1ad9b60fa37c332d19466fba1dc991d42bcd09bcd9f1c22a562646ffce0922793b6c69938b\
+076e5cd6cfb3c361fc767e5f40ce05486e1668825ffeecab89d7daa455a179736a387ae93b9b15d283d45ffa14cd4af7",
+		same_message_signature: "8bc1a8f8c419d04ea19ae597361728f6e9ad20c015695d6bebf6eb5599938e6915070961c1af0116200a888361b23c31\
+0d87368fc82e4aa9256ad60e8e798f52cc56029043a75d11b5df5adce23cd20828ef3e36bbac969f9b402c0e9a883f4c",
+	},
+	Key {
+		public_key: "a572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e",
+		proof_of_possession: "b9c8f3b4acd39eb4a9d1f9bf736202f76db8a1daccd74222b5ca83101fe6fa48c064c81279f3d068ab4cb087a20c3176\
+06a9354a75b0960210336f89eca4f7ee2595d5d77ba62d849c55f17fbdce7730766c4d252e5554eb50478ea41e08896e",
+		signature: "a3b71c8373adcadb031929b75955c8062750d537ae49badc67ec8771de8f1040055b180938adff059db1c63c16802ab6\
+04e2deac42f30e6cac3db6cad9b27aca4b76adc3116fb3c4478f9d4ed6ec668f8ce6cb43e342cd25726c24a5646cbfca",
+		same_message_signature: "b2034ff9aff5c52adaa65dfc15d3c7af41c28c961598f5c637496229cd7db893f6aef2bb6ac3af1962fbd7abf9c88cb0\
+03a74933ee2a0d25d9493290d732fbe327227afbe68cb5217f7bf9784fcfdb1a4a3fd488cfebc2fce071d311f43b3aa2",
+	},
+	Key {
+		public_key: "89ece308f9d1f0131765212deca99697b112d61f9be9a5f1f3780a51335b3ff981747a0b2ca2179b96d2c0c9024e5224",
+		proof_of_possession: "b9d5e0aa4c9def6ad336757ce08b31bbba2fc703673ddc61460871479d81ab06553efb2fbb1c50b2b4f11055ec110a6a\
+0344e7b3148e8b44533858cc015ef31f20a0863f35afa8f7bc9901199182b8a5336f46579acb6ba067abc08c0e9e1cdc",
+		signature: "951f7d465fae4b45ed97ab0564660762966c1354de1592900e31e2450e479d81772a3d4868876239d7cedc4cd84e12f9\
+087d5d2fa4391516ea5eba64bdb6f6081ff376b9f9c42d9cd410d6da3743b991596a953ba3a2c8b7f008ce38a5b53997",
+		same_message_signature: "a1eb21752902cb0b79537244c6bf78a7e87ac0e845e5452284b030de93f488e59ef475df90699fd0dd4c44cd9427b948\
+0a1eed278a66bf19b9b0aedbc9d92b9118eb90b1d119f6b9e9bb63c8b445c95376355365e1478f6ec16d2bf96dff8d6e",
+	},
+	Key {
+		public_key: "ac9b60d5afcbd5663a8a44b7c5a02f19e9a77ab0a35bd65809bb5c67ec582c897feb04decc694b13e08587f3ff9b5b60",
+		proof_of_possession: "8c0818f93d93455852801f5d61ba527bc07eed57e8c2aa05209fc7c2b31600a5f105ad60c4bbff09524c874fd6fd5bb1\
+18c147bbb145dac51a93ed9537df6b99229b21fb3638f44047132fd38bd359da491f965f94a804f200253932fdec4c28",
+		signature: "a11d520cff4f02795519d1703982d6619a3b5c216c970a5628db62a3e5bdf829d3482073473e60d121ebd6139549255e\
+078db5dc9e265c6bee8f6b5b555f5bb186a2233963df375ddc28e3aabf93371b73942b0ef3414ccec5bb25fcedfc0c09",
+		same_message_signature: "b051270e1b9a5257916c5510fdae4c349dbf4a603a1546bdcd07813329db26443f2091d9eff2f75dc6db3199b8f26e6c\
+0c24b4b8927a222145eb1333a42d48f34433d9cf7713d29fa9dbb0297a4c96bdccb1ecb85c2b36ae15d0d493b7f152ed",
+	},
+	Key {
+		public_key: "b0e7791fb972fe014159aa33a98622da3cdc98ff707965e536d8636b5fcc5ac7a91a8c46e59a00dca575af0f18fb13dc",
+		proof_of_possession: "86364cf1e152a63393df8507c9bd299326bb45a6a8f3af909ce52e6e50de06b2a70c89b0a488dce5a7a35a74895a299e\
+185c72616145e73c9685a7a1473a642247dc755304d37443deda47f19441aaaaa1b9b57ab351aeb7bfbb88966c8df5fc",
+		signature: "ae952201863a914fc4e5b6f5db8a5ff08a840dc1500e4ccb6a19e13806d18e75aa127e0e463bf08cd4c577ace03d9059\
+03a164cc69a85df81a4377ad2398146e23e3b1784d3c1d2161b5623b12a371aa6581d0e49a2e31ef8d553ea901e61215",
+		same_message_signature: "af5f71c2a38488d809d25b5daacf1f519d657269025bad90a334f6c0f592deb2667a2a39e20979e285c3321d80604a14\
+0ee4558a1409fc0c6ca8f8ff0e78f91b6d30514ae51f66c3c3877ad3c751ae53f361aa4fb697cef057433b8c27f2c37b",
+	},
+	Key {
+		public_key: "a6e82f6da4520f85c5d27d8f329eccfa05944fd1096b20734c894966d12a9e2a9a9744529d7212d33883113a0cadb909",
+		proof_of_possession: "a583f00fd3ff7370922cc7ecb809fe59742a13115bce03ac57e7ae545934b7f30d9a291dcb0cd3d15eeef1ddadfce9ab\
+05d9752c517d2b423cf0e02b887916648b43e2960575122f4a36d158842c1e1541bbd6f0b28f900e8298d00bb755c4bb",
+		signature: "b2e14cd967fc2e894f05ac23ba240664e4ab5b12195d505363d68bd7e1c0dfa636d6b382272b55fc6f4d2aa27992b4e1\
+0a51c42142db4f2af6337945d894892dc3ebbeb8855ee186098414de476cfd96824618dc6498fadb6d9d1c20c5430fac",
+		same_message_signature: "af109e2d0c8e9adb7307c4bceeb799cb48a1096364ea0a84bcf1b6cc4b774a1782dabbf61ba761acbc04e77fe5d77e93\
+129854a8e1e05ff8f5affe94ab1b18f2aeadb55feb7b6f6cfcd18a010a2314621b092fe88d27ad0dd554d589de89242e",
+	},
+	Key {
+		public_key: "b928f3beb93519eecf0145da903b40a4c97dca00b21f12ac0df3be9116ef2ef27b2ae6bcd4c5bc2d54ef5a70627efcb7",
+		proof_of_possession: "aa1ec06827a64d47a2312ac512cdfcc6e27414f8fb661de6c5ecdcfa251273946ca7e189de32490b01226ea1ae919043\
+14a7ff34e302e6df7a02b0ecbf05fef02a030d91d835f9dd795ff09fcd2df4875c794fdf9ee01457e383efe5d718e98c",
+		signature: "8f1479da017ac048a7c6afac1a17d778c5e3c60ae8eda22e3f1f094481561c3c3076001e8a54420e9c1f8ac3d9b9ba41\
+11921e913f55f55b89c2feb22560cbe6f043e27138d1b6f191bf4049d076d88c5616d1efe6faef42f8f9dfe543751eca",
+		same_message_signature: "a129db9e2bb706cf3a882b143c766e28c1342ed02d7f0cec9898701364e9f0a5b32e0ec75480feba490c260e19cd2e94\
+148d7215b3dc8fa1cee5009158a982084f9e494473a644bbac9b0e56a4dcc767ce2c65ffb184408a8ff78a8dd21872e4",
+	},
+	Key {
+		public_key: "a85ae765588126f5e860d019c0e26235f567a9c0c0b2d8ff30f3e8d436b1082596e5e7462d20f5be3764fd473e57f9cf",
+		proof_of_possession: "8b812eaa40b59276abafa6722dfccc09f6d174ecf6895949488a28d592837496fd3b3d1ab69d68dcba7940ac77d05692\
+0b7de48d3206f3ad16fe0b716d0e48c7ce6970a96c357bd9335ca51b866936b3ef4d7babb3d946b0a1f05c093b8912c1",
+		signature: "99e08019696d4434738dfb275d566fc71ee0203f077b16672327e3be3008c67e804a3461121c22c5ed1f6ae0358e4456\
+16e9f8fb4ed51ac9ef842dbb28c6c6358f13e12c3bede7c8a62694d490308e666465ed2824ca6a030aafdb8a86034975",
+		same_message_signature: "b3d8fa39c4fd87341828dc7e081e5c16add370bf86b42ccca4b6c28fad4911aecddb8a7bb74faa8c8332d17ad0b5bd24\
+00fef6006e3fe5fdc1c878a7d56506b0cba3114c7c478f6e2c6be24b93eb5f4a47c56d71430f2d893b04c66f07b0ca1b",
+	},
+	Key {
+		public_key: "99cdf3807146e68e041314ca93e1fee0991224ec2a74beb2866816fd0826ce7b6263ee31e953a86d1b72cc2215a57793",
+		proof_of_possession: "b4ecb2149f475104ffce0e7659cf76123c1b3ab2a07256e439b42f122cc9c70f11ef251a05a4d6a78ceddd6377254850\
+0c189622c63858adb6c57f23c85b2ef17fbbd46d822c8a70283765b621e047edaa452d5add500667e593b9e10442f847",
+		signature: "86b3a496961a615b20c0fa89040b9f81c64d6431bea9b28841702caab133c1d2b2a2236ed53212e67d6657f66450d4b9\
+04f0919b21ce8fc1b16d3b5096d49c5a3d0c379dfab1c49fdc308c679874bcfb9287c40e036acd83f7bda0491af7d03b",
+		same_message_signature: "abfa768b6bc74780e55bb16e1e1455a329f3cd3692454fa2ef1dc01f09cc7b52c0ae6ba3b0c76bfe737f1464520acc6c\
+175edea87aa643636e7d3411954422f77880845c04a0e55e375f74dc1591a5707bc126349f54ba62cbb213fcd76fe6b8",
+	},
+	Key {
+		public_key: "af81da25ecf1c84b577fefbedd61077a81dc43b00304015b2b596ab67f00e41c86bb00ebd0f90d4b125eb0539891aeed",
+		proof_of_possession: "b9df3dd29d191f30d7c3d449227c84983af033f3dba0e9aac9f1910ee7f2bc090767b2ca6b1fcdb88224beb91762cf38\
+19814edc68f8a76f2c1f863978dd31e00bd20c4cd75f6a29c7746cb06bb580f9152315a4e5b39d488274908ef1d88b52",
+		signature: "8ca555ce5be48e450075579b637c8d952ee5049da3733d3fdea3ce85c56e06f20d06f6d8cca57f05983dc3fd9f9e1b54\
+012cd640776819bd2fb203be28b2ee1cbbfbd662267748d06aca6c181d2b3ecbcf865253b85051ed7e12d53ff8c30b72",
+		same_message_signature: "a00bb1202dc183d251b85fade4f8eb0abba30a11a81006481b97e92b125be356321cd43d61d784fe71ea81594b52f187\
+0d5ac134a038ccb72fabb387d72820bc07e79544d017980462ff19256431f35964b7a17f11a92a6d4c7ad13a4873453e",
+	},
+	Key {
+		public_key: "80fd75ebcc0a21649e3177bcce15426da0e4f25d6828fbf4038d4d7ed3bd4421de3ef61d70f794687b12b2d571971a55",
+		proof_of_possession: "94542b5f96347b2c6acbbedd9b3f707dc829765d47be0af0ba1ea2e6a350bd6cc5679fddc6fa0d7f552b1ce982f75fd8\
+028fad69e3a661fb2a4320291ab60f0c519454c60bd14a007ac654d61108e5f08f851ff2caf732fff766158f1492c14f",
+		signature: "928847f7a18e3a9712c3a6ccfac37e351f699fc472acca3d5059e153578088834f834d5c367fddd583467c1f5af874c8\
+0c9c9e849a66b8597cd81f179e4ab8b16c3de70966bdc53ab3313339b56995bc8f90fdd979adce2ef00241ce58f78f6c",
+		same_message_signature: "80c37124ba78f4bdfa10c37e23cfdf4107904a214149521ffbf4115c78d6b3dbfd1782d2725a790ff2638b511eeb269b\
+040ae570af8d7b53d959925bff19154e3e441fb3039eaa7557c8d5937a64cdb1877a08d86ca48b575e4259eec5a515ea",
+	},
+	Key {
+		public_key: "8345dd80ffef0eaec8920e39ebb7f5e9ae9c1d6179e9129b705923df7830c67f3690cbc48649d4079eadf5397339580c",
+		proof_of_possession: "a1eb72793ced7ba78921eb34fb4849f5b788a0882c99c752275ccf2acb9838814b72c9b6e3b28d8c96ad08b05da2181b\
+0634b96ae4ba44fb694aee954b57a45933844591dcd3de8199c6a19b741eeda49400a350b55532e62d2ebd82d6706818",
+		signature: "855c4309b575b34b7f1c358b0ef53c8125cfe11696d11b8285ae98089e2e3becfd05088c37744b172c1868e896d8ecb7\
+0415dc4340548f81138826091705e6cdd5e034693baf481af7f4e6edb51b780f169d12383c38d79e0b857e32c6964e51",
+		same_message_signature: "97b92dfe37bcce5eaaad142881b6356fc047273029a879bed9b6fb5bac9ae443bc8d37f0d198db2e3f6feb1093da297d\
+0122140549f023861df5fb68ff536026bfa5b0b428de781dd2e0bfb67808619a474bf4050c2bfe0179e906c0042249ec",
+	},
+	Key {
+		public_key: "851f8a0b82a6d86202a61cbc3b0f3db7d19650b914587bde4715ccd372e1e40cab95517779d840416e1679c84a6db24e",
+		proof_of_possession: "b7550297ebd15fe21b877a7e8e87d412dc955dfe561352875c9b7aa545f771c7df025fd39de409af01ddc686c95a1612\
+0d7676f6644408872c51a7cee954ae008a066ad6c312574a7d6cf085aaafb3217e5f60f647250e6ea56cc980fc11e41d",
+		signature: "817e7a8803dc282557b937cb0f19f318964ce64473117732a8b47e48132a0f1b6732db7314b4870813c206d899dcd966\
+0a4da2b848ea2ade898d2f48110874a794ee13aa65f9470ed6ab7b5049be3f9dfe78e176430d7b65ea80df3fdbad8758",
+		same_message_signature: "a567412c38545ca1c982c04cdd3664c1ac06c030d7cab4d20eefd2da9608052c31712e05299a2d1396be6a2fc98e6f33\
+0c0216fe005ea37c5c0f617ebe3b52cf42ae9f5f4798dd66b87f9e59aec56e01633d78b3c35ded3527388f784b4320ed",
+	},
+	Key {
+		public_key: "99bef05aaba1ea467fcbc9c420f5e3153c9d2b5f9bf2c7e2e7f6946f854043627b45b008607b9a9108bb96f3c1c089d3",
+		proof_of_possession: "aa04a7dd449d83ecb9cacd62fdd374ecd9a6cd58c2964889baba223dad6dee64b1b91e297b8da6f6a08b5fe87924aaf2\
+06b12ab15f28a63e44b039f728655ac3b9ee5a5da9006e028a27e740fcb3efe59372a84f856ca68dc7429507b991f4f3",
+		signature: "b134f22123ba6a4a16c891cb5974fb7c2bafb3313ae27ef35f694785e38cc8d6a2b382f0eca4df7fa05b8cbbf1fe110d\
+07dbc81b19a71e5e312c861e55a502458905f40dcb8110e58315a226fb0b26acdbc4121e39ebced543b163a1a2363ad6",
+		same_message_signature: "b28e3013219c7e12aa45e59a071c136eff2ca0ad030cce8015e2a6eee3120131efe7afb476e02cd519102b3c8883c4eb\
+0633f1afad367067705fde601b768837a4beda61000554154b7d8ffd4e91b44feae5c3b1936b5243332ded14a1252bbf",
+	},
+	Key {
+		public_key: "8d9e19b3f4c7c233a6112e5397309f9812a4f61f754f11dd3dcb8b07d55a7b1dfea65f19a1488a14fef9a41495083582",
+		proof_of_possession: "ad389f460c94576cf9513798834add81126f2b9162dea01c92f63a4cb478b382b9712201a0b1c0b096e5d8ab0b2f2822\
+0c17748cb5fb2cf6ffedec735e946a9d0a19d26e6bdf8e252392d3586fb5cb99e0dcc57ecff175c0580c3c4b07cc35b9",
+		signature: "80f94f9b1e7dafad36d0071739e3aeb664de5830c76391e3f4014b81860774ae0b7b65e32c8862abfb39b5c95455634b\
+1176fd1f9334cea07e7fa879ffc1e6c7d91a130b53d33c74470736c63b950cc6734b37a76486580e03d8483bbc068e04",
+		same_message_signature: "b7a1befd6d2e1d1e4ed5913a05c3c88f772b5a731965a8d8f63206c04ba2aa2a06a5a25a2d67a549a93376c0983176a9\
+1863708607ee9eb23b01d0ac157f1cc7c59695bc0145f834576afd562aee78cace5d82de6131163c28f49024582934b2",
+	},
+	Key {
+		public_key: "a73eb991aa22cdb794da6fcde55a427f0a4df5a4a70de23a988b5e5fc8c4d844f66d990273267a54dd21579b7ba6a086",
+		proof_of_possession: "a78f47ad2ec5c7ba9883e70a1ab2f3159dba99c050de7ede3ad2731cc032ec4f69018a68da07225a23e3fd537e6a59e1\
+0f4e0faccc83ef697cb86c160fab24c4ba3d6b0e322b5c47c4de2f3dfad3b5d3e115a1c5cbe834ccf9ba17a522718252",
+		signature: "92d8db95c2a98f6e3d2094c510482f49914b36a4b04d8e4d3fb9936bc669ecf4d348b0a85e8fefe12f3a388f8566e485\
+12ee35518425226b1224609a28c74abbc56a12e0c5a5d39ee557bf75cb967832e4c4584d04a1bf656cbdd2855551400c",
+		same_message_signature: "86d664a69ea18daa6f9fddb0bf356ad5267414cf8cafc29814f3cc814c498c508a6ecb33b65c82dcd8ae60490ee25aa3\
+0c75624f67d4ad275dc5271eb7426b5669865c96eb3fede4a67a3dff3cf6944bc5a0b564d3f82df3c954775e02bcf50e",
+	},
+];
+
+// The same keys for [`MinSig`], with public keys in G2 and the messages hashed to G1, with the
+// `BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_` tag for the proofs of possession.
+const MIN_SIG_KEYS: [Key; 16] = [
+	Key {
+		public_key: "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+		proof_of_possession: "9586b1f346f7f0f281d1588d0209cc9815b95722166dcbfa34da8bdb1f78772d72df1f1abed50d27e0246fd0e70b5b21",
+		signature: "91c34d35ec1d9e09eaec1e97a5ad1b20410f09cc0a1474b24090bdfe96762d311ccbaf1ae2f72c475f0a8e6e4473e041",
+		same_message_signature: "990d4113a6678b8bc9fa3b852b6f73980f260d47b8121945b5430f184ce88fd5a2a9f5c1078384893e260a22df52b354",
+	},
+	Key {
+		public_key: "aa4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c33577\
+1638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053",
+		proof_of_possession: "a9885aca30b3b895f2d791a3b1579298fedfb53785e37937028fb52a670b7458db0096d537c58007dbd108e56b6aef12",
+		signature: "a3562933bef476d3c0b90b120be1299033a20a0849ac5f1c6cdf07dbe96bcd9e226d2b27cdc5c1fa9e01713b0886effb",
+		same_message_signature: "80df77c9f3ad408463cecdd1964a2cfa66b1fa0e2f195595ca2c38e9f353b31e0c8ad91a0baa4309306f92bf63de3c9d",
+	},
+	Key {
+		public_key: "89380275bbc8e5dcea7dc4dd7e0550ff2ac480905396eda55062650f8d251c96eb480673937cc6d9d6a44aaa56ca66dc\
+122915c824a0857e2ee414a3dccb23ae691ae54329781315a0c75df1c04d6d7a50a030fc866f09d516020ef82324afae",
+		proof_of_possession: "b29235ff3751859561e8d414556fa4a4afd609a1937d11528caba9d3c3f3e1e26cde3d7cbf3220848dda4ca274c331c6",
+		signature: "a47ccff5bf8fede0229c39af6f4be3dc9a37edada2700d82bcbca7ca013ccbdf29346e40bb2df1ede54d2dfd25b3f39e",
+		same_message_signature: "ae00508cbc662a872b6ed8cf203bf4c2d9051732ead57488cfec06b6e232b64df12a3c264b7410d3efd02a4d8546b2e4",
+	},
+	Key {
+		public_key: "870227d3f13684fdb7ce31b8065ba3acb35f7bde6fe2ddfefa359f8b35d08a9ab9537b43e24f4ffb720b5a0bda2a82f2\
+0e7a30979a8853a077454eb63b8dcee75f106221b262886bb8e01b0abb043368da82f60899cc1412e33e4120195fc557",
+		proof_of_possession: "804d6e4e47e6f0633c6a60019102eb1aa5b4f61319924aac176b68a81ad870b87331d49212f1845209c5c7401b8bd03e",
+		signature: "b7e664f802db5499cfb81925b3652998623b2b27e85224f1e9f840884156b7dd1b1d9c3a4c8187dfc362494e6c4ff259",
+		same_message_signature: "91c3e44bc2dc7a94f573cd73aa2ab00b87c9cff6a4982a05dfcac0b1824d5721ad12f44b29643bf6e93c06cbfb2587f3",
+	},
+	Key {
+		public_key: "80fb837804dba8213329db46608b6c121d973363c1234a86dd183baff112709cf97096c5e9a1a770ee9d7dc641a894d6\
+0411a5de6730ffece671a9f21d65028cc0f1102378de124562cb1ff49db6f004fcd14d683024b0548eff3d1468df2688",
+		proof_of_possession: "ab5a9e63e158ed189a2ba96cf2bc43c793e2f6dc3e31b44ca6d0f4aefbc90b4e35cfba3d0bbee76649efdb866858d1cd",
+		signature: "b51318ce478ea0dd0de79c71c7d47c6aa772119cc11d89ed2253f97f70ec3c7dff20c411932a2f27937bbfe4a4ee743f",
+		same_message_signature: "9338e428a3d7a1b6a9e4521aa391858a9e5e608c56a0d0e7a646a7c45bf78d288fe868fd822d79e869c274a8cd3a7b22",
+	},
+	Key {
+		public_key: "83f4b4e761936d90fd5f55f99087138a07a69755ad4a46e4dd1c2cfe6d11371e1cc033111a0595e3bba98d0f538db451\
+19e384121b7d70927c49e6d044fd8517c36bc6ed2813a8956dd64f049869e8a77f7e46930240e6984abe26fa6a89658f",
+		proof_of_possession: "ae3aef0952bc7f617ca01834ca50b1a17b91b485cb1c1d49d51e074ecde5f84526d206adf0a9d3ac518dce558fae55b7",
+		signature: "b62598a5287af10c08c019b11d4caed37f4a393b211a5787b3d4fe6c1c1ca54beceb6684922d3eddda796e9a4ed3caa3",
+		same_message_signature: "a7a9372d36d6758774d0c811bb4b49dfed8bffcb276c2edf8d3bde9eb300b4d8283f26fd23080f5c8ce09fe2d0f59f0c",
+	},
+	Key {
+		public_key: "8d0273f6bf31ed37c3b8d68083ec3d8e20b5f2cc170fa24b9b5be35b34ed013f9a921f1cad1644d4bdb14674247234c8\
+049cd1dbb2d2c3581e54c088135fef36505a6823d61b859437bfc79b617030dc8b40e32bad1fa85b9c0f368af6d38d3c",
+		proof_of_possession: "b4ec459579d966ad44a0cf1aa27c37f456e5a11317596832db43ddcf725a2ce27243495241d962425da8554bd4407f71",
+		signature: "95d990a0f9dd82960453a7cc3b22dad684f26c4c49f2b9e632fd40e9a1177ca0c3be28c57173591e8eee76484c066193",
+		same_message_signature: "87d4a8ac1cc9b1d44c8571ec4b936f2fdbbe78aa24b0fd20da6d086b3eeef0baaf6c0617d1115b8359b9300c832bc953",
+	},
+	Key {
+		public_key: "92be651a5fa620340d418834526d37a8c932652345400b4cd9d43c8f41c080f41a6d9558118ebeab9d4268bb73e850e1\
+02142a58bae275564a6d63cb6bd6266ca66bef07a6ab8ca37b9d0ba2d4effbccfd89c169649f7d0e8a3eb006846579ad",
+		proof_of_possession: "81113c8c57dda65df3a0c50b2e9208a756b1b8b7910611d290543f3322f3b423d69de7cbff5532a54017c84609abd777",
+		signature: "84e0b807c21c55507a6507949fe621f7b2475f42d122c4264b11c3045520f99874b3879a7974fa0e1a3ca9bbdd18640e",
+		same_message_signature: "b8de56cf4162801e1aab3e1163b0616d3b196c4546db993ae53eb8256d3d520203dd2945e6d8fbbb7c7921bcf685bcd1",
+	},
+	Key {
+		public_key: "ac48e0d4f9404ae0a7f10774c55a9e838bb09d3bae85b5eaa6b16b0f4dc2354368117f3799c37f3f7126d8b54d3f8393\
+018405e4b67f957b6465ead9f5afc47832d45643dc3aa03af7314c6cf980fa23dd3bb8db3358693ad06011f6a6b1a5ff",
+		proof_of_possession: "a0f31910cd0298a2e4adf00683ec1b3247307c7fba4466810701e04b8c6cb78809ef40f8c0c9f87384a1a572f384a3ab",
+		signature: "82cbf0aff238c07d9e2b5e3e3bfd860de466ee0e4b4477613d2935fe85dc234406ffb9a400d0562a305455dcd788a8a3",
+		same_message_signature: "833b85e81604e42241505729909d0a7d502ede0a06eed36f0496f3f4927a4d53afecec585f54886921cbaafb15891a37",
+	},
+	Key {
+		public_key: "afb665f5a7559cb0fa1300048a0e6f1ab5547226e86f8e752dd13c28eda4168492e3d3bf2f8a6b230dd57f79b1afa991\
+1796abe0d9e4a703962be528e6a5cb65c60725886f925db0e2a89107ec248bb39fa332bc63bd91d28ae66e0dfce8f754",
+		proof_of_possession: "8b117f4de5e23c9a72946791f0cdf334310bb872b0026ac81e8643ef34fe453e0a4f6aff3518005b623fe7f317976ddd",
+		signature: "87fae6b04719340bbadfafa8b1305494cc1ed6008a3ace57e569e01ff73fc3af8abff68f9925fa7c3a76a06de5591c44",
+		same_message_signature: "aea6ef9cdaa99fa4c738d4cb625043c4780cd60c9c4d3369b84ffc0b5c70c26eccbab33e4a0a348ff018459dfbfa9460",
+	},
+	Key {
+		public_key: "a190be857d602284393305bfe0a29e29a6982ed3f04ccaabafb7e59cdc7eda85c22bc3e8690355c7a0fb7590ae40f1b0\
+09303f04d568e289a35102b6df883d5ed620355c0eb5d02236718cdaf99fba6e19ef5cee2996268eb9a53ae1ee09bce3",
+		proof_of_possession: "a73e3b9855488d5268933a1b5994c72ac078128b32ef8db7894837ad73443f79069adacff03f5bd850c01e620f05194c",
+		signature: "aaeeaccdeab5345efda64d1470eb4a6cb735804d631e158390a643e72d8dcb2f31bc8855041c3a3fd91e9d1e2e160e5e",
+		same_message_signature: "aa3c0886f90c6d35ee2e0495039d1c52e74aa4ca54e5f9cb4db6c28bb95775c3fdbaf9a749670ff9acffbe6a8d256cbb",
+	},
+	Key {
+		public_key: "b23372d7d4c91a249df8f3e4f8e669087b252ab5d8cf2529a87e4ed3622e4158cf17dc44b473d5debd273261383e8a0f\
+0173ed58056bec9874464d3f23c3e7d3d429d6c8a167fc7f39368830eca839d0eb8260d64ca823f6c785c71f85893d84",
+		proof_of_possession: "afdf47449dbe9e9e8e416ff7d9a0880eec3aeba90ffb2c5f028557170e523f4d036e0c82046785eeb22a90895e67a9f1",
+		signature: "a9f4c9774e20fda1e4fde6944c3fc1b38d3273caf902f0610a067f7ea26b532b801494ba2f919bb189e09886cfceee44",
+		same_message_signature: "abb2976f2e591fe64bca6d1de0b93cd2665249ed61f0e75c12d59fc5501143a56479a47dc10173f3a4a3a50b2b9bc8a8",
+	},
+	Key {
+		public_key: "8bf78a97086750eb166986ed8e428ca1d23ae3bbf8b2ee67451d7dd84445311e8bc8ab558b0bc008199f577195fc39b7\
+152110e866f1a6e8c5348f6e005dbd93de671b7d0fbfa04d6614bcdd27a3cb2a70f0deacb3608ba95226268481a0be7c",
+		proof_of_possession: "8212a90f47344ecf6cee7c8a0b40798cbc583f4b7565e76494570d870d2c982c14689b3a838271cd30bc047383cdd86f",
+		signature: "895a9917920f3c7dae6db6f0a41dfdc70bf34bb434bf7d7551029f2789c8b88c10cd619835c41b7a5e10ea08ee3866db",
+		same_message_signature: "b5655ec252a40d6c38c0a71221b68ee5278484511cb11cceb26feab85103e31c3619989ff7b1a9276b2782c6cb148da8",
+	},
+	Key {
+		public_key: "9292b2ce751f6f859ec7882e14083eac9841b035f9d5ed938a81579dbce07dec2c0202b7f6b25226831cd9c578e893d0\
+0027513925b419f6c581788578379995290ab9478e08ecd1999d5e1a05c58144d2f9f06fb8c7fd1586f3ef6a973a3ed7",
+		proof_of_possession: "b16849e67ca9fecc7b04b28f8f6e62daf643192ec744fb2531904519c67728dffc39f22d28421de6c9800943d472b3d7",
+		signature: "af017ffc232ee15870eacf3520e4a3eb6f693e8b7d16141ffd2c5f3fea7575ca7e14859a4c2236558d90f8690ca76674",
+		same_message_signature: "89d7b6e46cbdbc6b2138e3bf8625e4b2fc93edb8627b943ecc5026589ed2597cd4a5db297430c2c04fba0b6126a71e95",
+	},
+	Key {
+		public_key: "8cc64109c67b342b6dbcf86cb60fca7ad378ed6398d89076ed108685c57a07d26e40ed3d5c4b3560b21e519db5875d49\
+090721a089bbbb130c21a529be0ede9271a91a2dde9cb2a8e091a19fd2c0a40c390ac2bda8304085c2d6e38e520eae44",
+		proof_of_possession: "a754c71d3ceea9de3155a1ff2ed17b018a0f21918198e8b738aec7b5599d5277852e56df09bb4118931a79f77f40c312",
+		signature: "9782d82f13ceaae0345e0bff382965626682c1fe14c3ff2d20385ba1b75c7c4335f58b087d2d00a670bd40613c037819",
+		same_message_signature: "ab11b06d1504c95c03ddbb1f9a9cb05c1033867194bf08bbaf747e22abd962dad846fae67c6262c44ef2796699af27e1",
+	},
+	Key {
+		public_key: "a70401d9bba01c0445e0a682406b099f21d16d9c348cc97156769084055ca328a145c134b8c8b58f019d62882b2965de\
+1800ecc167bb714100f31e7610cd3fd010ca299b394c01b1a89afd11b051e92989f6336db5e6d3212f6b04673526d839",
+		proof_of_possession: "b2d9919b7af0c602fc41729e47954f6b45c48e59e12e705c101f8843739493fe283b600bbd2a58d2f2df480f46b520bc",
+		signature: "8433deaf6f5387ad88076365a30d0667dfe70ba5a37c559cf9ec1a1806bbf9e88d2225f7b60064e2da01618faaf2d7d7",
+		same_message_signature: "98ca5d83bfa615f83431bd44d5eb983bec54985b92d46bd0e20b5d57b0afb39793a1a9d277372dcd5a1e49bfacad44ad",
+	},
+];
+
+/// The fixtures of a signature variant.
+pub trait BenchmarkVariant: Variant {
+	/// The fixture keys, with their proofs of possession and signatures.
+	const KEYS: [Key; 16];
+
+	/// The aggregate of `signatures`.
+	fn aggregate(signatures: Vec<Self::Signature>) -> Self::Signature;
+}
+
+impl BenchmarkVariant for MinPk {
+	const KEYS: [Key; 16] = MIN_PK_KEYS;
+
+	fn aggregate(signatures: Vec<[u8; 96]>) -> [u8; 96] {
+		sp_io::crypto::bls12_381_aggregate_g2(signatures).unwrap()
+	}
+}
+
+impl BenchmarkVariant for MinSig {
+	const KEYS: [Key; 16] = MIN_SIG_KEYS;
+
+	fn aggregate(signatures: Vec<[u8; 48]>) -> [u8; 48] {
+		sp_io::crypto::bls12_381_aggregate_g1(signatures).unwrap()
+	}
+}
+
+/// Decode a hex encoded fixture.
+fn decode<D: Decode>(hex: &str) -> D {
+	D::decode(&mut &array_bytes::hex2bytes_unchecked(hex)[..]).unwrap()
+}
+
+/// Register the fixture public key `i` for a new signer.
+fn signer<T: Config>(i: u32) -> T::AccountId
+where
+	T::Variant: BenchmarkVariant,
+{
+	let signer: T::AccountId = account("signer", i, 0);
+	PublicKeys::<T>::insert(
+		&signer,
+		decode::<PublicKeyOf<T>>(T::Variant::KEYS[i as usize].public_key),
+	);
+	signer
+}
+
+/// The message signed by the fixture signature `i`, or by all same message signatures for
+/// `i = 16`.
+fn message(i: u32) -> Vec<u8> {
+	vec![i as u8; 32]
+}
+
+/// The aggregate of the fixture signatures selected by `signature`, of the first `n` keys.
+fn aggregate<T: Config>(n: u32, signature: fn(&Key) -> &'static str) -> SignatureOf<T>
+where
+	T::Variant: BenchmarkVariant,
+{
+	T::Variant::aggregate(
+		T::Variant::KEYS[..n as usize]
+			.iter()
+			.map(|key| decode(signature(key)))
+			.collect(),
+	)
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	where_clause { where T::Variant: BenchmarkVariant }
+
+	register_key {
+		let caller: T::AccountId = whitelisted_caller();
+		let key = &T::Variant::KEYS[0];
+		let public_key = decode(key.public_key);
+		let proof_of_possession = decode(key.proof_of_possession);
+	}: _(RawOrigin::Signed(caller.clone()), public_key, proof_of_possession)
+	verify {
+		assert_last_event::<T>(Event::KeyRegistered { who: caller, public_key }.into());
+	}
+
+	remove_key {
+		let caller: T::AccountId = whitelisted_caller();
+		PublicKeys::<T>::insert(&caller, decode::<PublicKeyOf<T>>(T::Variant::KEYS[0].public_key));
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_last_event::<T>(Event::KeyRemoved { who: caller }.into());
+	}
+
+	verify_signature {
+		let caller: T::AccountId = whitelisted_caller();
+		let signer = signer::<T>(0);
+		let message: BoundedVec<_, _> = message(0).try_into().unwrap();
+		let signature = decode(T::Variant::KEYS[0].signature);
+	}: _(RawOrigin::Signed(caller), signer.clone(), message.clone(), signature)
+	verify {
+		let message_hash = T::Hashing::hash(&message);
+		assert_last_event::<T>(Event::SignatureVerified { signer, message_hash }.into());
+	}
+
+	verify_aggregate_signature {
+		let n in 1 .. T::MaxSigners::get().min(T::Variant::KEYS.len() as u32);
+		let caller: T::AccountId = whitelisted_caller();
+		let signers: BoundedVec<_, _> = (0..n).map(signer::<T>).collect::<Vec<_>>().try_into().unwrap();
+		let messages: BoundedVec<_, _> = (0..n)
+			.map(|i| message(i).try_into().unwrap())
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		let signature = aggregate::<T>(n, |key| key.signature);
+	}: _(RawOrigin::Signed(caller), signers.clone(), messages, signature)
+	verify {
+		assert_last_event::<T>(Event::AggregateSignatureVerified { signers }.into());
+	}
+
+	verify_fast_aggregate_signature {
+		let n in 1 .. T::MaxSigners::get().min(T::Variant::KEYS.len() as u32);
+		let caller: T::AccountId = whitelisted_caller();
+		let signers: BoundedVec<_, _> = (0..n).map(signer::<T>).collect::<Vec<_>>().try_into().unwrap();
+		let message: BoundedVec<_, _> = message(T::Variant::KEYS.len() as u32).try_into().unwrap();
+		let signature = aggregate::<T>(n, |key| key.same_message_signature);
+	}: _(RawOrigin::Signed(caller), signers.clone(), message.clone(), signature)
+	verify {
+		let message_hash = T::Hashing::hash(&message);
+		assert_last_event::<T>(Event::FastAggregateSignatureVerified { signers, message_hash }.into());
+	}
+
+	impl_benchmark_test_suite!(BlsSignatures, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,473 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # BLS Signatures Pallet
+//!
+//! Stores a BLS12-381 public key per account and verifies signatures by registered keys.
+//!
+//! Accounts register their public key with [`Pallet::register_key`], together with a proof of
+//! possession of its secret key. Signatures by registered keys can then be verified in
+//! extrinsics, either individually with [`Pallet::verify_signature`], aggregated over distinct
+//! messages with [`Pallet::verify_aggregate_signature`], or aggregated over a single message with
+//! [`Pallet::verify_fast_aggregate_signature`]. Other pallets, such as bridges and beacons, verify
+//! signatures through the [`BlsVerify`] trait.
+//!
+//! This follows the proof of possession ciphersuite of the IETF BLS signature draft. As every
+//! registered key came with a proof of possession, signatures of the same message by registered
+//! keys can be aggregated without rogue key attacks.
+//!
+//! The signature scheme is chosen with [`Config::Variant`]: [`MinPk`], with public keys in G1 and
+//! signatures in G2 as in Ethereum, or [`MinSig`], with the groups swapped. Points are compressed
+//! in the zcash encoding, and messages are hashed to the group of the signatures with the
+//! [`Variant::DST`] domain separation tag. Verification runs natively through the BLS12-381 host
+//! functions of `sp_io::crypto`.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::MaxEncodedLen;
+use frame_support::{dispatch::DispatchResult, ensure, Parameter};
+use sp_io::BlsAggregateVerifyError;
+use sp_std::prelude::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// A BLS signature variant, defined by the groups of the public keys and the signatures.
+pub trait Variant {
+	/// A compressed public key.
+	type PublicKey: Parameter + MaxEncodedLen + Copy;
+	/// A compressed signature.
+	type Signature: Parameter + MaxEncodedLen + Copy;
+	/// The domain separation tag messages are hashed with, that of the proof of possession
+	/// ciphersuite.
+	const DST: &'static [u8];
+
+	/// Whether `signature` is a signature of `message` by `public_key`.
+	fn verify(public_key: &Self::PublicKey, message: &[u8], signature: &Self::Signature) -> bool;
+
+	/// Whether `signature` is the aggregate of the signatures of each of `messages` by the public
+	/// key at the same index.
+	fn aggregate_verify(
+		public_keys: Vec<Self::PublicKey>,
+		messages: Vec<Vec<u8>>,
+		signature: &Self::Signature,
+	) -> Result<bool, BlsAggregateVerifyError>;
+
+	/// Whether `signature` is the aggregate of the signatures of `message` by all of
+	/// `public_keys`.
+	fn fast_aggregate_verify(
+		public_keys: Vec<Self::PublicKey>,
+		message: &[u8],
+		signature: &Self::Signature,
+	) -> Result<bool, BlsAggregateVerifyError>;
+
+	/// Whether `proof` is a proof of possession of the secret key of `public_key`.
+	fn pop_verify(public_key: &Self::PublicKey, proof: &Self::Signature) -> bool;
+}
+
+/// The variant with public keys in G1 and signatures in G2, as used by Ethereum.
+pub struct MinPk;
+
+impl Variant for MinPk {
+	type PublicKey = [u8; 48];
+	type Signature = [u8; 96];
+	const DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+	fn verify(public_key: &[u8; 48], message: &[u8], signature: &[u8; 96]) -> bool {
+		sp_io::crypto::bls12_381_verify(public_key, message, signature, Self::DST)
+	}
+
+	fn aggregate_verify(
+		public_keys: Vec<[u8; 48]>,
+		messages: Vec<Vec<u8>>,
+		signature: &[u8; 96],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		sp_io::crypto::bls12_381_aggregate_verify(public_keys, messages, signature, Self::DST)
+	}
+
+	fn fast_aggregate_verify(
+		public_keys: Vec<[u8; 48]>,
+		message: &[u8],
+		signature: &[u8; 96],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		sp_io::crypto::bls12_381_fast_aggregate_verify(public_keys, message, signature, Self::DST)
+	}
+
+	fn pop_verify(public_key: &[u8; 48], proof: &[u8; 96]) -> bool {
+		sp_io::crypto::bls12_381_pop_verify(public_key, proof)
+	}
+}
+
+/// The variant with public keys in G2 and signatures in G1.
+pub struct MinSig;
+
+impl Variant for MinSig {
+	type PublicKey = [u8; 96];
+	type Signature = [u8; 48];
+	const DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+	fn verify(public_key: &[u8; 96], message: &[u8], signature: &[u8; 48]) -> bool {
+		sp_io::crypto::bls12_381_verify_min_sig(public_key, message, signature, Self::DST)
+	}
+
+	fn aggregate_verify(
+		public_keys: Vec<[u8; 96]>,
+		messages: Vec<Vec<u8>>,
+		signature: &[u8; 48],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		sp_io::crypto::bls12_381_aggregate_verify_min_sig(
+			public_keys,
+			messages,
+			signature,
+			Self::DST,
+		)
+	}
+
+	fn fast_aggregate_verify(
+		public_keys: Vec<[u8; 96]>,
+		message: &[u8],
+		signature: &[u8; 48],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		sp_io::crypto::bls12_381_fast_aggregate_verify_min_sig(
+			public_keys,
+			message,
+			signature,
+			Self::DST,
+		)
+	}
+
+	fn pop_verify(public_key: &[u8; 96], proof: &[u8; 48]) -> bool {
+		sp_io::crypto::bls12_381_pop_verify_min_sig(public_key, proof)
+	}
+}
+
+/// The public key type of the variant of a runtime.
+pub type PublicKeyOf<T> = <<T as Config>::Variant as Variant>::PublicKey;
+
+/// The signature type of the variant of a runtime.
+pub type SignatureOf<T> = <<T as Config>::Variant as Variant>::Signature;
+
+/// Verification of BLS signatures by the public keys registered in this pallet.
+pub trait BlsVerify<AccountId> {
+	/// A compressed signature.
+	type Signature;
+
+	/// Whether `signature` is a signature of `message` by the public key of `signer`.
+	fn verify(signer: &AccountId, message: &[u8], signature: &Self::Signature) -> bool;
+
+	/// Whether `signature` is the aggregate of the signatures of each of `messages` by the public
+	/// key of the signer at the same index.
+	///
+	/// The messages must be distinct.
+	fn aggregate_verify(
+		signers: &[AccountId],
+		messages: &[Vec<u8>],
+		signature: &Self::Signature,
+	) -> bool;
+
+	/// Whether `signature` is the aggregate of the signatures of `message` by the public keys of
+	/// all of `signers`.
+	fn fast_aggregate_verify(
+		signers: &[AccountId],
+		message: &[u8],
+		signature: &Self::Signature,
+	) -> bool;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Hash;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The signature variant, which fixes the groups of the public keys and signatures.
+		type Variant: Variant;
+
+		/// The maximum length of a signed message.
+		#[pallet::constant]
+		type MaxMessageLength: Get<u32>;
+
+		/// The maximum number of signers of an aggregate signature.
+		///
+		/// Must be at most [`sp_io::BLS12_381_MAX_AGGREGATION`].
+		#[pallet::constant]
+		type MaxSigners: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The public key registered by each account.
+	#[pallet::storage]
+	pub type PublicKeys<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PublicKeyOf<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A public key was registered for an account, replacing any previous one.
+		KeyRegistered { who: T::AccountId, public_key: PublicKeyOf<T> },
+		/// The public key of an account was removed.
+		KeyRemoved { who: T::AccountId },
+		/// A signature of the message with the given hash by the key of `signer` was verified.
+		SignatureVerified { signer: T::AccountId, message_hash: T::Hash },
+		/// An aggregate signature by the keys of `signers` was verified.
+		AggregateSignatureVerified { signers: BoundedVec<T::AccountId, T::MaxSigners> },
+		/// An aggregate signature of the message with the given hash by the keys of `signers` was
+		/// verified.
+		FastAggregateSignatureVerified {
+			signers: BoundedVec<T::AccountId, T::MaxSigners>,
+			message_hash: T::Hash,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The public key is malformed or the point at infinity.
+		InvalidPublicKey,
+		/// The proof of possession of the public key did not verify.
+		InvalidProofOfPossession,
+		/// The account has no registered public key.
+		NotRegistered,
+		/// The signature is malformed or did not verify.
+		InvalidSignature,
+		/// An aggregate signature has no signers.
+		NoSigners,
+		/// An aggregate signature has more than [`sp_io::BLS12_381_MAX_AGGREGATION`] signers.
+		TooManySigners,
+		/// The numbers of signers and messages differ.
+		LengthMismatch,
+		/// An aggregate signature covers the same message more than once.
+		DuplicateMessage,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxSigners::get() <= sp_io::BLS12_381_MAX_AGGREGATION,
+				"`MaxSigners` must be at most `BLS12_381_MAX_AGGREGATION`",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register `public_key` as the public key of the caller, replacing any previous one.
+		///
+		/// `proof_of_possession` must be the signature of `public_key` by its own secret key, with
+		/// the `BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` domain separation tag for [`MinPk`]
+		/// or `BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_` for [`MinSig`].
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::register_key())]
+		pub fn register_key(
+			origin: OriginFor<T>,
+			public_key: PublicKeyOf<T>,
+			proof_of_possession: SignatureOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::Variant::pop_verify(&public_key, &proof_of_possession),
+				Error::<T>::InvalidProofOfPossession,
+			);
+			PublicKeys::<T>::insert(&who, public_key);
+
+			Self::deposit_event(Event::KeyRegistered { who, public_key });
+			Ok(())
+		}
+
+		/// Remove the public key of the caller.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::remove_key())]
+		pub fn remove_key(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(PublicKeys::<T>::contains_key(&who), Error::<T>::NotRegistered);
+			PublicKeys::<T>::remove(&who);
+
+			Self::deposit_event(Event::KeyRemoved { who });
+			Ok(())
+		}
+
+		/// Verify the `signature` of `message` by the public key of `signer`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::verify_signature())]
+		pub fn verify_signature(
+			origin: OriginFor<T>,
+			signer: T::AccountId,
+			message: BoundedVec<u8, T::MaxMessageLength>,
+			signature: SignatureOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::check_signature(&signer, &message, &signature)?;
+
+			let message_hash = T::Hashing::hash(&message);
+			Self::deposit_event(Event::SignatureVerified { signer, message_hash });
+			Ok(())
+		}
+
+		/// Verify the aggregate `signature` of each of `messages` by the public key of the signer
+		/// at the same index in `signers`.
+		///
+		/// The messages must be distinct.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::verify_aggregate_signature(signers.len() as u32))]
+		pub fn verify_aggregate_signature(
+			origin: OriginFor<T>,
+			signers: BoundedVec<T::AccountId, T::MaxSigners>,
+			messages: BoundedVec<BoundedVec<u8, T::MaxMessageLength>, T::MaxSigners>,
+			signature: SignatureOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let messages = messages.into_iter().map(Into::into).collect::<Vec<_>>();
+			Self::check_aggregate_signature(&signers, &messages, &signature)?;
+
+			Self::deposit_event(Event::AggregateSignatureVerified { signers });
+			Ok(())
+		}
+
+		/// Verify the aggregate `signature` of `message` by the public keys of all of `signers`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::verify_fast_aggregate_signature(signers.len() as u32))]
+		pub fn verify_fast_aggregate_signature(
+			origin: OriginFor<T>,
+			signers: BoundedVec<T::AccountId, T::MaxSigners>,
+			message: BoundedVec<u8, T::MaxMessageLength>,
+			signature: SignatureOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::check_fast_aggregate_signature(&signers, &message, &signature)?;
+
+			let message_hash = T::Hashing::hash(&message);
+			Self::deposit_event(Event::FastAggregateSignatureVerified { signers, message_hash });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The public key registered by `who`.
+	pub fn public_key(who: &T::AccountId) -> Result<PublicKeyOf<T>, Error<T>> {
+		PublicKeys::<T>::get(who).ok_or(Error::<T>::NotRegistered)
+	}
+
+	/// Verify the `signature` of `message` by the public key of `signer`.
+	pub fn check_signature(
+		signer: &T::AccountId,
+		message: &[u8],
+		signature: &SignatureOf<T>,
+	) -> DispatchResult {
+		let public_key = Self::public_key(signer)?;
+		ensure!(T::Variant::verify(&public_key, message, signature), Error::<T>::InvalidSignature,);
+		Ok(())
+	}
+
+	/// Verify the aggregate `signature` of each of `messages` by the public key of the signer at
+	/// the same index.
+	pub fn check_aggregate_signature(
+		signers: &[T::AccountId],
+		messages: &[Vec<u8>],
+		signature: &SignatureOf<T>,
+	) -> DispatchResult {
+		let public_keys = signers.iter().map(Self::public_key).collect::<Result<Vec<_>, _>>()?;
+		Self::aggregate_result(T::Variant::aggregate_verify(
+			public_keys,
+			messages.to_vec(),
+			signature,
+		))
+	}
+
+	/// Verify the aggregate `signature` of `message` by the public keys of all of `signers`.
+	///
+	/// This is secure against rogue key attacks because every registered key came with a proof
+	/// of possession.
+	pub fn check_fast_aggregate_signature(
+		signers: &[T::AccountId],
+		message: &[u8],
+		signature: &SignatureOf<T>,
+	) -> DispatchResult {
+		let public_keys = signers.iter().map(Self::public_key).collect::<Result<Vec<_>, _>>()?;
+		Self::aggregate_result(T::Variant::fast_aggregate_verify(public_keys, message, signature))
+	}
+
+	/// Convert the result of an aggregate verification host function.
+	fn aggregate_result(result: Result<bool, BlsAggregateVerifyError>) -> DispatchResult {
+		match result {
+			Ok(true) => Ok(()),
+			Ok(false) | Err(BlsAggregateVerifyError::InvalidSignature) =>
+				Err(Error::<T>::InvalidSignature.into()),
+			Err(BlsAggregateVerifyError::Empty) => Err(Error::<T>::NoSigners.into()),
+			Err(BlsAggregateVerifyError::TooMany) => Err(Error::<T>::TooManySigners.into()),
+			Err(BlsAggregateVerifyError::LengthMismatch) => Err(Error::<T>::LengthMismatch.into()),
+			Err(BlsAggregateVerifyError::InvalidPublicKey) =>
+				Err(Error::<T>::InvalidPublicKey.into()),
+			Err(BlsAggregateVerifyError::DuplicateMessage) =>
+				Err(Error::<T>::DuplicateMessage.into()),
+		}
+	}
+}
+
+impl<T: Config> BlsVerify<T::AccountId> for Pallet<T> {
+	type Signature = SignatureOf<T>;
+
+	fn verify(signer: &T::AccountId, message: &[u8], signature: &SignatureOf<T>) -> bool {
+		Self::check_signature(signer, message, signature).is_ok()
+	}
+
+	fn aggregate_verify(
+		signers: &[T::AccountId],
+		messages: &[Vec<u8>],
+		signature: &SignatureOf<T>,
+	) -> bool {
+		Self::check_aggregate_signature(signers, messages, signature).is_ok()
+	}
+
+	fn fast_aggregate_verify(
+		signers: &[T::AccountId],
+		message: &[u8],
+		signature: &SignatureOf<T>,
+	) -> bool {
+		Self::check_fast_aggregate_signature(signers, message, signature).is_ok()
+	}
+}
@@ -0,0 +1,342 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the BLS signatures pallet.
+
+use super::{
+	BlsVerify, Error, Event, MinPk, MinSig, Pallet as BlsSignatures, PublicKeyOf, PublicKeys,
+	SignatureOf, Variant,
+};
+use crate::mock::*;
+use ark_bls12_381::{g1, g2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+	AffineRepr,
+};
+use ark_ff::field_hashers::DefaultFieldHasher;
+use ark_serialize::CanonicalSerialize;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_system::RawOrigin;
+use sha2::Sha256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+fn compressed<const N: usize>(point: impl CanonicalSerialize) -> [u8; N] {
+	let mut bytes = [0; N];
+	point.serialize_compressed(&mut bytes[..]).unwrap();
+	bytes
+}
+
+fn public_key(secret: u64) -> PublicKeyOf<Test> {
+	compressed(G1Affine::generator() * Fr::from(secret))
+}
+
+fn hash_with(dst: &[u8], message: &[u8]) -> G2Affine {
+	MapToCurveBasedHasher::<G2Projective, DefaultFieldHasher<Sha256, 128>, WBMap<g2::Config>>::new(
+		dst,
+	)
+	.unwrap()
+	.hash(message)
+	.unwrap()
+}
+
+fn hash(message: &[u8]) -> G2Affine {
+	hash_with(MinPk::DST, message)
+}
+
+fn sign(secret: u64, message: &[u8]) -> SignatureOf<Test> {
+	compressed(hash(message) * Fr::from(secret))
+}
+
+fn proof_of_possession(secret: u64) -> SignatureOf<Test> {
+	compressed(hash_with(POP_DST, &public_key(secret)) * Fr::from(secret))
+}
+
+/// Register the public key of the secret key `account + 1` for `account`.
+fn register(account: u64) {
+	assert_ok!(BlsSignatures::<Test>::register_key(
+		RawOrigin::Signed(account).into(),
+		public_key(account + 1),
+		proof_of_possession(account + 1),
+	));
+}
+
+#[test]
+fn registers_and_removes_keys() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		assert_eq!(PublicKeys::<Test>::get(1), Some(public_key(2)));
+		System::assert_last_event(
+			Event::KeyRegistered { who: 1, public_key: public_key(2) }.into(),
+		);
+
+		// Registering again replaces the key.
+		assert_ok!(BlsSignatures::<Test>::register_key(
+			RawOrigin::Signed(1).into(),
+			public_key(7),
+			proof_of_possession(7),
+		));
+		assert_eq!(PublicKeys::<Test>::get(1), Some(public_key(7)));
+
+		assert_ok!(BlsSignatures::<Test>::remove_key(RawOrigin::Signed(1).into()));
+		assert_eq!(PublicKeys::<Test>::get(1), None);
+		System::assert_last_event(Event::KeyRemoved { who: 1 }.into());
+		assert_noop!(
+			BlsSignatures::<Test>::remove_key(RawOrigin::Signed(1).into()),
+			Error::<Test>::NotRegistered,
+		);
+	});
+}
+
+#[test]
+fn requires_proofs_of_possession() {
+	new_test_ext().execute_with(|| {
+		let register = |public_key, proof_of_possession| {
+			BlsSignatures::<Test>::register_key(
+				RawOrigin::Signed(1).into(),
+				public_key,
+				proof_of_possession,
+			)
+		};
+
+		assert_noop!(
+			register(public_key(2), proof_of_possession(3)),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+		// A signature of the public key is not a proof of possession.
+		assert_noop!(
+			register(public_key(2), sign(2, &public_key(2))),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+		// A rogue key, the difference of a chosen key and a registered one, has no proof of
+		// possession.
+		let rogue = compressed(
+			G1Affine::generator() * Fr::from(5u8) - G1Affine::generator() * Fr::from(2u8),
+		);
+		assert_noop!(
+			register(rogue, proof_of_possession(5)),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+		assert_noop!(
+			register(compressed(G1Affine::zero()), compressed(G2Affine::zero())),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+	});
+}
+
+#[test]
+fn verifies_signatures() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		let message: BoundedVec<_, _> = b"message".to_vec().try_into().unwrap();
+
+		assert_ok!(BlsSignatures::<Test>::verify_signature(
+			RawOrigin::Signed(2).into(),
+			1,
+			message.clone(),
+			sign(2, &message),
+		));
+		System::assert_last_event(
+			Event::SignatureVerified { signer: 1, message_hash: BlakeTwo256::hash(&message) }
+				.into(),
+		);
+
+		assert_noop!(
+			BlsSignatures::<Test>::verify_signature(
+				RawOrigin::Signed(2).into(),
+				1,
+				message.clone(),
+				sign(3, &message),
+			),
+			Error::<Test>::InvalidSignature,
+		);
+		assert_noop!(
+			BlsSignatures::<Test>::verify_signature(
+				RawOrigin::Signed(2).into(),
+				1,
+				message.clone(),
+				[0; 96],
+			),
+			Error::<Test>::InvalidSignature,
+		);
+		assert_noop!(
+			BlsSignatures::<Test>::verify_signature(
+				RawOrigin::Signed(2).into(),
+				2,
+				message.clone(),
+				sign(3, &message),
+			),
+			Error::<Test>::NotRegistered,
+		);
+	});
+}
+
+#[test]
+fn verifies_aggregate_signatures() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		register(2);
+		register(3);
+		let messages = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+		let signature = compressed(
+			(1..=3u64)
+				.zip(&messages)
+				.map(|(account, message)| hash(message) * Fr::from(account + 1))
+				.sum::<G2Projective>(),
+		);
+		let verify = |signers: Vec<u64>, messages: Vec<Vec<u8>>| {
+			BlsSignatures::<Test>::verify_aggregate_signature(
+				RawOrigin::Signed(1).into(),
+				signers.try_into().unwrap(),
+				messages
+					.into_iter()
+					.map(|message| message.try_into().unwrap())
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap(),
+				signature,
+			)
+		};
+
+		assert_ok!(verify(vec![1, 2, 3], messages.clone()));
+		System::assert_last_event(
+			Event::AggregateSignatureVerified { signers: vec![1, 2, 3].try_into().unwrap() }.into(),
+		);
+
+		assert_noop!(verify(vec![2, 1, 3], messages.clone()), Error::<Test>::InvalidSignature);
+		assert_noop!(verify(vec![1, 2], messages.clone()), Error::<Test>::LengthMismatch);
+		assert_noop!(verify(vec![1, 2, 4], messages.clone()), Error::<Test>::NotRegistered);
+		assert_noop!(verify(vec![], vec![]), Error::<Test>::NoSigners);
+		assert_noop!(
+			verify(vec![1, 2, 3], vec![b"a".to_vec(), b"a".to_vec(), b"c".to_vec()]),
+			Error::<Test>::DuplicateMessage,
+		);
+	});
+}
+
+#[test]
+fn verifies_fast_aggregate_signatures() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		register(2);
+		register(3);
+		let message: BoundedVec<_, _> = b"message".to_vec().try_into().unwrap();
+		let signature = compressed(hash(&message) * Fr::from(2 + 3 + 4u8));
+		let verify = |signers: Vec<u64>| {
+			BlsSignatures::<Test>::verify_fast_aggregate_signature(
+				RawOrigin::Signed(1).into(),
+				signers.try_into().unwrap(),
+				message.clone(),
+				signature,
+			)
+		};
+
+		assert_ok!(verify(vec![3, 1, 2]));
+		System::assert_last_event(
+			Event::FastAggregateSignatureVerified {
+				signers: vec![3, 1, 2].try_into().unwrap(),
+				message_hash: BlakeTwo256::hash(&message),
+			}
+			.into(),
+		);
+
+		assert_noop!(verify(vec![1, 2]), Error::<Test>::InvalidSignature);
+		assert_noop!(verify(vec![1, 2, 4]), Error::<Test>::NotRegistered);
+		assert_noop!(verify(vec![]), Error::<Test>::NoSigners);
+	});
+}
+
+#[test]
+fn verifies_for_other_pallets() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		register(2);
+
+		assert!(<BlsSignatures<Test> as BlsVerify<u64>>::verify(&1, b"m", &sign(2, b"m")));
+		assert!(!<BlsSignatures<Test> as BlsVerify<u64>>::verify(&2, b"m", &sign(2, b"m")));
+
+		let signature = compressed(hash(b"a") * Fr::from(2u8) + hash(b"b") * Fr::from(3u8));
+		assert!(<BlsSignatures<Test> as BlsVerify<u64>>::aggregate_verify(
+			&[1, 2],
+			&[b"a".to_vec(), b"b".to_vec()],
+			&signature,
+		));
+		assert!(!<BlsSignatures<Test> as BlsVerify<u64>>::aggregate_verify(
+			&[1, 2],
+			&[b"b".to_vec(), b"a".to_vec()],
+			&signature,
+		));
+
+		let signature = compressed(hash(b"m") * Fr::from(5u8));
+		assert!(<BlsSignatures<Test> as BlsVerify<u64>>::fast_aggregate_verify(
+			&[1, 2],
+			b"m",
+			&signature,
+		));
+		assert!(!<BlsSignatures<Test> as BlsVerify<u64>>::fast_aggregate_verify(
+			&[1],
+			b"m",
+			&signature,
+		));
+	});
+}
+
+#[test]
+fn min_sig_variant_verifies() {
+	let hash = |dst: &[u8], message: &[u8]| {
+		MapToCurveBasedHasher::<G1Projective, DefaultFieldHasher<Sha256, 128>, WBMap<g1::Config>>::new(
+			dst,
+		)
+		.unwrap()
+		.hash(message)
+		.unwrap()
+	};
+	let public_key =
+		|secret: u8| -> [u8; 96] { compressed(G2Affine::generator() * Fr::from(secret)) };
+	let pop_dst = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+	let proof_of_possession = compressed(hash(pop_dst, &public_key(2)) * Fr::from(2u8));
+	assert!(MinSig::pop_verify(&public_key(2), &proof_of_possession));
+	assert!(!MinSig::pop_verify(&public_key(3), &proof_of_possession));
+
+	let signature = compressed(hash(MinSig::DST, b"m") * Fr::from(2u8));
+	assert!(MinSig::verify(&public_key(2), b"m", &signature));
+	assert!(!MinSig::verify(&public_key(2), b"n", &signature));
+
+	let signature = compressed(
+		hash(MinSig::DST, b"a") * Fr::from(2u8) + hash(MinSig::DST, b"b") * Fr::from(3u8),
+	);
+	assert!(matches!(
+		MinSig::aggregate_verify(
+			vec![public_key(2), public_key(3)],
+			vec![b"a".to_vec(), b"b".to_vec()],
+			&signature,
+		),
+		Ok(true)
+	));
+
+	let signature = compressed(hash(MinSig::DST, b"m") * Fr::from(5u8));
+	assert!(matches!(
+		MinSig::fast_aggregate_verify(vec![public_key(2), public_key(3)], b"m", &signature),
+		Ok(true)
+	));
+	assert!(matches!(
+		MinSig::fast_aggregate_verify(vec![public_key(2)], b"m", &signature),
+		Ok(false)
+	));
+}
@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_bls_signatures
+//!
+//! These are conservative estimates until the weights are generated with the benchmark CLI:
+//!
+//! ./target/production/substrate benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_bls_signatures --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --template=./.maintain/frame-weight-template.hbs --output=./frame/bls-signatures/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_bls_signatures.
+pub trait WeightInfo {
+	fn register_key() -> Weight;
+	fn remove_key() -> Weight;
+	fn verify_signature() -> Weight;
+	fn verify_aggregate_signature(n: u32, ) -> Weight;
+	fn verify_fast_aggregate_signature(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_bls_signatures using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: BlsSignatures PublicKeys (r:0 w:1)
+	fn register_key() -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:1)
+	fn remove_key() -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:0)
+	fn verify_signature() -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:0)
+	fn verify_aggregate_signature(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(2_500_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:0)
+	fn verify_fast_aggregate_signature(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(500_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: BlsSignatures PublicKeys (r:0 w:1)
+	fn register_key() -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:1)
+	fn remove_key() -> Weight {
+		Weight::from_ref_time(50_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:0)
+	fn verify_signature() -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:0)
+	fn verify_aggregate_signature(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(2_500_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: BlsSignatures PublicKeys (r:1 w:0)
+	fn verify_fast_aggregate_signature(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(500_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+	}
+}
@@ -0,0 +1,211 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the Bulletproofs pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, Get},
+	BoundedVec,
+};
+use sp_runtime::traits::Bounded;
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+use crate::Pallet as Bulletproofs;
+
+// 64 bit range proofs for 1, 2, 4 and 8 commitments, generated with:
+// ```
+// let values = (0..m as u64).map(|i| 1_000 * (i + 1)).collect::<Vec<_>>();
+// let blindings = (0..m as u64).map(|i| Scalar::from(7 + i)).collect::<Vec<_>>();
+// let mut transcript = Transcript::new(b"Substrate Bulletproofs");
+// transcript.append_message(b"domain", TRANSCRIPT_DOMAIN);
+// RangeProof::prove_multiple(
+//     &BulletproofGens::new(64, m),
+//     &PedersenGens::default(),
+//     &mut transcript,
+//     &values,
+//     &blindings,
+//     64,
+// )
+// ```
+const PROOFS: [(&[&str], &str); 4] = [
+	(
+		&["2abb64b05270eb9702f95b0486894d78874b90007a3c7f4204026ee05c04cb18"],
+		"\
+5ecfe12d58771fcdb14fe03fd8412d3b0a07ffcbb2c3b224d3c0295e2a730c704ad9d8e3345e55d803523cea8d0a3eab\
+5e07a45517541fb3b5427a68591da30edaecd390ddebb43761f0db9c94da59f851408739aa60f7a26e4937dd47a5e34e\
+e0ec34e599126428958f1649c097bd4d693f8c5a09cf3f20dee15ea9bbbe1a4d35b70f0d1f37e038be740496ee61a944\
+e8115450c01c30db71218f88ecfa6a0573d4331f346075a9a97dc9eea4521eb303e16f7481eb4dd0d858b17f3730480c\
+d2924398c496edb4099afa58626bf95bfcf850e3cb31af6fd32dfb51ffe026011c9a0bb8f6154333eec613226c807c6b\
+78473a725a16850f10697bf3c0414d7cde322b25b5067488ec602360c01000b6801f7e40e747ea9b115b00d5dcfaa743\
+f415d5ada1ecc68f95e466fca4a9c4f0403628e8f3962850f3ad391ac0e7d3261e2800e4565069d7f8f9fa7e637ef8c1\
+74cc8284ce00640047e04e58be887b6bda9e0823b1a641792f962ff462415f6962243bb0a6c951e66eeb55aa86a32022\
+1cfadafa4c6d10d4f977609295d0c1b926302073f0cb5b56e72ebb97e4f0be0736c630ad37e20d635491638b807fd16b\
+01d77985d92011c7eca165e4293779264cbd75ba5e63cd5b3c886ed08c3f2110416c04bc066eff6329cafcf36decbf57\
+e67f1c9978410c47aa601c6c5516559f8fea64c8c921e49a3b6c761d2b1340457c79229b8e4b14bc36f87416438df18b\
+0cd3e886de2a96cd64786d4ea3338779e86db0d06eb37d310b8ac3d0a1d54288ef2b04398188fdaa285fd7330d44f017\
+04bd1e26dc67f22b7f9e0322645d32d8cda206ed60bfcbff0172b8daca2ad21219600db4568b43515b94b2916405291c\
+17e10c717b5129847af8bb95be8b210476ddd93b4f873eabfd9170c8337f8056fbf9a02fccfe221f44ecf891bdac460f\
+",
+	),
+	(
+		&[
+			"2abb64b05270eb9702f95b0486894d78874b90007a3c7f4204026ee05c04cb18",
+			"18bbb38e04e88767b6994df5873258b0bd46497d2453ad00e72cbd064d286f41",
+		],
+		"\
+3c2db7b83851ab43aab68c8886395df152f07533b0f1b7afdd0f5bd14027cd5ba2076e18f9b9cd49c8b9f36defdd400e\
+527c030a4efa1a5fc06cb8d5a6debd3b147fc835a55ecff37c569ad4f745812a1e711a3262b632fa0659a871c8646d09\
+b60efc320281508894b6155f7b07e6e5cfac1c9469b0b8c0fba61875f271d90f8f28297efe455d73795303710010397d\
+e7f8a329d5028255a311a9ea7b28ea021abcbff7839f06184ada21c2a9a93872a2063205f6976b00b2541e4f713c3201\
+4531de283b4ebaa23f214ce00021c3939e57b7e6399bfa5c14512af4c94a79045c08a3ffa97b5d9c0919d2f20e32f9ff\
+85e3a3e130f37e5f8d04bd8f6b8db100dc1d950d3df389c70afe24722263414a4d0caf299af3db650d95c22112c7ca02\
+2ae4c1fc6d5dcf8d1b6fb15fa7cc30e22e03f59ec352d1f19638c61232ae025dccca14c871270ffc5bb64b45ae41d4a2\
+e767c8a4b4350d49531f7a52a2f6c4304e460a671bd5a3ad57303ed88c633834f1850c0395deff0d49cd13c8ff802b54\
+a65c1d1099fa17660d39d8ba5788817d75c112fd13d4dd6ad49e64956ae3c213366bec93d83f2b0d2984e663717761ec\
+e100da1cb15c75d58f4fdd4015cc4706b2909dc4fa86f84fe27e39486e2591b0c22eb784a0b33548aa8413fa2fb4fc59\
+48848f76259d111ca3e519dbbe8cd59370cdb98ff12b5851b622636561a47b0f5a57f135c0c9ff95b28ff4f0cb7f37d7\
+7353b3685d70417c8a55482960f6617440d358d055b41af56f7d267dd0f9e31b0d8653262204430e2ff6846b13823d41\
+9816715582d28a9028944e157edec78f5ede7d3b7c15dac780f1caaff8216a35305fb71cf4e7258739d9ecf98c2a3b78\
+35236225f4670ba92679f385eaf811755e2d7393a3d5c1095dfcd49b79e62a52b4cfb0b1fe23afa48350706a198ca479\
+f14e3e24c1eafdf415338a65784b9a4765af7c1675a93a1590f7bd62caf39905806e108cb4d0ebb8cfaa4413957459e1\
+ef3bacc3b670c4148a41bcbcff3cc607\
+",
+	),
+	(
+		&[
+			"2abb64b05270eb9702f95b0486894d78874b90007a3c7f4204026ee05c04cb18",
+			"18bbb38e04e88767b6994df5873258b0bd46497d2453ad00e72cbd064d286f41",
+			"e00e6f41dc8963d99decacfaa49fe6ce74cbec10328db2b041c5aa5b9396db1f",
+			"5a6874f93e50e4aac7a8fd7c0d28aeeb619534407a01a19e2395ad13d1049421",
+		],
+		"\
+be925d8c9f68b98d355296778f38c8605abd593e5f2db1a59a00669baa952652703ee371d3ac9d220cd7cc6178037f81\
+7b64a84d23446b6d2c1fd02c1eab1e06be9b4049adacf06f4f2ee5d3d6cbc07492268b9b8ba1f8c73785b78a4688840a\
+00dd8fc9635eb9634c1cbcb527252173cd7eb845c36725b024695fa4df6729241ac78b71af3607ad2d5dd12b220fa739\
+336c1baf287feb14a55f4e628670f3006964f7fb8ac4727a6328c8667824068bba9ffd66a5f5c3df6d3692ddc6314303\
+4a3d2c455f7a3dd34d0f71b584943edbd3a374604003300361538c744eb3c00ab61a237f5e0d749f30019cdfe2e443a0\
+cb3c5aa5c36fcf1ca90ae54650f2ac1a863b1eb85e28ff20694d3ec3e9b158b24bb081e8c42ac7066556991577b9f369\
+8a5c15c06fcf38b3f16333620875b6345f3ffbea20fbba885f0e6aaa07b70b4d0c88f08cd5d4a798ba1e4f1984dbd0cb\
+f9f5c1365225545a93fbbebfcb479a3accd7d739fb15ea28e977731d1e1f6c73136c5979609cbd17e9a23d8a509d2b36\
+20d94b12379713d4527128e1c878cec72c47184ece80af2c050ce885f0e8390a14e32c4191897888ff626ef6f37ea881\
+b1da93a71635ee23af8f64913cb0203e9ae58829f26d83e0438ec5ea5cded152d7abf493e85129d0fd121b3668797c23\
+de1dd289255b8791d890e7f6347567abf00c605de21a9d46bfcecc8689b8542bfc01c36d162c18977e48fba27a0df904\
+6eba0d16a043a3e5e58be2284068336c9af917e2147f6b76c71feeee1f6f4b51c5f2abe67a665fc8c1c38c1121bf1d02\
+5a91eec51792d1cc01dfbb309f37eb13302f64c19f0e8a2724ef13aceb04d11310b8a1c3569dc460ff50a1e35e0e58d3\
+4aab6ceeba83055ac4fb63f234faf224b6087969ba68e38cc103e417f631411fe1a8f1521eec0e9165dd710146f8a01b\
+6e3f8800c4fa183e67c73f98e904787bb0e20e6c57e3914dabb0881fcc874742f8ebf4db8677985c00cb349af0f5fbfd\
+71e3a4d66c431a7bdb58e57c7b9fdf11fb14c63baf2960bb325d916c0ad552c026d97fe702e04745049302f4ed112e06\
+cd39b8236a4c6df5f89ad8c9bbb5d18c03869b1f85b366983b89b99051dcf50f\
+",
+	),
+	(
+		&[
+			"2abb64b05270eb9702f95b0486894d78874b90007a3c7f4204026ee05c04cb18",
+			"18bbb38e04e88767b6994df5873258b0bd46497d2453ad00e72cbd064d286f41",
+			"e00e6f41dc8963d99decacfaa49fe6ce74cbec10328db2b041c5aa5b9396db1f",
+			"5a6874f93e50e4aac7a8fd7c0d28aeeb619534407a01a19e2395ad13d1049421",
+			"144bbe3666212f4e236abf9a51b8ce1212538c37635eb4246fe36b43bd52b513",
+			"1a62ef5f9b55fba593c511be2e9b3839bd7e82e47729714370431fa2d982872b",
+			"0a0a2dc36932627d34209736c3621eda484d6a1f9c3461b45bd9d788a674045d",
+			"4c6eb4963524b486c319c935508461fbbb8ce212e42edf62b060244bbd985841",
+		],
+		"\
+9e1117a97ef6287feae852114aff104b7dc9c6289c69ccb3532b8b7f7176e41ada9ad3e9f2d7791b683c0c51e69e6301\
+1cc9852cfb77b12db9d94fd0d7f1de2dd2fff1619dd7add05ad9be64b54b6c01cc11266834b2cf6f4c3ec62df99e1b6f\
+54803a93e532f87ea6ee3e9339c6512bb52a86cdebfa02cb0169ae18c6582b31a04c7619b51a69076bd1ca8bb5c43ded\
+0da41e472f14c99153d7ad17ad327607a026cec617554f4674bb27baac32ee7130f771dee5f390070a3e0b2b2a8c1704\
+0c4730f253ad5ee75af4cdc76a949b4b5a63186db6db196bc46653d79c3b69002869ed3d072c0553535e0913d1fd10a2\
+66c33be9be31ced809506dcb9c3cf30dec1e6a4be6d663596f67528373840fed423c227971d01f15f8397c21a8031b4c\
+c488fe8f07f18769b5e55a88cb99e9edf2440d9e846936d0a34f97fad1b46e2aca246e6e6ecd51f61b859505a730cffd\
+989b2cc095eb523d147dea38d1c0f6380c49db3d9eeb4f056ecf33015dcc73acf6e61b0c6b28160fda70ee7aaa6a6910\
+bae54cf336ccf2e47e1f32f9247bcb6fb4294ecca12f9dc64e9d65c3cc49d23baa8eee8be6599528ea7067105777fd30\
+41460595f8cd20365e728194c521445e68a8d2ce6f6c56286f5dbe031c9747d7b79b717864e1e4f8d56d9182c36e085a\
+b448ed024879c69d70619002bb687ba57a47fe007b46b2a3afd7cf35e2829e2d64148461e866def6025fb660847bcf3f\
+7b9376029c75b5d64f277c84f23b173154bd111c6c1f0c6b066cb00f174fa92ff9c17e8d7c623897c42ae120d140f21b\
+be63b98f3a6871eb03347dd6232f33d252834407d787385ddfe585b9dfc321511809407d641c6a851141ad998f67b6b7\
+777c43c3bd3fe4d5d0b192de465a820c761be0dc4097d17c76d10c1c55d2909e480cbdc768b3d8c4c2fd761b823e8e37\
+dae16a55539dc96dc82671c5ab5989efef0e79da728060fa872ff191ed230e7930101ff287a2dff966a6059d5f49ac02\
+712df77e76b31c11a3cc855793bc720e00a690a626915eee95a24ecf61115d319932dfbed0d7611de77bc8cd2b670d3d\
+5ef4917ac5e88efd428622c0b23c4654de47481df62fc1d18f03434546b2bc2799864c5ea4a8156eb29cb70518776acd\
+fe14e59bedb9df39a2c7aed83fd27303146cb36587e06b8caf992b853be3485d688c4455af17a09b1a5e784aa0564303\
+",
+	),
+];
+
+/// The fixture proving the smallest power of two of commitments that is at least `c`.
+fn fixture<T: Config>(
+	c: u32,
+) -> (BoundedVec<Commitment, T::MaxCommitments>, BoundedVec<u8, MaxProofLength<T>>) {
+	let (commitments, proof) = PROOFS[c.next_power_of_two().trailing_zeros() as usize];
+	(
+		commitments
+			.iter()
+			.map(|c| array_bytes::hex2array_unchecked(c))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+		array_bytes::hex2bytes_unchecked(proof).try_into().unwrap(),
+	)
+}
+
+/// A caller able to pay the deposits of any number of commitments.
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	verify_range_proof {
+		let c in 1 .. T::MaxCommitments::get().min(8);
+		let caller = funded_caller::<T>();
+		let (commitments, proof) = fixture::<T>(c);
+	}: _(RawOrigin::Signed(caller.clone()), commitments.clone(), 64, proof)
+	verify {
+		assert_last_event::<T>(Event::RangeProofVerified { who: caller, commitments, bits: 64 }.into());
+	}
+
+	remove_commitments {
+		let c in 1 .. T::MaxCommitments::get().min(8);
+		let caller = funded_caller::<T>();
+		let (commitments, proof) = fixture::<T>(c);
+		Pallet::<T>::verify_range_proof(
+			RawOrigin::Signed(caller.clone()).into(),
+			commitments.clone(),
+			64,
+			proof,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), commitments.clone())
+	verify {
+		assert_last_event::<T>(Event::CommitmentsRemoved { who: caller, commitments }.into());
+	}
+
+	impl_benchmark_test_suite!(Bulletproofs, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,276 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Bulletproofs Pallet
+//!
+//! Verifies aggregated Bulletproofs range proofs over Ristretto Pedersen commitments.
+//!
+//! A successful [`Pallet::verify_range_proof`] call records every proven commitment in
+//! [`VerifiedCommitments`] together with the bit size of the range it was proven to lie in, so
+//! that other pallets (e.g. confidential assets) can require a commitment to be range checked
+//! before accepting it. Pallets that want to check a proof without recording anything can use
+//! [`Pallet::check_range_proof`].
+//!
+//! Recording a commitment reserves [`Config::CommitmentDeposit`] from the caller, which is
+//! returned when they remove it with [`Pallet::remove_commitments`].
+//!
+//! Verification runs natively through the `bulletproofs_verify_range_proof` host function.
+//! Provers must use the default Pedersen generators and the [`TRANSCRIPT_DOMAIN`].
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Currency, Get, ReservableCurrency},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::marker::PhantomData;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The domain appended to the range proof transcript.
+///
+/// Provers must use the same domain, otherwise verification fails.
+pub const TRANSCRIPT_DOMAIN: &[u8] = b"pallet-bulletproofs";
+
+/// A compressed Ristretto Pedersen commitment.
+pub type Commitment = [u8; 32];
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The length in bytes of a range proof of `bits` bits for `commitments` commitments.
+///
+/// Both must be powers of two.
+pub fn proof_len(bits: u32, commitments: u32) -> u32 {
+	// Four points and three scalars, and the inner product proof of `bits * commitments`
+	// generators, made of two points per halving round and two scalars.
+	32 * (9 + 2 * (bits * commitments).trailing_zeros())
+}
+
+/// The length of a 64 bit range proof for [`Config::MaxCommitments`] commitments.
+pub struct MaxProofLength<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for MaxProofLength<T> {
+	fn get() -> u32 {
+		proof_len(64, T::MaxCommitments::get())
+	}
+}
+
+/// The range a commitment was proven to lie in, and the deposit held for recording it.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ProvenRange<AccountId, Balance> {
+	/// The bit size of the smallest range the commitment was proven to lie in.
+	pub bits: u8,
+	/// The account that first recorded the commitment.
+	pub depositor: AccountId,
+	/// The amount reserved from the depositor.
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency the deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for every commitment recorded in [`VerifiedCommitments`].
+		#[pallet::constant]
+		type CommitmentDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of commitments covered by a single aggregated proof.
+		///
+		/// Must be a power of two and at most [`sp_io::BULLETPROOFS_MAX_COMMITMENTS`].
+		#[pallet::constant]
+		type MaxCommitments: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Commitments proven to open to a value in `[0, 2^bits)`.
+	///
+	/// If a commitment was proven several times, the smallest range is kept, and the deposit
+	/// stays with the account that recorded it first.
+	#[pallet::storage]
+	pub type VerifiedCommitments<T: Config> =
+		StorageMap<_, Blake2_128Concat, Commitment, ProvenRange<T::AccountId, BalanceOf<T>>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A range proof was verified for the given commitments.
+		RangeProofVerified {
+			who: T::AccountId,
+			commitments: BoundedVec<Commitment, T::MaxCommitments>,
+			bits: u8,
+		},
+		/// Recorded commitments were removed and their deposits returned.
+		CommitmentsRemoved {
+			who: T::AccountId,
+			commitments: BoundedVec<Commitment, T::MaxCommitments>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The range bit size is not one of 8, 16, 32 or 64.
+		InvalidBitSize,
+		/// The number of commitments is not a power of two, or exceeds
+		/// [`sp_io::BULLETPROOFS_MAX_COMMITMENTS`].
+		InvalidCommitmentCount,
+		/// The length of the proof does not match the bit size and number of commitments.
+		InvalidProofLength,
+		/// The range proof did not verify.
+		InvalidRangeProof,
+		/// The commitment is not recorded in [`VerifiedCommitments`].
+		UnknownCommitment,
+		/// The commitment was recorded by another account.
+		NotDepositor,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxCommitments::get().is_power_of_two(),
+				"`MaxCommitments` must be a power of two",
+			);
+			assert!(
+				T::MaxCommitments::get() <= sp_io::BULLETPROOFS_MAX_COMMITMENTS,
+				"`MaxCommitments` must not exceed the commitments accepted by the host function",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Verify an aggregated range proof showing that each of `commitments` opens to a value
+		/// in `[0, 2^bits)`, and record the commitments in [`VerifiedCommitments`].
+		///
+		/// The dispatch origin for this call must be _Signed_. [`Config::CommitmentDeposit`] is
+		/// reserved for every commitment that was not recorded yet.
+		#[pallet::weight(T::WeightInfo::verify_range_proof(commitments.len() as u32))]
+		pub fn verify_range_proof(
+			origin: OriginFor<T>,
+			commitments: BoundedVec<Commitment, T::MaxCommitments>,
+			bits: u8,
+			proof: BoundedVec<u8, MaxProofLength<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::check_range_proof(&commitments, bits, &proof)?;
+
+			let deposit = T::CommitmentDeposit::get();
+			let mut total_deposit = BalanceOf::<T>::default();
+			for commitment in commitments.iter() {
+				VerifiedCommitments::<T>::mutate(commitment, |proven| match proven {
+					Some(proven) => proven.bits = proven.bits.min(bits),
+					None => {
+						total_deposit.saturating_accrue(deposit);
+						*proven = Some(ProvenRange { bits, depositor: who.clone(), deposit });
+					},
+				});
+			}
+			T::Currency::reserve(&who, total_deposit)?;
+
+			Self::deposit_event(Event::RangeProofVerified { who, commitments, bits });
+			Ok(())
+		}
+
+		/// Remove `commitments` from [`VerifiedCommitments`] and return their deposits.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account that recorded all
+		/// of the commitments.
+		#[pallet::weight(T::WeightInfo::remove_commitments(commitments.len() as u32))]
+		pub fn remove_commitments(
+			origin: OriginFor<T>,
+			commitments: BoundedVec<Commitment, T::MaxCommitments>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut total_deposit = BalanceOf::<T>::default();
+			for commitment in commitments.iter() {
+				let proven = VerifiedCommitments::<T>::take(commitment)
+					.ok_or(Error::<T>::UnknownCommitment)?;
+				ensure!(proven.depositor == who, Error::<T>::NotDepositor);
+				total_deposit.saturating_accrue(proven.deposit);
+			}
+			T::Currency::unreserve(&who, total_deposit);
+
+			Self::deposit_event(Event::CommitmentsRemoved { who, commitments });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Verify an aggregated range proof for `commitments` without recording them.
+	pub fn check_range_proof(commitments: &[Commitment], bits: u8, proof: &[u8]) -> DispatchResult {
+		ensure!(matches!(bits, 8 | 16 | 32 | 64), Error::<T>::InvalidBitSize);
+		ensure!(
+			commitments.len().is_power_of_two() &&
+				commitments.len() <= sp_io::BULLETPROOFS_MAX_COMMITMENTS as usize,
+			Error::<T>::InvalidCommitmentCount,
+		);
+		ensure!(
+			proof.len() == proof_len(bits.into(), commitments.len() as u32) as usize,
+			Error::<T>::InvalidProofLength,
+		);
+		ensure!(
+			sp_io::crypto::bulletproofs_verify_range_proof(
+				proof,
+				commitments.to_vec(),
+				bits.into(),
+				TRANSCRIPT_DOMAIN,
+			),
+			Error::<T>::InvalidRangeProof,
+		);
+		Ok(())
+	}
+
+	/// The bit size of the smallest range `commitment` was proven to lie in, if any.
+	pub fn proven_range(commitment: &Commitment) -> Option<u8> {
+		VerifiedCommitments::<T>::get(commitment).map(|proven| proven.bits)
+	}
+}
@@ -0,0 +1,308 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Bulletproofs pallet.
+
+use super::{
+	proof_len, Commitment, Error, Event, MaxProofLength, Pallet as Bulletproofs, ProvenRange,
+	VerifiedCommitments, TRANSCRIPT_DOMAIN,
+};
+use crate::mock::*;
+use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
+use curve25519_dalek_ng::scalar::Scalar;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ConstU32, Get, ReservableCurrency},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use merlin::Transcript;
+
+type Proof = BoundedVec<u8, MaxProofLength<Test>>;
+
+fn prove_with_domain(values: &[u64], bits: usize, domain: &[u8]) -> (Vec<Commitment>, Proof) {
+	let blindings = (0..values.len() as u64).map(|i| Scalar::from(i + 1)).collect::<Vec<_>>();
+	let mut transcript = Transcript::new(b"Substrate Bulletproofs");
+	transcript.append_message(b"domain", domain);
+	let (proof, commitments) = RangeProof::prove_multiple(
+		&BulletproofGens::new(64, values.len()),
+		&PedersenGens::default(),
+		&mut transcript,
+		values,
+		&blindings,
+		bits,
+	)
+	.unwrap();
+	(commitments.iter().map(|c| c.to_bytes()).collect(), proof.to_bytes().try_into().unwrap())
+}
+
+fn prove(values: &[u64], bits: usize) -> (Vec<Commitment>, Proof) {
+	prove_with_domain(values, bits, TRANSCRIPT_DOMAIN)
+}
+
+fn bounded(commitments: Vec<Commitment>) -> BoundedVec<Commitment, ConstU32<8>> {
+	commitments.try_into().unwrap()
+}
+
+#[test]
+fn verifies_and_records_commitments() {
+	new_test_ext().execute_with(|| {
+		let (commitments, proof) = prove(&[0, 1, 42, u32::MAX as u64], 32);
+
+		assert_ok!(Bulletproofs::<Test>::verify_range_proof(
+			RawOrigin::Signed(1).into(),
+			bounded(commitments.clone()),
+			32,
+			proof,
+		));
+
+		for commitment in &commitments {
+			assert_eq!(Bulletproofs::<Test>::proven_range(commitment), Some(32));
+		}
+		System::assert_last_event(
+			Event::RangeProofVerified { who: 1, commitments: bounded(commitments), bits: 32 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn keeps_smallest_proven_range() {
+	new_test_ext().execute_with(|| {
+		let (commitments, proof) = prove(&[7], 8);
+		assert_ok!(Bulletproofs::<Test>::verify_range_proof(
+			RawOrigin::Signed(1).into(),
+			bounded(commitments.clone()),
+			8,
+			proof,
+		));
+
+		// The same commitment proven in a larger range does not widen the recorded range.
+		let (same_commitments, proof) = prove(&[7], 64);
+		assert_eq!(same_commitments, commitments);
+		assert_ok!(Bulletproofs::<Test>::verify_range_proof(
+			RawOrigin::Signed(1).into(),
+			bounded(commitments.clone()),
+			64,
+			proof,
+		));
+
+		assert_eq!(
+			VerifiedCommitments::<Test>::get(&commitments[0]),
+			Some(ProvenRange { bits: 8, depositor: 1, deposit: 10 }),
+		);
+	});
+}
+
+#[test]
+fn reserves_deposits_for_new_commitments() {
+	new_test_ext().execute_with(|| {
+		let (commitments, proof) = prove(&[1, 2], 16);
+		assert_ok!(Bulletproofs::<Test>::verify_range_proof(
+			RawOrigin::Signed(1).into(),
+			bounded(commitments.clone()),
+			16,
+			proof,
+		));
+		assert_eq!(Balances::reserved_balance(1), 20);
+
+		// Only the commitment that was not recorded yet is charged.
+		let (more_commitments, proof) = prove(&[1, 2, 3, 4], 8);
+		assert_eq!(more_commitments[..2], commitments[..]);
+		assert_ok!(Bulletproofs::<Test>::verify_range_proof(
+			RawOrigin::Signed(2).into(),
+			bounded(more_commitments.clone()),
+			8,
+			proof,
+		));
+		assert_eq!(Balances::reserved_balance(1), 20);
+		assert_eq!(Balances::reserved_balance(2), 20);
+		assert_eq!(VerifiedCommitments::<Test>::get(&commitments[0]).unwrap().depositor, 1);
+		assert_eq!(Bulletproofs::<Test>::proven_range(&commitments[0]), Some(8));
+
+		// Not enough free balance for the deposits.
+		assert_ok!(Balances::reserve(&1, 75));
+		let (commitments, proof) = prove(&[5], 8);
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments),
+				8,
+				proof,
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn removes_commitments_and_returns_deposits() {
+	new_test_ext().execute_with(|| {
+		let (commitments, proof) = prove(&[1, 2], 16);
+		assert_ok!(Bulletproofs::<Test>::verify_range_proof(
+			RawOrigin::Signed(1).into(),
+			bounded(commitments.clone()),
+			16,
+			proof,
+		));
+
+		assert_noop!(
+			Bulletproofs::<Test>::remove_commitments(
+				RawOrigin::Signed(2).into(),
+				bounded(commitments.clone()),
+			),
+			Error::<Test>::NotDepositor,
+		);
+		assert_noop!(
+			Bulletproofs::<Test>::remove_commitments(
+				RawOrigin::Signed(1).into(),
+				bounded(vec![commitments[0], [0; 32]]),
+			),
+			Error::<Test>::UnknownCommitment,
+		);
+
+		assert_ok!(Bulletproofs::<Test>::remove_commitments(
+			RawOrigin::Signed(1).into(),
+			bounded(vec![commitments[0]]),
+		));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Bulletproofs::<Test>::proven_range(&commitments[0]), None);
+		assert_eq!(Bulletproofs::<Test>::proven_range(&commitments[1]), Some(16));
+		System::assert_last_event(
+			Event::CommitmentsRemoved { who: 1, commitments: bounded(vec![commitments[0]]) }.into(),
+		);
+	});
+}
+
+#[test]
+fn rejects_invalid_proofs() {
+	new_test_ext().execute_with(|| {
+		let (commitments, proof) = prove(&[1, 2], 16);
+
+		// Proven for a different range.
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments.clone()),
+				32,
+				proof.clone(),
+			),
+			Error::<Test>::InvalidProofLength,
+		);
+		// A proof of the same length for a different range and number of commitments.
+		let (other_commitments, other_proof) = prove(&[1], 32);
+		assert_eq!(other_proof.len(), proof.len());
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(other_commitments),
+				32,
+				proof.clone(),
+			),
+			Error::<Test>::InvalidRangeProof,
+		);
+
+		// Commitments in the wrong order.
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments.iter().rev().cloned().collect()),
+				16,
+				proof.clone(),
+			),
+			Error::<Test>::InvalidRangeProof,
+		);
+
+		// Malformed proofs.
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments.clone()),
+				16,
+				proof[1..].to_vec().try_into().unwrap(),
+			),
+			Error::<Test>::InvalidProofLength,
+		);
+		let mut corrupted = proof.to_vec();
+		corrupted[0] ^= 1;
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments.clone()),
+				16,
+				corrupted.try_into().unwrap(),
+			),
+			Error::<Test>::InvalidRangeProof,
+		);
+
+		// Proven for another domain.
+		let (commitments, proof) = prove_with_domain(&[1, 2], 16, b"other");
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments),
+				16,
+				proof,
+			),
+			Error::<Test>::InvalidRangeProof,
+		);
+	});
+}
+
+#[test]
+fn rejects_invalid_parameters() {
+	new_test_ext().execute_with(|| {
+		let (commitments, proof) = prove(&[1, 2], 16);
+
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(commitments.clone()),
+				12,
+				proof.clone(),
+			),
+			Error::<Test>::InvalidBitSize,
+		);
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(vec![commitments[0]; 3]),
+				16,
+				proof.clone(),
+			),
+			Error::<Test>::InvalidCommitmentCount,
+		);
+		assert_noop!(
+			Bulletproofs::<Test>::verify_range_proof(
+				RawOrigin::Signed(1).into(),
+				bounded(vec![]),
+				16,
+				proof,
+			),
+			Error::<Test>::InvalidCommitmentCount,
+		);
+	});
+}
+
+#[test]
+fn proof_lengths_match_the_prover() {
+	for (values, bits) in [(&[1][..], 8), (&[1, 2][..], 64), (&[0; 8][..], 64)] {
+		let (_, proof) = prove(values, bits);
+		assert_eq!(proof.len() as u32, proof_len(bits as u32, values.len() as u32));
+	}
+	assert_eq!(MaxProofLength::<Test>::get(), proof_len(64, 8));
+}
@@ -0,0 +1,49 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the Groth16 verifier pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::DispatchResult;
+use sp_std::vec::Vec;
+
+pub use pallet_groth16_verifier::{G1Affine, G2Affine, Proof, PublicInput};
+
+sp_api::decl_runtime_apis! {
+	/// The elliptic curve operations and proof verification of the runtime, so that clients can
+	/// run them without submitting extrinsics.
+	pub trait ArkworksApi<VerifyingKeyId> where
+		VerifyingKeyId: Codec,
+	{
+		/// Verify `proof` of `public_inputs` against the stored verifying key `vk_id`.
+		fn verify_groth16(
+			vk_id: VerifyingKeyId,
+			proof: Proof,
+			public_inputs: Vec<PublicInput>,
+		) -> DispatchResult;
+
+		/// Whether the product of the pairings `e(g1ᵢ, g2ᵢ)` is one, or `None` if the vectors
+		/// have different lengths.
+		fn pairing_check(g1: Vec<G1Affine>, g2: Vec<G2Affine>) -> Option<bool>;
+
+		/// The multi-scalar multiplication `Σ scalarsᵢ·basesᵢ`, or `None` if the vectors have
+		/// different lengths.
+		fn msm(bases: Vec<G1Affine>, scalars: Vec<PublicInput>) -> Option<G1Affine>;
+	}
+}
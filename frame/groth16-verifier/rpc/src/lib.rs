@@ -0,0 +1,137 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the Groth16 verifier pallet.
+
+use std::sync::Arc;
+
+use codec::{Codec, Decode};
+use jsonrpsee::{
+	core::{DeserializeOwned, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_groth16_verifier::{public_input, Proof};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_groth16_verifier_rpc_runtime_api::ArkworksApi as ArkworksRuntimeApi;
+
+#[rpc(client, server)]
+pub trait ZkApi<BlockHash, VerifyingKeyId> {
+	/// Verify a proof against the verifying key `vk_id` stored at the block `at`, or the best
+	/// block, without submitting it.
+	///
+	/// The proof is SCALE encoded, and the public inputs are 32-byte big-endian integers smaller
+	/// than the modulus of the scalar field.
+	#[method(name = "zk_verifyProof")]
+	fn verify_proof(
+		&self,
+		vk_id: VerifyingKeyId,
+		proof: Bytes,
+		public_inputs: Vec<H256>,
+		at: Option<BlockHash>,
+	) -> RpcResult<()>;
+}
+
+/// Provides RPC methods to verify proofs against the verifying keys on chain.
+pub struct Zk<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Zk<C, P> {
+	/// Creates a new instance of the Zk Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The proof or a public input was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The proof was rejected.
+	VerificationError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::VerificationError => 3,
+		}
+	}
+}
+
+impl<C, Block, VerifyingKeyId> ZkApiServer<<Block as BlockT>::Hash, VerifyingKeyId> for Zk<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ArkworksRuntimeApi<Block, VerifyingKeyId>,
+	VerifyingKeyId: Codec + DeserializeOwned + Send + Sync + 'static,
+{
+	fn verify_proof(
+		&self,
+		vk_id: VerifyingKeyId,
+		proof: Bytes,
+		public_inputs: Vec<H256>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<()> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let decode_error = |message: String| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to verify proof.",
+				Some(message),
+			))
+		};
+		let proof = Proof::decode(&mut &*proof).map_err(|e| decode_error(format!("{:?}", e)))?;
+		let public_inputs = public_inputs
+			.iter()
+			.map(|input| {
+				public_input(input.as_bytes())
+					.ok_or_else(|| decode_error(format!("{:?} is not a field element", input)))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		api.verify_groth16(&at, vk_id, proof, public_inputs)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to verify proof.",
+					Some(e.to_string()),
+				))
+			})?
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::VerificationError.into(),
+					"Proof verification failed.",
+					Some(format!("{:?}", e)),
+				))
+				.into()
+			})
+	}
+}
@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the Groth16 verifier pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use codec::Decode;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{dispatch::UnfilteredDispatchable, traits::EnsureOrigin};
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+use sp_runtime::traits::TrailingZeroInput;
+
+#[cfg(test)]
+use crate::Pallet as Groth16Verifier;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+fn key_id<T: Config>() -> T::VerifyingKeyId {
+	T::VerifyingKeyId::decode(&mut TrailingZeroInput::zeroes())
+		.expect("infinite input is enough to decode a key identifier")
+}
+
+benchmarks! {
+	register_vk {
+		let n in 1 .. MaxKeyPoints::<T>::get();
+		let call = Call::<T>::register_vk {
+			key_id: key_id::<T>(),
+			key: Box::new(fixtures::verifying_key::<T>(n - 1)),
+		};
+		let origin = T::RegistrarOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::VerifyingKeyRegistered { key_id: key_id::<T>() }.into());
+	}
+
+	update_vk {
+		let n in 1 .. MaxKeyPoints::<T>::get();
+		VerifyingKeys::<T>::insert(key_id::<T>(), fixtures::verifying_key::<T>(0));
+		let call = Call::<T>::update_vk {
+			key_id: key_id::<T>(),
+			key: Box::new(fixtures::verifying_key::<T>(n - 1)),
+		};
+		let origin = T::RegistrarOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::VerifyingKeyUpdated { key_id: key_id::<T>() }.into());
+	}
+
+	remove_vk {
+		VerifyingKeys::<T>::insert(key_id::<T>(), fixtures::verifying_key::<T>(0));
+		let call = Call::<T>::remove_vk { key_id: key_id::<T>() };
+		let origin = T::RegistrarOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::VerifyingKeyRemoved { key_id: key_id::<T>() }.into());
+	}
+
+	// Named after the verification, as `verify` is reserved for the checks of a benchmark.
+	verify_proof {
+		let n in 0 .. T::MaxPublicInputs::get();
+		let caller: T::AccountId = whitelisted_caller();
+		VerifyingKeys::<T>::insert(key_id::<T>(), fixtures::verifying_key::<T>(n));
+		let public_inputs = fixtures::public_inputs(n).try_into().unwrap();
+	}: verify(RawOrigin::Signed(caller.clone()), key_id::<T>(), Box::new(fixtures::proof(n)), public_inputs)
+	verify {
+		assert_last_event::<T>(Event::ProofVerified { who: caller, key_id: key_id::<T>() }.into());
+	}
+
+	impl_benchmark_test_suite!(Groth16Verifier, crate::mock::new_test_ext(), crate::mock::Test);
+}
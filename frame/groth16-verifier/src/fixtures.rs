@@ -0,0 +1,70 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifying keys and proofs shared by the tests and benchmarks.
+//!
+//! The proofs are not computed by a prover for a circuit. Since the discrete logarithms of all
+//! points of the key are known, a proof satisfying the verification equation can be computed
+//! for any public inputs: with `α = a·G1`, `β = b·G2`, `γ = δ = G2` and `Kᵢ = (i + 1)·G1`, the
+//! proof `(A, B, C) = ((a·b + k + 1)·G1, G2, G1)` is valid for the public inputs whose input
+//! commitment is `k·G1`.
+
+use crate::{BoundedVerifyingKey, Config, Proof, PublicInput};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use sp_ark_scale::ArkScale;
+use sp_std::prelude::*;
+
+/// The discrete logarithm of `α`.
+const ALPHA: u64 = 0xa1fa;
+/// The discrete logarithm of `β`.
+const BETA: u64 = 0xbe7a;
+
+fn g1(scalar: Fr) -> G1Affine {
+	(G1Affine::generator() * scalar).into_affine()
+}
+
+/// The public inputs `1, …, n`.
+pub fn public_inputs(n: u32) -> Vec<PublicInput> {
+	(1..=n).map(|input| ArkScale(Fr::from(input))).collect()
+}
+
+/// A verifying key with `n` public inputs.
+pub fn verifying_key<T: Config>(n: u32) -> BoundedVerifyingKey<T> {
+	BoundedVerifyingKey::<T> {
+		alpha_g1: ArkScale(g1(ALPHA.into())),
+		beta_g2: ArkScale((G2Affine::generator() * Fr::from(BETA)).into_affine()),
+		gamma_g2: ArkScale(G2Affine::generator()),
+		delta_g2: ArkScale(G2Affine::generator()),
+		gamma_abc_g1: (0..=n)
+			.map(|i| ArkScale(g1(Fr::from(i + 1))))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("`n` is at most `MaxPublicInputs`"),
+	}
+}
+
+/// A proof of [`public_inputs`] for the [`verifying_key`] with `n` public inputs.
+pub fn proof(n: u32) -> Proof {
+	// `K₀ + Σ xᵢ·Kᵢ = (1 + Σ i·(i + 1))·G1`.
+	let commitment = (1..=n).map(|i| Fr::from(i) * Fr::from(i + 1)).sum::<Fr>() + Fr::from(1u8);
+	ArkScale(ark_groth16::Proof {
+		a: g1(Fr::from(ALPHA) * Fr::from(BETA) + commitment + Fr::from(1u8)),
+		b: G2Affine::generator(),
+		c: G1Affine::generator(),
+	})
+}
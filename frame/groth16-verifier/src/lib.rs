@@ -0,0 +1,313 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Groth16 Verifier Pallet
+//!
+//! Verifies Groth16 proofs over BN254 against verifying keys kept on chain.
+//!
+//! Verifying keys are stored in [`VerifyingKeys`] under an identifier of type
+//! [`Config::VerifyingKeyId`] chosen by the application, as [`BoundedVerifyingKey`]s with at most
+//! [`Config::MaxPublicInputs`] public inputs. The keys are set in the genesis configuration, and
+//! registered, updated and removed by [`Config::RegistrarOrigin`] through [`Pallet::register_vk`],
+//! [`Pallet::update_vk`] and [`Pallet::remove_vk`]. Each change emits an event, so that indexers
+//! can track the circuits a chain verifies.
+//!
+//! Proofs can be verified through [`Pallet::verify`], or by other pallets through the
+//! [`ProofVerifier`] trait. Proofs and public inputs are passed in the SCALE encoding of
+//! [`sp_ark_scale`], which is the compressed arkworks serialization, so they are checked to be
+//! valid points and field elements when they are decoded.
+//!
+//! For a key with the points `α`, `β`, `γ`, `δ` and `K₀, …, Kₙ`, a proof `(A, B, C)` of the public
+//! inputs `x₁, …, xₙ` is valid if
+//!
+//! `e(-A, B) · e(α, β) · e(K₀ + Σ xᵢ·Kᵢ, γ) · e(C, δ) = 1`.
+//!
+//! The sum is computed with the `bn254_ec_mul` and `bn254_ec_add` host functions, and the
+//! product of pairings is checked with a single call to the `bn254_ec_pairing` host function.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod fixtures;
+mod verification;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use ark_ff::{BigInteger, PrimeField};
+use frame_support::{dispatch::DispatchResult, traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// A Groth16 proof.
+pub type Proof = sp_ark_scale::bn254::Proof;
+/// A public input of a proof, an element of the scalar field.
+pub type PublicInput = sp_ark_scale::bn254::Fr;
+/// A point of the G1 group.
+pub type G1Affine = sp_ark_scale::bn254::G1Affine;
+/// A point of the G2 group.
+pub type G2Affine = sp_ark_scale::bn254::G2Affine;
+/// A verifying key with at most [`Config::MaxPublicInputs`] public inputs.
+pub type BoundedVerifyingKey<T> = sp_ark_scale::bn254::BoundedVerifyingKey<MaxKeyPoints<T>>;
+
+/// The maximum number of points `Kᵢ` of a verifying key, one more than
+/// [`Config::MaxPublicInputs`].
+pub struct MaxKeyPoints<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for MaxKeyPoints<T> {
+	fn get() -> u32 {
+		T::MaxPublicInputs::get().saturating_add(1)
+	}
+}
+
+/// The public input encoded by the 32 bytes `bytes` in big-endian, if it is smaller than the
+/// modulus.
+///
+/// Values that are not reduced are rejected, as they would alias other values in the circuit.
+pub fn public_input(bytes: &[u8]) -> Option<PublicInput> {
+	let element = ark_bn254::Fr::from_be_bytes_mod_order(bytes);
+	if element.into_bigint().to_bytes_be() != bytes {
+		return None
+	}
+	Some(sp_ark_scale::ArkScale(element))
+}
+
+/// The Keccak-256 hash of `data` shifted right by 8 bits, which is always a public input, as
+/// computed by the Semaphore and Tornado Cash contracts.
+pub fn hash_to_public_input(data: &[u8]) -> PublicInput {
+	let hash = sp_io::hashing::keccak_256(data);
+	sp_ark_scale::ArkScale(ark_bn254::Fr::from_be_bytes_mod_order(&hash[..31]))
+}
+
+/// The multi-scalar multiplication `Σ scalarsᵢ·basesᵢ`, computed with the same host functions as
+/// the verification of proofs, or `None` if the slices have different lengths.
+pub fn msm(bases: &[G1Affine], scalars: &[PublicInput]) -> Option<G1Affine> {
+	let bases = bases.iter().map(|base| base.0).collect::<sp_std::vec::Vec<_>>();
+	let scalars = scalars.iter().map(|scalar| scalar.0).collect::<sp_std::vec::Vec<_>>();
+	verification::msm(&bases, &scalars).map(sp_ark_scale::ArkScale)
+}
+
+/// Whether the product of the pairings `e(g1ᵢ, g2ᵢ)` is one, computed with the same host function
+/// as the verification of proofs, or `None` if the slices have different lengths.
+pub fn pairing_check(g1: &[G1Affine], g2: &[G2Affine]) -> Option<bool> {
+	let g1 = g1.iter().map(|point| point.0).collect::<sp_std::vec::Vec<_>>();
+	let g2 = g2.iter().map(|point| point.0).collect::<sp_std::vec::Vec<_>>();
+	verification::pairing_check(&g1, &g2)
+}
+
+/// Verification of Groth16 proofs against stored verifying keys, for other pallets.
+pub trait ProofVerifier<VerifyingKeyId> {
+	/// Verify `proof` of `public_inputs` against the verifying key `key_id`.
+	fn verify_proof(
+		key_id: &VerifyingKeyId,
+		proof: &Proof,
+		public_inputs: &[PublicInput],
+	) -> DispatchResult;
+
+	/// The weight of [`ProofVerifier::verify_proof`] for `public_inputs` public inputs.
+	fn verify_proof_weight(public_inputs: u32) -> Weight;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::MaybeSerializeDeserialize;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The identifier of a verifying key, chosen by the application using it.
+		type VerifyingKeyId: Parameter + Member + MaxEncodedLen + MaybeSerializeDeserialize;
+
+		/// The origin allowed to register, update and remove verifying keys.
+		type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of public inputs of a verifying key.
+		#[pallet::constant]
+		type MaxPublicInputs: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The verifying keys by their identifier.
+	#[pallet::storage]
+	pub type VerifyingKeys<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::VerifyingKeyId, BoundedVerifyingKey<T>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The verifying keys to store, with their identifiers.
+		pub verifying_keys: Vec<(T::VerifyingKeyId, BoundedVerifyingKey<T>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { verifying_keys: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (key_id, key) in &self.verifying_keys {
+				assert!(!key.gamma_abc_g1.is_empty(), "verifying keys must have a point `K₀`");
+				VerifyingKeys::<T>::insert(key_id, key);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The verifying key `key_id` was registered.
+		VerifyingKeyRegistered { key_id: T::VerifyingKeyId },
+		/// The verifying key `key_id` was replaced.
+		VerifyingKeyUpdated { key_id: T::VerifyingKeyId },
+		/// The verifying key `key_id` was removed.
+		VerifyingKeyRemoved { key_id: T::VerifyingKeyId },
+		/// A proof was verified against the verifying key `key_id`.
+		ProofVerified { who: T::AccountId, key_id: T::VerifyingKeyId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There is no verifying key with the given identifier.
+		UnknownVerifyingKey,
+		/// There is already a verifying key with the given identifier.
+		VerifyingKeyExists,
+		/// The verifying key has no point `K₀`.
+		InvalidVerifyingKey,
+		/// The number of public inputs does not match the verifying key.
+		PublicInputsMismatch,
+		/// The proof did not verify.
+		InvalidProof,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the verifying key `key` under the new identifier `key_id`.
+		///
+		/// The dispatch origin for this call must be [`Config::RegistrarOrigin`].
+		#[pallet::weight(T::WeightInfo::register_vk(key.gamma_abc_g1.len() as u32))]
+		pub fn register_vk(
+			origin: OriginFor<T>,
+			key_id: T::VerifyingKeyId,
+			key: Box<BoundedVerifyingKey<T>>,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+			ensure!(!VerifyingKeys::<T>::contains_key(&key_id), Error::<T>::VerifyingKeyExists);
+			ensure!(!key.gamma_abc_g1.is_empty(), Error::<T>::InvalidVerifyingKey);
+
+			VerifyingKeys::<T>::insert(&key_id, *key);
+
+			Self::deposit_event(Event::VerifyingKeyRegistered { key_id });
+			Ok(())
+		}
+
+		/// Replace the verifying key `key_id` with `key`.
+		///
+		/// The dispatch origin for this call must be [`Config::RegistrarOrigin`].
+		#[pallet::weight(T::WeightInfo::update_vk(key.gamma_abc_g1.len() as u32))]
+		pub fn update_vk(
+			origin: OriginFor<T>,
+			key_id: T::VerifyingKeyId,
+			key: Box<BoundedVerifyingKey<T>>,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+			ensure!(VerifyingKeys::<T>::contains_key(&key_id), Error::<T>::UnknownVerifyingKey);
+			ensure!(!key.gamma_abc_g1.is_empty(), Error::<T>::InvalidVerifyingKey);
+
+			VerifyingKeys::<T>::insert(&key_id, *key);
+
+			Self::deposit_event(Event::VerifyingKeyUpdated { key_id });
+			Ok(())
+		}
+
+		/// Remove the verifying key `key_id`.
+		///
+		/// The dispatch origin for this call must be [`Config::RegistrarOrigin`].
+		#[pallet::weight(T::WeightInfo::remove_vk())]
+		pub fn remove_vk(origin: OriginFor<T>, key_id: T::VerifyingKeyId) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+			ensure!(VerifyingKeys::<T>::contains_key(&key_id), Error::<T>::UnknownVerifyingKey);
+
+			VerifyingKeys::<T>::remove(&key_id);
+
+			Self::deposit_event(Event::VerifyingKeyRemoved { key_id });
+			Ok(())
+		}
+
+		/// Verify `proof` of `public_inputs` against the verifying key `key_id`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::verify_proof(public_inputs.len() as u32))]
+		pub fn verify(
+			origin: OriginFor<T>,
+			key_id: T::VerifyingKeyId,
+			proof: Box<Proof>,
+			public_inputs: BoundedVec<PublicInput, T::MaxPublicInputs>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::verify_proof(&key_id, &proof, &public_inputs)?;
+
+			Self::deposit_event(Event::ProofVerified { who, key_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> ProofVerifier<T::VerifyingKeyId> for Pallet<T> {
+	fn verify_proof(
+		key_id: &T::VerifyingKeyId,
+		proof: &Proof,
+		public_inputs: &[PublicInput],
+	) -> DispatchResult {
+		let key = VerifyingKeys::<T>::get(key_id).ok_or(Error::<T>::UnknownVerifyingKey)?;
+		frame_support::ensure!(
+			key.gamma_abc_g1.len() == public_inputs.len() + 1,
+			Error::<T>::PublicInputsMismatch,
+		);
+		frame_support::ensure!(
+			verification::verify(&key, &proof.0, public_inputs),
+			Error::<T>::InvalidProof,
+		);
+		Ok(())
+	}
+
+	fn verify_proof_weight(public_inputs: u32) -> Weight {
+		T::WeightInfo::verify_proof(public_inputs)
+	}
+}
@@ -0,0 +1,215 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Groth16 verifier pallet.
+
+use super::{
+	fixtures, BoundedVerifyingKey, Error, Event, Pallet as Groth16Verifier, ProofVerifier,
+};
+use crate::mock::*;
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::Groth16;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef};
+use ark_std::{test_rng, UniformRand};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_ark_scale::ArkScale;
+use sp_runtime::DispatchError;
+
+/// A proof of knowledge of `a` and `b` with `a · b = c` for the public input `c`.
+#[derive(Clone)]
+struct MultiplierCircuit(Fr, Fr);
+
+impl ConstraintSynthesizer<Fr> for MultiplierCircuit {
+	fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> ark_relations::r1cs::Result<()> {
+		use ark_relations::lc;
+
+		let a = cs.new_witness_variable(|| Ok(self.0))?;
+		let b = cs.new_witness_variable(|| Ok(self.1))?;
+		let c = cs.new_input_variable(|| Ok(self.0 * self.1))?;
+		cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + c)
+	}
+}
+
+#[test]
+fn registers_updates_and_removes_keys() {
+	new_test_ext().execute_with(|| {
+		let key = |n| Box::new(fixtures::verifying_key::<Test>(n));
+
+		assert_ok!(Groth16Verifier::<Test>::register_vk(RawOrigin::Root.into(), 1, key(1)));
+		assert_eq!(super::VerifyingKeys::<Test>::get(1), Some(*key(1)));
+		System::assert_last_event(Event::VerifyingKeyRegistered { key_id: 1 }.into());
+		assert_noop!(
+			Groth16Verifier::<Test>::register_vk(RawOrigin::Root.into(), 1, key(2)),
+			Error::<Test>::VerifyingKeyExists,
+		);
+
+		assert_ok!(Groth16Verifier::<Test>::update_vk(RawOrigin::Root.into(), 1, key(3)));
+		assert_eq!(super::VerifyingKeys::<Test>::get(1), Some(*key(3)));
+		System::assert_last_event(Event::VerifyingKeyUpdated { key_id: 1 }.into());
+		assert_ok!(Groth16Verifier::<Test>::verify_proof(
+			&1,
+			&fixtures::proof(3),
+			&fixtures::public_inputs(3),
+		));
+
+		assert_ok!(Groth16Verifier::<Test>::remove_vk(RawOrigin::Root.into(), 1));
+		assert_eq!(super::VerifyingKeys::<Test>::get(1), None);
+		System::assert_last_event(Event::VerifyingKeyRemoved { key_id: 1 }.into());
+		assert_noop!(
+			Groth16Verifier::<Test>::remove_vk(RawOrigin::Root.into(), 1),
+			Error::<Test>::UnknownVerifyingKey,
+		);
+		assert_noop!(
+			Groth16Verifier::<Test>::update_vk(RawOrigin::Root.into(), 1, key(1)),
+			Error::<Test>::UnknownVerifyingKey,
+		);
+	});
+}
+
+#[test]
+fn key_registration_is_checked() {
+	new_test_ext().execute_with(|| {
+		let key = || Box::new(fixtures::verifying_key::<Test>(1));
+
+		assert_noop!(
+			Groth16Verifier::<Test>::register_vk(RawOrigin::Signed(1).into(), 1, key()),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Groth16Verifier::<Test>::update_vk(RawOrigin::Signed(1).into(), KEY_ID, key()),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Groth16Verifier::<Test>::remove_vk(RawOrigin::Signed(1).into(), KEY_ID),
+			DispatchError::BadOrigin,
+		);
+
+		let mut empty = key();
+		empty.gamma_abc_g1 = Default::default();
+		assert_noop!(
+			Groth16Verifier::<Test>::register_vk(RawOrigin::Root.into(), 1, empty.clone()),
+			Error::<Test>::InvalidVerifyingKey,
+		);
+		assert_noop!(
+			Groth16Verifier::<Test>::update_vk(RawOrigin::Root.into(), KEY_ID, empty),
+			Error::<Test>::InvalidVerifyingKey,
+		);
+	});
+}
+
+#[test]
+fn verifies_proofs() {
+	new_test_ext().execute_with(|| {
+		let verify = |key_id, public_inputs: Vec<_>| {
+			Groth16Verifier::<Test>::verify(
+				RawOrigin::Signed(1).into(),
+				key_id,
+				Box::new(fixtures::proof(2)),
+				public_inputs.try_into().unwrap(),
+			)
+		};
+
+		assert_ok!(verify(KEY_ID, fixtures::public_inputs(2)));
+		System::assert_last_event(Event::ProofVerified { who: 1, key_id: KEY_ID }.into());
+
+		let mut public_inputs = fixtures::public_inputs(2);
+		public_inputs[1] = ArkScale(Fr::from(3u8));
+		assert_noop!(verify(KEY_ID, public_inputs), Error::<Test>::InvalidProof);
+		assert_noop!(
+			verify(KEY_ID, fixtures::public_inputs(1)),
+			Error::<Test>::PublicInputsMismatch
+		);
+		assert_noop!(
+			verify(KEY_ID, fixtures::public_inputs(3)),
+			Error::<Test>::PublicInputsMismatch
+		);
+		assert_noop!(verify(0, fixtures::public_inputs(2)), Error::<Test>::UnknownVerifyingKey);
+		assert_noop!(
+			Groth16Verifier::<Test>::verify(
+				RawOrigin::None.into(),
+				KEY_ID,
+				Box::new(fixtures::proof(2)),
+				fixtures::public_inputs(2).try_into().unwrap(),
+			),
+			DispatchError::BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn verifies_proofs_without_public_inputs() {
+	new_test_ext().execute_with(|| {
+		super::VerifyingKeys::<Test>::insert(0, fixtures::verifying_key::<Test>(0));
+
+		assert_ok!(Groth16Verifier::<Test>::verify_proof(&0, &fixtures::proof(0), &[]));
+		assert_noop!(
+			Groth16Verifier::<Test>::verify_proof(&0, &fixtures::proof(1), &[]),
+			Error::<Test>::InvalidProof,
+		);
+	});
+}
+
+#[test]
+fn verifies_proofs_of_the_arkworks_prover() {
+	new_test_ext().execute_with(|| {
+		let mut rng = test_rng();
+		let circuit = MultiplierCircuit(Fr::from(3u8), Fr::from(11u8));
+		let proving_key =
+			Groth16::<Bn254>::generate_random_parameters_with_reduction(circuit.clone(), &mut rng)
+				.unwrap();
+		let proof =
+			Groth16::<Bn254>::create_random_proof_with_reduction(circuit, &proving_key, &mut rng)
+				.unwrap();
+		let key = BoundedVerifyingKey::<Test>::try_from(proving_key.vk).unwrap();
+		super::VerifyingKeys::<Test>::insert(0, key);
+
+		let verify = |input: u8| {
+			Groth16Verifier::<Test>::verify_proof(
+				&0,
+				&ArkScale(proof.clone()),
+				&[ArkScale(Fr::from(input))],
+			)
+		};
+		assert_ok!(verify(33));
+		assert_noop!(verify(34), Error::<Test>::InvalidProof);
+	});
+}
+
+#[test]
+fn computes_msms_and_pairing_checks() {
+	let mut rng = test_rng();
+	let bases = [G1Affine::rand(&mut rng), G1Affine::rand(&mut rng), G1Affine::zero()];
+	let scalars = [Fr::rand(&mut rng), Fr::from(0u8), Fr::rand(&mut rng)];
+	let expected = bases[0] * scalars[0];
+
+	let bases = bases.map(ArkScale);
+	let scalars = scalars.map(ArkScale);
+	assert_eq!(super::msm(&bases, &scalars).unwrap().0, expected.into_affine());
+	assert_eq!(super::msm(&[], &[]).unwrap().0, G1Affine::zero());
+	assert!(super::msm(&bases, &scalars[1..]).is_none());
+
+	let a = ArkScale(G1Affine::rand(&mut rng));
+	let b = ArkScale(G2Affine::rand(&mut rng));
+	let c = ArkScale(G2Affine::rand(&mut rng));
+	let minus_a = ArkScale(-a.0);
+	assert_eq!(super::pairing_check(&[a, minus_a], &[b, b]), Some(true));
+	assert_eq!(super::pairing_check(&[a, minus_a], &[b, c]), Some(false));
+	assert_eq!(super::pairing_check(&[], &[]), Some(true));
+	assert!(super::pairing_check(&[a], &[]).is_none());
+}
@@ -0,0 +1,133 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of Groth16 proofs through the BN254 host functions.
+//!
+//! Points and scalars are passed to the host functions in the encoding of the EIP-196 and
+//! EIP-197 precompiles: big-endian integers of 32 bytes, G1 points as `x` followed by `y` and G2
+//! points with the coefficients of each coordinate ordered as `c1` followed by `c0`.
+
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField, Zero};
+use sp_ark_scale::{groth16::BoundedVerifyingKey, ArkScale};
+use sp_runtime::traits::Get;
+
+/// Encode a G1 point, the point at infinity as all zeros.
+fn encode_g1(point: &G1Affine) -> [u8; 64] {
+	let mut encoded = [0; 64];
+	if let Some((x, y)) = point.xy() {
+		encoded[..32].copy_from_slice(&x.into_bigint().to_bytes_be());
+		encoded[32..].copy_from_slice(&y.into_bigint().to_bytes_be());
+	}
+	encoded
+}
+
+/// Decode a G1 point returned by a host function, which is on the curve.
+fn decode_g1(encoded: &[u8; 64]) -> G1Affine {
+	if encoded.iter().all(Zero::is_zero) {
+		return G1Affine::identity()
+	}
+	let x = Fq::from_be_bytes_mod_order(&encoded[..32]);
+	let y = Fq::from_be_bytes_mod_order(&encoded[32..]);
+	G1Affine::new_unchecked(x, y)
+}
+
+/// Encode a G2 point, the point at infinity as all zeros.
+fn encode_g2(point: &G2Affine) -> [u8; 128] {
+	let mut encoded = [0; 128];
+	if let Some((x, y)) = point.xy() {
+		for (chunk, coefficient) in encoded.chunks_mut(32).zip([x.c1, x.c0, y.c1, y.c0]) {
+			chunk.copy_from_slice(&coefficient.into_bigint().to_bytes_be());
+		}
+	}
+	encoded
+}
+
+/// Add the products of the pairs of `terms` to the encoded point `sum`, returning `None` if a
+/// host function rejects its input.
+fn accumulate<'a>(
+	mut sum: [u8; 64],
+	terms: impl Iterator<Item = (&'a G1Affine, &'a Fr)>,
+) -> Option<[u8; 64]> {
+	for (base, scalar) in terms {
+		let scalar = scalar.into_bigint().to_bytes_be();
+		let term = sp_io::crypto::bn254_ec_mul(&[&encode_g1(base)[..], &scalar].concat())?;
+		sum = sp_io::crypto::bn254_ec_add(&[&sum[..], &term].concat())?;
+	}
+	Some(sum)
+}
+
+/// Compute `Σ scalarsᵢ·basesᵢ`, or `None` if the slices have different lengths.
+pub fn msm(bases: &[G1Affine], scalars: &[Fr]) -> Option<G1Affine> {
+	if bases.len() != scalars.len() {
+		return None
+	}
+	accumulate([0; 64], bases.iter().zip(scalars)).as_ref().map(decode_g1)
+}
+
+/// Check that the product of the pairings `e(g1ᵢ, g2ᵢ)` is one, or `None` if the slices have
+/// different lengths.
+pub fn pairing_check(g1: &[G1Affine], g2: &[G2Affine]) -> Option<bool> {
+	if g1.len() != g2.len() {
+		return None
+	}
+	let input = g1
+		.iter()
+		.zip(g2)
+		.flat_map(|(a, b)| encode_g1(a).into_iter().chain(encode_g2(b)))
+		.collect::<sp_std::vec::Vec<_>>();
+	Some(sp_io::crypto::bn254_ec_pairing(&input)?[31] == 1)
+}
+
+/// Verify `proof` of `public_inputs` against `key`, whose number of points `Kᵢ` must be one more
+/// than the number of public inputs.
+pub fn verify<MaxPoints: Get<u32>>(
+	key: &BoundedVerifyingKey<Bn254, MaxPoints>,
+	proof: &ark_groth16::Proof<Bn254>,
+	public_inputs: &[ArkScale<Fr>],
+) -> bool {
+	check(key, proof, public_inputs).unwrap_or(false)
+}
+
+/// Check the pairing equation, returning `None` if a host function rejects its input.
+fn check<MaxPoints: Get<u32>>(
+	key: &BoundedVerifyingKey<Bn254, MaxPoints>,
+	proof: &ark_groth16::Proof<Bn254>,
+	public_inputs: &[ArkScale<Fr>],
+) -> Option<bool> {
+	let (first, points) = key.gamma_abc_g1.split_first()?;
+	if points.len() != public_inputs.len() {
+		return None
+	}
+
+	let terms = points.iter().zip(public_inputs).map(|(point, input)| (&point.0, &input.0));
+	let inputs = accumulate(encode_g1(&first.0), terms)?;
+
+	let input = [
+		&encode_g1(&-proof.a)[..],
+		&encode_g2(&proof.b),
+		&encode_g1(&key.alpha_g1.0),
+		&encode_g2(&key.beta_g2.0),
+		&inputs,
+		&encode_g2(&key.gamma_g2.0),
+		&encode_g1(&proof.c),
+		&encode_g2(&key.delta_g2.0),
+	]
+	.concat();
+	Some(sp_io::crypto::bn254_ec_pairing(&input)?[31] == 1)
+}
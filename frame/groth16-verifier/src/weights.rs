@@ -0,0 +1,107 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Weights for pallet_groth16_verifier
+//!
+//! These are conservative estimates until the weights are generated with the benchmark CLI:
+//!
+//! ./target/production/substrate benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_groth16_verifier --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --template=./.maintain/frame-weight-template.hbs --output=./frame/groth16-verifier/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_groth16_verifier.
+pub trait WeightInfo {
+	fn register_vk(n: u32, ) -> Weight;
+	fn update_vk(n: u32, ) -> Weight;
+	fn remove_vk() -> Weight;
+	fn verify_proof(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_groth16_verifier using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:1)
+	fn register_vk(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			// Storing a compressed G1 point for every point `Kᵢ` of the key.
+			.saturating_add(Weight::from_ref_time(100_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:1)
+	fn update_vk(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			// Storing a compressed G1 point for every point `Kᵢ` of the key.
+			.saturating_add(Weight::from_ref_time(100_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:1)
+	fn remove_vk() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:0)
+	fn verify_proof(n: u32, ) -> Weight {
+		Weight::from_ref_time(2_500_000_000 as u64)
+			// One multiplication and one addition of G1 points for every public input.
+			.saturating_add(Weight::from_ref_time(200_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:1)
+	fn register_vk(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			// Storing a compressed G1 point for every point `Kᵢ` of the key.
+			.saturating_add(Weight::from_ref_time(100_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:1)
+	fn update_vk(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			// Storing a compressed G1 point for every point `Kᵢ` of the key.
+			.saturating_add(Weight::from_ref_time(100_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:1)
+	fn remove_vk() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Groth16Verifier VerifyingKeys (r:1 w:0)
+	fn verify_proof(n: u32, ) -> Weight {
+		Weight::from_ref_time(2_500_000_000 as u64)
+			// One multiplication and one addition of G1 points for every public input.
+			.saturating_add(Weight::from_ref_time(200_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+	}
+}
@@ -0,0 +1,61 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the incremental Merkle tree pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::{Get, Hooks};
+use sp_runtime::traits::{Hash, One};
+
+#[cfg(test)]
+use crate::Pallet as MerkleTree;
+
+fn leaf<T: Config>(i: u32) -> T::Hash {
+	T::Hashing::hash_of(&i)
+}
+
+benchmarks! {
+	insert_leaf {
+		let max = T::MaxLeavesPerBlock::get();
+		for i in 1 .. max {
+			Pallet::<T>::insert_leaf(leaf::<T>(i))?;
+		}
+	}: { Pallet::<T>::insert_leaf(leaf::<T>(0))? }
+	verify {
+		assert_eq!(PendingLeaves::<T>::decode_len(), Some(max as usize));
+	}
+
+	on_finalize {
+		let n in 0 .. T::MaxLeavesPerBlock::get();
+		for i in 0 .. n {
+			Pallet::<T>::insert_leaf(leaf::<T>(i))?;
+		}
+	}: { Pallet::<T>::on_finalize(One::one()) }
+	verify {
+		assert_eq!(Pallet::<T>::tree().len(), n as u64);
+		assert!(PendingLeaves::<T>::get().is_empty());
+	}
+
+	impl_benchmark_test_suite!(
+		MerkleTree,
+		crate::mock::new_test_ext(),
+		crate::mock::Test,
+	);
+}
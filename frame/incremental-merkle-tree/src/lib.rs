@@ -0,0 +1,277 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Incremental Merkle Tree Pallet
+//!
+//! Maintains an append-only Merkle tree of fixed depth of which only the `O(depth)` frontier
+//! is kept in storage, see [`sp_smt::IncrementalMerkleTree`]. The nodes are hashed with any
+//! [`NodeHasher`], like [`sp_smt::FieldNodeHasher`] of [`sp_smt::Poseidon`] for trees whose
+//! membership is proven in zero-knowledge circuits.
+//!
+//! Other pallets queue leaves with [`Pallet::insert_leaf`], or through the [`AppendOnlyTree`]
+//! trait. The leaves queued during a block are inserted as one batch when the block is
+//! finalized, and the inserted leaves are emitted in [`Event::LeavesInserted`], so that
+//! off-chain witnesses can be built and kept up to date from the events alone.
+//!
+//! The roots of the last [`Config::RootHistorySize`] batches are kept, so that proofs against a
+//! root remain valid for a while after further leaves are inserted, see
+//! [`Pallet::is_known_root`].
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use frame_support::{dispatch::DispatchClass, ensure, traits::Get, weights::Weight};
+use sp_runtime::DispatchError;
+use sp_smt::{IncrementalMerkleTree, NodeHasher};
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// An append-only Merkle tree, for other pallets.
+pub trait AppendOnlyTree<Hash> {
+	/// Queue `leaf` for insertion and return its index in the tree.
+	fn insert_leaf(leaf: Hash) -> Result<u64, DispatchError>;
+
+	/// The weight of [`AppendOnlyTree::insert_leaf`].
+	fn insert_leaf_weight() -> Weight;
+
+	/// The current root of the tree.
+	fn root() -> Hash;
+
+	/// Whether `root` is one of the recent roots of the tree.
+	fn is_known_root(root: &Hash) -> bool;
+
+	/// Insert the queued leaves, so that the current root includes them and is known.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert_queued_leaves();
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{defensive, pallet_prelude::*};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The hasher of the tree nodes.
+		type Hasher: NodeHasher<Out = Self::Hash>;
+
+		/// The depth of the tree.
+		///
+		/// Must be at most [`sp_smt::MAX_DEPTH`].
+		#[pallet::constant]
+		type Depth: Get<u32>;
+
+		/// The maximum number of leaves inserted in a single block.
+		#[pallet::constant]
+		type MaxLeavesPerBlock: Get<u32>;
+
+		/// The number of recent roots that are kept.
+		///
+		/// Must be larger than zero.
+		#[pallet::constant]
+		type RootHistorySize: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The frontier of the tree, unset until the first leaves are inserted.
+	#[pallet::storage]
+	pub type Tree<T: Config> = StorageValue<_, IncrementalMerkleTree<T::Hash>>;
+
+	/// The leaves queued for insertion at the end of the current block.
+	#[pallet::storage]
+	pub type PendingLeaves<T: Config> =
+		StorageValue<_, BoundedVec<T::Hash, T::MaxLeavesPerBlock>, ValueQuery>;
+
+	/// The recent roots, in a ring buffer of [`Config::RootHistorySize`] slots.
+	#[pallet::storage]
+	pub type RootHistory<T: Config> = StorageMap<_, Twox64Concat, u32, T::Hash>;
+
+	/// The slot of [`RootHistory`] written next.
+	#[pallet::storage]
+	pub type NextRootSlot<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The slots of the roots in [`RootHistory`], by root.
+	#[pallet::storage]
+	pub type KnownRoots<T: Config> = StorageMap<_, Identity, T::Hash, u32>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A batch of leaves was inserted starting at `start_index`, changing the root to `root`.
+		LeavesInserted {
+			start_index: u64,
+			leaves: BoundedVec<T::Hash, T::MaxLeavesPerBlock>,
+			root: T::Hash,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The maximum number of leaves was already queued in this block.
+		TooManyLeaves,
+		/// The tree has no room for another leaf.
+		TreeFull,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Return the weight used in `on_finalize` for an empty queue.
+		///
+		/// The cost of inserting each queued leaf is charged by `insert_leaf`.
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			T::WeightInfo::on_finalize(0)
+		}
+
+		/// Insert the leaves queued during the block.
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			let leaves = PendingLeaves::<T>::take();
+			if leaves.is_empty() {
+				return
+			}
+
+			let mut tree = Self::tree();
+			let start_index = tree.len();
+			match tree.insert::<T::Hasher>(&leaves) {
+				Some(_) => {
+					let root = tree.root();
+					Tree::<T>::put(tree);
+					Self::record_root(root);
+					Self::deposit_event(Event::LeavesInserted { start_index, leaves, root });
+				},
+				None => {
+					defensive!("the room for queued leaves is checked in `insert_leaf`");
+				},
+			}
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::Depth::get() <= sp_smt::MAX_DEPTH,
+				"`Depth` must be at most `sp_smt::MAX_DEPTH`",
+			);
+			assert!(T::RootHistorySize::get() > 0, "`RootHistorySize` must be larger than zero");
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The tree, which is empty until the first leaves are inserted.
+	pub fn tree() -> IncrementalMerkleTree<T::Hash> {
+		Tree::<T>::get().unwrap_or_else(|| {
+			IncrementalMerkleTree::new::<T::Hasher>(T::Depth::get())
+				.expect("`Depth` is checked in `integrity_test`; qed")
+		})
+	}
+
+	/// The current root of the tree.
+	///
+	/// Leaves queued in the current block are not included before the block is finalized.
+	pub fn root() -> T::Hash {
+		Self::tree().root()
+	}
+
+	/// Whether `root` is the root of the tree after one of the last [`Config::RootHistorySize`]
+	/// batches of leaves.
+	pub fn is_known_root(root: &T::Hash) -> bool {
+		KnownRoots::<T>::contains_key(root)
+	}
+
+	/// Queue `leaf` for insertion at the end of the block and return its index in the tree.
+	///
+	/// The tree hashes leaves as they are, so callers taking leaves from users should check that
+	/// they are canonical for hashers like [`sp_smt::FieldNodeHasher`].
+	///
+	/// Callers are responsible for charging [`WeightInfo::insert_leaf`]. The cost of inserting
+	/// the leaf into the tree in `on_finalize` is registered to the block here.
+	pub fn insert_leaf(leaf: T::Hash) -> Result<u64, DispatchError> {
+		let tree = Self::tree();
+		let index = PendingLeaves::<T>::try_mutate(|pending| -> Result<_, DispatchError> {
+			ensure!((pending.len() as u64) < tree.remaining(), Error::<T>::TreeFull);
+			pending.try_push(leaf).map_err(|_| Error::<T>::TooManyLeaves)?;
+			Ok(tree.len() + pending.len() as u64 - 1)
+		})?;
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			Self::finalize_weight_per_leaf(),
+			DispatchClass::Mandatory,
+		);
+		Ok(index)
+	}
+
+	/// Write `root` to the next slot of the root history, forgetting the oldest root.
+	fn record_root(root: T::Hash) {
+		let slot = NextRootSlot::<T>::get();
+		if let Some(oldest) = RootHistory::<T>::get(slot) {
+			// The oldest root is still known if it was recorded again since.
+			if KnownRoots::<T>::get(oldest) == Some(slot) {
+				KnownRoots::<T>::remove(oldest);
+			}
+		}
+		RootHistory::<T>::insert(slot, root);
+		KnownRoots::<T>::insert(root, slot);
+		NextRootSlot::<T>::put((slot + 1) % T::RootHistorySize::get());
+	}
+
+	/// The weight added to `on_finalize` by every queued leaf.
+	fn finalize_weight_per_leaf() -> Weight {
+		T::WeightInfo::on_finalize(1).saturating_sub(T::WeightInfo::on_finalize(0))
+	}
+}
+
+impl<T: Config> AppendOnlyTree<T::Hash> for Pallet<T> {
+	fn insert_leaf(leaf: T::Hash) -> Result<u64, DispatchError> {
+		Self::insert_leaf(leaf)
+	}
+
+	fn insert_leaf_weight() -> Weight {
+		T::WeightInfo::insert_leaf()
+	}
+
+	fn root() -> T::Hash {
+		Self::root()
+	}
+
+	fn is_known_root(root: &T::Hash) -> bool {
+		Self::is_known_root(root)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert_queued_leaves() {
+		use frame_support::traits::Hooks;
+		use sp_runtime::traits::Zero;
+
+		Self::on_finalize(Zero::zero())
+	}
+}
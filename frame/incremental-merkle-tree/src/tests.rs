@@ -0,0 +1,204 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the incremental Merkle tree pallet.
+
+use super::{
+	AppendOnlyTree, Error, Event, KnownRoots, Pallet as MerkleTree, PendingLeaves, Tree, WeightInfo,
+};
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchClass, traits::Hooks};
+use sp_core::H256;
+use sp_smt::{FieldNodeHasher, NodeHasher, Poseidon};
+
+fn leaf(i: u64) -> H256 {
+	H256::from_low_u64_be(i + 1)
+}
+
+/// The root of the depth 3 tree with the given leaves, computed from all nodes.
+fn naive_root(leaves: &[H256]) -> H256 {
+	let mut level = leaves.to_vec();
+	level.resize(8, H256::zero());
+	while level.len() > 1 {
+		level = level
+			.chunks(2)
+			.map(|pair| FieldNodeHasher::<Poseidon>::hash_node(&pair[0], &pair[1]))
+			.collect();
+	}
+	level[0]
+}
+
+fn inserted_event() -> (u64, Vec<H256>, H256) {
+	match System::events().pop().expect("an event was deposited").event {
+		RuntimeEvent::MerkleTree(Event::LeavesInserted { start_index, leaves, root }) =>
+			(start_index, leaves.into_inner(), root),
+		event => panic!("unexpected event {:?}", event),
+	}
+}
+
+#[test]
+fn leaves_are_inserted_at_the_end_of_the_block() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(MerkleTree::<Test>::insert_leaf(leaf(0)), Ok(0));
+		assert_eq!(MerkleTree::<Test>::insert_leaf(leaf(1)), Ok(1));
+
+		// Queued leaves are not part of the tree yet.
+		assert_eq!(MerkleTree::<Test>::root(), naive_root(&[]));
+		assert!(Tree::<Test>::get().is_none());
+
+		next_block();
+
+		let (start_index, leaves, root) = inserted_event();
+		assert_eq!(start_index, 0);
+		assert_eq!(leaves, vec![leaf(0), leaf(1)]);
+		assert_eq!(root, naive_root(&leaves));
+		assert_eq!(MerkleTree::<Test>::root(), root);
+		assert_eq!(MerkleTree::<Test>::tree().len(), 2);
+		assert!(PendingLeaves::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn batches_extend_the_tree() {
+	new_test_ext().execute_with(|| {
+		for i in 0..3 {
+			assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(i)));
+		}
+		next_block();
+		for i in 3..5 {
+			assert_eq!(MerkleTree::<Test>::insert_leaf(leaf(i)), Ok(i));
+		}
+		next_block();
+
+		let (start_index, leaves, root) = inserted_event();
+		assert_eq!(start_index, 3);
+		assert_eq!(leaves, vec![leaf(3), leaf(4)]);
+		assert_eq!(root, naive_root(&(0..5).map(leaf).collect::<Vec<_>>()));
+	});
+}
+
+#[test]
+fn empty_blocks_do_not_change_the_tree() {
+	new_test_ext().execute_with(|| {
+		next_block();
+
+		assert!(System::events().is_empty());
+		assert!(Tree::<Test>::get().is_none());
+	});
+}
+
+#[test]
+fn insertions_are_limited() {
+	new_test_ext().execute_with(|| {
+		for i in 0..3 {
+			assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(i)));
+		}
+		assert_noop!(MerkleTree::<Test>::insert_leaf(leaf(3)), Error::<Test>::TooManyLeaves);
+		next_block();
+
+		for i in 3..6 {
+			assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(i)));
+		}
+		next_block();
+
+		// Leaves queued in the block count against the room left in the tree.
+		for i in 6..8 {
+			assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(i)));
+		}
+		assert_noop!(MerkleTree::<Test>::insert_leaf(leaf(8)), Error::<Test>::TreeFull);
+		next_block();
+
+		assert_eq!(MerkleTree::<Test>::root(), naive_root(&(0..8).map(leaf).collect::<Vec<_>>()));
+		assert_noop!(MerkleTree::<Test>::insert_leaf(leaf(8)), Error::<Test>::TreeFull);
+	});
+}
+
+#[test]
+fn insertion_weight_is_charged_per_leaf() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(MerkleTree::<Test>::on_initialize(1), <() as WeightInfo>::on_finalize(0));
+
+		let per_leaf = <() as WeightInfo>::on_finalize(1) - <() as WeightInfo>::on_finalize(0);
+		let mandatory = || *System::block_weight().get(DispatchClass::Mandatory);
+		let before = mandatory();
+		assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(0)));
+		assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(1)));
+		assert_eq!(mandatory(), before + per_leaf * 2);
+
+		// Rejected leaves are not charged.
+		assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(2)));
+		assert_noop!(MerkleTree::<Test>::insert_leaf(leaf(3)), Error::<Test>::TooManyLeaves);
+		assert_eq!(mandatory(), before + per_leaf * 3);
+	});
+}
+
+#[test]
+fn recent_roots_are_known() {
+	new_test_ext().execute_with(|| {
+		let mut roots = Vec::new();
+		for i in 0..3 {
+			assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(i)));
+			next_block();
+			roots.push(MerkleTree::<Test>::root());
+		}
+
+		// Only the roots of the last two batches are kept.
+		assert!(!MerkleTree::<Test>::is_known_root(&naive_root(&[])));
+		assert!(!MerkleTree::<Test>::is_known_root(&roots[0]));
+		assert!(MerkleTree::<Test>::is_known_root(&roots[1]));
+		assert!(MerkleTree::<Test>::is_known_root(&roots[2]));
+		assert_eq!(KnownRoots::<Test>::iter().count(), 2);
+	});
+}
+
+#[test]
+fn repeated_roots_stay_known() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(0)));
+		next_block();
+		let root = MerkleTree::<Test>::root();
+
+		// Zero leaves are empty, so they do not change the root.
+		assert_ok!(MerkleTree::<Test>::insert_leaf(H256::zero()));
+		next_block();
+		assert_eq!(MerkleTree::<Test>::root(), root);
+
+		// Evicting the first record of the root keeps the second one.
+		assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(2)));
+		next_block();
+		assert!(MerkleTree::<Test>::is_known_root(&root));
+		assert_ok!(MerkleTree::<Test>::insert_leaf(leaf(3)));
+		next_block();
+		assert!(!MerkleTree::<Test>::is_known_root(&root));
+	});
+}
+
+#[test]
+fn is_an_append_only_tree() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<MerkleTree<Test> as AppendOnlyTree<H256>>::insert_leaf(leaf(0)), Ok(0));
+		next_block();
+
+		let root = naive_root(&[leaf(0)]);
+		assert_eq!(<MerkleTree<Test> as AppendOnlyTree<H256>>::root(), root);
+		assert!(<MerkleTree<Test> as AppendOnlyTree<H256>>::is_known_root(&root));
+		assert_eq!(
+			<MerkleTree<Test> as AppendOnlyTree<H256>>::insert_leaf_weight(),
+			<() as WeightInfo>::insert_leaf(),
+		);
+	});
+}
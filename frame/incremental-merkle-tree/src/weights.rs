@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Weights for pallet_incremental_merkle_tree
+//!
+//! These are conservative estimates until the weights are generated with the benchmark CLI:
+//!
+//! ./target/production/substrate benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_incremental_merkle_tree --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --template=./.maintain/frame-weight-template.hbs --output=./frame/incremental-merkle-tree/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_incremental_merkle_tree.
+pub trait WeightInfo {
+	fn insert_leaf() -> Weight;
+	fn on_finalize(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_incremental_merkle_tree using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: IncrementalMerkleTree Tree (r:1 w:0)
+	// Storage: IncrementalMerkleTree PendingLeaves (r:1 w:1)
+	fn insert_leaf() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: IncrementalMerkleTree PendingLeaves (r:1 w:1)
+	// Storage: IncrementalMerkleTree Tree (r:1 w:1)
+	// Storage: IncrementalMerkleTree NextRootSlot (r:1 w:1)
+	// Storage: IncrementalMerkleTree RootHistory (r:1 w:1)
+	// Storage: IncrementalMerkleTree KnownRoots (r:1 w:2)
+	fn on_finalize(n: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			// Hashing the path of every leaf with Poseidon, for trees of depth up to 64.
+			.saturating_add(Weight::from_ref_time(6_400_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: IncrementalMerkleTree Tree (r:1 w:0)
+	// Storage: IncrementalMerkleTree PendingLeaves (r:1 w:1)
+	fn insert_leaf() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: IncrementalMerkleTree PendingLeaves (r:1 w:1)
+	// Storage: IncrementalMerkleTree Tree (r:1 w:1)
+	// Storage: IncrementalMerkleTree NextRootSlot (r:1 w:1)
+	// Storage: IncrementalMerkleTree RootHistory (r:1 w:1)
+	// Storage: IncrementalMerkleTree KnownRoots (r:1 w:2)
+	fn on_finalize(n: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000 as u64)
+			// Hashing the path of every leaf with Poseidon, for trees of depth up to 64.
+			.saturating_add(Weight::from_ref_time(6_400_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+}
@@ -0,0 +1,55 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the powers-of-tau pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use codec::Encode;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::{EventRecord, Pallet as System};
+use sp_std::vec;
+
+#[cfg(test)]
+use crate::Pallet as PowersOfTau;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	// The verification costs the same for any secret, so the contribution of the secret one
+	// to the initial accumulator, which leaves all powers at the generators, is benchmarked.
+	contribute {
+		let g in (T::G2Powers::get()) .. T::G1Powers::get();
+		let h in 2 .. T::G2Powers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let g1_powers = vec![G1_GENERATOR; g as usize];
+		let g2_powers = vec![G2_GENERATOR; h as usize];
+	}: { Pallet::<T>::do_contribute(caller.clone(), &g1_powers, &g2_powers, &G2_GENERATOR)? }
+	verify {
+		let digest = sp_io::hashing::blake2_256(&(&g1_powers, &g2_powers).encode());
+		assert_last_event::<T>(Event::Contributed { who: caller, index: 1, digest }.into());
+	}
+
+	impl_benchmark_test_suite!(PowersOfTau, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,207 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Powers-of-Tau Pallet
+//!
+//! Runs a perpetual powers-of-tau ceremony over BLS12-381.
+//!
+//! The accumulator holds [`Config::G1Powers`] powers of a secret `τ` times the generator of G1
+//! and [`Config::G2Powers`] times the generator of G2, from `τ⁰` up. It starts from `τ = 1`.
+//! Anyone can contribute through [`Pallet::contribute`] by multiplying the secret by a secret
+//! `τ'` of their own, submitting the new powers together with `τ'·G2`. The contribution is
+//! verified by the `bls12_381_powers_of_tau_verify` host function against the first power of
+//! the previous secret, which is all the pallet keeps of the accumulator besides its digest:
+//! the powers themselves remain in the extrinsics, where the next contributor finds them.
+//!
+//! The resulting setup is secure as long as a single contributor discarded their secret.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The generator of G1, compressed in the zcash encoding.
+pub const G1_GENERATOR: [u8; 48] = [
+	0x97, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+	0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+	0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+];
+
+/// The generator of G2, compressed in the zcash encoding.
+pub const G2_GENERATOR: [u8; 96] = [
+	0x93, 0xe0, 0x2b, 0x60, 0x52, 0x71, 0x9f, 0x60, 0x7d, 0xac, 0xd3, 0xa0, 0x88, 0x27, 0x4f, 0x65,
+	0x59, 0x6b, 0xd0, 0xd0, 0x99, 0x20, 0xb6, 0x1a, 0xb5, 0xda, 0x61, 0xbb, 0xdc, 0x7f, 0x50, 0x49,
+	0x33, 0x4c, 0xf1, 0x12, 0x13, 0x94, 0x5d, 0x57, 0xe5, 0xac, 0x7d, 0x05, 0x5d, 0x04, 0x2b, 0x7e,
+	0x02, 0x4a, 0xa2, 0xb2, 0xf0, 0x8f, 0x0a, 0x91, 0x26, 0x08, 0x05, 0x27, 0x2d, 0xc5, 0x10, 0x51,
+	0xc6, 0xe4, 0x7a, 0xd4, 0xfa, 0x40, 0x3b, 0x02, 0xb4, 0x51, 0x0b, 0x64, 0x7a, 0xe3, 0xd1, 0x77,
+	0x0b, 0xac, 0x03, 0x26, 0xa8, 0x05, 0xbb, 0xef, 0xd4, 0x80, 0x56, 0xc8, 0xc1, 0x21, 0xbd, 0xb8,
+];
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use codec::Encode;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	/// A compressed G1 point.
+	pub type G1Point = [u8; 48];
+
+	/// A compressed G2 point.
+	pub type G2Point = [u8; 96];
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The number of powers of the secret in G1.
+		///
+		/// Must be at most `BLS12_381_POWERS_OF_TAU_MAX_POWERS`.
+		#[pallet::constant]
+		type G1Powers: Get<u32>;
+
+		/// The number of powers of the secret in G2.
+		///
+		/// Must be at least two and at most [`Config::G1Powers`].
+		#[pallet::constant]
+		type G2Powers: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultForTauG1() -> G1Point {
+		G1_GENERATOR
+	}
+
+	/// The product of the current secret with the generator of G1.
+	#[pallet::storage]
+	#[pallet::getter(fn tau_g1)]
+	pub type TauG1<T> = StorageValue<_, G1Point, ValueQuery, DefaultForTauG1>;
+
+	/// The blake2-256 hash of the encoded G1 and G2 powers of the last contribution.
+	#[pallet::storage]
+	#[pallet::getter(fn digest)]
+	pub type Digest<T> = StorageValue<_, [u8; 32]>;
+
+	/// The number of accepted contributions.
+	#[pallet::storage]
+	#[pallet::getter(fn contributions)]
+	pub type Contributions<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A contribution was verified and accepted.
+		Contributed { who: T::AccountId, index: u32, digest: [u8; 32] },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The contribution does not have [`Config::G1Powers`] and [`Config::G2Powers`] powers.
+		WrongNumberOfPowers,
+		/// The contribution did not verify.
+		InvalidContribution,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::G1Powers::get() <= sp_io::BLS12_381_POWERS_OF_TAU_MAX_POWERS,
+				"`G1Powers` must be at most `BLS12_381_POWERS_OF_TAU_MAX_POWERS`",
+			);
+			assert!(
+				(2..=T::G1Powers::get()).contains(&T::G2Powers::get()),
+				"`G2Powers` must be at least two and at most `G1Powers`",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Contribute to the ceremony by multiplying the secret by a secret `τ'`.
+		///
+		/// `g1_powers` and `g2_powers` are the powers of the new secret times the generators,
+		/// from `τ⁰` up, and `witness` is `τ'` times the generator of G2.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::contribute(T::G1Powers::get(), T::G2Powers::get()))]
+		pub fn contribute(
+			origin: OriginFor<T>,
+			g1_powers: BoundedVec<G1Point, T::G1Powers>,
+			g2_powers: BoundedVec<G2Point, T::G2Powers>,
+			witness: G2Point,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				g1_powers.len() == T::G1Powers::get() as usize &&
+					g2_powers.len() == T::G2Powers::get() as usize,
+				Error::<T>::WrongNumberOfPowers,
+			);
+
+			Self::do_contribute(who, &g1_powers, &g2_powers, &witness)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Verify the contribution of `who` and make its secret the current one.
+		pub(crate) fn do_contribute(
+			who: T::AccountId,
+			g1_powers: &[G1Point],
+			g2_powers: &[G2Point],
+			witness: &G2Point,
+		) -> DispatchResult {
+			ensure!(
+				sp_io::crypto::bls12_381_powers_of_tau_verify(
+					&TauG1::<T>::get(),
+					g1_powers.to_vec(),
+					g2_powers.to_vec(),
+					witness,
+				),
+				Error::<T>::InvalidContribution,
+			);
+
+			let digest = sp_io::hashing::blake2_256(&(g1_powers, g2_powers).encode());
+			let index = Contributions::<T>::mutate(|contributions| {
+				*contributions = contributions.saturating_add(1);
+				*contributions
+			});
+			TauG1::<T>::put(g1_powers[1]);
+			Digest::<T>::put(digest);
+
+			Self::deposit_event(Event::Contributed { who, index, digest });
+			Ok(())
+		}
+	}
+}
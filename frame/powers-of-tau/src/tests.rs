@@ -0,0 +1,129 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the powers-of-tau pallet.
+
+use super::{
+	Contributions, Digest, Error, Event, G1Point, G2Point, Pallet as PowersOfTau, TauG1,
+	G1_GENERATOR, G2_GENERATOR,
+};
+use crate::mock::*;
+use ark_bls12_381::{Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::Field;
+use ark_serialize::CanonicalSerialize;
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_system::RawOrigin;
+
+fn compressed<const N: usize>(point: impl CanonicalSerialize) -> [u8; N] {
+	let mut bytes = [0; N];
+	point.serialize_compressed(&mut bytes[..]).unwrap();
+	bytes
+}
+
+fn powers(tau: u64, len: u64) -> impl Iterator<Item = Fr> {
+	(0..len).map(move |i| Fr::from(tau).pow([i]))
+}
+
+fn g1_powers(tau: u64, len: u64) -> Vec<G1Point> {
+	powers(tau, len)
+		.map(|power| compressed(G1Affine::generator() * power))
+		.collect()
+}
+
+fn g2_powers(tau: u64, len: u64) -> Vec<G2Point> {
+	powers(tau, len)
+		.map(|power| compressed(G2Affine::generator() * power))
+		.collect()
+}
+
+/// Contribute as `who` the powers of `tau` with the witness of `secret`.
+fn contribute(who: u64, tau: u64, secret: u64) -> frame_support::dispatch::DispatchResult {
+	PowersOfTau::<Test>::contribute(
+		RawOrigin::Signed(who).into(),
+		g1_powers(tau, 8).try_into().unwrap(),
+		g2_powers(tau, 3).try_into().unwrap(),
+		g2_powers(secret, 2)[1],
+	)
+}
+
+#[test]
+fn generators_are_correct() {
+	assert_eq!(G1_GENERATOR, compressed(G1Affine::generator()));
+	assert_eq!(G2_GENERATOR, compressed(G2Affine::generator()));
+}
+
+#[test]
+fn accepts_contributions() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TauG1::<Test>::get(), G1_GENERATOR);
+
+		assert_ok!(contribute(1, 3, 3));
+		let digest = sp_io::hashing::blake2_256(&(g1_powers(3, 8), g2_powers(3, 3)).encode());
+		assert_eq!(TauG1::<Test>::get(), g1_powers(3, 2)[1]);
+		assert_eq!(Digest::<Test>::get(), Some(digest));
+		System::assert_last_event(Event::Contributed { who: 1, index: 1, digest }.into());
+
+		assert_ok!(contribute(2, 15, 5));
+		assert_eq!(TauG1::<Test>::get(), g1_powers(15, 2)[1]);
+		assert_eq!(Contributions::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn rejects_invalid_contributions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(contribute(1, 3, 3));
+
+		// The contribution must build on the current secret.
+		assert_noop!(contribute(2, 5, 5), Error::<Test>::InvalidContribution);
+		assert_noop!(contribute(2, 15, 7), Error::<Test>::InvalidContribution);
+		// A zero secret would erase the setup.
+		assert_noop!(contribute(2, 0, 0), Error::<Test>::InvalidContribution);
+
+		let mut tampered = g1_powers(15, 8);
+		tampered.swap(3, 4);
+		assert_noop!(
+			PowersOfTau::<Test>::contribute(
+				RawOrigin::Signed(2).into(),
+				tampered.try_into().unwrap(),
+				g2_powers(15, 3).try_into().unwrap(),
+				g2_powers(5, 2)[1],
+			),
+			Error::<Test>::InvalidContribution,
+		);
+		assert_noop!(
+			PowersOfTau::<Test>::contribute(
+				RawOrigin::Signed(2).into(),
+				g1_powers(15, 7).try_into().unwrap(),
+				g2_powers(15, 3).try_into().unwrap(),
+				g2_powers(5, 2)[1],
+			),
+			Error::<Test>::WrongNumberOfPowers,
+		);
+		assert_noop!(
+			PowersOfTau::<Test>::contribute(
+				RawOrigin::Signed(2).into(),
+				g1_powers(15, 8).try_into().unwrap(),
+				BoundedVec::truncate_from(g2_powers(15, 2)),
+				g2_powers(5, 2)[1],
+			),
+			Error::<Test>::WrongNumberOfPowers,
+		);
+	});
+}
@@ -0,0 +1,68 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Weights for pallet_powers_of_tau
+//!
+//! These are conservative estimates until the weights are generated with the benchmark CLI:
+//!
+//! ./target/production/substrate benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_powers_of_tau --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --template=./.maintain/frame-weight-template.hbs --output=./frame/powers-of-tau/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_powers_of_tau.
+pub trait WeightInfo {
+	fn contribute(g: u32, h: u32, ) -> Weight;
+}
+
+/// Weights for pallet_powers_of_tau using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PowersOfTau TauG1 (r:1 w:1)
+	// Storage: PowersOfTau Contributions (r:1 w:1)
+	// Storage: PowersOfTau Digest (r:0 w:1)
+	fn contribute(g: u32, h: u32, ) -> Weight {
+		Weight::from_ref_time(15_000_000_000 as u64)
+			// Decoding and subgroup checks of the points and their multi-scalar multiplications
+			.saturating_add(Weight::from_ref_time(200_000_000 as u64).saturating_mul(g as u64))
+			.saturating_add(Weight::from_ref_time(600_000_000 as u64).saturating_mul(h as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: PowersOfTau TauG1 (r:1 w:1)
+	// Storage: PowersOfTau Contributions (r:1 w:1)
+	// Storage: PowersOfTau Digest (r:0 w:1)
+	fn contribute(g: u32, h: u32, ) -> Weight {
+		Weight::from_ref_time(15_000_000_000 as u64)
+			// Decoding and subgroup checks of the points and their multi-scalar multiplications
+			.saturating_add(Weight::from_ref_time(200_000_000 as u64).saturating_mul(g as u64))
+			.saturating_add(Weight::from_ref_time(600_000_000 as u64).saturating_mul(h as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+}
@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the rollup pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::Hooks;
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+use sp_runtime::traits::{Hash, Zero};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+use crate::Pallet as Rollup;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+/// A hash whose first byte is cleared, so that it encodes a field element.
+fn field_hash<T: Config>(i: u32) -> T::Hash {
+	let mut hash = T::Hashing::hash_of(&i);
+	hash.as_mut()[0] = 0;
+	hash
+}
+
+/// Request `n` forced exits with the largest payloads.
+fn request_exits<T: Config>(n: u32) {
+	let payload: BoundedVec<_, _> = vec![0; T::MaxExitLength::get() as usize].try_into().unwrap();
+	for id in 0..n as u64 {
+		let exit = ForcedExit {
+			who: whitelisted_caller(),
+			payload: payload.clone(),
+			requested_at: System::<T>::block_number(),
+		};
+		ForcedExits::<T>::insert(id, exit);
+	}
+	ExitCount::<T>::put(n as u64);
+}
+
+fn pending_batch<T: Config>(i: u32, exits_processed: u64) -> PendingBatch<T::Hash, T::BlockNumber> {
+	PendingBatch { new_root: field_hash::<T>(i), exits_processed, submitted_at: Zero::zero() }
+}
+
+benchmarks! {
+	// Everything but the verification of the proof, which is charged with the weight of the
+	// `Verifier`.
+	submit_batch {
+		let n in 0 .. T::MaxBatchLength::get();
+		// The batch is appended after all but one pending batch, checking an unprocessed exit.
+		let pending = (1 .. T::MaxPendingBatches::get())
+			.map(|i| pending_batch::<T>(i, 0))
+			.collect::<Vec<_>>();
+		PendingBatches::<T>::put(BoundedVec::try_from(pending).unwrap());
+		request_exits::<T>(1);
+		let old_root = Pallet::<T>::latest().0;
+		let new_root = field_hash::<T>(0);
+		let data = vec![0; n as usize];
+	}: {
+		Pallet::<T>::check_batch(&new_root, 0, &data)?;
+		Pallet::<T>::queue_batch(old_root, new_root, 0)?;
+	}
+	verify {
+		assert_last_event::<T>(
+			Event::BatchSubmitted { old_root, new_root, exits_processed: 0 }.into()
+		);
+	}
+
+	request_exit {
+		let n in 0 .. T::MaxExitLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let payload: BoundedVec<_, _> = vec![0; n as usize].try_into().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), payload.clone())
+	verify {
+		assert_last_event::<T>(Event::ExitRequested { id: 0, who: caller, payload }.into());
+	}
+
+	finalize_batch {
+		let e in 0 .. T::MaxExitsPerBatch::get();
+		request_exits::<T>(e);
+		let batch = pending_batch::<T>(0, e as u64);
+		PendingBatches::<T>::put(BoundedVec::try_from(vec![batch.clone()]).unwrap());
+		let now = T::FinalizationDelay::get();
+	}: { Pallet::<T>::on_initialize(now) }
+	verify {
+		assert_eq!(StateRoot::<T>::get(), batch.new_root);
+		assert_eq!(FinalizedExitCount::<T>::get(), e as u64);
+	}
+
+	impl_benchmark_test_suite!(Rollup, crate::mock::new_test_ext(), crate::mock::Test);
+}
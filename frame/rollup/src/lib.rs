@@ -0,0 +1,376 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Rollup Pallet
+//!
+//! Settles the state transitions of a zk-rollup whose state is committed to by a root.
+//!
+//! The sequencer, [`Config::SubmitOrigin`], submits batches with [`Pallet::submit_batch`], each
+//! with the new root and a Groth16 proof verified by the [`Config::Verifier`] against the key
+//! [`Config::VerifyingKey`]. The public inputs of the proof are, in this order:
+//!
+//! - the root before the batch,
+//! - the root after the batch,
+//! - the Keccak-256 hash, shifted right by 8 bits, of the SCALE encoding of the number of forced
+//!   exits processed after the batch followed by the batch data.
+//!
+//! Batches extend the latest queued root. They are queued in [`PendingBatches`] and finalized
+//! one per block after [`Config::FinalizationDelay`] blocks, when the root becomes the
+//! [`StateRoot`].
+//!
+//! Users who cannot get their transactions included by the sequencer request an exit from the
+//! rollup with [`Pallet::request_exit`]. Forced exits are numbered in the order they are
+//! requested and every batch declares the number of forced exits processed after it, which its
+//! proof commits to. Batches not processing the exits requested more than
+//! [`Config::ExitDeadline`] blocks ago are rejected. When a batch is finalized, its processed
+//! exits are passed to the [`Config::OnForcedExit`] hook, e.g. for a bridge to release funds.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, weights::Weight, BoundedVec};
+use pallet_groth16_verifier::{
+	hash_to_public_input, public_input, Proof, ProofVerifier, PublicInput,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, DispatchError, RuntimeDebug};
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The number of public inputs of a batch proof.
+pub const PUBLIC_INPUTS: u32 = 3;
+
+/// A batch whose proof was verified, waiting to be finalized.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingBatch<Hash, BlockNumber> {
+	/// The root after the batch.
+	pub new_root: Hash,
+	/// The number of forced exits processed after the batch.
+	pub exits_processed: u64,
+	/// The block in which the batch was submitted.
+	pub submitted_at: BlockNumber,
+}
+
+/// A request to exit the rollup.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ForcedExit<AccountId, Payload, BlockNumber> {
+	/// The account requesting the exit.
+	pub who: AccountId,
+	/// The description of the exit, interpreted by the rollup.
+	pub payload: Payload,
+	/// The block in which the exit was requested.
+	pub requested_at: BlockNumber,
+}
+
+/// Handler of the forced exits processed by finalized batches.
+pub trait OnForcedExit<AccountId> {
+	/// The forced exit `id` requested by `who` with `payload` was processed.
+	fn on_forced_exit(id: u64, who: &AccountId, payload: &[u8]);
+}
+
+impl<AccountId> OnForcedExit<AccountId> for () {
+	fn on_forced_exit(_id: u64, _who: &AccountId, _payload: &[u8]) {}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to submit batches, i.e. the sequencer.
+		type SubmitOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The identifier of a verifying key of the [`Config::Verifier`].
+		type VerifyingKeyId: Parameter;
+
+		/// The verifier of the batch proofs.
+		type Verifier: ProofVerifier<Self::VerifyingKeyId>;
+
+		/// The verifying key of the state transition circuit.
+		#[pallet::constant]
+		type VerifyingKey: Get<Self::VerifyingKeyId>;
+
+		/// The maximum length of the data of a batch in bytes.
+		#[pallet::constant]
+		type MaxBatchLength: Get<u32>;
+
+		/// The maximum number of batches waiting to be finalized.
+		#[pallet::constant]
+		type MaxPendingBatches: Get<u32>;
+
+		/// The number of blocks after which a batch is finalized.
+		#[pallet::constant]
+		type FinalizationDelay: Get<Self::BlockNumber>;
+
+		/// The maximum number of forced exits processed by a batch.
+		#[pallet::constant]
+		type MaxExitsPerBatch: Get<u32>;
+
+		/// The maximum length of the payload of a forced exit in bytes.
+		#[pallet::constant]
+		type MaxExitLength: Get<u32>;
+
+		/// The number of blocks after which a forced exit must be processed by every batch.
+		#[pallet::constant]
+		type ExitDeadline: Get<Self::BlockNumber>;
+
+		/// The handler of the processed forced exits.
+		type OnForcedExit: OnForcedExit<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The root of the finalized state.
+	#[pallet::storage]
+	pub type StateRoot<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	/// The batches waiting to be finalized, oldest first.
+	#[pallet::storage]
+	pub type PendingBatches<T: Config> = StorageValue<
+		_,
+		BoundedVec<PendingBatch<T::Hash, T::BlockNumber>, T::MaxPendingBatches>,
+		ValueQuery,
+	>;
+
+	/// The forced exits that are not processed by a finalized batch yet, by identifier.
+	#[pallet::storage]
+	pub type ForcedExits<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		ForcedExit<T::AccountId, BoundedVec<u8, T::MaxExitLength>, T::BlockNumber>,
+	>;
+
+	/// The number of forced exits requested.
+	#[pallet::storage]
+	pub type ExitCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The number of forced exits processed by finalized batches.
+	#[pallet::storage]
+	pub type FinalizedExitCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The root of the initial state.
+		pub state_root: T::Hash,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { state_root: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(
+				public_input(self.state_root.as_ref()).is_some(),
+				"the state root must be a field element",
+			);
+			StateRoot::<T>::put(self.state_root);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A batch changing the root from `old_root` to `new_root` was verified and queued.
+		BatchSubmitted { old_root: T::Hash, new_root: T::Hash, exits_processed: u64 },
+		/// A batch was finalized, making `state_root` the root of the finalized state.
+		BatchFinalized { state_root: T::Hash },
+		/// The forced exit `id` was requested by `who`.
+		ExitRequested { id: u64, who: T::AccountId, payload: BoundedVec<u8, T::MaxExitLength> },
+		/// The forced exit `id` was processed by a finalized batch.
+		ExitProcessed { id: u64 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The new root is not the encoding of a field element.
+		InvalidFieldElement,
+		/// The maximum number of batches is already waiting to be finalized.
+		TooManyPendingBatches,
+		/// The number of processed forced exits is smaller than the one of the previous batch,
+		/// larger than the number of requested exits, or increases by more than
+		/// [`Config::MaxExitsPerBatch`].
+		InvalidExitCount,
+		/// The batch does not process a forced exit past its deadline.
+		OverdueExit,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Finalize the oldest pending batch if its delay has passed.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let mut pending = PendingBatches::<T>::get();
+			let due = pending
+				.first()
+				.map(|batch| batch.submitted_at.saturating_add(T::FinalizationDelay::get()) <= now)
+				.unwrap_or(false);
+			if !due {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let batch = pending.remove(0);
+			PendingBatches::<T>::put(pending);
+			let exits = Self::finalize(batch);
+			T::WeightInfo::finalize_batch(exits)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit a batch changing the latest root to `new_root`, after which `exits_processed`
+		/// forced exits are processed, with `proof` of the state transition.
+		///
+		/// The dispatch origin for this call must be [`Config::SubmitOrigin`].
+		#[pallet::weight(
+			T::WeightInfo::submit_batch(data.len() as u32)
+				.saturating_add(T::Verifier::verify_proof_weight(PUBLIC_INPUTS))
+		)]
+		pub fn submit_batch(
+			origin: OriginFor<T>,
+			new_root: T::Hash,
+			exits_processed: u64,
+			data: BoundedVec<u8, T::MaxBatchLength>,
+			proof: Box<Proof>,
+		) -> DispatchResult {
+			T::SubmitOrigin::ensure_origin(origin)?;
+
+			let (old_root, public_inputs) = Self::check_batch(&new_root, exits_processed, &data)?;
+			T::Verifier::verify_proof(&T::VerifyingKey::get(), &proof, &public_inputs)?;
+			Self::queue_batch(old_root, new_root, exits_processed)
+		}
+
+		/// Request an exit from the rollup described by `payload`, which every batch must
+		/// process after [`Config::ExitDeadline`] blocks.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::request_exit(payload.len() as u32))]
+		pub fn request_exit(
+			origin: OriginFor<T>,
+			payload: BoundedVec<u8, T::MaxExitLength>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let id = ExitCount::<T>::mutate(|count| {
+				*count += 1;
+				*count - 1
+			});
+			let exit = ForcedExit {
+				who: who.clone(),
+				payload: payload.clone(),
+				requested_at: frame_system::Pallet::<T>::block_number(),
+			};
+			ForcedExits::<T>::insert(id, exit);
+
+			Self::deposit_event(Event::ExitRequested { id, who, payload });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The latest root and number of processed forced exits, including the pending batches.
+	pub fn latest() -> (T::Hash, u64) {
+		match PendingBatches::<T>::get().last() {
+			Some(batch) => (batch.new_root, batch.exits_processed),
+			None => (StateRoot::<T>::get(), FinalizedExitCount::<T>::get()),
+		}
+	}
+
+	/// Check a batch against the latest root and the forced exits, and return the latest root
+	/// and the public inputs of its proof.
+	fn check_batch(
+		new_root: &T::Hash,
+		exits_processed: u64,
+		data: &[u8],
+	) -> Result<(T::Hash, [PublicInput; PUBLIC_INPUTS as usize]), DispatchError> {
+		let (old_root, previous_exits) = Self::latest();
+		ensure!(
+			exits_processed >= previous_exits &&
+				exits_processed <= ExitCount::<T>::get() &&
+				exits_processed - previous_exits <= T::MaxExitsPerBatch::get() as u64,
+			Error::<T>::InvalidExitCount,
+		);
+		if let Some(exit) = ForcedExits::<T>::get(exits_processed) {
+			let deadline = exit.requested_at.saturating_add(T::ExitDeadline::get());
+			ensure!(frame_system::Pallet::<T>::block_number() <= deadline, Error::<T>::OverdueExit);
+		}
+
+		let public_inputs = [
+			public_input(old_root.as_ref()).ok_or(Error::<T>::InvalidFieldElement)?,
+			public_input(new_root.as_ref()).ok_or(Error::<T>::InvalidFieldElement)?,
+			hash_to_public_input(&(exits_processed, data).encode()),
+		];
+		Ok((old_root, public_inputs))
+	}
+
+	/// Queue a verified batch for finalization.
+	fn queue_batch(old_root: T::Hash, new_root: T::Hash, exits_processed: u64) -> DispatchResult {
+		let batch = PendingBatch {
+			new_root,
+			exits_processed,
+			submitted_at: frame_system::Pallet::<T>::block_number(),
+		};
+		PendingBatches::<T>::try_append(batch).map_err(|_| Error::<T>::TooManyPendingBatches)?;
+
+		Self::deposit_event(Event::BatchSubmitted { old_root, new_root, exits_processed });
+		Ok(())
+	}
+
+	/// Make the root of `batch` the finalized state root and process its forced exits, returning
+	/// their number.
+	fn finalize(batch: PendingBatch<T::Hash, T::BlockNumber>) -> u32 {
+		let first = FinalizedExitCount::<T>::get();
+		for id in first..batch.exits_processed {
+			if let Some(exit) = ForcedExits::<T>::take(id) {
+				T::OnForcedExit::on_forced_exit(id, &exit.who, &exit.payload);
+				Self::deposit_event(Event::ExitProcessed { id });
+			}
+		}
+		FinalizedExitCount::<T>::put(batch.exits_processed);
+		StateRoot::<T>::put(batch.new_root);
+
+		Self::deposit_event(Event::BatchFinalized { state_root: batch.new_root });
+		(batch.exits_processed - first) as u32
+	}
+}
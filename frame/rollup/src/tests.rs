@@ -0,0 +1,171 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the rollup pallet.
+
+use super::{
+	Error, Event, ExitCount, FinalizedExitCount, ForcedExits, Pallet as Rollup, PendingBatches,
+	StateRoot,
+};
+use crate::mock::*;
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use pallet_groth16_verifier::{hash_to_public_input, public_input};
+use sp_core::H256;
+use sp_runtime::{DispatchError, DispatchResult};
+
+fn root(i: u64) -> H256 {
+	H256::from_low_u64_be(i + 2)
+}
+
+fn submit(new_root: H256, exits_processed: u64) -> DispatchResult {
+	Rollup::<Test>::submit_batch(
+		RawOrigin::Root.into(),
+		new_root,
+		exits_processed,
+		b"batch".to_vec().try_into().unwrap(),
+		valid_proof(),
+	)
+}
+
+fn request_exit(who: u64, payload: &[u8]) {
+	assert_ok!(Rollup::<Test>::request_exit(
+		RawOrigin::Signed(who).into(),
+		payload.to_vec().try_into().unwrap(),
+	));
+}
+
+#[test]
+fn batches_are_verified_and_finalized() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(submit(root(0), 0));
+		System::assert_last_event(
+			Event::BatchSubmitted { old_root: GENESIS_ROOT, new_root: root(0), exits_processed: 0 }
+				.into(),
+		);
+		let public_inputs = VERIFIED_INPUTS.with(|inputs| inputs.borrow().last().cloned().unwrap());
+		assert_eq!(
+			public_inputs,
+			vec![
+				public_input(GENESIS_ROOT.as_bytes()).unwrap(),
+				public_input(root(0).as_bytes()).unwrap(),
+				hash_to_public_input(&(0u64, b"batch".to_vec()).encode()),
+			],
+		);
+
+		// Batches extend the latest queued root.
+		run_to_block(2);
+		assert_ok!(submit(root(1), 0));
+		System::assert_last_event(
+			Event::BatchSubmitted { old_root: root(0), new_root: root(1), exits_processed: 0 }
+				.into(),
+		);
+		assert_eq!(StateRoot::<Test>::get(), GENESIS_ROOT);
+
+		run_to_block(3);
+		assert_eq!(StateRoot::<Test>::get(), root(0));
+		System::assert_last_event(Event::BatchFinalized { state_root: root(0) }.into());
+		run_to_block(4);
+		assert_eq!(StateRoot::<Test>::get(), root(1));
+		assert!(PendingBatches::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn batches_are_checked() {
+	new_test_ext().execute_with(|| {
+		let mut invalid_proof = valid_proof();
+		invalid_proof.0.a = Default::default();
+		assert_noop!(
+			Rollup::<Test>::submit_batch(
+				RawOrigin::Root.into(),
+				root(0),
+				0,
+				Default::default(),
+				invalid_proof,
+			),
+			DispatchError::Other("invalid proof"),
+		);
+		assert_noop!(
+			Rollup::<Test>::submit_batch(
+				RawOrigin::Signed(1).into(),
+				root(0),
+				0,
+				Default::default(),
+				valid_proof(),
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(submit(H256::repeat_byte(0xff), 0), Error::<Test>::InvalidFieldElement);
+
+		assert_ok!(submit(root(0), 0));
+		assert_ok!(submit(root(1), 0));
+		assert_noop!(submit(root(2), 0), Error::<Test>::TooManyPendingBatches);
+	});
+}
+
+#[test]
+fn forced_exits_are_processed() {
+	new_test_ext().execute_with(|| {
+		request_exit(1, b"a");
+		System::assert_last_event(
+			Event::ExitRequested { id: 0, who: 1, payload: b"a".to_vec().try_into().unwrap() }
+				.into(),
+		);
+		request_exit(2, b"b");
+		request_exit(3, b"c");
+		assert_eq!(ExitCount::<Test>::get(), 3);
+
+		// Batches cannot process more exits than requested, or more than two at once.
+		assert_noop!(submit(root(0), 4), Error::<Test>::InvalidExitCount);
+		assert_noop!(submit(root(0), 3), Error::<Test>::InvalidExitCount);
+
+		assert_ok!(submit(root(0), 2));
+		// Batches cannot unprocess exits.
+		assert_noop!(submit(root(1), 1), Error::<Test>::InvalidExitCount);
+		assert_ok!(submit(root(1), 3));
+
+		run_to_block(3);
+		assert_eq!(FinalizedExitCount::<Test>::get(), 2);
+		assert_eq!(
+			PROCESSED_EXITS.with(|exits| exits.borrow().clone()),
+			vec![(0, 1, b"a".to_vec()), (1, 2, b"b".to_vec())],
+		);
+		assert!(ForcedExits::<Test>::get(0).is_none());
+		assert!(ForcedExits::<Test>::get(2).is_some());
+
+		run_to_block(4);
+		assert_eq!(FinalizedExitCount::<Test>::get(), 3);
+		System::assert_has_event(Event::ExitProcessed { id: 2 }.into());
+		assert_eq!(ForcedExits::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn overdue_exits_must_be_processed() {
+	new_test_ext().execute_with(|| {
+		request_exit(1, b"a");
+		run_to_block(6);
+		assert_ok!(submit(root(0), 0));
+
+		// The exit requested in block 1 must be processed from block 7 on.
+		run_to_block(7);
+		assert_noop!(submit(root(1), 0), Error::<Test>::OverdueExit);
+		assert_ok!(submit(root(1), 1));
+	});
+}
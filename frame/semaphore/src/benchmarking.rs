@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the Semaphore pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::{
+	dispatch::UnfilteredDispatchable,
+	traits::{EnsureOrigin, Get},
+};
+use frame_system::{EventRecord, Pallet as System};
+use sp_runtime::traits::Hash;
+use sp_std::prelude::*;
+
+#[cfg(test)]
+use crate::Pallet as Semaphore;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+/// A hash whose first byte is cleared, so that it encodes a field element.
+fn field_hash<T: Config>(i: u32) -> T::Hash {
+	let mut hash = T::Hashing::hash_of(&i);
+	hash.as_mut()[0] = 0;
+	hash
+}
+
+benchmarks! {
+	add_member {
+		let identity_commitment = field_hash::<T>(0);
+		let call = Call::<T>::add_member { identity_commitment };
+		let origin = T::MembershipOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::MemberAdded { index: 0, identity_commitment }.into());
+	}
+
+	// Everything but the verification of the proof, which is charged with the weight of the
+	// `Verifier`.
+	signal {
+		let s in 0 .. T::MaxSignalLength::get();
+		T::Tree::insert_leaf(field_hash::<T>(0))?;
+		T::Tree::insert_queued_leaves();
+		let root = T::Tree::root();
+		let nullifier_hash = field_hash::<T>(1);
+		let external_nullifier = field_hash::<T>(2);
+		let message: BoundedVec<_, _> = vec![0; s as usize].try_into().unwrap();
+	}: {
+		Pallet::<T>::public_inputs(&root, &nullifier_hash, &external_nullifier, &message)?;
+		Pallet::<T>::note_signal(external_nullifier, nullifier_hash, message.clone());
+	}
+	verify {
+		assert!(Nullifiers::<T>::contains_key(nullifier_hash));
+	}
+
+	impl_benchmark_test_suite!(Semaphore, crate::mock::new_test_ext(), crate::mock::Test);
+}
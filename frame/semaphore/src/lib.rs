@@ -0,0 +1,209 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Semaphore Pallet
+//!
+//! Anonymous signaling with [Semaphore](https://semaphore.pse.dev) proofs.
+//!
+//! Members join the group by adding their identity commitment to the [`Config::Tree`] through
+//! [`Pallet::add_member`], which is dispatched by [`Config::MembershipOrigin`]. The tree must be
+//! hashed with Poseidon and have the depth of the circuit, like a
+//! `pallet-incremental-merkle-tree` hashed with `sp_smt::FieldNodeHasher<sp_smt::Poseidon>`.
+//!
+//! A member broadcasts a signal with [`Pallet::signal`] and a Groth16 proof, verified by the
+//! [`Config::Verifier`] against the key [`Config::VerifyingKey`], that they know an identity
+//! whose commitment is in the tree under a recent root and whose nullifier hash for the external
+//! nullifier is the given one. The public inputs of the proof are, in this order:
+//!
+//! - the root of the tree,
+//! - the nullifier hash,
+//! - the hash of the signal,
+//! - the hash of the external nullifier,
+//!
+//! where the hashes are the Keccak-256 hash shifted right by 8 bits, as in the Semaphore
+//! contracts. Nullifier hashes are kept in [`Nullifiers`] and only accepted once, so that a member
+//! signals at most once for every external nullifier without revealing which member they are.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use frame_support::{ensure, BoundedVec};
+use pallet_groth16_verifier::{
+	hash_to_public_input, public_input, Proof, ProofVerifier, PublicInput,
+};
+use pallet_incremental_merkle_tree::AppendOnlyTree;
+use sp_runtime::DispatchError;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The number of public inputs of a Semaphore proof.
+pub const PUBLIC_INPUTS: u32 = 4;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to add members.
+		type MembershipOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The tree of identity commitments.
+		type Tree: AppendOnlyTree<Self::Hash>;
+
+		/// The identifier of a verifying key of the [`Config::Verifier`].
+		type VerifyingKeyId: Parameter;
+
+		/// The verifier of the proofs.
+		type Verifier: ProofVerifier<Self::VerifyingKeyId>;
+
+		/// The verifying key of the Semaphore circuit.
+		#[pallet::constant]
+		type VerifyingKey: Get<Self::VerifyingKeyId>;
+
+		/// The maximum length of a signal in bytes.
+		#[pallet::constant]
+		type MaxSignalLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The nullifier hashes of the signals.
+	#[pallet::storage]
+	pub type Nullifiers<T: Config> = StorageMap<_, Identity, T::Hash, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The identity commitment was added to the tree at `index`.
+		MemberAdded { index: u64, identity_commitment: T::Hash },
+		/// A member broadcast `signal` for `external_nullifier`.
+		Signaled {
+			external_nullifier: T::Hash,
+			nullifier_hash: T::Hash,
+			signal: BoundedVec<u8, T::MaxSignalLength>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The value is not the encoding of a field element.
+		InvalidFieldElement,
+		/// The root is not one of the recent roots of the tree.
+		UnknownRoot,
+		/// A signal with the nullifier hash was already broadcast.
+		NullifierUsed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `identity_commitment` to the tree.
+		///
+		/// The commitment becomes usable in proofs when the tree is updated.
+		///
+		/// The dispatch origin for this call must be [`Config::MembershipOrigin`].
+		#[pallet::weight(T::WeightInfo::add_member())]
+		pub fn add_member(origin: OriginFor<T>, identity_commitment: T::Hash) -> DispatchResult {
+			T::MembershipOrigin::ensure_origin(origin)?;
+			ensure!(
+				public_input(identity_commitment.as_ref()).is_some(),
+				Error::<T>::InvalidFieldElement,
+			);
+
+			let index = T::Tree::insert_leaf(identity_commitment)?;
+
+			Self::deposit_event(Event::MemberAdded { index, identity_commitment });
+			Ok(())
+		}
+
+		/// Broadcast `signal` for `external_nullifier`, with `proof` of membership in the tree
+		/// under `root` and of the nullifier hash `nullifier_hash`.
+		///
+		/// The dispatch origin for this call must be _Signed_, and may be a relayer unrelated to
+		/// the member.
+		#[pallet::weight(
+			T::WeightInfo::signal(signal.len() as u32)
+				.saturating_add(T::Verifier::verify_proof_weight(PUBLIC_INPUTS))
+		)]
+		pub fn signal(
+			origin: OriginFor<T>,
+			root: T::Hash,
+			nullifier_hash: T::Hash,
+			external_nullifier: T::Hash,
+			signal: BoundedVec<u8, T::MaxSignalLength>,
+			proof: Box<Proof>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let public_inputs =
+				Self::public_inputs(&root, &nullifier_hash, &external_nullifier, &signal)?;
+			T::Verifier::verify_proof(&T::VerifyingKey::get(), &proof, &public_inputs)?;
+			Self::note_signal(external_nullifier, nullifier_hash, signal);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check the root and the nullifier hash of a signal and return the public inputs of its
+	/// proof.
+	fn public_inputs(
+		root: &T::Hash,
+		nullifier_hash: &T::Hash,
+		external_nullifier: &T::Hash,
+		signal: &[u8],
+	) -> Result<[PublicInput; PUBLIC_INPUTS as usize], DispatchError> {
+		ensure!(T::Tree::is_known_root(root), Error::<T>::UnknownRoot);
+		ensure!(!Nullifiers::<T>::contains_key(nullifier_hash), Error::<T>::NullifierUsed);
+		Ok([
+			public_input(root.as_ref()).ok_or(Error::<T>::InvalidFieldElement)?,
+			public_input(nullifier_hash.as_ref()).ok_or(Error::<T>::InvalidFieldElement)?,
+			hash_to_public_input(signal),
+			hash_to_public_input(external_nullifier.as_ref()),
+		])
+	}
+
+	/// Record the nullifier hash of a verified signal.
+	fn note_signal(
+		external_nullifier: T::Hash,
+		nullifier_hash: T::Hash,
+		signal: BoundedVec<u8, T::MaxSignalLength>,
+	) {
+		Nullifiers::<T>::insert(nullifier_hash, ());
+		Self::deposit_event(Event::Signaled { external_nullifier, nullifier_hash, signal });
+	}
+}
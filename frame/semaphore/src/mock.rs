@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the Semaphore pallet.
+
+use crate as pallet_semaphore;
+use ark_bn254::G1Affine;
+use ark_ec::AffineRepr;
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{ConstU16, ConstU32, ConstU64, Hooks},
+	weights::Weight,
+};
+use pallet_groth16_verifier::{Proof, ProofVerifier, PublicInput};
+use sp_ark_scale::ArkScale;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DispatchError,
+};
+use sp_smt::{FieldNodeHasher, Poseidon};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+pub type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		MerkleTree: pallet_incremental_merkle_tree::{Pallet, Storage, Event<T>},
+		Semaphore: pallet_semaphore::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_incremental_merkle_tree::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Hasher = FieldNodeHasher<Poseidon>;
+	type Depth = ConstU32<3>;
+	type MaxLeavesPerBlock = ConstU32<3>;
+	type RootHistorySize = ConstU32<2>;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	/// The public inputs of the proofs checked by [`MockVerifier`].
+	pub static VERIFIED_INPUTS: RefCell<Vec<Vec<PublicInput>>> = RefCell::new(Vec::new());
+}
+
+/// The proof accepted by [`MockVerifier`].
+pub fn valid_proof() -> Box<Proof> {
+	Box::new(ArkScale(ark_groth16::Proof {
+		a: G1Affine::generator(),
+		b: Default::default(),
+		c: Default::default(),
+	}))
+}
+
+/// A verifier accepting [`valid_proof`] for any public inputs, and recording them.
+pub struct MockVerifier;
+
+impl ProofVerifier<u32> for MockVerifier {
+	fn verify_proof(key_id: &u32, proof: &Proof, public_inputs: &[PublicInput]) -> DispatchResult {
+		assert_eq!(*key_id, VERIFYING_KEY);
+		VERIFIED_INPUTS.with(|inputs| inputs.borrow_mut().push(public_inputs.to_vec()));
+		if *proof != *valid_proof() {
+			return Err(DispatchError::Other("invalid proof"))
+		}
+		Ok(())
+	}
+
+	fn verify_proof_weight(_public_inputs: u32) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// The identifier of the verifying key of the Semaphore circuit.
+pub const VERIFYING_KEY: u32 = 3;
+
+impl pallet_semaphore::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MembershipOrigin = frame_system::EnsureRoot<u64>;
+	type Tree = MerkleTree;
+	type VerifyingKeyId = u32;
+	type Verifier = MockVerifier;
+	type VerifyingKey = ConstU32<VERIFYING_KEY>;
+	type MaxSignalLength = ConstU32<32>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = GenesisConfig { system: Default::default() }.build_storage().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Finalize the current block and start the next one.
+pub fn next_block() {
+	MerkleTree::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+}
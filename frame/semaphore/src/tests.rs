@@ -0,0 +1,178 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Semaphore pallet.
+
+use super::{Error, Event, Nullifiers, Pallet as Semaphore};
+use crate::mock::*;
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use pallet_groth16_verifier::{Proof, PublicInput};
+use sp_ark_scale::ArkScale;
+use sp_core::H256;
+use sp_runtime::{DispatchError, DispatchResult};
+
+const EXTERNAL_NULLIFIER: H256 = H256::repeat_byte(0xe0);
+
+fn commitment(i: u64) -> H256 {
+	H256::from_low_u64_be(i + 1)
+}
+
+fn add_member(i: u64) {
+	assert_ok!(Semaphore::<Test>::add_member(RawOrigin::Root.into(), commitment(i)));
+}
+
+fn signal(root: H256, nullifier_hash: H256, proof: Box<Proof>) -> DispatchResult {
+	Semaphore::<Test>::signal(
+		RawOrigin::Signed(1).into(),
+		root,
+		nullifier_hash,
+		EXTERNAL_NULLIFIER,
+		b"signal".to_vec().try_into().unwrap(),
+		proof,
+	)
+}
+
+/// The Keccak-256 hash of `data` shifted right by 8 bits.
+fn shifted_keccak(data: &[u8]) -> PublicInput {
+	let mut shifted = [0; 32];
+	shifted[1..].copy_from_slice(&sp_io::hashing::keccak_256(data)[..31]);
+	ArkScale(Fr::from_be_bytes_mod_order(&shifted))
+}
+
+fn last_verified_inputs() -> Vec<PublicInput> {
+	VERIFIED_INPUTS.with(|inputs| inputs.borrow().last().cloned().expect("a proof was verified"))
+}
+
+#[test]
+fn adds_members() {
+	new_test_ext().execute_with(|| {
+		add_member(0);
+		System::assert_last_event(
+			Event::MemberAdded { index: 0, identity_commitment: commitment(0) }.into(),
+		);
+		add_member(1);
+		System::assert_last_event(
+			Event::MemberAdded { index: 1, identity_commitment: commitment(1) }.into(),
+		);
+
+		assert_noop!(
+			Semaphore::<Test>::add_member(RawOrigin::Signed(1).into(), commitment(2)),
+			DispatchError::BadOrigin,
+		);
+		// Commitments must be field elements.
+		assert_noop!(
+			Semaphore::<Test>::add_member(RawOrigin::Root.into(), H256::repeat_byte(0xff)),
+			Error::<Test>::InvalidFieldElement,
+		);
+
+		next_block();
+		assert_eq!(MerkleTree::tree().len(), 2);
+	});
+}
+
+#[test]
+fn signals() {
+	new_test_ext().execute_with(|| {
+		add_member(0);
+		next_block();
+		let root = MerkleTree::root();
+		let nullifier_hash = H256::from_low_u64_be(0x4e);
+
+		assert_ok!(signal(root, nullifier_hash, valid_proof()));
+		System::assert_last_event(
+			Event::Signaled {
+				external_nullifier: EXTERNAL_NULLIFIER,
+				nullifier_hash,
+				signal: b"signal".to_vec().try_into().unwrap(),
+			}
+			.into(),
+		);
+		assert!(Nullifiers::<Test>::contains_key(nullifier_hash));
+		assert_eq!(
+			last_verified_inputs(),
+			vec![
+				ArkScale(Fr::from_be_bytes_mod_order(root.as_bytes())),
+				ArkScale(Fr::from(0x4eu8)),
+				shifted_keccak(b"signal"),
+				shifted_keccak(EXTERNAL_NULLIFIER.as_bytes()),
+			],
+		);
+
+		// Nullifier hashes are only accepted once.
+		assert_noop!(signal(root, nullifier_hash, valid_proof()), Error::<Test>::NullifierUsed);
+	});
+}
+
+#[test]
+fn signals_are_checked() {
+	new_test_ext().execute_with(|| {
+		add_member(0);
+		next_block();
+		let root = MerkleTree::root();
+		let nullifier_hash = H256::from_low_u64_be(0x4e);
+
+		let mut invalid_proof = valid_proof();
+		invalid_proof.0.a = Default::default();
+		assert_noop!(
+			signal(root, nullifier_hash, invalid_proof),
+			DispatchError::Other("invalid proof"),
+		);
+		assert_noop!(
+			signal(H256::from_low_u64_be(1), nullifier_hash, valid_proof()),
+			Error::<Test>::UnknownRoot,
+		);
+		// Nullifier hashes must be field elements, so that they have a single encoding.
+		assert_noop!(
+			signal(root, H256::repeat_byte(0xff), valid_proof()),
+			Error::<Test>::InvalidFieldElement,
+		);
+		assert_noop!(
+			Semaphore::<Test>::signal(
+				RawOrigin::None.into(),
+				root,
+				nullifier_hash,
+				EXTERNAL_NULLIFIER,
+				Default::default(),
+				valid_proof(),
+			),
+			DispatchError::BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn recent_roots_are_accepted() {
+	new_test_ext().execute_with(|| {
+		add_member(0);
+		next_block();
+		let root = MerkleTree::root();
+		add_member(1);
+		next_block();
+
+		assert_ok!(signal(root, H256::from_low_u64_be(1), valid_proof()));
+
+		add_member(2);
+		next_block();
+		assert_noop!(
+			signal(root, H256::from_low_u64_be(2), valid_proof()),
+			Error::<Test>::UnknownRoot,
+		);
+	});
+}
@@ -0,0 +1,74 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the VDF pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{EnsureOrigin, Get, UnfilteredDispatchable};
+use frame_system::{EventRecord, Pallet as System, RawOrigin};
+
+#[cfg(test)]
+use crate::Pallet as Vdf;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	set_parameters {
+		let modulus: IntegerOf<T> = fixtures::FIXTURES[0].modulus().try_into().unwrap();
+		let call = Call::<T>::set_parameters { modulus: modulus.clone(), iterations: 1024 };
+		let origin = T::ParametersOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Modulus::<T>::get(), Some(modulus));
+		assert_last_event::<T>(Event::ParametersSet { iterations: 1024 }.into());
+	}
+
+	set_challenge {
+		let challenge = fixtures::challenge();
+		let call = Call::<T>::set_challenge { challenge };
+		let origin = T::ParametersOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(Event::ChallengeSet { challenge }.into());
+	}
+
+	submit_output {
+		let n in 64 .. T::MaxModulusLength::get().min(512);
+		let caller: T::AccountId = whitelisted_caller();
+		let fixture = fixtures::fixture(n);
+		Modulus::<T>::put(IntegerOf::<T>::try_from(fixture.modulus()).unwrap());
+		Iterations::<T>::put(fixtures::ITERATIONS);
+		Challenge::<T>::put(fixtures::challenge());
+		let output: IntegerOf<T> = fixture.output().try_into().unwrap();
+		let proof: IntegerOf<T> = fixture.proof().try_into().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), output.clone(), proof)
+	verify {
+		assert_last_event::<T>(
+			Event::OutputAccepted { who: caller, challenge: fixtures::challenge(), output }.into()
+		);
+	}
+
+	impl_benchmark_test_suite!(Vdf, crate::mock::new_test_ext(), crate::mock::Test);
+}
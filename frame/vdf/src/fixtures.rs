@@ -0,0 +1,181 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! VDF proofs shared by the tests and benchmarks.
+
+use sp_std::prelude::*;
+
+/// The number of squarings of all fixtures.
+pub const ITERATIONS: u64 = 1024;
+
+/// The challenge of all fixtures.
+pub fn challenge() -> [u8; 32] {
+	sp_io::hashing::blake2_256(b"challenge")
+}
+
+/// A VDF output and proof for [`challenge`] and [`ITERATIONS`].
+pub struct Fixture {
+	pub modulus: &'static str,
+	pub output: &'static str,
+	pub proof: &'static str,
+}
+
+impl Fixture {
+	pub fn modulus(&self) -> Vec<u8> {
+		array_bytes::hex2bytes_unchecked(self.modulus)
+	}
+
+	pub fn output(&self) -> Vec<u8> {
+		array_bytes::hex2bytes_unchecked(self.output)
+	}
+
+	pub fn proof(&self) -> Vec<u8> {
+		array_bytes::hex2bytes_unchecked(self.proof)
+	}
+}
+
+/// Proofs for products of two random primes of 64, 128, 256 and 512 bytes.
+///
+/// Outputs and proofs are the representatives `min(v, modulus - v)` in the group of signed
+/// quadratic residues.
+pub const FIXTURES: [Fixture; 4] = [
+	Fixture {
+		modulus: "\
+836fc37563f7ee320e5e3fac55e7ea983e7ae119304f78f842b7f9310a4d4dad5ebaed43ce4e65e4910687f7a5087995\
+6728bece3ad122e228dd0e65984c21c5\
+",
+		output: "\
+1f99532038c3854358b09eb28f7f0763bb59f1d3fb266effc1699f27bd7726a1493f213c05cd00bef6381f7a380c6483\
+9156f5cacc6680ee35fc27ea8763801e\
+",
+		proof: "\
+3abd1fef473fa63dcbd7191d9eb3d7633e53532639a5957e391078d25cac37463f2422a502956077b2f67373ffb0b1fb\
+a2f05fd69e4864e9d9d2115956b0e24c\
+",
+	},
+	Fixture {
+		modulus: "\
+894fb556188e6ed0e374873ae9b3ee9a815e858be83a136cd0065d9481a87f8068aeb7ab56eabd09bb208e2a26f34e6c\
+2b68f3dcf6d41a223f9ca57341454d59122024d3480e0eb94db95283b71105d56ee6b490db27b7c3bab75943aa4baaa5\
+a637d7891c572579082a765c9d068489d91c99a2fae7ab5432b57463196ac6ab\
+",
+		output: "\
+1340c2a0ea5187b0bd99ebd24bb329377298570ce6843187caeefe85544637405074754141ab60eb64807ced69dc6bc3\
+59c1ab2c1fcded7369bb4bc825e7ea1ca2dd78603c884b266b912aaaa0fad3d66d3177fbe9546153d1fe9a2ca9ca565f\
+b7e2046560acdb5913204aea99bc6ac38e0fae9845898c80f596558e49aba3ab\
+",
+		proof: "\
+1c6f4a17e6beec5cca3994fe623f701a5f81fcef7fbab530a24ee411fc153cd1612727d5eb5adff0b9409c72bacf9cbe\
+5052d628e7ebb01ccb7b14ae028b8ee653ba2eb39f7b2fe6da3374e8985a79fc484cbe329dbe5edfc114ece7fc4aebf7\
+0e93f8cea47ba1f25e719ba87921479e4a94bcbc2db65941342848a04fcccdda\
+",
+	},
+	Fixture {
+		modulus: "\
+682b9e45ef4a973d2e7ea4f3604738579f3b0608a0a716ff83650c170702e453eb23604cd9d769907d9d8c8ee558287c\
+dca59b7667419314f272337f183764b9caf6a4959e020a88f7fecd90a24ef0eb4f4b05001bc6c27916b5bd4ead013cbd\
+f02840f4d865b59c3e67b1bfdb053efb064dbd8ab02a33b9ac934ca3a88bdc5d7a4356fb9c830bc65e35ea7e7f2124a2\
+4e42725b623f3349c2ba301b96d4cb611b37e44989f84b2719385312c28c33d1bea807bf5c04a2d45c648538e0bcfb1b\
+60f5903487d080f42510260a85c46e0356aab53b64266878a4a5fdac116c30ec1810d746f7309cd3e3993f5a6ff33726\
+7f093c191d07c63f247e8bf46605de65\
+",
+		output: "\
+2e5157b765dcb452127a9d3371589f602df7841e9b3e27b27bc19d1e3451abaf77f1ecb083cd1a283d5138d62c192578\
+b943ac2cbebac9a0c48f0125cebead61dcf0861167889205d559542324984ffd12575056421220773ff1383574182893\
+27a7aa4c4e14648c5b6596f63889d65ae6aa6512602d0b03b2150fc777309e4641d5f2c5c10aee45fa083330ee0851e1\
+345d6df1e8d7beac52bdf47830f6d3ee2ef8fab3517625c420d7d5512fedd72643e569c785131bdf56afcf625c2d9e0b\
+c996243367b44552c3dbb0f15baf63773d770e56ca948e37b57c4853d7e075334f27a6c97616a7c89e8008439f9ec802\
+08115f82b3ac63b0e32815edb38fc7a6\
+",
+		proof: "\
+1337d491d2e6396ab8eb8f7446b96689fa7a36a451ed8ec0966123895cf6811ca897a64bda2b5ba397ac2a17f081a687\
+de818d83127650ec5d3e9d3f48f2c170629039b96708b713532c2b2a30a86895acbd5d248d6481130418459bc0c71553\
+add57f1e13ee7e305dafe2060a8c92dff33b3c7bcbea9fb062a1949be6e300cf5f6811f8143b81ee050ebe94a74b4f56\
+6eb27a94597f85efd6ed0ddbc945a33f9c57cead34335ece4c342e56d473c6dda1e40d3e94cf4d0e999e1abe7cf938c8\
+de1a7dfe79a3d716fe537e5c6e23c9d067611fe9ef0b0b6aeafcc49c2146196521884673028414449fbaa0569768cd5a\
+31fcd86618546a176e1eee5e3b48a00a\
+",
+	},
+	Fixture {
+		modulus: "\
+813bb18893fd3804b9f924dac3b4ebca6d925b4b3b32cc28e6fe815357d6f3f2a4a1d5d61ded1615f36cc7f712cbbe36\
+0ee19df813f213907e99bef314c765f84d2c86bb094690b6a7d2b5c2a627de2e05d9dc57acddb33bc3f78e8b6e3c4fce\
+492bba3ee461daaa4cded813613178fdd2196df408920f4a1ab930ca11e30360bd5531e1aa44dbb5ec86d5b84e6b1b95\
+cfc7c67287dd82b742164a0f6374e71f7f242df0858aeb28b93dfd02ce30cf67963c58cc636b74d5254292e858d12cff\
+3f920b8016d20041640ffa66c98d38b35ddea02ee9a8fd0d8ae92805a03b393b102a8aa590597fa9e728aa37c3a98e5e\
+799d69fa1b2873d82a1c81682253994d31fc1d115ed6b29653e4d838eef6e2ba2b4d8e92773215b1ece1f05a586a835c\
+1f26acfece7f0e85cfdc45b174561b6e75e496da984635b3f4cd4c850f7dc1081f69efbccd3833b5b0c55fd4b4cfad4f\
+5d5002d12cda05473ba9c2fddd7d9922e86f565f40c0bcf11610d4043a4401ef7ba251b1bbaf7c582d1d4d4754545d1b\
+731c208bfcbc7584b3ceaad60e0277de002f0e56c825933877fa6c53f42a34c4ce9bcbf0f02acd12b668388f8d262e0d\
+608fd23f7b1475604917fd66c80c143e2588ebf85996ddba525648dba28d0d18ee449d1ae75b74cf96d1b1fd4f0f7815\
+9c9caac2470c1551dd0f878878ce355b44062400ed6897e799f1df4810b49067\
+",
+		output: "\
+228f7e367e38c5b3ca9f5b7dd572bfc387f7ede2a406d81b3b4a23f4e808d31ea4c489274ffae3aa7b05f3258659035d\
+20343cfdadf889aa1c80ed974a63ceab5bd97e4267f651333da93ec1cdc3de4ddddfc9ee644095e1ca368cf5f91c5217\
+069caf6a70fcfce72c81c989c7fc3f1082975b17c65bdca824cdf9c14057921dc3c97875a6de8dee5b269c5595715535\
+e1c6ce3c582f138bac620c378541563c820b9b00c2ac129daf0e04cb9eb30bf0a1e5c3b0d34f8872d78239855080e075\
+9c29400cabb16bebf85a6b72ac29dbd2e5bea97839cd79c5fd6735f0948fd2ed21f1046b510af87fb61a1823c5de4b62\
+3cf23fb141d5824d47c2d26bb4c3ea1d1d66b68e810fc381eb074fb6f9bc943cd0a3f60920d69d98bc50c056fc859971\
+3a4a83eff9e3263fb3a447ac05212e640de11eeea5afc50c9315c290c33cfb11eba07afeb35ca9ecf5ac310ff127197d\
+334689c0a7ed817b6cd5eb6b35f0379f96946dfaabdd6122ab485f53a793d1c60ed063d875501170ad2c0070c7d8f058\
+340ce8414a980ed648cf848a3e3ddb719c2cdd259fa04381143f12638342f3726be0061d2d8fb4fb73a80ed1f1912b64\
+69435a8fab2153ea3daa9001407239ea8abe25091d4cc6e2d3aea891b4bee999673d1fc1ad3fe24618c676c3e8aeb910\
+16a2f648462ec879524da09f1737c772a432c180d9077236f44db55374b6ac23\
+",
+		proof: "\
+11789c5592262f33e2d80f87ae8b51be6e3b43cbbe0cba2e22a26349df13ca6ad29a153dae2022755f4baadf1a6b11f9\
+572f37c85ec2815cbb60af8ee12d773ec3b0c92e488f6ef7ce509c5ac8e970d3871b0586210c62a55055bbbe453eb0f4\
+5618ab7fcc446d01eab30ed7d4d53f4d107a7096976a1b38f18ffd75ac6c3e17cc903071c45e2c71235d259c7be42943\
+b3ff67b858cd71e25024aab3b3f9ca73ef3e8ebcdd7294b9edb159a1b05892715d6f17562a9b6c83081b6d748007585f\
+af0191ff007aeab7c1962e99da2010f6168ef7684df0c91ef09976c952fce767afa31229bf5220ded2240e28bff9b894\
+74f861a69518f90cb64e9ee0224fb1052d0b40702302d8811065f113a12c571604cfcb36c17c6e61b6c908055c064bc0\
+16745756fee51d9482b468b0fd8215af15d4695fd614c55fc2bed6f234bdb5674720d8e6a8719378653b3a1dd350f08d\
+a7c9a1bc5582ef4e8d661e1e98a5273cf0091d54a188c20c8147fe13ca3419da5394c7822483f138f20c4b2ac288d8ad\
+c8902586643c3f5c9d6bd4fff15d4741f13d15085f2ebbf55f741dab210396dc051980f5065d9ea45bb2bd4031108b89\
+28869c41e2df76f41806d54efe03fe9c7f893beeb7d773a86c80a14ced68ef6ec330d2374b1514f1187441d5ba491e37\
+9ff5db3b4dfe01959ddfa86d65ac392cc4c7fa252a1fc1b12d957a1e9799bba0\
+",
+	},
+];
+
+/// The negation of the output of the first fixture, with the negated proof for its challenge
+/// prime.
+///
+/// The pair satisfies the verification equation in `Z_n*`, but is not a representative in the
+/// group of signed quadratic residues.
+#[cfg(test)]
+pub const NEGATED: Fixture = Fixture {
+	modulus: FIXTURES[0].modulus,
+	output: "\
+63d670552b3468eeb5ada0f9c668e3348320ef45352909f8814e5a094cd6270c157bcc07c88165259ace687d6cfc1511\
+d5d1c9036e6aa1f3f2e0e67b10e8a1a7\
+",
+	proof: "\
+11bb313e9a9e777f1e7e5efb13806b24476caec9856139cb35c9f4053da1897a8a8f3e4ccfd38f01d677cbc5b9c7ab08\
+1e8f01214e8b23cafa7c872d665fc2a9\
+",
+};
+
+/// The fixture with the longest modulus of at most `n` bytes, or the shortest one.
+pub fn fixture(n: u32) -> &'static Fixture {
+	FIXTURES
+		.iter()
+		.rev()
+		.find(|fixture| fixture.modulus.len() <= 2 * n as usize)
+		.unwrap_or(&FIXTURES[0])
+}
@@ -0,0 +1,211 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # VDF Pallet
+//!
+//! Accepts outputs of a Wesolowski verifiable delay function over an RSA group.
+//!
+//! The RSA modulus and the number of squarings are set by [`Config::ParametersOrigin`] through
+//! [`Pallet::set_parameters`], and so is the first challenge through [`Pallet::set_challenge`].
+//! Anyone can then submit the output of the VDF for the current challenge together with its
+//! proof through [`Pallet::submit_output`]. Once verified by the
+//! `vdf_wesolowski_verify` host function, the output is stored and its blake2-256 hash becomes
+//! the next challenge, forming a chain of delay-based randomness that cannot be biased by the
+//! submitter.
+//!
+//! The VDF is evaluated in the group of signed quadratic residues, where an integer `v` and
+//! `modulus - v` are the same element and only the smaller one is accepted. Every challenge thus
+//! has exactly one valid output, which would not hold in the multiplicative group itself, where
+//! the negated output verifies with the negated proof.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod fixtures;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	/// A big-endian integer of at most [`Config::MaxModulusLength`] bytes.
+	pub type IntegerOf<T> = BoundedVec<u8, <T as Config>::MaxModulusLength>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to set the VDF parameters and challenge.
+		type ParametersOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum length of the RSA modulus in bytes.
+		///
+		/// Must be at least 64, the length of the smallest modulus the benchmarks use. Secure
+		/// moduli are much longer.
+		#[pallet::constant]
+		type MaxModulusLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The RSA modulus of the group the VDF is evaluated in.
+	#[pallet::storage]
+	#[pallet::getter(fn modulus)]
+	pub type Modulus<T: Config> = StorageValue<_, IntegerOf<T>>;
+
+	/// The number of squarings the VDF consists of.
+	#[pallet::storage]
+	#[pallet::getter(fn iterations)]
+	pub type Iterations<T> = StorageValue<_, u64, ValueQuery>;
+
+	/// The challenge the next output must be computed for.
+	#[pallet::storage]
+	#[pallet::getter(fn challenge)]
+	pub type Challenge<T> = StorageValue<_, [u8; 32]>;
+
+	/// The last accepted VDF output.
+	#[pallet::storage]
+	#[pallet::getter(fn output)]
+	pub type Output<T: Config> = StorageValue<_, IntegerOf<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The VDF parameters were changed.
+		ParametersSet { iterations: u64 },
+		/// A new challenge was set.
+		ChallengeSet { challenge: [u8; 32] },
+		/// A VDF output was verified and accepted.
+		OutputAccepted { who: T::AccountId, challenge: [u8; 32], output: IntegerOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The modulus is not longer than 32 bytes or has leading zeros.
+		InvalidModulus,
+		/// The number of iterations is zero.
+		ZeroIterations,
+		/// The parameters or the challenge have not been set yet.
+		NotConfigured,
+		/// The VDF proof did not verify.
+		InvalidProof,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(T::MaxModulusLength::get() >= 64, "`MaxModulusLength` must be at least 64");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the RSA `modulus` and the number of squarings of the VDF.
+		///
+		/// The modulus must be longer than 32 bytes so that every challenge is an element of
+		/// the group. Its factorization must be unknown, otherwise outputs can be computed
+		/// without delay.
+		///
+		/// The dispatch origin for this call must be [`Config::ParametersOrigin`].
+		#[pallet::weight(T::WeightInfo::set_parameters())]
+		pub fn set_parameters(
+			origin: OriginFor<T>,
+			modulus: IntegerOf<T>,
+			iterations: u64,
+		) -> DispatchResult {
+			T::ParametersOrigin::ensure_origin(origin)?;
+
+			ensure!(modulus.len() > 32 && modulus[0] != 0, Error::<T>::InvalidModulus);
+			ensure!(iterations > 0, Error::<T>::ZeroIterations);
+
+			Modulus::<T>::put(modulus);
+			Iterations::<T>::put(iterations);
+
+			Self::deposit_event(Event::ParametersSet { iterations });
+			Ok(())
+		}
+
+		/// Set the challenge the next output must be computed for.
+		///
+		/// The dispatch origin for this call must be [`Config::ParametersOrigin`].
+		#[pallet::weight(T::WeightInfo::set_challenge())]
+		pub fn set_challenge(origin: OriginFor<T>, challenge: [u8; 32]) -> DispatchResult {
+			T::ParametersOrigin::ensure_origin(origin)?;
+
+			Challenge::<T>::put(challenge);
+
+			Self::deposit_event(Event::ChallengeSet { challenge });
+			Ok(())
+		}
+
+		/// Submit the VDF `output` for the current challenge together with its Wesolowski
+		/// `proof`.
+		///
+		/// Both must be the representatives `min(v, modulus - v)` in the group of signed
+		/// quadratic residues.
+		///
+		/// On success the output is stored and its blake2-256 hash becomes the next challenge.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::submit_output(T::MaxModulusLength::get()))]
+		pub fn submit_output(
+			origin: OriginFor<T>,
+			output: IntegerOf<T>,
+			proof: IntegerOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let modulus = Modulus::<T>::get().ok_or(Error::<T>::NotConfigured)?;
+			let challenge = Challenge::<T>::get().ok_or(Error::<T>::NotConfigured)?;
+
+			ensure!(
+				sp_io::crypto::vdf_wesolowski_verify(
+					&modulus,
+					&challenge,
+					Iterations::<T>::get(),
+					&output,
+					&proof,
+				),
+				Error::<T>::InvalidProof,
+			);
+
+			Challenge::<T>::put(sp_io::hashing::blake2_256(&output));
+			Output::<T>::put(output.clone());
+
+			Self::deposit_event(Event::OutputAccepted { who, challenge, output });
+			Ok(Some(T::WeightInfo::submit_output(modulus.len() as u32)).into())
+		}
+	}
+}
@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the VDF pallet.
+
+use super::{fixtures, Challenge, Error, Event, IntegerOf, Output, Pallet as Vdf};
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+fn integer(bytes: Vec<u8>) -> IntegerOf<Test> {
+	bytes.try_into().unwrap()
+}
+
+fn configure(fixture: &fixtures::Fixture) {
+	assert_ok!(Vdf::<Test>::set_parameters(
+		RawOrigin::Root.into(),
+		integer(fixture.modulus()),
+		fixtures::ITERATIONS,
+	));
+	assert_ok!(Vdf::<Test>::set_challenge(RawOrigin::Root.into(), fixtures::challenge()));
+}
+
+#[test]
+fn set_parameters_works() {
+	new_test_ext().execute_with(|| {
+		let modulus = integer(fixtures::FIXTURES[0].modulus());
+
+		assert_noop!(
+			Vdf::<Test>::set_parameters(RawOrigin::Signed(1).into(), modulus.clone(), 10),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Vdf::<Test>::set_parameters(RawOrigin::Root.into(), integer(vec![0xff; 32]), 10),
+			Error::<Test>::InvalidModulus,
+		);
+		let mut leading_zero = modulus.to_vec();
+		leading_zero.insert(0, 0);
+		assert_noop!(
+			Vdf::<Test>::set_parameters(RawOrigin::Root.into(), integer(leading_zero), 10),
+			Error::<Test>::InvalidModulus,
+		);
+		assert_noop!(
+			Vdf::<Test>::set_parameters(RawOrigin::Root.into(), modulus.clone(), 0),
+			Error::<Test>::ZeroIterations,
+		);
+
+		assert_ok!(Vdf::<Test>::set_parameters(RawOrigin::Root.into(), modulus.clone(), 10));
+		assert_eq!(Vdf::<Test>::modulus(), Some(modulus));
+		assert_eq!(Vdf::<Test>::iterations(), 10);
+		System::assert_last_event(Event::ParametersSet { iterations: 10 }.into());
+	});
+}
+
+#[test]
+fn set_challenge_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vdf::<Test>::set_challenge(RawOrigin::Signed(1).into(), [1; 32]),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(Vdf::<Test>::set_challenge(RawOrigin::Root.into(), [1; 32]));
+		assert_eq!(Vdf::<Test>::challenge(), Some([1; 32]));
+		System::assert_last_event(Event::ChallengeSet { challenge: [1; 32] }.into());
+	});
+}
+
+#[test]
+fn submit_output_works() {
+	new_test_ext().execute_with(|| {
+		for fixture in &fixtures::FIXTURES {
+			configure(fixture);
+			let output = integer(fixture.output());
+
+			assert_ok!(Vdf::<Test>::submit_output(
+				RawOrigin::Signed(1).into(),
+				output.clone(),
+				integer(fixture.proof()),
+			));
+
+			assert_eq!(Output::<Test>::get(), Some(output.clone()));
+			assert_eq!(Challenge::<Test>::get(), Some(sp_io::hashing::blake2_256(&output)));
+			System::assert_last_event(
+				Event::OutputAccepted { who: 1, challenge: fixtures::challenge(), output }.into(),
+			);
+		}
+	});
+}
+
+#[test]
+fn submit_output_requires_configuration() {
+	new_test_ext().execute_with(|| {
+		let fixture = &fixtures::FIXTURES[0];
+		let submit = || {
+			Vdf::<Test>::submit_output(
+				RawOrigin::Signed(1).into(),
+				integer(fixture.output()),
+				integer(fixture.proof()),
+			)
+		};
+
+		assert_noop!(submit(), Error::<Test>::NotConfigured);
+		assert_ok!(Vdf::<Test>::set_parameters(
+			RawOrigin::Root.into(),
+			integer(fixture.modulus()),
+			fixtures::ITERATIONS,
+		));
+		assert_noop!(submit(), Error::<Test>::NotConfigured);
+	});
+}
+
+#[test]
+fn submit_output_rejects_invalid_proofs() {
+	new_test_ext().execute_with(|| {
+		let fixture = &fixtures::FIXTURES[0];
+		configure(fixture);
+		let output = integer(fixture.output());
+		let proof = integer(fixture.proof());
+
+		// Tampered proof.
+		let mut tampered = proof.to_vec();
+		tampered[10] ^= 1;
+		assert_noop!(
+			Vdf::<Test>::submit_output(
+				RawOrigin::Signed(1).into(),
+				output.clone(),
+				integer(tampered)
+			),
+			Error::<Test>::InvalidProof,
+		);
+
+		// Output and proof swapped.
+		assert_noop!(
+			Vdf::<Test>::submit_output(RawOrigin::Signed(1).into(), proof.clone(), output.clone()),
+			Error::<Test>::InvalidProof,
+		);
+
+		// The negated output with the negated proof for its challenge prime, which would verify
+		// in the multiplicative group.
+		assert_noop!(
+			Vdf::<Test>::submit_output(
+				RawOrigin::Signed(1).into(),
+				integer(fixtures::NEGATED.output()),
+				integer(fixtures::NEGATED.proof()),
+			),
+			Error::<Test>::InvalidProof,
+		);
+
+		// Computed for another challenge.
+		assert_ok!(Vdf::<Test>::set_challenge(RawOrigin::Root.into(), [1; 32]));
+		assert_noop!(
+			Vdf::<Test>::submit_output(RawOrigin::Signed(1).into(), output, proof),
+			Error::<Test>::InvalidProof,
+		);
+	});
+}
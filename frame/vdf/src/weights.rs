@@ -0,0 +1,92 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Weights for pallet_vdf
+//!
+//! These are conservative estimates until the weights are generated with the benchmark CLI:
+//!
+//! ./target/production/substrate benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_vdf --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --template=./.maintain/frame-weight-template.hbs --output=./frame/vdf/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_vdf.
+pub trait WeightInfo {
+	fn set_parameters() -> Weight;
+	fn set_challenge() -> Weight;
+	fn submit_output(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_vdf using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Vdf Modulus (r:0 w:1)
+	// Storage: Vdf Iterations (r:0 w:1)
+	fn set_parameters() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Vdf Challenge (r:0 w:1)
+	fn set_challenge() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Vdf Modulus (r:1 w:0)
+	// Storage: Vdf Challenge (r:1 w:1)
+	// Storage: Vdf Iterations (r:1 w:0)
+	// Storage: Vdf Output (r:0 w:1)
+	fn submit_output(n: u32, ) -> Weight {
+		Weight::from_ref_time(1_000_000_000 as u64)
+			// The modular exponentiations are quadratic in the modulus length
+			.saturating_add(Weight::from_ref_time(25_000 as u64).saturating_mul((n as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Vdf Modulus (r:0 w:1)
+	// Storage: Vdf Iterations (r:0 w:1)
+	fn set_parameters() -> Weight {
+		Weight::from_ref_time(20_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Vdf Challenge (r:0 w:1)
+	fn set_challenge() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Vdf Modulus (r:1 w:0)
+	// Storage: Vdf Challenge (r:1 w:1)
+	// Storage: Vdf Iterations (r:1 w:0)
+	// Storage: Vdf Output (r:0 w:1)
+	fn submit_output(n: u32, ) -> Weight {
+		Weight::from_ref_time(1_000_000_000 as u64)
+			// The modular exponentiations are quadratic in the modulus length
+			.saturating_add(Weight::from_ref_time(25_000 as u64).saturating_mul((n as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+}
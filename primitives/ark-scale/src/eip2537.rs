@@ -0,0 +1,171 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of BLS12-381 points from and to the encoding of the EIP-2537 precompiles.
+//!
+//! EIP-2537 encodes a base field element as 64 bytes: 16 zero bytes followed by the 48-byte
+//! big-endian value. Points are the uncompressed concatenation of `x` and `y`, 128 bytes for G1
+//! and 256 bytes for G2, where elements of the quadratic extension are encoded as `c0` followed
+//! by `c1`, the reverse of the zcash order. The point at infinity is encoded as all zeros.
+//!
+//! This allows data copied verbatim from Ethereum transactions to be converted into arkworks
+//! points. Decoding also performs the subgroup check, which EIP-2537 only requires for
+//! multiplication and pairing inputs.
+
+use crate::zcash::{ZcashEncoding, COMPRESSION_FLAG, INFINITY_FLAG, SORT_FLAG};
+use ark_ec::short_weierstrass::Affine;
+use ark_serialize::{Compress, SerializationError};
+use sp_std::{vec, vec::Vec};
+
+/// The length of an encoded base field element.
+const FP_LEN: usize = 64;
+/// The number of zero bytes an encoded base field element starts with.
+const FP_PADDING: usize = 16;
+/// The length of a base field element in the zcash encoding.
+const ZCASH_FP_LEN: usize = FP_LEN - FP_PADDING;
+
+/// BLS12-381 points convertible from and to the EIP-2537 encoding.
+pub trait Eip2537Encoding: ZcashEncoding {
+	/// The number of base field elements per coordinate.
+	const DEGREE: usize;
+	/// The length of the encoding in bytes.
+	const ENCODED_LEN: usize = 2 * Self::DEGREE * FP_LEN;
+
+	/// Encode the point.
+	fn to_eip2537_bytes(&self) -> Vec<u8> {
+		let mut bytes = vec![0; Self::ENCODED_LEN];
+		if self.is_zero() {
+			return bytes
+		}
+
+		let zcash = self.to_zcash_bytes(Compress::No);
+		for (index, element) in zcash.chunks(ZCASH_FP_LEN).enumerate() {
+			let start = reorder(index, Self::DEGREE) * FP_LEN + FP_PADDING;
+			bytes[start..start + ZCASH_FP_LEN].copy_from_slice(element);
+		}
+		bytes
+	}
+
+	/// Decode a point.
+	///
+	/// Fails if `bytes` is not the encoding of a point in the prime order subgroup.
+	fn from_eip2537_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+		if bytes.len() != Self::ENCODED_LEN {
+			return Err(SerializationError::InvalidData)
+		}
+		if bytes.iter().all(|b| *b == 0) {
+			return Ok(Self::zero())
+		}
+
+		let mut zcash = vec![0; Self::ENCODED_LEN / FP_LEN * ZCASH_FP_LEN];
+		for (index, element) in bytes.chunks(FP_LEN).enumerate() {
+			let (padding, element) = element.split_at(FP_PADDING);
+			// Base field elements are smaller than `2^381`, so the bits zcash uses for flags are
+			// never set. Rejecting them here keeps them from being read as flags below.
+			if padding.iter().any(|b| *b != 0) ||
+				element[0] & (COMPRESSION_FLAG | INFINITY_FLAG | SORT_FLAG) != 0
+			{
+				return Err(SerializationError::InvalidData)
+			}
+			let start = reorder(index, Self::DEGREE) * ZCASH_FP_LEN;
+			zcash[start..start + ZCASH_FP_LEN].copy_from_slice(element);
+		}
+		Self::from_zcash_bytes(&zcash)
+	}
+}
+
+/// Map the position of a base field element between the zcash and the EIP-2537 encoding.
+///
+/// Both encode `x` before `y`, but extension field elements are ordered from the highest
+/// coefficient in zcash and from the lowest in EIP-2537. The mapping is its own inverse.
+fn reorder(index: usize, degree: usize) -> usize {
+	index - index % degree + (degree - 1 - index % degree)
+}
+
+// See the comment in `bls12_381` on why these are implemented on `Affine`.
+impl Eip2537Encoding for Affine<ark_bls12_381::g1::Config> {
+	const DEGREE: usize = 1;
+}
+
+impl Eip2537Encoding for Affine<ark_bls12_381::g2::Config> {
+	const DEGREE: usize = 2;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_bls12_381::{G1Affine, G2Affine};
+	use ark_ec::AffineRepr;
+	use ark_std::{test_rng, UniformRand};
+
+	/// The big-endian base field elements of the `x` coordinates of the generators.
+	const G1_GENERATOR_X: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+	const G2_GENERATOR_X_C0: &str = "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+	const G2_GENERATOR_X_C1: &str = "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e";
+
+	fn padded(element: &str) -> Vec<u8> {
+		let mut bytes = vec![0; FP_PADDING];
+		bytes.extend(array_bytes::hex2bytes_unchecked(element));
+		bytes
+	}
+
+	fn round_trip<P: Eip2537Encoding + UniformRand>(len: usize) {
+		let point = P::rand(&mut test_rng());
+		let bytes = point.to_eip2537_bytes();
+		assert_eq!(bytes.len(), len);
+		assert_eq!(P::from_eip2537_bytes(&bytes).unwrap(), point);
+		assert!(P::from_eip2537_bytes(&bytes[..len - 1]).is_err());
+
+		assert_eq!(P::zero().to_eip2537_bytes(), vec![0; len]);
+		assert_eq!(P::from_eip2537_bytes(&vec![0; len]).unwrap(), P::zero());
+	}
+
+	#[test]
+	fn points_round_trip() {
+		round_trip::<G1Affine>(128);
+		round_trip::<G2Affine>(256);
+	}
+
+	#[test]
+	fn generators_match_eip2537() {
+		let g1 = G1Affine::generator().to_eip2537_bytes();
+		assert_eq!(g1[..FP_LEN], padded(G1_GENERATOR_X));
+
+		let g2 = G2Affine::generator().to_eip2537_bytes();
+		assert_eq!(g2[..FP_LEN], padded(G2_GENERATOR_X_C0));
+		assert_eq!(g2[FP_LEN..2 * FP_LEN], padded(G2_GENERATOR_X_C1));
+		assert_eq!(G2Affine::from_eip2537_bytes(&g2).unwrap(), G2Affine::generator());
+	}
+
+	#[test]
+	fn invalid_encodings_are_rejected() {
+		let g1 = G1Affine::generator().to_eip2537_bytes();
+
+		let mut padding = g1.clone();
+		padding[FP_LEN] = 1;
+		assert!(G1Affine::from_eip2537_bytes(&padding).is_err());
+
+		// `2^382` as `x`, which would be read as the zcash infinity flag.
+		let mut too_large = vec![0; 128];
+		too_large[FP_PADDING] = INFINITY_FLAG;
+		assert!(G1Affine::from_eip2537_bytes(&too_large).is_err());
+
+		let mut off_curve = g1;
+		off_curve[127] ^= 1;
+		assert!(G1Affine::from_eip2537_bytes(&off_curve).is_err());
+	}
+}
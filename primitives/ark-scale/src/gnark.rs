@@ -0,0 +1,447 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import of Groth16 proofs and verifying keys serialized by gnark.
+//!
+//! gnark writes points big-endian with the flags of gnark-crypto in the most significant bits of
+//! the first byte, and each point is read compressed or uncompressed depending on them. For
+//! BLS12-381 these are the zcash flags, see [`crate::zcash`]. For BN254 the two highest bits are
+//! `0b00` for uncompressed points, `0b10` and `0b11` for compressed points whose `y` is the
+//! lexicographically smallest and largest candidate, and `0b01` for the compressed point at
+//! infinity. The uncompressed point at infinity is all zeros, and the coefficients of G2
+//! coordinates are ordered `A1` followed by `A0`.
+//!
+//! Proofs are read as `Ar`, `Bs` and `Krs`. Verifying keys are read as `α` in G1, `β` in G1
+//! and G2, `γ` in G2, `δ` in G1 and G2, and `K` prefixed with its length as a big-endian `u32`.
+//! This is the layout written by `WriteTo` and `WriteRawTo` up to gnark 0.8. Later versions
+//! append the data of the Pedersen commitment extension, which is not supported, so such input
+//! is rejected as having trailing bytes.
+//!
+//! The layout follows the gnark and gnark-crypto sources. It is tested against the published
+//! encodings of the generators and against files laid out as described here, but not yet
+//! against files exported by gnark itself.
+
+use crate::ArkScale;
+use ark_ec::{pairing::Pairing, short_weierstrass::Affine, AffineRepr};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_serialize::SerializationError;
+
+/// Points readable from the gnark serialization.
+pub trait GnarkPoint: AffineRepr {
+	/// Read a point from the front of `input`, advancing it past the point.
+	///
+	/// Fails if the point is not on the curve or not in the prime order subgroup.
+	fn read_gnark(input: &mut &[u8]) -> Result<Self, SerializationError>;
+}
+
+/// Split the first `len` bytes off `input`.
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], SerializationError> {
+	if input.len() < len {
+		return Err(SerializationError::InvalidData)
+	}
+	let (head, tail) = input.split_at(len);
+	*input = tail;
+	Ok(head)
+}
+
+/// Import a Groth16 proof serialized by gnark.
+pub fn proof_from_gnark<E>(mut bytes: &[u8]) -> Result<ArkScale<Proof<E>>, SerializationError>
+where
+	E: Pairing,
+	E::G1Affine: GnarkPoint,
+	E::G2Affine: GnarkPoint,
+{
+	let input = &mut bytes;
+	let proof = Proof {
+		a: GnarkPoint::read_gnark(input)?,
+		b: GnarkPoint::read_gnark(input)?,
+		c: GnarkPoint::read_gnark(input)?,
+	};
+
+	if !input.is_empty() {
+		return Err(SerializationError::InvalidData)
+	}
+	Ok(ArkScale(proof))
+}
+
+/// Import a Groth16 verifying key serialized by gnark.
+///
+/// The G1 points `β` and `δ` are not part of arkworks' verifying key and are only checked to be
+/// valid points.
+pub fn verifying_key_from_gnark<E>(
+	mut bytes: &[u8],
+) -> Result<ArkScale<VerifyingKey<E>>, SerializationError>
+where
+	E: Pairing,
+	E::G1Affine: GnarkPoint,
+	E::G2Affine: GnarkPoint,
+{
+	let input = &mut bytes;
+	let alpha_g1 = GnarkPoint::read_gnark(input)?;
+	let _beta_g1 = E::G1Affine::read_gnark(input)?;
+	let beta_g2 = GnarkPoint::read_gnark(input)?;
+	let gamma_g2 = GnarkPoint::read_gnark(input)?;
+	let _delta_g1 = E::G1Affine::read_gnark(input)?;
+	let delta_g2 = GnarkPoint::read_gnark(input)?;
+
+	let len = take(input, 4)?.try_into().map(u32::from_be_bytes).expect("Took 4 bytes; qed");
+	let gamma_abc_g1 =
+		(0..len).map(|_| GnarkPoint::read_gnark(input)).collect::<Result<Vec<_>, _>>()?;
+
+	if !input.is_empty() {
+		return Err(SerializationError::InvalidData)
+	}
+	Ok(ArkScale(VerifyingKey { alpha_g1, beta_g2, gamma_g2, delta_g2, gamma_abc_g1 }))
+}
+
+#[cfg(feature = "bls12-381")]
+mod bls12_381 {
+	use super::*;
+	use crate::zcash::{ZcashEncoding, COMPRESSION_FLAG};
+
+	fn read_zcash<P: ZcashEncoding>(input: &mut &[u8]) -> Result<P, SerializationError> {
+		let flags = *input.first().ok_or(SerializationError::InvalidData)?;
+		let len =
+			if flags & COMPRESSION_FLAG != 0 { P::COMPRESSED_LEN } else { 2 * P::COMPRESSED_LEN };
+		P::from_zcash_bytes(take(input, len)?)
+	}
+
+	// See the comment in `crate::bls12_381` on why these are implemented on `Affine`.
+	impl GnarkPoint for Affine<ark_bls12_381::g1::Config> {
+		fn read_gnark(input: &mut &[u8]) -> Result<Self, SerializationError> {
+			read_zcash(input)
+		}
+	}
+
+	impl GnarkPoint for Affine<ark_bls12_381::g2::Config> {
+		fn read_gnark(input: &mut &[u8]) -> Result<Self, SerializationError> {
+			read_zcash(input)
+		}
+	}
+}
+
+#[cfg(feature = "bn254")]
+mod bn254 {
+	use super::*;
+	use ark_bn254::{Fq, Fq2};
+	use ark_ec::short_weierstrass::SWCurveConfig;
+	use ark_serialize::{CanonicalDeserialize, Valid};
+	use ark_std::Zero;
+
+	/// The bits of the first byte holding the flags.
+	const FLAGS_MASK: u8 = 0b11 << 6;
+	const UNCOMPRESSED: u8 = 0b00 << 6;
+	const COMPRESSED_INFINITY: u8 = 0b01 << 6;
+	const COMPRESSED_LARGEST: u8 = 0b11 << 6;
+
+	/// Base fields of the BN254 groups.
+	trait BaseField: Zero + Sized {
+		/// The length of an element in bytes.
+		const LEN: usize;
+
+		/// Read an element, failing if it is not smaller than the modulus.
+		fn read(bytes: &[u8]) -> Result<Self, SerializationError>;
+	}
+
+	impl BaseField for Fq {
+		const LEN: usize = 32;
+
+		fn read(bytes: &[u8]) -> Result<Self, SerializationError> {
+			// arkworks serializes field elements little-endian.
+			let mut bytes = bytes.to_vec();
+			bytes.reverse();
+			Fq::deserialize_uncompressed(&bytes[..])
+		}
+	}
+
+	impl BaseField for Fq2 {
+		const LEN: usize = 64;
+
+		fn read(bytes: &[u8]) -> Result<Self, SerializationError> {
+			Ok(Fq2::new(Fq::read(&bytes[Fq::LEN..])?, Fq::read(&bytes[..Fq::LEN])?))
+		}
+	}
+
+	fn read_point<P>(input: &mut &[u8]) -> Result<Affine<P>, SerializationError>
+	where
+		P: SWCurveConfig,
+		P::BaseField: BaseField,
+	{
+		let len = P::BaseField::LEN;
+		let flags = *input.first().ok_or(SerializationError::InvalidData)? & FLAGS_MASK;
+		let point = match flags {
+			UNCOMPRESSED => {
+				let bytes = take(input, 2 * len)?;
+				let (x, y) =
+					(P::BaseField::read(&bytes[..len])?, P::BaseField::read(&bytes[len..])?);
+				if x.is_zero() && y.is_zero() {
+					Affine::identity()
+				} else {
+					Affine::new_unchecked(x, y)
+				}
+			},
+			COMPRESSED_INFINITY => {
+				let bytes = take(input, len)?;
+				if bytes[0] & !FLAGS_MASK != 0 || bytes[1..].iter().any(|b| *b != 0) {
+					return Err(SerializationError::UnexpectedFlags)
+				}
+				Affine::identity()
+			},
+			_ => {
+				let mut bytes = take(input, len)?.to_vec();
+				bytes[0] &= !FLAGS_MASK;
+				let x = P::BaseField::read(&bytes)?;
+				Affine::get_point_from_x_unchecked(x, flags == COMPRESSED_LARGEST)
+					.ok_or(SerializationError::InvalidData)?
+			},
+		};
+
+		point.check()?;
+		Ok(point)
+	}
+
+	impl GnarkPoint for Affine<ark_bn254::g1::Config> {
+		fn read_gnark(input: &mut &[u8]) -> Result<Self, SerializationError> {
+			read_point(input)
+		}
+	}
+
+	impl GnarkPoint for Affine<ark_bn254::g2::Config> {
+		fn read_gnark(input: &mut &[u8]) -> Result<Self, SerializationError> {
+			read_point(input)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G2Affine};
+	use ark_ec::short_weierstrass::SWCurveConfig;
+	use ark_serialize::CanonicalSerialize;
+	use ark_std::{test_rng, UniformRand};
+
+	/// Field elements in gnark's byte order.
+	trait GnarkBytes {
+		fn gnark_bytes(&self) -> Vec<u8>;
+	}
+
+	impl GnarkBytes for Fq {
+		fn gnark_bytes(&self) -> Vec<u8> {
+			let mut bytes = Vec::new();
+			self.serialize_uncompressed(&mut bytes).unwrap();
+			bytes.reverse();
+			bytes
+		}
+	}
+
+	impl GnarkBytes for Fq2 {
+		fn gnark_bytes(&self) -> Vec<u8> {
+			[self.c1.gnark_bytes(), self.c0.gnark_bytes()].concat()
+		}
+	}
+
+	/// Write a BN254 point the way gnark-crypto does.
+	fn gnark<P>(point: &Affine<P>, compress: bool) -> Vec<u8>
+	where
+		P: SWCurveConfig,
+		P::BaseField: GnarkBytes,
+	{
+		let len = P::BaseField::default().gnark_bytes().len();
+		match (point.xy(), compress) {
+			(None, false) => vec![0; 2 * len],
+			(None, true) => {
+				let mut bytes = vec![0; len];
+				bytes[0] = 0b01 << 6;
+				bytes
+			},
+			(Some((x, y)), false) => [x.gnark_bytes(), y.gnark_bytes()].concat(),
+			(Some((x, y)), true) => {
+				let mut bytes = x.gnark_bytes();
+				bytes[0] |= if *y > -*y { 0b11 << 6 } else { 0b10 << 6 };
+				bytes
+			},
+		}
+	}
+
+	fn read<P: GnarkPoint>(bytes: &[u8]) -> Result<P, SerializationError> {
+		let input = &mut &bytes[..];
+		let point = P::read_gnark(input)?;
+		assert!(input.is_empty());
+		Ok(point)
+	}
+
+	#[test]
+	fn bn254_points_are_read() {
+		// The generator of G1 is `(1, 2)`, where `2` is the smaller candidate.
+		let mut generator = vec![0; 32];
+		generator[0] = 0b10 << 6;
+		generator[31] = 1;
+		assert_eq!(gnark(&G1Affine::generator(), true), generator);
+		assert_eq!(read::<G1Affine>(&generator).unwrap(), G1Affine::generator());
+		generator[0] = 0b11 << 6;
+		assert_eq!(read::<G1Affine>(&generator).unwrap(), -G1Affine::generator());
+
+		let rng = &mut test_rng();
+		for compress in [true, false] {
+			let (a, b) = (G1Affine::rand(rng), G2Affine::rand(rng));
+			assert_eq!(read::<G1Affine>(&gnark(&a, compress)).unwrap(), a);
+			assert_eq!(read::<G2Affine>(&gnark(&b, compress)).unwrap(), b);
+			assert_eq!(read::<G1Affine>(&gnark(&-a, compress)).unwrap(), -a);
+			assert_eq!(read::<G2Affine>(&gnark(&-b, compress)).unwrap(), -b);
+			assert!(read::<G1Affine>(&gnark(&G1Affine::zero(), compress)).unwrap().is_zero());
+			assert!(read::<G2Affine>(&gnark(&G2Affine::zero(), compress)).unwrap().is_zero());
+		}
+
+		// Non-zero bytes after the compressed point at infinity.
+		let mut infinity = gnark(&G1Affine::zero(), true);
+		infinity[31] = 1;
+		assert!(read::<G1Affine>(&infinity).is_err());
+		// `(1, 3)` is not on the curve.
+		let mut off_curve = gnark(&G1Affine::generator(), false);
+		off_curve[63] = 3;
+		assert!(read::<G1Affine>(&off_curve).is_err());
+	}
+
+	#[test]
+	fn published_generators_are_read() {
+		use ark_bls12_381::{G1Affine as BlsG1, G2Affine as BlsG2};
+		let hex = |hex: &str| array_bytes::hex2bytes_unchecked(hex);
+
+		// The BN254 generator of G2 as given in EIP-197, whose `y` is the smaller candidate.
+		let x = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+			1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed";
+		let y = "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+			12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+		assert_eq!(read::<G2Affine>(&hex(&format!("{x}{y}"))).unwrap(), G2Affine::generator());
+		let compressed = format!("99{}", &x[2..]);
+		assert_eq!(read::<G2Affine>(&hex(&compressed)).unwrap(), G2Affine::generator());
+
+		// The BLS12-381 generators in the zcash encoding.
+		let x = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905\
+			a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+		let y = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af6\
+			00db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+		assert_eq!(read::<BlsG1>(&hex(&format!("{x}{y}"))).unwrap(), BlsG1::generator());
+		let compressed = format!("97{}", &x[2..]);
+		assert_eq!(read::<BlsG1>(&hex(&compressed)).unwrap(), BlsG1::generator());
+		let compressed = "93e02b6052719f607dacd3a088274f65596bd0d09920b61a\
+			b5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+			024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02\
+			b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+		assert_eq!(read::<BlsG2>(&hex(compressed)).unwrap(), BlsG2::generator());
+	}
+
+	#[test]
+	fn proofs_in_the_gnark_layout_verify() {
+		use ark_bls12_381::Bls12_381;
+		use ark_groth16::Groth16;
+
+		fn verify<E>(key: &[u8], proof: &[u8]) -> [bool; 2]
+		where
+			E: Pairing,
+			E::G1Affine: GnarkPoint,
+			E::G2Affine: GnarkPoint,
+		{
+			let key =
+				ark_groth16::prepare_verifying_key(&verifying_key_from_gnark::<E>(key).unwrap().0);
+			let proof = proof_from_gnark::<E>(proof).unwrap().0;
+			[33u8, 34]
+				.map(|input| Groth16::<E>::verify_proof(&key, &proof, &[input.into()]).unwrap())
+		}
+
+		// These files were not exported by gnark but laid out by hand from other provers' output,
+		// see `fixtures/gnark/README.md`.
+		//
+		// The snarkjs fixture proving a factorization of 33, with compressed points in the key
+		// and uncompressed ones in the proof. The G1 generator stands in for `β` and `δ` in G1,
+		// which snarkjs does not export.
+		assert_eq!(
+			verify::<Bn254>(
+				include_bytes!("../fixtures/gnark/bn254/verifying_key.bin"),
+				include_bytes!("../fixtures/gnark/bn254/proof.bin"),
+			),
+			[true, false],
+		);
+		// A proof of the same statement by the arkworks prover, laid out the same way.
+		assert_eq!(
+			verify::<Bls12_381>(
+				include_bytes!("../fixtures/gnark/bls12_381/verifying_key.bin"),
+				include_bytes!("../fixtures/gnark/bls12_381/proof.bin"),
+			),
+			[true, false],
+		);
+	}
+
+	#[test]
+	fn bn254_proofs_and_verifying_keys_are_imported() {
+		let rng = &mut test_rng();
+		let proof = Proof::<Bn254> {
+			a: G1Affine::rand(rng),
+			b: G2Affine::rand(rng),
+			c: G1Affine::rand(rng),
+		};
+		let bytes = [gnark(&proof.a, true), gnark(&proof.b, false), gnark(&proof.c, true)].concat();
+		assert_eq!(proof_from_gnark::<Bn254>(&bytes).unwrap().0, proof);
+		assert!(proof_from_gnark::<Bn254>(&[&bytes[..], &[0]].concat()).is_err());
+		assert!(proof_from_gnark::<Bn254>(&bytes[..bytes.len() - 1]).is_err());
+
+		let key = VerifyingKey::<Bn254> {
+			alpha_g1: G1Affine::rand(rng),
+			beta_g2: G2Affine::rand(rng),
+			gamma_g2: G2Affine::rand(rng),
+			delta_g2: G2Affine::rand(rng),
+			gamma_abc_g1: vec![G1Affine::rand(rng), G1Affine::rand(rng)],
+		};
+		let bytes = [
+			gnark(&key.alpha_g1, true),
+			gnark(&G1Affine::rand(rng), true),
+			gnark(&key.beta_g2, true),
+			gnark(&key.gamma_g2, true),
+			gnark(&G1Affine::rand(rng), true),
+			gnark(&key.delta_g2, true),
+			2u32.to_be_bytes().to_vec(),
+			gnark(&key.gamma_abc_g1[0], true),
+			gnark(&key.gamma_abc_g1[1], true),
+		]
+		.concat();
+		assert_eq!(verifying_key_from_gnark::<Bn254>(&bytes).unwrap().0, key);
+		assert!(verifying_key_from_gnark::<Bn254>(&[&bytes[..], &[0]].concat()).is_err());
+		assert!(verifying_key_from_gnark::<Bn254>(&bytes[..bytes.len() - 1]).is_err());
+	}
+
+	#[test]
+	fn bls12_381_proofs_are_imported() {
+		use crate::zcash::ZcashEncoding;
+		use ark_bls12_381::Bls12_381;
+		use ark_serialize::Compress;
+
+		let rng = &mut test_rng();
+		let proof = Proof::<Bls12_381> {
+			a: UniformRand::rand(rng),
+			b: UniformRand::rand(rng),
+			c: UniformRand::rand(rng),
+		};
+		let bytes = [
+			proof.a.to_zcash_bytes(Compress::Yes),
+			proof.b.to_zcash_bytes(Compress::No),
+			proof.c.to_zcash_bytes(Compress::Yes),
+		]
+		.concat();
+		assert_eq!(proof_from_gnark::<Bls12_381>(&bytes).unwrap().0, proof);
+	}
+}
@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Groth16 types usable in bounded storage.
+
+use crate::{ArkScale, FixedEncodedLen};
+use ark_ec::pairing::Pairing;
+use ark_groth16::{Proof, VerifyingKey};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::{bounded::BoundedVec, Get};
+use sp_std::fmt;
+
+impl<E: Pairing> FixedEncodedLen for Proof<E>
+where
+	E::G1Affine: FixedEncodedLen,
+	E::G2Affine: FixedEncodedLen,
+{
+	const ENCODED_LEN: usize = 2 * E::G1Affine::ENCODED_LEN + E::G2Affine::ENCODED_LEN;
+}
+
+/// A Groth16 verifying key with at most `MaxPoints` points in `gamma_abc_g1`, which is one more
+/// than the number of public inputs.
+///
+/// The points are encoded as in [`ArkScale`] and `gamma_abc_g1` as a SCALE vector, so unlike
+/// `ArkScale<VerifyingKey<E>>` the encoding has a maximum length.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
+#[scale_info(skip_type_params(E, MaxPoints))]
+pub struct BoundedVerifyingKey<E: Pairing, MaxPoints: Get<u32>> {
+	/// The `alpha * G`, where `G` is the generator of `E::G1`.
+	pub alpha_g1: ArkScale<E::G1Affine>,
+	/// The `beta * H`, where `H` is the generator of `E::G2`.
+	pub beta_g2: ArkScale<E::G2Affine>,
+	/// The `gamma * H`, where `H` is the generator of `E::G2`.
+	pub gamma_g2: ArkScale<E::G2Affine>,
+	/// The `delta * H`, where `H` is the generator of `E::G2`.
+	pub delta_g2: ArkScale<E::G2Affine>,
+	/// The `gamma^{-1} * (beta * a_i + alpha * b_i + c_i) * G`, where `G` is the generator of
+	/// `E::G1`.
+	pub gamma_abc_g1: BoundedVec<ArkScale<E::G1Affine>, MaxPoints>,
+}
+
+impl<E: Pairing, MaxPoints: Get<u32>> Clone for BoundedVerifyingKey<E, MaxPoints> {
+	fn clone(&self) -> Self {
+		Self {
+			alpha_g1: self.alpha_g1,
+			beta_g2: self.beta_g2,
+			gamma_g2: self.gamma_g2,
+			delta_g2: self.delta_g2,
+			gamma_abc_g1: self.gamma_abc_g1.clone(),
+		}
+	}
+}
+
+impl<E: Pairing, MaxPoints: Get<u32>> PartialEq for BoundedVerifyingKey<E, MaxPoints> {
+	fn eq(&self, other: &Self) -> bool {
+		self.alpha_g1 == other.alpha_g1 &&
+			self.beta_g2 == other.beta_g2 &&
+			self.gamma_g2 == other.gamma_g2 &&
+			self.delta_g2 == other.delta_g2 &&
+			self.gamma_abc_g1 == other.gamma_abc_g1
+	}
+}
+
+impl<E: Pairing, MaxPoints: Get<u32>> Eq for BoundedVerifyingKey<E, MaxPoints> {}
+
+impl<E: Pairing, MaxPoints: Get<u32>> fmt::Debug for BoundedVerifyingKey<E, MaxPoints> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("BoundedVerifyingKey")
+			.field("alpha_g1", &self.alpha_g1)
+			.field("beta_g2", &self.beta_g2)
+			.field("gamma_g2", &self.gamma_g2)
+			.field("delta_g2", &self.delta_g2)
+			.field("gamma_abc_g1", &self.gamma_abc_g1)
+			.finish()
+	}
+}
+
+impl<E: Pairing, MaxPoints: Get<u32>> From<BoundedVerifyingKey<E, MaxPoints>> for VerifyingKey<E> {
+	fn from(key: BoundedVerifyingKey<E, MaxPoints>) -> Self {
+		Self {
+			alpha_g1: key.alpha_g1.0,
+			beta_g2: key.beta_g2.0,
+			gamma_g2: key.gamma_g2.0,
+			delta_g2: key.delta_g2.0,
+			gamma_abc_g1: key.gamma_abc_g1.into_iter().map(ArkScale::into_inner).collect(),
+		}
+	}
+}
+
+impl<E: Pairing, MaxPoints: Get<u32>> TryFrom<VerifyingKey<E>>
+	for BoundedVerifyingKey<E, MaxPoints>
+{
+	type Error = VerifyingKey<E>;
+
+	/// Bound `key`, returning it back if it has more than `MaxPoints` points in `gamma_abc_g1`.
+	fn try_from(key: VerifyingKey<E>) -> Result<Self, Self::Error> {
+		if key.gamma_abc_g1.len() > MaxPoints::get() as usize {
+			return Err(key)
+		}
+
+		Ok(Self {
+			alpha_g1: ArkScale(key.alpha_g1),
+			beta_g2: ArkScale(key.beta_g2),
+			gamma_g2: ArkScale(key.gamma_g2),
+			delta_g2: ArkScale(key.delta_g2),
+			gamma_abc_g1: BoundedVec::truncate_from(
+				key.gamma_abc_g1.into_iter().map(ArkScale).collect(),
+			),
+		})
+	}
+}
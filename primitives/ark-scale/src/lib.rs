@@ -0,0 +1,438 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SCALE codec wrappers for arkworks types.
+//!
+//! [`ArkScale`] wraps any type implementing arkworks' [`CanonicalSerialize`] and
+//! [`CanonicalDeserialize`], such as curve points, field elements, Groth16 proofs and verifying
+//! keys, and implements [`Encode`] and [`Decode`] for it. This allows arkworks types to be used
+//! directly as extrinsic arguments and storage items instead of raw bytes.
+//!
+//! The SCALE encoding of `ArkScale(value)` is the canonical compressed serialization of `value`,
+//! without a length prefix. Decoding fully validates the value, including the subgroup checks of
+//! curve points, so decoded values can be used without further checks.
+//!
+//! Wrapped types whose serialization has a fixed length, see [`FixedEncodedLen`], also implement
+//! [`MaxEncodedLen`] and [`TypeInfo`], where they are described as byte arrays, so they can be
+//! kept in bounded storage and appear in metadata. Verifying keys have a variable length and can
+//! be stored as a [`groth16::BoundedVerifyingKey`] instead.
+//!
+//! With the `serde` feature, the wrapped types are serialized as `0x`-prefixed hex of their SCALE
+//! encoding, which is the byte layout host functions consume.
+//!
+//! The `bls12-381`, `bn254` and `groth16` features provide aliases for the wrapped types of
+//! these curves. The [`zcash`] and [`eip2537`] modules convert BLS12-381 points from and to the
+//! encodings used by zcash and blst, and by the Ethereum BLS precompiles. With `std`, the
+//! [`gnark`] module imports Groth16 proofs and verifying keys serialized by gnark. With the
+//! `snarkjs` feature, the `snarkjs` module imports the JSON files exported by snarkjs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, Write};
+use ark_std::io::{self, ErrorKind};
+use codec::{Decode, Encode, EncodeLike, Input, MaxEncodedLen, Output};
+use scale_info::{MetaType, Type, TypeDefArray, TypeInfo};
+
+#[cfg(feature = "bls12-381")]
+pub mod eip2537;
+#[cfg(all(feature = "std", feature = "groth16"))]
+pub mod gnark;
+#[cfg(feature = "groth16")]
+pub mod groth16;
+#[cfg(feature = "snarkjs")]
+pub mod snarkjs;
+#[cfg(feature = "bls12-381")]
+pub mod zcash;
+
+/// SCALE codec wrapper for arkworks types.
+///
+/// See the crate-level docs for the encoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash)]
+pub struct ArkScale<T>(pub T);
+
+impl<T> From<T> for ArkScale<T> {
+	fn from(value: T) -> Self {
+		Self(value)
+	}
+}
+
+impl<T> ArkScale<T> {
+	/// Return the wrapped value.
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+/// Adapter writing to a SCALE [`Output`] through arkworks' [`Write`].
+struct OutputWriter<'a, O: Output + ?Sized>(&'a mut O);
+
+impl<O: Output + ?Sized> Write for OutputWriter<'_, O> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.0.write(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+/// Adapter reading from a SCALE [`Input`] through arkworks' [`Read`].
+///
+/// Every read fills the whole buffer or fails.
+struct InputReader<'a, I: Input>(&'a mut I);
+
+impl<I: Input> Read for InputReader<'_, I> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.0.read(buf).map_err(|_| ErrorKind::UnexpectedEof)?;
+		Ok(buf.len())
+	}
+}
+
+impl<T: CanonicalSerialize> Encode for ArkScale<T> {
+	fn size_hint(&self) -> usize {
+		self.0.compressed_size()
+	}
+
+	fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+		self.0
+			.serialize_compressed(OutputWriter(dest))
+			.expect("Writing to a SCALE output never fails; qed");
+	}
+}
+
+impl<T: CanonicalSerialize> EncodeLike for ArkScale<T> {}
+
+impl<T: CanonicalDeserialize> Decode for ArkScale<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		T::deserialize_compressed(InputReader(input))
+			.map(Self)
+			.map_err(|_| "Invalid arkworks encoding".into())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<T: CanonicalSerialize> serde::Serialize for ArkScale<T> {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		impl_serde::serialize::serialize(&self.encode(), serializer)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: CanonicalDeserialize> serde::Deserialize<'de> for ArkScale<T> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let bytes = impl_serde::serialize::deserialize(deserializer)?;
+		codec::DecodeAll::decode_all(&mut &bytes[..])
+			.map_err(|e: codec::Error| serde::de::Error::custom(e.to_string()))
+	}
+}
+
+/// Arkworks types whose compressed serialization has a fixed length.
+pub trait FixedEncodedLen {
+	/// The length of the compressed serialization in bytes.
+	const ENCODED_LEN: usize;
+}
+
+impl<T: CanonicalSerialize + FixedEncodedLen> MaxEncodedLen for ArkScale<T> {
+	fn max_encoded_len() -> usize {
+		T::ENCODED_LEN
+	}
+}
+
+impl<T: FixedEncodedLen + 'static> TypeInfo for ArkScale<T> {
+	type Identity = Self;
+
+	fn type_info() -> Type {
+		TypeDefArray::new(T::ENCODED_LEN as u32, MetaType::new::<u8>()).into()
+	}
+}
+
+/// Aliases for the wrapped BLS12-381 types.
+#[cfg(feature = "bls12-381")]
+pub mod bls12_381 {
+	use super::{ArkScale, FixedEncodedLen};
+	use ark_ec::short_weierstrass::Affine;
+
+	// Points are implemented on `Affine` of the curve configurations, as coherence does not
+	// see through the `G1Affine` and `G2Affine` aliases.
+	impl FixedEncodedLen for Affine<ark_bls12_381::g1::Config> {
+		const ENCODED_LEN: usize = 48;
+	}
+
+	impl FixedEncodedLen for Affine<ark_bls12_381::g2::Config> {
+		const ENCODED_LEN: usize = 96;
+	}
+
+	impl FixedEncodedLen for ark_bls12_381::Fr {
+		const ENCODED_LEN: usize = 32;
+	}
+
+	impl FixedEncodedLen for ark_bls12_381::Fq12 {
+		const ENCODED_LEN: usize = 576;
+	}
+
+	/// A point of the G1 group in affine form.
+	pub type G1Affine = ArkScale<ark_bls12_381::G1Affine>;
+	/// A point of the G2 group in affine form.
+	pub type G2Affine = ArkScale<ark_bls12_381::G2Affine>;
+	/// An element of the scalar field.
+	pub type Fr = ArkScale<ark_bls12_381::Fr>;
+	/// An element of the degree 12 extension field the pairing maps to.
+	pub type Fq12 = ArkScale<ark_bls12_381::Fq12>;
+	/// A Groth16 proof.
+	#[cfg(feature = "groth16")]
+	pub type Proof = ArkScale<ark_groth16::Proof<ark_bls12_381::Bls12_381>>;
+	/// A Groth16 verifying key.
+	#[cfg(feature = "groth16")]
+	pub type VerifyingKey = ArkScale<ark_groth16::VerifyingKey<ark_bls12_381::Bls12_381>>;
+	/// A Groth16 verifying key with at most `MaxPoints` points in `gamma_abc_g1`.
+	#[cfg(feature = "groth16")]
+	pub type BoundedVerifyingKey<MaxPoints> =
+		crate::groth16::BoundedVerifyingKey<ark_bls12_381::Bls12_381, MaxPoints>;
+}
+
+/// Aliases for the wrapped BN254 types.
+#[cfg(feature = "bn254")]
+pub mod bn254 {
+	use super::{ArkScale, FixedEncodedLen};
+	use ark_ec::short_weierstrass::Affine;
+
+	// See the comment in `bls12_381`.
+	impl FixedEncodedLen for Affine<ark_bn254::g1::Config> {
+		const ENCODED_LEN: usize = 32;
+	}
+
+	impl FixedEncodedLen for Affine<ark_bn254::g2::Config> {
+		const ENCODED_LEN: usize = 64;
+	}
+
+	impl FixedEncodedLen for ark_bn254::Fr {
+		const ENCODED_LEN: usize = 32;
+	}
+
+	impl FixedEncodedLen for ark_bn254::Fq12 {
+		const ENCODED_LEN: usize = 384;
+	}
+
+	/// A point of the G1 group in affine form.
+	pub type G1Affine = ArkScale<ark_bn254::G1Affine>;
+	/// A point of the G2 group in affine form.
+	pub type G2Affine = ArkScale<ark_bn254::G2Affine>;
+	/// An element of the scalar field.
+	pub type Fr = ArkScale<ark_bn254::Fr>;
+	/// An element of the degree 12 extension field the pairing maps to.
+	pub type Fq12 = ArkScale<ark_bn254::Fq12>;
+	/// A Groth16 proof.
+	#[cfg(feature = "groth16")]
+	pub type Proof = ArkScale<ark_groth16::Proof<ark_bn254::Bn254>>;
+	/// A Groth16 verifying key.
+	#[cfg(feature = "groth16")]
+	pub type VerifyingKey = ArkScale<ark_groth16::VerifyingKey<ark_bn254::Bn254>>;
+	/// A Groth16 verifying key with at most `MaxPoints` points in `gamma_abc_g1`.
+	#[cfg(feature = "groth16")]
+	pub type BoundedVerifyingKey<MaxPoints> =
+		crate::groth16::BoundedVerifyingKey<ark_bn254::Bn254, MaxPoints>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_std::{test_rng, UniformRand};
+
+	fn round_trip<T>(value: T, len: usize)
+	where
+		T: CanonicalSerialize + CanonicalDeserialize + PartialEq + core::fmt::Debug,
+	{
+		let wrapped = ArkScale(value);
+		let encoded = wrapped.encode();
+
+		let mut expected = Vec::new();
+		wrapped.0.serialize_compressed(&mut expected).unwrap();
+		assert_eq!(encoded, expected);
+		assert_eq!(encoded.len(), len);
+		assert_eq!(wrapped.size_hint(), len);
+		assert_eq!(ArkScale::<T>::decode(&mut &encoded[..]).unwrap(), wrapped);
+		assert!(ArkScale::<T>::decode(&mut &encoded[..len - 1]).is_err());
+	}
+
+	#[test]
+	fn bls12_381_types_round_trip() {
+		use ark_bls12_381::{Bls12_381, Fq12, Fr, G1Affine, G2Affine};
+
+		let rng = &mut test_rng();
+		round_trip(G1Affine::rand(rng), 48);
+		round_trip(G2Affine::rand(rng), 96);
+		round_trip(G1Affine::identity(), 48);
+		round_trip(Fr::rand(rng), 32);
+		round_trip(Fq12::rand(rng), 576);
+		round_trip(
+			ark_groth16::Proof::<Bls12_381> {
+				a: G1Affine::rand(rng),
+				b: G2Affine::rand(rng),
+				c: G1Affine::rand(rng),
+			},
+			192,
+		);
+		round_trip(
+			ark_groth16::VerifyingKey::<Bls12_381> {
+				alpha_g1: G1Affine::rand(rng),
+				beta_g2: G2Affine::rand(rng),
+				gamma_g2: G2Affine::rand(rng),
+				delta_g2: G2Affine::rand(rng),
+				gamma_abc_g1: vec![G1Affine::rand(rng); 3],
+			},
+			48 * 4 + 96 * 3 + 8,
+		);
+	}
+
+	#[test]
+	fn bn254_types_round_trip() {
+		use ark_bn254::{Bn254, Fq12, Fr, G1Affine, G2Affine};
+
+		let rng = &mut test_rng();
+		round_trip(G1Affine::rand(rng), 32);
+		round_trip(G2Affine::rand(rng), 64);
+		round_trip(Fr::rand(rng), 32);
+		round_trip(Fq12::rand(rng), 384);
+		round_trip(
+			ark_groth16::Proof::<Bn254> {
+				a: G1Affine::rand(rng),
+				b: G2Affine::rand(rng),
+				c: G1Affine::rand(rng),
+			},
+			128,
+		);
+	}
+
+	#[test]
+	fn fixed_encoded_len_matches_serialization() {
+		fn check<T: CanonicalSerialize + FixedEncodedLen + UniformRand + 'static>() {
+			let value = ArkScale(T::rand(&mut test_rng()));
+			assert_eq!(ArkScale::<T>::max_encoded_len(), value.encoded_size());
+			assert_eq!(
+				ArkScale::<T>::type_info().type_def,
+				TypeDefArray::new(value.encoded_size() as u32, MetaType::new::<u8>()).into(),
+			);
+		}
+
+		check::<ark_bls12_381::G1Affine>();
+		check::<ark_bls12_381::G2Affine>();
+		check::<ark_bls12_381::Fr>();
+		check::<ark_bls12_381::Fq12>();
+		check::<ark_bn254::G1Affine>();
+		check::<ark_bn254::G2Affine>();
+		check::<ark_bn254::Fr>();
+		check::<ark_bn254::Fq12>();
+		assert_eq!(bls12_381::Proof::max_encoded_len(), 192);
+		assert_eq!(bn254::Proof::max_encoded_len(), 128);
+	}
+
+	#[test]
+	fn bounded_verifying_keys_work() {
+		use ark_bls12_381::{Bls12_381, G1Affine, G2Affine};
+		use sp_core::ConstU32;
+
+		let rng = &mut test_rng();
+		let key = ark_groth16::VerifyingKey::<Bls12_381> {
+			alpha_g1: G1Affine::rand(rng),
+			beta_g2: G2Affine::rand(rng),
+			gamma_g2: G2Affine::rand(rng),
+			delta_g2: G2Affine::rand(rng),
+			gamma_abc_g1: vec![G1Affine::rand(rng); 3],
+		};
+
+		assert!(bls12_381::BoundedVerifyingKey::<ConstU32<2>>::try_from(key.clone()).is_err());
+		let bounded = bls12_381::BoundedVerifyingKey::<ConstU32<4>>::try_from(key.clone()).unwrap();
+		let encoded = bounded.encode();
+		assert_eq!(encoded.len(), 48 + 96 * 3 + 1 + 48 * 3);
+		assert_eq!(
+			bls12_381::BoundedVerifyingKey::<ConstU32<4>>::max_encoded_len(),
+			48 + 96 * 3 + 1 + 48 * 4,
+		);
+		assert_eq!(
+			bls12_381::BoundedVerifyingKey::<ConstU32<4>>::decode(&mut &encoded[..]).unwrap(),
+			bounded,
+		);
+		assert!(bls12_381::BoundedVerifyingKey::<ConstU32<2>>::decode(&mut &encoded[..]).is_err());
+		assert_eq!(ark_groth16::VerifyingKey::from(bounded), key);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_uses_hex_of_the_encoding() {
+		use ark_bls12_381::{Fr, G1Affine};
+
+		let point = ArkScale(G1Affine::rand(&mut test_rng()));
+		let json = serde_json::to_string(&point).unwrap();
+		assert_eq!(json, format!("\"{}\"", array_bytes::bytes2hex("0x", &point.encode())));
+		assert_eq!(serde_json::from_str::<ArkScale<G1Affine>>(&json).unwrap(), point);
+
+		// Trailing bytes and invalid encodings are rejected.
+		let trailing = format!("\"{}00\"", array_bytes::bytes2hex("0x", &point.encode()));
+		assert!(serde_json::from_str::<ArkScale<G1Affine>>(&trailing).is_err());
+		let invalid = format!("\"{}\"", array_bytes::bytes2hex("0x", &[0xff; 32]));
+		assert!(serde_json::from_str::<ArkScale<Fr>>(&invalid).is_err());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_of_bounded_verifying_keys_works() {
+		use ark_bn254::{Bn254, G1Affine, G2Affine};
+		use sp_core::ConstU32;
+
+		let rng = &mut test_rng();
+		let key = bn254::BoundedVerifyingKey::<ConstU32<2>>::try_from(ark_groth16::VerifyingKey::<
+			Bn254,
+		> {
+			alpha_g1: G1Affine::rand(rng),
+			beta_g2: G2Affine::rand(rng),
+			gamma_g2: G2Affine::rand(rng),
+			delta_g2: G2Affine::rand(rng),
+			gamma_abc_g1: vec![G1Affine::rand(rng); 2],
+		})
+		.unwrap();
+
+		let json = serde_json::to_value(&key).unwrap();
+		assert_eq!(json["alpha_g1"], array_bytes::bytes2hex("0x", &key.alpha_g1.encode()));
+		assert_eq!(json["gamma_abc_g1"].as_array().unwrap().len(), 2);
+		assert_eq!(
+			serde_json::from_value::<bn254::BoundedVerifyingKey<ConstU32<2>>>(json.clone())
+				.unwrap(),
+			key
+		);
+		assert!(serde_json::from_value::<bn254::BoundedVerifyingKey<ConstU32<1>>>(json).is_err());
+	}
+
+	#[test]
+	fn invalid_encodings_are_rejected() {
+		use ark_bls12_381::{Fr, G1Affine};
+
+		// An x coordinate for which no point exists.
+		let mut not_on_curve = [0u8; 48];
+		not_on_curve[0] = 0x80;
+		not_on_curve[47] = 7;
+		assert!(bls12_381::G1Affine::decode(&mut &not_on_curve[..]).is_err());
+
+		// A non-canonical scalar.
+		assert!(bls12_381::Fr::decode(&mut &[0xff; 32][..]).is_err());
+
+		let point = ArkScale(G1Affine::rand(&mut test_rng())).encode();
+		assert!(ArkScale::<G1Affine>::decode(&mut &point[..]).is_ok());
+		assert!(ArkScale::<Fr>::decode(&mut &[][..]).is_err());
+	}
+}
@@ -0,0 +1,339 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import of Groth16 verifying keys, proofs and public inputs exported by snarkjs.
+//!
+//! snarkjs writes `verification_key.json`, `proof.json` and `public.json` with all numbers as
+//! decimal strings. Points are given as projective coordinates `[x, y, z]`, where `z` is one for
+//! affine points and zero for the point at infinity, and elements of the quadratic extension
+//! as `[c0, c1]`. Numbers must be canonical, i.e. smaller than the modulus and without leading
+//! zeros, and points must be in the prime order subgroup.
+
+use crate::ArkScale;
+use ark_ec::{
+	pairing::Pairing,
+	short_weierstrass::{Affine, SWCurveConfig},
+};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_serialize::Valid;
+use serde::Deserialize;
+use std::{fmt::Display, str::FromStr};
+
+/// Errors importing snarkjs files.
+#[derive(Debug)]
+pub enum Error {
+	/// The input is not JSON of the expected shape.
+	Json(serde_json::Error),
+	/// The file is for a protocol other than Groth16.
+	UnsupportedProtocol(String),
+	/// The file is for a different curve.
+	UnexpectedCurve(String),
+	/// A number is not the canonical decimal representation of a field element.
+	InvalidNumber(String),
+	/// A point is not at infinity or in affine form, not on the curve or not in the prime
+	/// order subgroup.
+	InvalidPoint,
+	/// The number of `IC` points does not match the number of public inputs.
+	InvalidPublicInputCount,
+}
+
+impl From<serde_json::Error> for Error {
+	fn from(error: serde_json::Error) -> Self {
+		Self::Json(error)
+	}
+}
+
+/// A G1 point as `[x, y, z]`.
+type G1Json = [String; 3];
+/// A G2 point as `[x, y, z]` with each coordinate as `[c0, c1]`.
+type G2Json = [[String; 2]; 3];
+
+#[derive(Deserialize)]
+struct VerifyingKeyJson {
+	protocol: String,
+	curve: Option<String>,
+	#[serde(rename = "nPublic")]
+	n_public: usize,
+	vk_alpha_1: G1Json,
+	vk_beta_2: G2Json,
+	vk_gamma_2: G2Json,
+	vk_delta_2: G2Json,
+	#[serde(rename = "IC")]
+	ic: Vec<G1Json>,
+}
+
+#[derive(Deserialize)]
+struct ProofJson {
+	protocol: String,
+	curve: Option<String>,
+	pi_a: G1Json,
+	pi_b: G2Json,
+	pi_c: G1Json,
+}
+
+/// Pairing engines snarkjs can export files for.
+pub trait SnarkjsCurve: Pairing {
+	/// The name snarkjs uses for the curve.
+	const NAME: &'static str;
+
+	/// Read a G1 point.
+	fn g1(point: &G1Json) -> Result<Self::G1Affine, Error>;
+
+	/// Read a G2 point.
+	fn g2(point: &G2Json) -> Result<Self::G2Affine, Error>;
+}
+
+/// Read a field element from its canonical decimal representation.
+fn field<F: FromStr + Display>(number: &str) -> Result<F, Error> {
+	// Field elements display zero as the empty string.
+	match F::from_str(number) {
+		Ok(value) if number == "0" || value.to_string() == number => Ok(value),
+		_ => Err(Error::InvalidNumber(number.into())),
+	}
+}
+
+/// Build a point from its coordinates, where `z` holds the coefficients of the `z` coordinate.
+fn point<P: SWCurveConfig>(
+	x: P::BaseField,
+	y: P::BaseField,
+	z: &[String],
+) -> Result<Affine<P>, Error> {
+	let point = match z {
+		[z, rest @ ..] if rest.iter().all(|c| c == "0") && z == "0" => Affine::identity(),
+		[z, rest @ ..] if rest.iter().all(|c| c == "0") && z == "1" => Affine::new_unchecked(x, y),
+		_ => return Err(Error::InvalidPoint),
+	};
+	point.check().map_err(|_| Error::InvalidPoint)?;
+	Ok(point)
+}
+
+fn check_header<E: SnarkjsCurve>(protocol: String, curve: Option<String>) -> Result<(), Error> {
+	if protocol != "groth16" {
+		return Err(Error::UnsupportedProtocol(protocol))
+	}
+	match curve {
+		Some(curve) if curve != E::NAME => Err(Error::UnexpectedCurve(curve)),
+		_ => Ok(()),
+	}
+}
+
+/// Import a verifying key from the contents of `verification_key.json`.
+pub fn verifying_key_from_json<E: SnarkjsCurve>(
+	json: &str,
+) -> Result<ArkScale<VerifyingKey<E>>, Error> {
+	let key: VerifyingKeyJson = serde_json::from_str(json)?;
+	check_header::<E>(key.protocol, key.curve)?;
+	if key.ic.len() != key.n_public + 1 {
+		return Err(Error::InvalidPublicInputCount)
+	}
+
+	Ok(ArkScale(VerifyingKey {
+		alpha_g1: E::g1(&key.vk_alpha_1)?,
+		beta_g2: E::g2(&key.vk_beta_2)?,
+		gamma_g2: E::g2(&key.vk_gamma_2)?,
+		delta_g2: E::g2(&key.vk_delta_2)?,
+		gamma_abc_g1: key.ic.iter().map(E::g1).collect::<Result<_, _>>()?,
+	}))
+}
+
+/// Import a proof from the contents of `proof.json`.
+pub fn proof_from_json<E: SnarkjsCurve>(json: &str) -> Result<ArkScale<Proof<E>>, Error> {
+	let proof: ProofJson = serde_json::from_str(json)?;
+	check_header::<E>(proof.protocol, proof.curve)?;
+
+	Ok(ArkScale(Proof { a: E::g1(&proof.pi_a)?, b: E::g2(&proof.pi_b)?, c: E::g1(&proof.pi_c)? }))
+}
+
+/// Import the public inputs from the contents of `public.json`.
+pub fn public_inputs_from_json<E: SnarkjsCurve>(
+	json: &str,
+) -> Result<Vec<ArkScale<E::ScalarField>>, Error> {
+	let inputs: Vec<String> = serde_json::from_str(json)?;
+	inputs.iter().map(|input| field(input).map(ArkScale)).collect()
+}
+
+#[cfg(feature = "bls12-381")]
+impl SnarkjsCurve for ark_bls12_381::Bls12_381 {
+	const NAME: &'static str = "bls12381";
+
+	fn g1([x, y, z]: &G1Json) -> Result<Self::G1Affine, Error> {
+		point(field(x)?, field(y)?, std::slice::from_ref(z))
+	}
+
+	fn g2([x, y, z]: &G2Json) -> Result<Self::G2Affine, Error> {
+		let fq2 = |[c0, c1]: &[String; 2]| -> Result<_, Error> {
+			Ok(ark_bls12_381::Fq2::new(field(c0)?, field(c1)?))
+		};
+		point(fq2(x)?, fq2(y)?, z)
+	}
+}
+
+#[cfg(feature = "bn254")]
+impl SnarkjsCurve for ark_bn254::Bn254 {
+	const NAME: &'static str = "bn128";
+
+	fn g1([x, y, z]: &G1Json) -> Result<Self::G1Affine, Error> {
+		point(field(x)?, field(y)?, std::slice::from_ref(z))
+	}
+
+	fn g2([x, y, z]: &G2Json) -> Result<Self::G2Affine, Error> {
+		let fq2 = |[c0, c1]: &[String; 2]| -> Result<_, Error> {
+			Ok(ark_bn254::Fq2::new(field(c0)?, field(c1)?))
+		};
+		point(fq2(x)?, fq2(y)?, z)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+	use ark_ec::AffineRepr;
+	use ark_groth16::Groth16;
+	use ark_std::{test_rng, UniformRand};
+
+	const G1_GENERATOR: &str = r#"["1", "2", "1"]"#;
+	const G2_GENERATOR: &str = r#"[
+		[
+			"10857046999023057135944570762232829481370756359578518086990519993285655852781",
+			"11559732032986387107991004021392285783925812861821192530917403151452391805634"
+		],
+		[
+			"8495653923123431417604973247489272438418190587263600148770280649306958101930",
+			"4082367875863433681332203403145435568316851327593401208105741076214120093531"
+		],
+		["1", "0"]
+	]"#;
+	const G1_INFINITY: &str = r#"["0", "1", "0"]"#;
+
+	fn g1_json(point: &G1Affine) -> String {
+		format!(r#"["{}", "{}", "1"]"#, point.x, point.y)
+	}
+
+	fn g2_json(point: &G2Affine) -> String {
+		format!(
+			r#"[["{}", "{}"], ["{}", "{}"], ["1", "0"]]"#,
+			point.x.c0, point.x.c1, point.y.c0, point.y.c1
+		)
+	}
+
+	fn verifying_key_json(curve: &str, n_public: usize, ic: &[&str]) -> String {
+		format!(
+			r#"{{
+				"protocol": "groth16",
+				"curve": "{curve}",
+				"nPublic": {n_public},
+				"vk_alpha_1": {G1_GENERATOR},
+				"vk_beta_2": {G2_GENERATOR},
+				"vk_gamma_2": {G2_GENERATOR},
+				"vk_delta_2": {G2_GENERATOR},
+				"vk_alphabeta_12": [],
+				"IC": [{}]
+			}}"#,
+			ic.join(", "),
+		)
+	}
+
+	#[test]
+	fn exported_proofs_verify() {
+		// Exported by snarkjs for a circuit proving the knowledge of a factorization of 33.
+		let key = verifying_key_from_json::<Bn254>(include_str!(
+			"../fixtures/snarkjs/verification_key.json"
+		))
+		.unwrap();
+		let proof =
+			proof_from_json::<Bn254>(include_str!("../fixtures/snarkjs/proof.json")).unwrap();
+		let inputs =
+			public_inputs_from_json::<Bn254>(include_str!("../fixtures/snarkjs/public.json"))
+				.unwrap();
+		assert_eq!(inputs, vec![ArkScale(Fr::from(33u8))]);
+
+		let key = ark_groth16::prepare_verifying_key(&key.0);
+		let verify =
+			|inputs: &[Fr]| Groth16::<Bn254>::verify_proof(&key, &proof.0, inputs).unwrap();
+		assert!(verify(&[Fr::from(33u8)]));
+		assert!(!verify(&[Fr::from(34u8)]));
+	}
+
+	#[test]
+	fn verifying_keys_are_imported() {
+		let json = verifying_key_json("bn128", 1, &[G1_GENERATOR, G1_INFINITY]);
+		let key = verifying_key_from_json::<Bn254>(&json).unwrap().0;
+		assert_eq!(key.alpha_g1, G1Affine::generator());
+		assert_eq!(key.beta_g2, G2Affine::generator());
+		assert_eq!(key.gamma_g2, G2Affine::generator());
+		assert_eq!(key.delta_g2, G2Affine::generator());
+		assert_eq!(key.gamma_abc_g1, vec![G1Affine::generator(), G1Affine::zero()]);
+
+		let json = verifying_key_json("bn128", 2, &[G1_GENERATOR, G1_INFINITY]);
+		assert!(matches!(
+			verifying_key_from_json::<Bn254>(&json),
+			Err(Error::InvalidPublicInputCount)
+		));
+		let json = verifying_key_json("bls12381", 1, &[G1_GENERATOR, G1_INFINITY]);
+		assert!(matches!(verifying_key_from_json::<Bn254>(&json), Err(Error::UnexpectedCurve(_))));
+		assert!(matches!(verifying_key_from_json::<Bn254>("{}"), Err(Error::Json(_))));
+	}
+
+	#[test]
+	fn proofs_are_imported() {
+		let rng = &mut test_rng();
+		let proof = Proof::<Bn254> {
+			a: G1Affine::rand(rng),
+			b: G2Affine::rand(rng),
+			c: G1Affine::rand(rng),
+		};
+		let json = format!(
+			r#"{{"pi_a": {}, "pi_b": {}, "pi_c": {}, "protocol": "groth16", "curve": "bn128"}}"#,
+			g1_json(&proof.a),
+			g2_json(&proof.b),
+			g1_json(&proof.c),
+		);
+		assert_eq!(proof_from_json::<Bn254>(&json).unwrap().0, proof);
+
+		let plonk = json.replace("groth16", "plonk");
+		assert!(matches!(proof_from_json::<Bn254>(&plonk), Err(Error::UnsupportedProtocol(_))));
+		// `(1, 3)` is not on the curve.
+		let off_curve = json.replace(&g1_json(&proof.a), r#"["1", "3", "1"]"#);
+		assert!(matches!(proof_from_json::<Bn254>(&off_curve), Err(Error::InvalidPoint)));
+		let projective = json.replace(&g1_json(&proof.a), r#"["1", "2", "2"]"#);
+		assert!(matches!(proof_from_json::<Bn254>(&projective), Err(Error::InvalidPoint)));
+	}
+
+	#[test]
+	fn public_inputs_are_imported() {
+		let input = Fr::rand(&mut test_rng());
+		let json = format!(r#"["0", "1", "{}"]"#, input);
+		assert_eq!(
+			public_inputs_from_json::<Bn254>(&json).unwrap(),
+			vec![ArkScale(Fr::from(0u8)), ArkScale(Fr::from(1u8)), ArkScale(input)],
+		);
+
+		// The modulus and numbers with leading zeros are not canonical.
+		let modulus =
+			r#"["21888242871839275222246405745257275088548364400416034343698204186575808495617"]"#;
+		assert!(matches!(public_inputs_from_json::<Bn254>(modulus), Err(Error::InvalidNumber(_))));
+		assert!(matches!(
+			public_inputs_from_json::<Bn254>(r#"["01"]"#),
+			Err(Error::InvalidNumber(_))
+		));
+		assert!(matches!(
+			public_inputs_from_json::<Bn254>(r#"["-1"]"#),
+			Err(Error::InvalidNumber(_))
+		));
+	}
+}
@@ -0,0 +1,186 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of BLS12-381 points from and to the zcash encoding.
+//!
+//! The zcash encoding, also used by blst and the IETF BLS signature drafts, stores the big-endian
+//! coordinates of a point with three flags in the most significant bits of the first byte: the
+//! encoding is compressed, the point is the point at infinity and, for compressed points, `y` is
+//! the lexicographically largest of the two candidates for `x`. G1 points take 48 bytes
+//! compressed and 96 bytes uncompressed, G2 points twice as much.
+//!
+//! arkworks uses the same layout, but accepts some encodings the zcash specification rejects,
+//! e.g. the point at infinity followed by non-zero bytes, and does not check that uncompressed
+//! points lie on the curve. [`ZcashEncoding::from_zcash_bytes`] only accepts canonical encodings
+//! of points in the prime order subgroup, and detects the compression from the flags.
+
+use ark_ec::{short_weierstrass::Affine, AffineRepr};
+use ark_serialize::{Compress, SerializationError, Validate};
+use sp_std::vec::Vec;
+
+/// The flag set in compressed encodings.
+pub(crate) const COMPRESSION_FLAG: u8 = 1 << 7;
+/// The flag set in encodings of the point at infinity.
+pub(crate) const INFINITY_FLAG: u8 = 1 << 6;
+/// The flag set in compressed encodings if `y` is lexicographically largest.
+pub(crate) const SORT_FLAG: u8 = 1 << 5;
+
+/// BLS12-381 points convertible from and to the zcash encoding.
+pub trait ZcashEncoding: AffineRepr {
+	/// The length of the compressed encoding in bytes.
+	const COMPRESSED_LEN: usize;
+
+	/// Encode the point, compressed or not depending on `compress`.
+	fn to_zcash_bytes(&self, compress: Compress) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(self.serialized_size(compress));
+		self.serialize_with_mode(&mut bytes, compress)
+			.expect("Writing to a vector never fails; qed");
+		bytes
+	}
+
+	/// Decode a compressed or uncompressed point.
+	///
+	/// Fails if `bytes` is not the canonical encoding of a point in the prime order subgroup.
+	fn from_zcash_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+		let flags = *bytes.first().ok_or(SerializationError::InvalidData)?;
+		let compress = if flags & COMPRESSION_FLAG != 0 { Compress::Yes } else { Compress::No };
+		let len = match compress {
+			Compress::Yes => Self::COMPRESSED_LEN,
+			Compress::No => 2 * Self::COMPRESSED_LEN,
+		};
+		if bytes.len() != len {
+			return Err(SerializationError::InvalidData)
+		}
+
+		if flags & INFINITY_FLAG != 0 {
+			// All other bits of the point at infinity are zero.
+			if flags & !(COMPRESSION_FLAG | INFINITY_FLAG) != 0 ||
+				bytes[1..].iter().any(|b| *b != 0)
+			{
+				return Err(SerializationError::UnexpectedFlags)
+			}
+			return Ok(Self::zero())
+		}
+		if compress == Compress::No && flags & SORT_FLAG != 0 {
+			return Err(SerializationError::UnexpectedFlags)
+		}
+
+		let point = Self::deserialize_with_mode(bytes, compress, Validate::No)?;
+		point.check()?;
+		Ok(point)
+	}
+}
+
+// See the comment in `bls12_381` on why these are implemented on `Affine`.
+impl ZcashEncoding for Affine<ark_bls12_381::g1::Config> {
+	const COMPRESSED_LEN: usize = 48;
+}
+
+impl ZcashEncoding for Affine<ark_bls12_381::g2::Config> {
+	const COMPRESSED_LEN: usize = 96;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_bls12_381::{G1Affine, G2Affine};
+	use ark_std::{test_rng, UniformRand};
+
+	/// The compressed zcash encodings of the generators.
+	const G1_GENERATOR: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+	const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+	fn round_trip<P: ZcashEncoding + UniformRand>() {
+		let point = P::rand(&mut test_rng());
+		for (compress, len) in
+			[(Compress::Yes, P::COMPRESSED_LEN), (Compress::No, 2 * P::COMPRESSED_LEN)]
+		{
+			let bytes = point.to_zcash_bytes(compress);
+			assert_eq!(bytes.len(), len);
+			assert_eq!(P::from_zcash_bytes(&bytes).unwrap(), point);
+			assert!(P::from_zcash_bytes(&bytes[..len - 1]).is_err());
+		}
+	}
+
+	#[test]
+	fn points_round_trip() {
+		round_trip::<G1Affine>();
+		round_trip::<G2Affine>();
+	}
+
+	#[test]
+	fn generators_match_zcash() {
+		let g1 = array_bytes::hex2bytes_unchecked(G1_GENERATOR);
+		assert_eq!(G1Affine::generator().to_zcash_bytes(Compress::Yes), g1);
+		assert_eq!(G1Affine::from_zcash_bytes(&g1).unwrap(), G1Affine::generator());
+
+		let g2 = array_bytes::hex2bytes_unchecked(G2_GENERATOR);
+		assert_eq!(G2Affine::generator().to_zcash_bytes(Compress::Yes), g2);
+		assert_eq!(G2Affine::from_zcash_bytes(&g2).unwrap(), G2Affine::generator());
+	}
+
+	#[test]
+	fn sign_flag_selects_y() {
+		let g1 = G1Affine::generator().to_zcash_bytes(Compress::Yes);
+		let negated = (-G1Affine::generator()).to_zcash_bytes(Compress::Yes);
+		assert_eq!(g1[0] & SORT_FLAG, 0);
+		assert_eq!(negated[0], g1[0] | SORT_FLAG);
+		assert_eq!(negated[1..], g1[1..]);
+		assert_eq!(G1Affine::from_zcash_bytes(&negated).unwrap(), -G1Affine::generator());
+
+		// Uncompressed encodings carry `y` and must not set the flag.
+		let mut uncompressed = G1Affine::generator().to_zcash_bytes(Compress::No);
+		assert_eq!(uncompressed[0] & (COMPRESSION_FLAG | SORT_FLAG), 0);
+		uncompressed[0] |= SORT_FLAG;
+		assert!(G1Affine::from_zcash_bytes(&uncompressed).is_err());
+	}
+
+	#[test]
+	fn infinity_is_canonical() {
+		let mut compressed = vec![0; 48];
+		compressed[0] = COMPRESSION_FLAG | INFINITY_FLAG;
+		assert_eq!(G1Affine::zero().to_zcash_bytes(Compress::Yes), compressed);
+		assert_eq!(G1Affine::from_zcash_bytes(&compressed).unwrap(), G1Affine::zero());
+
+		let mut uncompressed = vec![0; 192];
+		uncompressed[0] = INFINITY_FLAG;
+		assert_eq!(G2Affine::zero().to_zcash_bytes(Compress::No), uncompressed);
+		assert_eq!(G2Affine::from_zcash_bytes(&uncompressed).unwrap(), G2Affine::zero());
+
+		// Accepted by arkworks, but not canonical.
+		let mut trailing = compressed.clone();
+		trailing[47] = 1;
+		assert!(G1Affine::from_zcash_bytes(&trailing).is_err());
+		let mut signed = compressed;
+		signed[0] |= SORT_FLAG;
+		assert!(G1Affine::from_zcash_bytes(&signed).is_err());
+	}
+
+	#[test]
+	fn points_off_the_curve_are_rejected() {
+		let mut bytes = G1Affine::generator().to_zcash_bytes(Compress::No);
+		bytes[95] ^= 1;
+		assert!(G1Affine::from_zcash_bytes(&bytes).is_err());
+
+		// An x coordinate for which no point exists.
+		let mut bytes = vec![0; 48];
+		bytes[0] = COMPRESSION_FLAG;
+		bytes[47] = 7;
+		assert!(G1Affine::from_zcash_bytes(&bytes).is_err());
+		assert!(G1Affine::from_zcash_bytes(&[]).is_err());
+	}
+}
@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bandersnatch VRF types.
+//!
+//! Bandersnatch is an Edwards curve defined over the scalar field of BLS12-381. Its points are
+//! compressed to 32 bytes, and its Pedersen and ring VRFs are verified with the `bandersnatch_*`
+//! host functions of `sp-io`.
+
+use crate::RuntimeDebug;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime_interface::pass_by::PassByCodec;
+
+/// The length of a compressed point.
+pub const POINT_LEN: usize = 32;
+/// The length of a Pedersen VRF proof.
+pub const PEDERSEN_PROOF_LEN: usize = 160;
+/// The length of a ring VRF proof.
+pub const RING_PROOF_LEN: usize = 752;
+/// The length of the verifier key of a ring.
+pub const RING_VERIFIER_KEY_LEN: usize = 384;
+
+/// A public key.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub struct Public(pub [u8; POINT_LEN]);
+
+/// The output point of a VRF, whose hash is the randomness.
+#[derive(
+	Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug, PassByCodec,
+)]
+pub struct VrfOutput(pub [u8; POINT_LEN]);
+
+/// A Pedersen VRF proof, which proves that an output is by the key of a blinded commitment.
+#[derive(
+	Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug, PassByCodec,
+)]
+pub struct PedersenVrfProof(pub [u8; PEDERSEN_PROOF_LEN]);
+
+/// A ring VRF proof, which proves that an output is by one of the keys of a ring without
+/// revealing which.
+#[derive(
+	Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug, PassByCodec,
+)]
+pub struct RingVrfProof(pub [u8; RING_PROOF_LEN]);
+
+/// A commitment to a ring of public keys.
+///
+/// Holds the number of keys and the verifier key of the ring, which includes the KZG verifying
+/// key of the SRS the commitment was computed with.
+#[derive(
+	Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug, PassByCodec,
+)]
+pub struct RingCommitment {
+	/// The number of keys in the ring.
+	pub ring_size: u32,
+	/// The compressed verifier key of the ring.
+	pub verifier_key: [u8; RING_VERIFIER_KEY_LEN],
+}
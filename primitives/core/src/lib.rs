@@ -51,6 +51,7 @@ pub mod hashing;
 
 #[cfg(feature = "full_crypto")]
 pub use hashing::{blake2_128, blake2_256, keccak_256, twox_128, twox_256, twox_64};
+pub mod bandersnatch;
 pub mod bounded;
 pub mod crypto;
 pub mod hexdisplay;
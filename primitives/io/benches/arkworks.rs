@@ -0,0 +1,302 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the native implementations of the arkworks-based host functions.
+//!
+//! Points are compressed, the only encoding the host functions accept. Signatures and proofs
+//! that would be expensive to produce for every input size are replaced by points that do not
+//! verify, which costs as much as valid ones since all pairings are computed either way.
+
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::Groth16;
+use ark_relations::{
+	lc,
+	r1cs::{ConstraintSynthesizer, ConstraintSystemRef},
+};
+use ark_serialize::CanonicalSerialize;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sp_core::bandersnatch;
+use sp_io::{crypto, hashing, ScalarField};
+
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+fn compressed<const N: usize>(point: impl CanonicalSerialize) -> [u8; N] {
+	let mut bytes = [0; N];
+	point.serialize_compressed(&mut bytes[..]).unwrap();
+	bytes
+}
+
+fn scalar(scalar: Fr) -> [u8; 32] {
+	scalar.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn g1(secret: u64) -> [u8; 48] {
+	compressed(G1Affine::generator() * Fr::from(secret))
+}
+
+fn g2(secret: u64) -> [u8; 96] {
+	compressed(G2Affine::generator() * Fr::from(secret))
+}
+
+/// A proof of knowledge of `a` and `b` with `a · b = c` for the public input `c`.
+#[derive(Clone)]
+struct MultiplierCircuit(Fr, Fr);
+
+impl ConstraintSynthesizer<Fr> for MultiplierCircuit {
+	fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> ark_relations::r1cs::Result<()> {
+		let a = cs.new_witness_variable(|| Ok(self.0))?;
+		let b = cs.new_witness_variable(|| Ok(self.1))?;
+		let c = cs.new_input_variable(|| Ok(self.0 * self.1))?;
+		cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + c)
+	}
+}
+
+fn bench_groth16(c: &mut Criterion) {
+	let mut rng = ark_std::test_rng();
+	let circuit = MultiplierCircuit(Fr::from(3u8), Fr::from(11u8));
+	let proving_key =
+		Groth16::<Bls12_381>::generate_random_parameters_with_reduction(circuit.clone(), &mut rng)
+			.unwrap();
+	let proof =
+		Groth16::<Bls12_381>::create_random_proof_with_reduction(circuit, &proving_key, &mut rng)
+			.unwrap();
+	let mut verifying_key = Vec::new();
+	proving_key.vk.serialize_compressed(&mut verifying_key).unwrap();
+	let proof: [u8; 192] = compressed(proof);
+	let prepared_verifying_key =
+		crypto::bls12_381_groth16_prepare_verifying_key(&verifying_key).unwrap();
+	let inputs = vec![scalar(Fr::from(33u8))];
+
+	let mut group = c.benchmark_group("bls12_381_groth16");
+	group.bench_function("verify", |b| {
+		b.iter(|| {
+			crypto::bls12_381_groth16_verify(&verifying_key, &proof, black_box(inputs.clone()))
+		})
+	});
+	group.bench_function("prepare_verifying_key", |b| {
+		b.iter(|| crypto::bls12_381_groth16_prepare_verifying_key(black_box(&verifying_key)))
+	});
+	group.bench_function("verify_prepared", |b| {
+		b.iter(|| {
+			crypto::bls12_381_groth16_verify_prepared(
+				&prepared_verifying_key,
+				&proof,
+				black_box(inputs.clone()),
+			)
+		})
+	});
+	group.finish();
+}
+
+fn bench_kzg(c: &mut Criterion) {
+	let mut group = c.benchmark_group("bls12_381_kzg");
+	group.bench_function("verify", |b| {
+		b.iter(|| {
+			crypto::bls12_381_kzg_verify(&g1(2), &[0; 32], &[0; 32], black_box(&g1(3)), &g2(7))
+		})
+	});
+	for n in [1, 16, 128] {
+		group.bench_with_input(BenchmarkId::new("batch_verify", n), &n, |b, &n| {
+			b.iter(|| {
+				crypto::bls12_381_kzg_batch_verify(
+					vec![g1(2); n],
+					vec![[0; 32]; n],
+					vec![[0; 32]; n],
+					vec![g1(3); n],
+					&g2(7),
+					black_box(&[0; 32]),
+				)
+			})
+		});
+	}
+	// The constant polynomial one commits to the generator, and its opening proofs are the
+	// point at infinity.
+	let blob = scalar(Fr::from(1u8)).repeat(4096);
+	group.bench_function("eip4844_verify_blob_kzg_proof", |b| {
+		b.iter(|| {
+			crypto::bls12_381_eip4844_verify_blob_kzg_proof(
+				black_box(&blob),
+				&g1(1),
+				&compressed(G1Affine::zero()),
+			)
+		})
+	});
+	group.finish();
+}
+
+fn bench_signatures(c: &mut Criterion) {
+	let mut group = c.benchmark_group("bls12_381_signatures");
+	group.bench_function("verify", |b| {
+		b.iter(|| crypto::bls12_381_verify(&g1(2), black_box(b"message"), &g2(3), DST))
+	});
+	for n in [1, 16, 128, 1024] {
+		let public_keys = (1..=n as u64).map(g1).collect::<Vec<_>>();
+		let messages = (0..n as u32).map(|i| i.to_be_bytes().to_vec()).collect::<Vec<_>>();
+		group.bench_with_input(BenchmarkId::new("aggregate_verify", n), &n, |b, _| {
+			b.iter(|| {
+				crypto::bls12_381_aggregate_verify(
+					public_keys.clone(),
+					messages.clone(),
+					black_box(&g2(3)),
+					DST,
+				)
+			})
+		});
+		group.bench_with_input(BenchmarkId::new("fast_aggregate_verify", n), &n, |b, _| {
+			b.iter(|| {
+				crypto::bls12_381_fast_aggregate_verify(
+					public_keys.clone(),
+					b"message",
+					black_box(&g2(3)),
+					DST,
+				)
+			})
+		});
+		group.bench_with_input(BenchmarkId::new("aggregate_g1", n), &n, |b, _| {
+			b.iter(|| crypto::bls12_381_aggregate_g1(black_box(public_keys.clone())))
+		});
+		let signatures = (1..=n as u64).map(g2).collect::<Vec<_>>();
+		group.bench_with_input(BenchmarkId::new("threshold_combine", n), &n, |b, &n| {
+			b.iter(|| {
+				crypto::bls12_381_threshold_combine(
+					(1..=n as u32).collect(),
+					black_box(signatures.clone()),
+				)
+			})
+		});
+	}
+	group.finish();
+}
+
+fn bench_powers_of_tau(c: &mut Criterion) {
+	let mut group = c.benchmark_group("bls12_381_powers_of_tau");
+	group.sample_size(10);
+	// The contribution of the secret one to the initial accumulator, which costs as much as any.
+	for n in [16, 256, 4096] {
+		group.bench_with_input(BenchmarkId::new("verify", n), &n, |b, &n| {
+			b.iter(|| {
+				crypto::bls12_381_powers_of_tau_verify(
+					&g1(1),
+					black_box(vec![g1(1); n]),
+					vec![g2(1); 2],
+					&g2(1),
+				)
+			})
+		});
+	}
+	group.finish();
+}
+
+fn bench_bandersnatch(c: &mut Criterion) {
+	use ark_vrf::{
+		pedersen::Prover as PedersenProver,
+		reexports::ark_serialize::CanonicalSerialize,
+		ring::Prover as RingProver,
+		suites::bandersnatch::{Input, RingProofParams, Secret},
+	};
+
+	fn encode<const N: usize>(value: impl CanonicalSerialize) -> [u8; N] {
+		let mut bytes = [0; N];
+		value.serialize_compressed(&mut bytes[..]).unwrap();
+		bytes
+	}
+
+	let secret = Secret::from_seed(b"secret");
+	let input = Input::new(b"input").unwrap();
+	let output = secret.output(input);
+	let vrf_output = bandersnatch::VrfOutput(encode(output));
+	let (proof, _) = PedersenProver::prove(&secret, input, output, b"data");
+	let proof = bandersnatch::PedersenVrfProof(encode(proof));
+
+	let mut group = c.benchmark_group("bandersnatch");
+	group.sample_size(10);
+	group.bench_function("pedersen_vrf_verify", |b| {
+		b.iter(|| {
+			crypto::bandersnatch_pedersen_vrf_verify(
+				black_box(b"input"),
+				b"data",
+				&vrf_output,
+				&proof,
+			)
+		})
+	});
+	for n in [4, 64, 1024] {
+		let params = RingProofParams::from_seed(n, [0; 32]);
+		let mut srs = Vec::new();
+		params.pcs.serialize_compressed(&mut srs).unwrap();
+		let secrets =
+			(0..n as u32).map(|i| Secret::from_seed(&i.to_be_bytes())).collect::<Vec<_>>();
+		let ring = secrets.iter().map(|secret| secret.public().0).collect::<Vec<_>>();
+		let public_keys = ring
+			.iter()
+			.map(|public_key| bandersnatch::Public(encode(*public_key)))
+			.collect::<Vec<_>>();
+		group.bench_with_input(BenchmarkId::new("ring_commitment", n), &n, |b, _| {
+			b.iter(|| crypto::bandersnatch_ring_commitment(&srs, black_box(public_keys.clone())))
+		});
+
+		let commitment = crypto::bandersnatch_ring_commitment(&srs, public_keys).unwrap();
+		let output = secrets[0].output(input);
+		let vrf_output = bandersnatch::VrfOutput(encode(output));
+		let prover = params.prover(params.prover_key(&ring), 0);
+		let proof = bandersnatch::RingVrfProof(encode(RingProver::prove(
+			&secrets[0],
+			input,
+			output,
+			b"data",
+			&prover,
+		)));
+		group.bench_with_input(BenchmarkId::new("ring_vrf_verify", n), &n, |b, _| {
+			b.iter(|| {
+				crypto::bandersnatch_ring_vrf_verify(
+					&commitment,
+					black_box(b"input"),
+					b"data",
+					&vrf_output,
+					&proof,
+				)
+			})
+		});
+	}
+	group.finish();
+}
+
+fn bench_mimc_sponge(c: &mut Criterion) {
+	let mut group = c.benchmark_group("mimc_sponge");
+	for n in [2, 16, 256] {
+		for (name, field) in [("bn254", ScalarField::Bn254), ("bls12_381", ScalarField::Bls12_381)]
+		{
+			group.bench_with_input(BenchmarkId::new(name, n), &n, |b, &n| {
+				b.iter(|| hashing::mimc_sponge(field, black_box(vec![[0; 32]; n]), &[0; 32], 1))
+			});
+		}
+	}
+	group.finish();
+}
+
+criterion_group!(
+	benches,
+	bench_groth16,
+	bench_kzg,
+	bench_signatures,
+	bench_powers_of_tau,
+	bench_bandersnatch,
+	bench_mimc_sponge,
+);
+criterion_main!(benches);
@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bandersnatch ECVRF, Pedersen VRF and ring VRF verification.
+//!
+//! Points are compressed in the arkworks encoding. The Pedersen and ring VRFs use the twisted
+//! Edwards form, hashing inputs to the curve with Elligator 2 and outputs with SHA-512, as in the
+//! `Bandersnatch_SHA-512_ELL2` suite of ark-vrf. The ECVRF takes the suite as a parameter.
+
+use crate::{EcvrfSuite, BANDERSNATCH_MAX_RING_SIZE};
+use ark_vrf::{
+	ietf::{self, IetfSuite},
+	pedersen,
+	reexports::ark_serialize::{CanonicalDeserialize, CanonicalSerialize},
+	ring,
+	suites::{
+		bandersnatch::{
+			BandersnatchSha512Ell2, Input, Output, PcsParams, PedersenProof, Public, RingProof,
+			RingProofParams, RingVerifierKey,
+		},
+		bandersnatch_sw::BandersnatchSha512Tai,
+	},
+};
+use sp_core::bandersnatch::{
+	PedersenVrfProof, RingCommitment, RingVrfProof, VrfOutput, RING_VERIFIER_KEY_LEN,
+};
+
+/// The length of a compressed BLS12-381 G1 point.
+const G1_LEN: usize = 48;
+/// The length of a compressed BLS12-381 G2 point.
+const G2_LEN: usize = 96;
+/// The number of powers of tau in G1 needed for rings of [`BANDERSNATCH_MAX_RING_SIZE`] keys.
+const MAX_SRS_G1_POWERS: usize = 3 * 2048 + 1;
+
+/// Check the vector lengths of the KZG setup `srs` against its length, before they are used to
+/// allocate memory.
+fn check_srs_len(srs: &[u8]) -> Option<()> {
+	let read_len = |offset: usize| -> Option<usize> {
+		let len = u64::from_le_bytes(srs.get(offset..offset.checked_add(8)?)?.try_into().ok()?);
+		len.try_into().ok()
+	};
+	let g1_powers = read_len(0)?;
+	if g1_powers > MAX_SRS_G1_POWERS {
+		return None
+	}
+	let g2_offset = 8 + g1_powers * G1_LEN;
+	let g2_powers = read_len(g2_offset)?;
+	let len = g2_powers.checked_mul(G2_LEN)?.checked_add(g2_offset + 8)?;
+	(len == srs.len()).then_some(())
+}
+
+/// Hash `input` to the curve and read `output`, returning both with the hash of the output.
+fn read_io(input: &[u8], output: &VrfOutput) -> Option<(Input, Output, [u8; 64])> {
+	let input = Input::new(input)?;
+	let output = Output::deserialize_compressed(&output.0[..]).ok()?;
+	let hash = output.hash().into();
+	Some((input, output, hash))
+}
+
+/// Read `bytes` as a whole, failing if they are longer than the encoding.
+fn read_exact<T: CanonicalSerialize + CanonicalDeserialize>(bytes: &[u8]) -> Option<T> {
+	T::deserialize_compressed(bytes)
+		.ok()
+		.filter(|value| value.compressed_size() == bytes.len())
+}
+
+/// Verify the RFC 9381 ECVRF `proof` of the suite `S`.
+fn ietf_verify<S: IetfSuite>(
+	public_key: &[u8],
+	input: &[u8],
+	aux_data: &[u8],
+	output: &[u8],
+	proof: &[u8],
+) -> Option<[u8; 64]> {
+	use ietf::Verifier;
+
+	let public_key = read_exact::<ark_vrf::Public<S>>(public_key)?;
+	let input = ark_vrf::Input::<S>::new(input)?;
+	let output = read_exact::<ark_vrf::Output<S>>(output)?;
+	let proof = read_exact::<ietf::Proof<S>>(proof)?;
+	public_key.verify(input, output, aux_data, &proof).ok()?;
+	output.hash().as_slice().try_into().ok()
+}
+
+/// Verify the RFC 9381 ECVRF `proof` that `output` is the VRF output of `input` by
+/// `public_key`, and return its hash.
+pub fn ecvrf_verify(
+	suite: EcvrfSuite,
+	public_key: &[u8],
+	input: &[u8],
+	aux_data: &[u8],
+	output: &[u8],
+	proof: &[u8; 64],
+) -> Option<[u8; 64]> {
+	match suite {
+		EcvrfSuite::BandersnatchSha512Ell2 =>
+			ietf_verify::<BandersnatchSha512Ell2>(public_key, input, aux_data, output, proof),
+		EcvrfSuite::BandersnatchSwSha512Tai =>
+			ietf_verify::<BandersnatchSha512Tai>(public_key, input, aux_data, output, proof),
+	}
+}
+
+/// Verify that `output` is the VRF output of `input` by the key committed to in `proof`, and
+/// return its hash.
+pub fn pedersen_verify(
+	input: &[u8],
+	aux_data: &[u8],
+	output: &VrfOutput,
+	proof: &PedersenVrfProof,
+) -> Option<[u8; 64]> {
+	use pedersen::Verifier;
+
+	let (input, output, hash) = read_io(input, output)?;
+	let proof = PedersenProof::deserialize_compressed(&proof.0[..]).ok()?;
+	Public::verify(input, output, aux_data, &proof).ok()?;
+	Some(hash)
+}
+
+/// Commit to the ring of `public_keys` with the KZG setup `srs`, the compressed powers of tau in
+/// G1 and G2.
+pub fn ring_commitment(
+	srs: &[u8],
+	public_keys: &[sp_core::bandersnatch::Public],
+) -> Option<RingCommitment> {
+	if public_keys.is_empty() || public_keys.len() > BANDERSNATCH_MAX_RING_SIZE as usize {
+		return None
+	}
+	let public_keys = public_keys
+		.iter()
+		.map(|public_key| Public::deserialize_compressed(&public_key.0[..]).ok().map(|key| key.0))
+		.collect::<Option<Vec<_>>>()?;
+	check_srs_len(srs)?;
+	let srs = PcsParams::deserialize_compressed(srs).ok()?;
+	let params = RingProofParams::from_pcs_params(public_keys.len(), srs).ok()?;
+
+	let mut verifier_key = [0; RING_VERIFIER_KEY_LEN];
+	params
+		.verifier_key(&public_keys)
+		.serialize_compressed(&mut verifier_key[..])
+		.ok()?;
+	Some(RingCommitment { ring_size: public_keys.len() as u32, verifier_key })
+}
+
+/// Verify that `output` is the VRF output of `input` by one of the keys of the ring committed to
+/// in `commitment`, and return its hash.
+pub fn ring_verify(
+	commitment: &RingCommitment,
+	input: &[u8],
+	aux_data: &[u8],
+	output: &VrfOutput,
+	proof: &RingVrfProof,
+) -> Option<[u8; 64]> {
+	use ring::Verifier;
+
+	if commitment.ring_size == 0 || commitment.ring_size > BANDERSNATCH_MAX_RING_SIZE {
+		return None
+	}
+	let (input, output, hash) = read_io(input, output)?;
+	let proof = RingProof::deserialize_compressed(&proof.0[..]).ok()?;
+	let verifier_key =
+		RingVerifierKey::deserialize_compressed(&commitment.verifier_key[..]).ok()?;
+	let verifier =
+		RingProofParams::verifier_no_context(verifier_key, commitment.ring_size as usize);
+	Public::verify(input, output, aux_data, &proof, &verifier).ok()?;
+	Some(hash)
+}
@@ -0,0 +1,790 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 verification of Groth16 proofs, KZG openings and BLS signatures.
+//!
+//! Points are compressed in the zcash encoding, as 48-byte G1 and 96-byte G2 points, and must be
+//! in the prime order subgroup. Scalars are 32-byte big-endian integers, which must be smaller
+//! than the group order.
+//!
+//! Groth16 verifying keys and proofs are in the compressed arkworks serialization, which
+//! encodes their points as above.
+//!
+//! Signatures come in two variants, [`MinPk`] with public keys in G1 and signatures in G2, and
+//! [`MinSig`] with the groups swapped. Messages are hashed to the group of the signatures with
+//! the `BLS12381G2_XMD:SHA-256_SSWU_RO_` or `BLS12381G1_XMD:SHA-256_SSWU_RO_` suite of RFC 9380.
+//! The beacon rounds of drand are [`MinPk`] signatures.
+//!
+//! Elements of the target group are in the arkworks serialization of Fq12, 576 bytes.
+
+use crate::{
+	BlsAggregateVerifyError, Groth16VerifyError, BLS12_381_FQ12_POW_MAX_EXPONENT_LEN,
+	BLS12_381_GROTH16_MAX_PUBLIC_INPUTS, BLS12_381_KZG_MAX_OPENINGS, BLS12_381_MAX_AGGREGATION,
+	BLS12_381_MAX_PAIRINGS, BLS12_381_POWERS_OF_TAU_MAX_POWERS,
+};
+use ark_bls12_381::{g1, g2, Bls12_381, Fq12, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+	pairing::{Pairing, PairingOutput},
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{
+	batch_inversion, field_hashers::DefaultFieldHasher, BigInt, BigInteger, Field, One, PrimeField,
+	Zero,
+};
+use ark_groth16::{Groth16, PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeSet, ops::Neg};
+
+/// The length of a compressed G1 point.
+const G1_LEN: usize = 48;
+/// The length of a compressed G2 point.
+const G2_LEN: usize = 96;
+/// The length of a scalar.
+const FR_LEN: usize = 32;
+/// The length of an element of the target group.
+const GT_LEN: usize = 576;
+/// The number of field elements of an EIP-4844 blob.
+const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+/// Read a compressed point, failing if it is not in the prime order subgroup.
+fn read_point<P: CanonicalDeserialize>(bytes: &[u8]) -> Option<P> {
+	P::deserialize_compressed(bytes).ok()
+}
+
+/// Read compressed points as [`read_point`], in parallel with the `parallel` feature.
+fn read_points<P: CanonicalDeserialize + Send>(
+	points: &[impl AsRef<[u8]> + Sync],
+) -> Option<Vec<P>> {
+	#[cfg(feature = "parallel")]
+	use rayon::prelude::*;
+
+	#[cfg(feature = "parallel")]
+	let points = points.par_iter();
+	#[cfg(not(feature = "parallel"))]
+	let points = points.iter();
+	points.map(|point| read_point(point.as_ref())).collect()
+}
+
+/// Compress `point` into `N` bytes.
+fn write_point<P: CanonicalSerialize, const N: usize>(point: P) -> [u8; N] {
+	let mut bytes = [0u8; N];
+	point
+		.serialize_compressed(&mut bytes[..])
+		.expect("Compressed points fit the buffer; qed");
+	bytes
+}
+
+/// Read a big-endian scalar, failing if it is not smaller than the group order.
+fn read_scalar(bytes: &[u8; 32]) -> Option<Fr> {
+	let mut limbs = [0u64; 4];
+	for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("Chunks are 8 bytes long; qed"));
+	}
+	Fr::from_bigint(BigInt(limbs))
+}
+
+/// Whether the product of the pairings of the points of `a` and `b` at the same index is one.
+///
+/// A Miller loop of zero has no final exponentiation, and counts as a product other than one
+/// instead of panicking.
+fn pairings_cancel(
+	a: impl IntoIterator<Item = G1Affine>,
+	b: impl IntoIterator<Item = G2Affine>,
+) -> bool {
+	matches!(
+		Bls12_381::final_exponentiation(Bls12_381::multi_miller_loop(a, b)),
+		Some(product) if product.0.is_one()
+	)
+}
+
+/// The compressed length of the verifying key with the most public inputs.
+fn max_verifying_key_len() -> usize {
+	// `alpha_g1`, `beta_g2`, `gamma_g2` and `delta_g2`, followed by the length prefixed
+	// `gamma_abc_g1`, with one point more than public inputs.
+	G1_LEN + 3 * G2_LEN + 8 + (BLS12_381_GROTH16_MAX_PUBLIC_INPUTS as usize + 1) * G1_LEN
+}
+
+/// Read `bytes` as a whole, failing if they are longer than the encoding.
+fn read_exact<T: CanonicalSerialize + CanonicalDeserialize>(bytes: &[u8]) -> Option<T> {
+	read_point(bytes).filter(|value: &T| value.compressed_size() == bytes.len())
+}
+
+/// Verify `proof` of `public_inputs` against the prepared `verifying_key`.
+fn groth16_verify_with(
+	verifying_key: &PreparedVerifyingKey<Bls12_381>,
+	proof: &[u8],
+	public_inputs: &[[u8; 32]],
+) -> Result<bool, Groth16VerifyError> {
+	let proof: Proof<Bls12_381> = read_exact(proof).ok_or(Groth16VerifyError::BadProof)?;
+	if verifying_key.vk.gamma_abc_g1.len() != public_inputs.len() + 1 {
+		return Err(Groth16VerifyError::BadPublicInputs)
+	}
+
+	let public_inputs = public_inputs
+		.iter()
+		.map(read_scalar)
+		.collect::<Option<Vec<_>>>()
+		.ok_or(Groth16VerifyError::BadPublicInputs)?;
+	Groth16::<Bls12_381>::verify_proof(verifying_key, &proof, &public_inputs)
+		.map_err(|_| Groth16VerifyError::BadPublicInputs)
+}
+
+/// Read a verifying key, failing if it has more than [`BLS12_381_GROTH16_MAX_PUBLIC_INPUTS`]
+/// public inputs.
+fn read_verifying_key(verifying_key: &[u8]) -> Option<VerifyingKey<Bls12_381>> {
+	if verifying_key.len() > max_verifying_key_len() {
+		return None
+	}
+
+	read_exact(verifying_key)
+		.filter(|verifying_key: &VerifyingKey<Bls12_381>| !verifying_key.gamma_abc_g1.is_empty())
+}
+
+/// Verify a Groth16 `proof` of `public_inputs` against `verifying_key`.
+pub fn groth16_verify(
+	verifying_key: &[u8],
+	proof: &[u8],
+	public_inputs: &[[u8; 32]],
+) -> Result<bool, Groth16VerifyError> {
+	let verifying_key =
+		read_verifying_key(verifying_key).ok_or(Groth16VerifyError::BadVerifyingKey)?;
+	groth16_verify_with(&ark_groth16::prepare_verifying_key(&verifying_key), proof, public_inputs)
+}
+
+/// Prepare `verifying_key` for [`groth16_verify_prepared`].
+///
+/// The prepared key adds the pairing of `alpha_g1` and `beta_g2` and the line coefficients of the
+/// negated `gamma_g2` and `delta_g2` to the key, and is serialized like it.
+pub fn groth16_prepare_verifying_key(verifying_key: &[u8]) -> Option<Vec<u8>> {
+	let mut prepared = Vec::new();
+	ark_groth16::prepare_verifying_key(&read_verifying_key(verifying_key)?)
+		.serialize_compressed(&mut prepared)
+		.expect("Serializing into a vector does not fail; qed");
+	Some(prepared)
+}
+
+/// Verify a Groth16 `proof` of `public_inputs` against a key prepared by
+/// [`groth16_prepare_verifying_key`].
+///
+/// The line coefficients of the prepared key are not checked against its points, so it must be
+/// the unmodified output of [`groth16_prepare_verifying_key`].
+pub fn groth16_verify_prepared(
+	prepared_verifying_key: &[u8],
+	proof: &[u8],
+	public_inputs: &[[u8; 32]],
+) -> Result<bool, Groth16VerifyError> {
+	let max_len = max_verifying_key_len() +
+		PairingOutput::<Bls12_381>::default().compressed_size() +
+		2 * <Bls12_381 as Pairing>::G2Prepared::from(G2Affine::generator()).compressed_size();
+	if prepared_verifying_key.len() > max_len {
+		return Err(Groth16VerifyError::BadVerifyingKey)
+	}
+
+	let verifying_key: PreparedVerifyingKey<Bls12_381> =
+		read_exact(prepared_verifying_key).ok_or(Groth16VerifyError::BadVerifyingKey)?;
+	if verifying_key.vk.gamma_abc_g1.is_empty() {
+		return Err(Groth16VerifyError::BadVerifyingKey)
+	}
+	groth16_verify_with(&verifying_key, proof, public_inputs)
+}
+
+/// Verify that `proof` opens `commitment` to `value` at `point`.
+///
+/// The polynomial `p` committed to as `p(τ)·G1` evaluates to `value` at `point` if
+/// `e(commitment - value·G1 + point·proof, G2) = e(proof, τ·G2)`, with `srs_g2` being `τ·G2`.
+pub fn kzg_verify(
+	commitment: &[u8; 48],
+	point: &[u8; 32],
+	value: &[u8; 32],
+	proof: &[u8; 48],
+	srs_g2: &[u8; 96],
+) -> Option<bool> {
+	let commitment: G1Affine = read_point(commitment)?;
+	let (point, value) = (read_scalar(point)?, read_scalar(value)?);
+	let proof: G1Affine = read_point(proof)?;
+	let srs_g2: G2Affine = read_point(srs_g2)?;
+
+	let lhs = G1Projective::from(commitment) - G1Affine::generator() * value + proof * point;
+	Some(pairings_cancel([lhs.into_affine(), -proof], [G2Affine::generator(), srs_g2]))
+}
+
+/// Verify that each of `proofs` opens the commitment at the same index to the value at that
+/// index at the point at that index.
+///
+/// Checks a random linear combination of the equations of [`kzg_verify`], with the powers of a
+/// challenge `r` as coefficients:
+/// `e(Σ rⁱ·(commitmentᵢ - valueᵢ·G1 + pointᵢ·proofᵢ), G2) = e(Σ rⁱ·proofᵢ, τ·G2)`. The challenge
+/// is the SHA-256 hash of `seed` followed by the commitments, points, values and proofs,
+/// reduced modulo the group order, so that the check is sound for any `seed`.
+///
+/// Fails if the number of commitments, points, values and proofs differ or exceed
+/// [`BLS12_381_KZG_MAX_OPENINGS`].
+pub fn kzg_batch_verify(
+	commitments: &[[u8; 48]],
+	points: &[[u8; 32]],
+	values: &[[u8; 32]],
+	proofs: &[[u8; 48]],
+	srs_g2: &[u8; 96],
+	seed: &[u8; 32],
+) -> Option<bool> {
+	let len = commitments.len();
+	if len > BLS12_381_KZG_MAX_OPENINGS as usize ||
+		points.len() != len ||
+		values.len() != len ||
+		proofs.len() != len
+	{
+		return None
+	}
+
+	let mut hasher = Sha256::new().chain_update(seed);
+	for (((commitment, point), value), proof) in
+		commitments.iter().zip(points).zip(values).zip(proofs)
+	{
+		hasher.update(commitment);
+		hasher.update(point);
+		hasher.update(value);
+		hasher.update(proof);
+	}
+	let challenge = Fr::from_be_bytes_mod_order(&hasher.finalize());
+
+	let mut bases = Vec::with_capacity(2 * len + 1);
+	let mut scalars = Vec::with_capacity(2 * len + 1);
+	let mut proof_bases = Vec::with_capacity(len);
+	let mut proof_scalars = Vec::with_capacity(len);
+	let (mut coefficient, mut value_sum) = (Fr::one(), Fr::zero());
+	for (((commitment, point), value), proof) in
+		commitments.iter().zip(points).zip(values).zip(proofs)
+	{
+		let proof: G1Affine = read_point(proof)?;
+		bases.push(read_point(commitment)?);
+		scalars.push(coefficient);
+		bases.push(proof);
+		scalars.push(coefficient * read_scalar(point)?);
+		value_sum += coefficient * read_scalar(value)?;
+		proof_bases.push(proof);
+		proof_scalars.push(coefficient);
+		coefficient *= challenge;
+	}
+	bases.push(G1Affine::generator());
+	scalars.push(-value_sum);
+
+	let lhs = G1Projective::msm(&bases, &scalars).ok()?;
+	let rhs = G1Projective::msm(&proof_bases, &proof_scalars).ok()?;
+	Some(pairings_cancel(
+		[lhs.into_affine(), -rhs.into_affine()],
+		[G2Affine::generator(), read_point(srs_g2)?],
+	))
+}
+
+/// The EIP-4844 versioned hash of `commitment`, its SHA-256 hash with the first byte replaced by
+/// the version one.
+pub fn kzg_versioned_hash(commitment: &[u8; 48]) -> [u8; 32] {
+	let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+	hash[0] = 1;
+	hash
+}
+
+/// Verify an opening as the EIP-4844 point evaluation precompile does.
+///
+/// Checks that `versioned_hash` is the [`kzg_versioned_hash`] of `commitment` and that `proof`
+/// opens `commitment` to `value` at `point`.
+pub fn kzg_point_evaluation(
+	versioned_hash: &[u8; 32],
+	point: &[u8; 32],
+	value: &[u8; 32],
+	commitment: &[u8; 48],
+	proof: &[u8; 48],
+	srs_g2: &[u8; 96],
+) -> Option<bool> {
+	if kzg_versioned_hash(commitment) != *versioned_hash {
+		return Some(false)
+	}
+
+	kzg_verify(commitment, point, value, proof, srs_g2)
+}
+
+/// The roots of unity of the EIP-4844 evaluation domain, in bit-reversed order.
+fn blob_domain() -> Vec<Fr> {
+	// The primitive root of unity of order `FIELD_ELEMENTS_PER_BLOB` is `7 ^ ((r - 1) / 4096)`.
+	let mut exponent = Fr::MODULUS;
+	exponent.sub_with_borrow(&BigInt::from(1u64));
+	exponent.divn(FIELD_ELEMENTS_PER_BLOB.trailing_zeros());
+	let root = Fr::from(7u64).pow(exponent);
+
+	let bits = FIELD_ELEMENTS_PER_BLOB.trailing_zeros();
+	let mut powers = Vec::with_capacity(FIELD_ELEMENTS_PER_BLOB);
+	let mut power = Fr::one();
+	for _ in 0..FIELD_ELEMENTS_PER_BLOB {
+		powers.push(power);
+		power *= root;
+	}
+	(0..FIELD_ELEMENTS_PER_BLOB)
+		.map(|i| powers[i.reverse_bits() >> (usize::BITS - bits)])
+		.collect()
+}
+
+/// The evaluation at `point` of the polynomial with the values `evaluations` over the
+/// [`blob_domain`].
+fn evaluate_blob(evaluations: &[Fr], point: Fr) -> Fr {
+	let domain = blob_domain();
+	if let Some(index) = domain.iter().position(|root| *root == point) {
+		return evaluations[index]
+	}
+
+	// The barycentric formula `(zⁿ - 1) / n · Σ fᵢ·ωᵢ / (z - ωᵢ)`.
+	let mut denominators: Vec<Fr> = domain.iter().map(|root| point - root).collect();
+	batch_inversion(&mut denominators);
+	let sum: Fr = evaluations
+		.iter()
+		.zip(&domain)
+		.zip(&denominators)
+		.map(|((evaluation, root), denominator)| *evaluation * root * denominator)
+		.sum();
+	let n = Fr::from(FIELD_ELEMENTS_PER_BLOB as u64);
+	sum * (point.pow([FIELD_ELEMENTS_PER_BLOB as u64]) - Fr::one()) *
+		n.inverse().expect("The domain size is not zero; qed")
+}
+
+/// Verify that `proof` opens `commitment` to the EIP-4844 `blob` at its Fiat-Shamir challenge.
+///
+/// The blob consists of the 4096 big-endian scalars evaluating the committed polynomial over
+/// the bit-reversed roots of unity of that order. The challenge is the SHA-256 hash of
+/// `FSBLOBVERIFY_V1_`, the number of field elements as 16-byte big-endian integer, `blob` and
+/// `commitment`, reduced modulo the group order.
+pub fn kzg_verify_blob(
+	blob: &[u8],
+	commitment: &[u8; 48],
+	proof: &[u8; 48],
+	srs_g2: &[u8; 96],
+) -> Option<bool> {
+	if blob.len() != FIELD_ELEMENTS_PER_BLOB * FR_LEN {
+		return None
+	}
+
+	let evaluations = blob
+		.chunks(FR_LEN)
+		.map(|chunk| read_scalar(chunk.try_into().expect("Chunks are 32 bytes long; qed")))
+		.collect::<Option<Vec<_>>>()?;
+	let challenge = Fr::from_be_bytes_mod_order(
+		&Sha256::new()
+			.chain_update(b"FSBLOBVERIFY_V1_")
+			.chain_update((FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes())
+			.chain_update(blob)
+			.chain_update(commitment)
+			.finalize(),
+	);
+	let value = evaluate_blob(&evaluations, challenge);
+
+	let point = write_scalar(challenge);
+	kzg_verify(commitment, &point, &write_scalar(value), proof, srs_g2)
+}
+
+/// Write `scalar` as 32 big-endian bytes.
+fn write_scalar(scalar: Fr) -> [u8; 32] {
+	scalar
+		.into_bigint()
+		.to_bytes_be()
+		.try_into()
+		.expect("Scalars are 32 bytes long; qed")
+}
+
+/// Whether the product of the pairings of the points of `g1` and `g2` at the same index is
+/// `expected`, an element of the target group.
+///
+/// Fails if the numbers of points differ or exceed [`BLS12_381_MAX_PAIRINGS`], or if `expected`
+/// is not an encoded element of Fq12. That it is in the target group is not checked, as the product
+/// always is.
+pub fn multi_pairing_equals(g1: &[[u8; 48]], g2: &[[u8; 96]], expected: &[u8]) -> Option<bool> {
+	if g1.len() != g2.len() ||
+		g1.len() > BLS12_381_MAX_PAIRINGS as usize ||
+		expected.len() != GT_LEN
+	{
+		return None
+	}
+
+	let expected = Fq12::deserialize_compressed_unchecked(expected).ok()?;
+	let g1 = read_points::<G1Affine>(g1)?;
+	let g2 = read_points::<G2Affine>(g2)?;
+	Some(matches!(
+		Bls12_381::final_exponentiation(Bls12_381::multi_miller_loop(g1, g2)),
+		Some(product) if product.0 == expected
+	))
+}
+
+/// Raise the Fq12 element `f` to the power of the big-endian `exponent`.
+///
+/// Fails if `f` is not an encoded element of Fq12 or `exponent` is longer than
+/// [`BLS12_381_FQ12_POW_MAX_EXPONENT_LEN`].
+pub fn fq12_pow(f: &[u8], exponent: &[u8]) -> Option<Vec<u8>> {
+	if f.len() != GT_LEN || exponent.len() > BLS12_381_FQ12_POW_MAX_EXPONENT_LEN as usize {
+		return None
+	}
+
+	let f = Fq12::deserialize_compressed_unchecked(f).ok()?;
+	// Little-endian limbs, the first from the last eight bytes.
+	let limbs = exponent
+		.rchunks(8)
+		.map(|chunk| chunk.iter().fold(0u64, |limb, byte| limb << 8 | u64::from(*byte)))
+		.collect::<Vec<_>>();
+	let mut power = Vec::with_capacity(GT_LEN);
+	f.pow(limbs)
+		.serialize_compressed(&mut power)
+		.expect("Writing to a vector does not fail; qed");
+	Some(power)
+}
+
+/// A BLS signature variant, defined by the groups of the public keys and the signatures.
+pub trait Variant {
+	/// The group of the public keys.
+	type PublicKey: AffineRepr + Neg<Output = Self::PublicKey>;
+	/// The group of the signatures, which messages are hashed to.
+	type Signature: AffineRepr;
+	/// The domain separation tag of the proofs of possession.
+	const POP_DST: &'static [u8];
+
+	/// Hash `message` to the group of the signatures with the domain separation tag `dst`.
+	fn hash_to_curve(message: &[u8], dst: &[u8]) -> Option<Self::Signature>;
+
+	/// Whether the product of the pairings of the public keys and signatures at the same index
+	/// is one.
+	fn pairings_cancel(public_keys: Vec<Self::PublicKey>, signatures: Vec<Self::Signature>)
+		-> bool;
+}
+
+/// The variant with public keys in G1 and signatures in G2.
+pub struct MinPk;
+
+impl Variant for MinPk {
+	type PublicKey = G1Affine;
+	type Signature = G2Affine;
+	const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+	fn hash_to_curve(message: &[u8], dst: &[u8]) -> Option<G2Affine> {
+		MapToCurveBasedHasher::<G2Projective, DefaultFieldHasher<Sha256, 128>, WBMap<g2::Config>>::new(dst)
+			.and_then(|hasher| hasher.hash(message))
+			.ok()
+	}
+
+	fn pairings_cancel(public_keys: Vec<G1Affine>, signatures: Vec<G2Affine>) -> bool {
+		pairings_cancel(public_keys, signatures)
+	}
+}
+
+/// The variant with public keys in G2 and signatures in G1.
+pub struct MinSig;
+
+impl Variant for MinSig {
+	type PublicKey = G2Affine;
+	type Signature = G1Affine;
+	const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+	fn hash_to_curve(message: &[u8], dst: &[u8]) -> Option<G1Affine> {
+		MapToCurveBasedHasher::<G1Projective, DefaultFieldHasher<Sha256, 128>, WBMap<g1::Config>>::new(dst)
+			.and_then(|hasher| hasher.hash(message))
+			.ok()
+	}
+
+	fn pairings_cancel(public_keys: Vec<G2Affine>, signatures: Vec<G1Affine>) -> bool {
+		pairings_cancel(signatures, public_keys)
+	}
+}
+
+/// Read a public key, failing if it is the point at infinity.
+fn read_public_key<V: Variant>(bytes: &[u8]) -> Option<V::PublicKey> {
+	read_point(bytes).filter(|public_key: &V::PublicKey| !public_key.is_zero())
+}
+
+/// Verify the BLS `signature` of `message` by `public_key`, which must not be the point at
+/// infinity.
+///
+/// The signature is valid if `e(public_key, H(message)) = e(generator, signature)`, with `H`
+/// hashing to the group of the signatures with the domain separation tag `dst`.
+pub fn verify<V: Variant>(
+	public_key: &[u8],
+	message: &[u8],
+	signature: &[u8],
+	dst: &[u8],
+) -> Option<bool> {
+	let public_key = read_public_key::<V>(public_key)?;
+	let signature: V::Signature = read_point(signature)?;
+	let hash = V::hash_to_curve(message, dst)?;
+	Some(V::pairings_cancel(vec![public_key, -V::PublicKey::generator()], vec![hash, signature]))
+}
+
+/// Check that there are between one and [`BLS12_381_MAX_AGGREGATION`] public keys and, if given,
+/// as many distinct `messages`.
+pub(crate) fn check_aggregation(
+	public_keys: usize,
+	messages: Option<&[Vec<u8>]>,
+) -> Result<(), BlsAggregateVerifyError> {
+	if public_keys == 0 {
+		return Err(BlsAggregateVerifyError::Empty)
+	}
+	if public_keys > BLS12_381_MAX_AGGREGATION as usize {
+		return Err(BlsAggregateVerifyError::TooMany)
+	}
+	if let Some(messages) = messages {
+		if messages.len() != public_keys {
+			return Err(BlsAggregateVerifyError::LengthMismatch)
+		}
+		if messages.iter().collect::<BTreeSet<_>>().len() != messages.len() {
+			return Err(BlsAggregateVerifyError::DuplicateMessage)
+		}
+	}
+	Ok(())
+}
+
+/// Verify the aggregate `signature` of each of `messages` by the public key at the same index.
+///
+/// The messages must be distinct, which makes the check
+/// `Π e(public_keyᵢ, H(messageᵢ)) = e(generator, signature)` secure without proofs of
+/// possession of the keys.
+pub fn aggregate_verify<V: Variant>(
+	public_keys: &[impl AsRef<[u8]> + Sync],
+	messages: &[Vec<u8>],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, BlsAggregateVerifyError> {
+	check_aggregation(public_keys.len(), Some(messages))?;
+
+	let mut keys: Vec<V::PublicKey> =
+		read_points(public_keys).ok_or(BlsAggregateVerifyError::InvalidPublicKey)?;
+	if keys.iter().any(|public_key| public_key.is_zero()) {
+		return Err(BlsAggregateVerifyError::InvalidPublicKey)
+	}
+	let signature: V::Signature =
+		read_point(signature).ok_or(BlsAggregateVerifyError::InvalidSignature)?;
+	let mut hashes = Vec::with_capacity(messages.len() + 1);
+	for message in messages {
+		match V::hash_to_curve(message, dst) {
+			Some(hash) => hashes.push(hash),
+			None => return Ok(false),
+		}
+	}
+
+	keys.push(-V::PublicKey::generator());
+	hashes.push(signature);
+	Ok(V::pairings_cancel(keys, hashes))
+}
+
+/// Verify the aggregate `signature` of `message` by all of `public_keys`.
+///
+/// Checks the signature against the sum of the public keys, which is only secure if each of
+/// them came with a valid proof of possession, see [`pop_verify`].
+pub fn fast_aggregate_verify<V: Variant>(
+	public_keys: &[impl AsRef<[u8]>],
+	message: &[u8],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, BlsAggregateVerifyError> {
+	check_aggregation(public_keys.len(), None)?;
+
+	let mut aggregate = <V::PublicKey as AffineRepr>::Group::zero();
+	for public_key in public_keys {
+		aggregate += read_public_key::<V>(public_key.as_ref())
+			.ok_or(BlsAggregateVerifyError::InvalidPublicKey)?;
+	}
+	let signature: V::Signature =
+		read_point(signature).ok_or(BlsAggregateVerifyError::InvalidSignature)?;
+
+	let aggregate = aggregate.into_affine();
+	if aggregate.is_zero() {
+		return Ok(false)
+	}
+	let hash = match V::hash_to_curve(message, dst) {
+		Some(hash) => hash,
+		None => return Ok(false),
+	};
+	Ok(V::pairings_cancel(vec![aggregate, -V::PublicKey::generator()], vec![hash, signature]))
+}
+
+/// Sum the compressed `points`, failing if there are more than [`BLS12_381_MAX_AGGREGATION`].
+pub fn aggregate<P: AffineRepr, const N: usize>(points: &[[u8; N]]) -> Option<[u8; N]> {
+	if points.len() > BLS12_381_MAX_AGGREGATION as usize {
+		return None
+	}
+
+	let mut sum = P::Group::zero();
+	for point in points {
+		sum += read_point::<P>(point)?;
+	}
+	Some(write_point(sum.into_affine()))
+}
+
+/// Verify the proof of possession `proof` of the secret key of `public_key`.
+///
+/// The proof is the signature of the compressed public key with the domain separation tag
+/// [`Variant::POP_DST`].
+pub fn pop_verify<V: Variant>(public_key: &[u8], proof: &[u8]) -> Option<bool> {
+	verify::<V>(public_key, public_key, proof, V::POP_DST)
+}
+
+/// Evaluate at `index` the polynomial committed to by `commitments`, its coefficients times the
+/// generator of G1 from the constant term up.
+fn evaluate_commitments(commitments: &[[u8; 48]], index: u32) -> Option<G1Projective> {
+	if commitments.is_empty() || commitments.len() > BLS12_381_MAX_AGGREGATION as usize {
+		return None
+	}
+
+	let index = Fr::from(index);
+	let mut evaluation = G1Projective::zero();
+	for commitment in commitments.iter().rev() {
+		evaluation = evaluation * index + read_point::<G1Affine>(commitment)?;
+	}
+	Some(evaluation)
+}
+
+/// The public key of the share at `index` of the secret whose polynomial is committed to by
+/// `commitments`.
+pub fn threshold_public_key(commitments: &[[u8; 48]], index: u32) -> Option<[u8; 48]> {
+	evaluate_commitments(commitments, index).map(|public_key| write_point(public_key.into_affine()))
+}
+
+/// Whether `share` is the evaluation at `index` of the polynomial committed to by `commitments`.
+pub fn threshold_verify_share(
+	commitments: &[[u8; 48]],
+	index: u32,
+	share: &[u8; 32],
+) -> Option<bool> {
+	let share = read_scalar(share)?;
+	Some(G1Affine::generator() * share == evaluate_commitments(commitments, index)?)
+}
+
+/// Combine the `signatures` by the shares at the same index of `indices` into the signature by
+/// the shared secret, interpolating the shares at zero.
+///
+/// The indices must be distinct and not zero.
+pub fn threshold_combine(indices: &[u32], signatures: &[[u8; 96]]) -> Option<[u8; 96]> {
+	if indices.is_empty() ||
+		indices.len() > BLS12_381_MAX_AGGREGATION as usize ||
+		signatures.len() != indices.len() ||
+		indices.contains(&0) ||
+		indices.iter().collect::<BTreeSet<_>>().len() != indices.len()
+	{
+		return None
+	}
+
+	// The Lagrange coefficient of `xᵢ` at zero is `Π xⱼ / (xᵢ · Π_{j ≠ i} (xⱼ - xᵢ))`.
+	let xs = indices.iter().map(|index| Fr::from(*index)).collect::<Vec<_>>();
+	let mut coefficients = xs
+		.iter()
+		.map(|xi| *xi * xs.iter().filter(|xj| *xj != xi).map(|xj| *xj - xi).product::<Fr>())
+		.collect::<Vec<_>>();
+	batch_inversion(&mut coefficients);
+	let product = xs.iter().product::<Fr>();
+	coefficients.iter_mut().for_each(|coefficient| *coefficient *= product);
+
+	let signatures: Vec<G2Affine> = read_points(signatures)?;
+	let signature = G2Projective::msm(&signatures, &coefficients).ok()?;
+	Some(write_point(signature.into_affine()))
+}
+
+/// The domain separation tag of the drand beacons with signatures in G2.
+pub(crate) const BEACON_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Verify the drand beacon `signature` of `round` by the group public key `group_public_key`.
+///
+/// The signed message is `sha256(previous_signature || round)`, with the round as big-endian
+/// bytes. Chained beacons sign the signature of the previous round, while unchained beacons pass
+/// an empty `previous_signature`.
+pub fn beacon_verify(
+	group_public_key: &[u8; 48],
+	round: u64,
+	previous_signature: &[u8],
+	signature: &[u8; 96],
+) -> Option<bool> {
+	verify::<MinPk>(
+		group_public_key,
+		&beacon_message(round, previous_signature),
+		signature,
+		BEACON_DST,
+	)
+}
+
+/// The message signed by the drand beacon of `round`, `sha256(previous_signature || round)`.
+pub(crate) fn beacon_message(round: u64, previous_signature: &[u8]) -> [u8; 32] {
+	Sha256::new()
+		.chain_update(previous_signature)
+		.chain_update(round.to_be_bytes())
+		.finalize()
+		.into()
+}
+
+/// Verify a contribution to a powers-of-tau ceremony.
+///
+/// Checks that `g1_powers` and `g2_powers` are the powers of the same secret from the generator
+/// up, and that the secret is the previous one, whose first power is `previous_tau_g1`, times the
+/// secret of the contribution, whose product with the generator of G2 is `witness`. The powers
+/// are checked in a random linear combination, whose coefficients are the powers of the SHA-256
+/// hash of all inputs.
+pub fn powers_of_tau_verify(
+	previous_tau_g1: &[u8; 48],
+	g1_powers: &[[u8; 48]],
+	g2_powers: &[[u8; 96]],
+	witness: &[u8; 96],
+) -> Option<bool> {
+	if g1_powers.len() > BLS12_381_POWERS_OF_TAU_MAX_POWERS as usize ||
+		g2_powers.len() > g1_powers.len() ||
+		g2_powers.len() < 2
+	{
+		return None
+	}
+
+	let previous: G1Affine = read_point(previous_tau_g1)?;
+	let secret: G2Affine = read_point(witness)?;
+	let g1: Vec<G1Affine> = read_points(g1_powers)?;
+	let g2: Vec<G2Affine> = read_points(g2_powers)?;
+	if g1[0] != G1Affine::generator() ||
+		g2[0] != G2Affine::generator() ||
+		g1[1].is_zero() ||
+		secret.is_zero()
+	{
+		return Some(false)
+	}
+
+	let mut hasher = Sha256::new().chain_update(previous_tau_g1).chain_update(witness);
+	for power in g1_powers {
+		hasher.update(power);
+	}
+	for power in g2_powers {
+		hasher.update(power);
+	}
+	let challenge = Fr::from_be_bytes_mod_order(&hasher.finalize());
+	let coefficients =
+		core::iter::successors(Some(Fr::one()), |coefficient| Some(*coefficient * challenge))
+			.take(g1.len() - 1)
+			.collect::<Vec<_>>();
+
+	// Each power is the previous one times the secret, the first power of G2.
+	let (g1_len, g2_len) = (g1.len() - 1, g2.len() - 1);
+	let g1_lower = G1Projective::msm(&g1[..g1_len], &coefficients).ok()?;
+	let g1_upper = G1Projective::msm(&g1[1..], &coefficients).ok()?;
+	let g2_lower = G2Projective::msm(&g2[..g2_len], &coefficients[..g2_len]).ok()?;
+	let g2_upper = G2Projective::msm(&g2[1..], &coefficients[..g2_len]).ok()?;
+	Some(
+		pairings_cancel([g1[1], -previous], [G2Affine::generator(), secret]) &&
+			pairings_cancel(
+				[g1_lower.into_affine(), -g1_upper.into_affine()],
+				[g2[1], G2Affine::generator()],
+			) && pairings_cancel(
+			[g1[1], -G1Affine::generator()],
+			[g2_lower.into_affine(), g2_upper.into_affine()],
+		),
+	)
+}
@@ -0,0 +1,213 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 with blst: signature verification and constant-time scalar multiplication.
+//!
+//! Behind the `blst-signatures` feature, the BLS signature host functions use these functions
+//! instead of the arkworks ones of [`crate::bls12_381`]. They accept the same encodings, check the
+//! same preconditions in the same order and return the same results. blst verifies aggregate
+//! signatures on its own thread pool, which [`crate::set_crypto_threads`] does not bound.
+//!
+//! The scalar multiplication of arkworks is variable-time, so the constant-time host functions
+//! always use blst, whose multiplication by a full-width scalar selects table entries with
+//! constant-time copies.
+
+use crate::{
+	bls12_381::{beacon_message, check_aggregation, MinPk, MinSig, Variant, BEACON_DST},
+	BlsAggregateVerifyError,
+};
+use blst::BLST_ERROR;
+
+/// A signature [`Variant`] verified with the blst types of its groups.
+pub trait BlstVariant: Variant {
+	/// Verify `signature` of each of `messages` by the public key at the same index, failing if
+	/// a public key is malformed, not in the prime order subgroup or the point at infinity, or
+	/// if the signature is malformed or not in the prime order subgroup.
+	fn verify_all(
+		public_keys: &[&[u8]],
+		messages: &[&[u8]],
+		signature: &[u8],
+		dst: &[u8],
+	) -> Result<bool, BlsAggregateVerifyError>;
+
+	/// Verify `signature` of `message` by the sum of `public_keys`, failing as
+	/// [`BlstVariant::verify_all`].
+	fn verify_sum(
+		public_keys: &[&[u8]],
+		message: &[u8],
+		signature: &[u8],
+		dst: &[u8],
+	) -> Result<bool, BlsAggregateVerifyError>;
+}
+
+macro_rules! impl_blst_variant {
+	($variant:ty, $module:ident) => {
+		impl BlstVariant for $variant {
+			fn verify_all(
+				public_keys: &[&[u8]],
+				messages: &[&[u8]],
+				signature: &[u8],
+				dst: &[u8],
+			) -> Result<bool, BlsAggregateVerifyError> {
+				let public_keys = public_keys
+					.iter()
+					.map(|public_key| blst::$module::PublicKey::key_validate(public_key).ok())
+					.collect::<Option<Vec<_>>>()
+					.ok_or(BlsAggregateVerifyError::InvalidPublicKey)?;
+				let signature = blst::$module::Signature::sig_validate(signature, false)
+					.map_err(|_| BlsAggregateVerifyError::InvalidSignature)?;
+				let public_keys = public_keys.iter().collect::<Vec<_>>();
+				Ok(signature.aggregate_verify(false, messages, dst, &public_keys, false) ==
+					BLST_ERROR::BLST_SUCCESS)
+			}
+
+			fn verify_sum(
+				public_keys: &[&[u8]],
+				message: &[u8],
+				signature: &[u8],
+				dst: &[u8],
+			) -> Result<bool, BlsAggregateVerifyError> {
+				let public_keys = public_keys
+					.iter()
+					.map(|public_key| blst::$module::PublicKey::key_validate(public_key).ok())
+					.collect::<Option<Vec<_>>>()
+					.ok_or(BlsAggregateVerifyError::InvalidPublicKey)?;
+				let signature = blst::$module::Signature::sig_validate(signature, false)
+					.map_err(|_| BlsAggregateVerifyError::InvalidSignature)?;
+				let public_keys = public_keys.iter().collect::<Vec<_>>();
+				// Fails if the sum is the point at infinity.
+				Ok(signature.fast_aggregate_verify(false, message, dst, &public_keys) ==
+					BLST_ERROR::BLST_SUCCESS)
+			}
+		}
+	};
+}
+
+impl_blst_variant!(MinPk, min_pk);
+impl_blst_variant!(MinSig, min_sig);
+
+/// Verify the BLS `signature` of `message` by `public_key`, as [`crate::bls12_381::verify`].
+pub fn verify<V: BlstVariant>(
+	public_key: &[u8],
+	message: &[u8],
+	signature: &[u8],
+	dst: &[u8],
+) -> Option<bool> {
+	V::verify_all(&[public_key], &[message], signature, dst).ok()
+}
+
+/// Verify the aggregate `signature` of each of `messages` by the public key at the same index,
+/// as [`crate::bls12_381::aggregate_verify`].
+pub fn aggregate_verify<V: BlstVariant>(
+	public_keys: &[impl AsRef<[u8]> + Sync],
+	messages: &[Vec<u8>],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, BlsAggregateVerifyError> {
+	check_aggregation(public_keys.len(), Some(messages))?;
+
+	let public_keys = public_keys.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+	let messages = messages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+	V::verify_all(&public_keys, &messages, signature, dst)
+}
+
+/// Verify the aggregate `signature` of `message` by all of `public_keys`, as
+/// [`crate::bls12_381::fast_aggregate_verify`].
+pub fn fast_aggregate_verify<V: BlstVariant>(
+	public_keys: &[impl AsRef<[u8]>],
+	message: &[u8],
+	signature: &[u8],
+	dst: &[u8],
+) -> Result<bool, BlsAggregateVerifyError> {
+	check_aggregation(public_keys.len(), None)?;
+
+	let public_keys = public_keys.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+	V::verify_sum(&public_keys, message, signature, dst)
+}
+
+/// Verify the proof of possession `proof` of the secret key of `public_key`, as
+/// [`crate::bls12_381::pop_verify`].
+pub fn pop_verify<V: BlstVariant>(public_key: &[u8], proof: &[u8]) -> Option<bool> {
+	verify::<V>(public_key, public_key, proof, V::POP_DST)
+}
+
+/// Verify the drand beacon `signature` of `round`, as [`crate::bls12_381::beacon_verify`].
+pub fn beacon_verify(
+	group_public_key: &[u8; 48],
+	round: u64,
+	previous_signature: &[u8],
+	signature: &[u8; 96],
+) -> Option<bool> {
+	verify::<MinPk>(
+		group_public_key,
+		&beacon_message(round, previous_signature),
+		signature,
+		BEACON_DST,
+	)
+}
+
+macro_rules! mul_ct {
+	(
+		$(#[$attr:meta])*
+		fn $name:ident([u8; $len:literal]),
+		$affine:ident, $point:ident,
+		$uncompress:ident, $in_group:ident, $from_affine:ident, $mult:ident, $compress:ident
+	) => {
+		$(#[$attr])*
+		pub fn $name(point: &[u8; $len], scalar: &[u8; 32]) -> Option<[u8; $len]> {
+			let mut affine = blst::$affine::default();
+			let mut value = blst::blst_scalar::default();
+			let mut projective = blst::$point::default();
+			let mut product = blst::$point::default();
+			let mut compressed = [0; $len];
+			// SAFETY: every pointer is to a live value of the type and length blst expects.
+			unsafe {
+				if blst::$uncompress(&mut affine, point.as_ptr()) != BLST_ERROR::BLST_SUCCESS ||
+					!blst::$in_group(&affine)
+				{
+					return None
+				}
+				blst::blst_scalar_from_bendian(&mut value, scalar.as_ptr());
+				if !blst::blst_scalar_fr_check(&value) {
+					return None
+				}
+				blst::$from_affine(&mut projective, &affine);
+				// Always the full width of the group order, so the time does not depend on the
+				// length of the scalar.
+				blst::$mult(&mut product, &projective, value.b.as_ptr(), 255);
+				blst::$compress(compressed.as_mut_ptr(), &product);
+			}
+			Some(compressed)
+		}
+	};
+}
+
+mul_ct!(
+	/// Multiply the compressed G1 `point` by the big-endian `scalar` in constant time, as
+	/// [`crate::crypto::bls12_381_mul_g1_ct`].
+	fn mul_g1_ct([u8; 48]),
+	blst_p1_affine, blst_p1,
+	blst_p1_uncompress, blst_p1_affine_in_g1, blst_p1_from_affine, blst_p1_mult, blst_p1_compress
+);
+
+mul_ct!(
+	/// Multiply the compressed G2 `point` by the big-endian `scalar` in constant time, as
+	/// [`crate::crypto::bls12_381_mul_g2_ct`].
+	fn mul_g2_ct([u8; 96]),
+	blst_p2_affine, blst_p2,
+	blst_p2_uncompress, blst_p2_affine_in_g2, blst_p2_from_affine, blst_p2_mult, blst_p2_compress
+);
@@ -0,0 +1,136 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BN254 operations with the semantics of the EIP-196 and EIP-197 precompiles.
+//!
+//! Base field elements are 32-byte big-endian integers, which must be smaller than the field
+//! modulus. G1 points are encoded as `x` followed by `y`, G2 points as `x` followed by `y` with
+//! the coefficients of each coordinate ordered as `c1` followed by `c0`. The point at infinity
+//! is encoded as all zeros.
+
+use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInt, BigInteger, One, PrimeField, Zero};
+
+/// The length of an encoded base field element.
+const FQ_LEN: usize = 32;
+/// The length of an encoded G1 point.
+const G1_LEN: usize = 2 * FQ_LEN;
+/// The length of an encoded G2 point.
+const G2_LEN: usize = 4 * FQ_LEN;
+/// The length of a G1 and G2 point pair in the pairing input.
+const PAIR_LEN: usize = G1_LEN + G2_LEN;
+
+/// Read `N` bytes of `input`, padding it with zeros and ignoring bytes beyond.
+fn padded<const N: usize>(input: &[u8]) -> [u8; N] {
+	let mut bytes = [0u8; N];
+	let len = input.len().min(N);
+	bytes[..len].copy_from_slice(&input[..len]);
+	bytes
+}
+
+/// Read a big-endian 256-bit integer.
+fn read_bigint(bytes: &[u8]) -> BigInt<4> {
+	let mut limbs = [0u64; 4];
+	for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("Chunks are 8 bytes long; qed"));
+	}
+	BigInt(limbs)
+}
+
+/// Read a base field element, failing if it is not smaller than the modulus.
+fn read_fq(bytes: &[u8]) -> Option<Fq> {
+	Fq::from_bigint(read_bigint(bytes))
+}
+
+/// Read a G1 point, failing if it is not on the curve.
+fn read_g1(bytes: &[u8]) -> Option<G1Affine> {
+	let (x, y) = (read_fq(&bytes[..FQ_LEN])?, read_fq(&bytes[FQ_LEN..G1_LEN])?);
+	if x.is_zero() && y.is_zero() {
+		return Some(G1Affine::zero())
+	}
+
+	// G1 has a cofactor of one, so every point on the curve is in the subgroup.
+	let point = G1Affine::new_unchecked(x, y);
+	point.is_on_curve().then_some(point)
+}
+
+/// Read a G2 point, failing if it is not on the curve or not in the prime order subgroup.
+fn read_g2(bytes: &[u8]) -> Option<G2Affine> {
+	let read_fq2 =
+		|bytes: &[u8]| Some(Fq2::new(read_fq(&bytes[FQ_LEN..])?, read_fq(&bytes[..FQ_LEN])?));
+	let (x, y) = (read_fq2(&bytes[..2 * FQ_LEN])?, read_fq2(&bytes[2 * FQ_LEN..G2_LEN])?);
+	if x.is_zero() && y.is_zero() {
+		return Some(G2Affine::zero())
+	}
+
+	let point = G2Affine::new_unchecked(x, y);
+	(point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+/// Encode a G1 point.
+fn write_g1(point: G1Affine) -> [u8; G1_LEN] {
+	let mut bytes = [0u8; G1_LEN];
+	if let Some((x, y)) = point.xy() {
+		bytes[..FQ_LEN].copy_from_slice(&x.into_bigint().to_bytes_be());
+		bytes[FQ_LEN..].copy_from_slice(&y.into_bigint().to_bytes_be());
+	}
+	bytes
+}
+
+/// The `ECADD` precompile of EIP-196.
+///
+/// `input` is read as two G1 points, padded with zeros to 128 bytes.
+pub fn add(input: &[u8]) -> Option<[u8; 64]> {
+	let input = padded::<{ 2 * G1_LEN }>(input);
+	let (a, b) = (read_g1(&input[..G1_LEN])?, read_g1(&input[G1_LEN..])?);
+	Some(write_g1((a + b).into_affine()))
+}
+
+/// The `ECMUL` precompile of EIP-196.
+///
+/// `input` is read as a G1 point followed by a 256-bit big-endian scalar, padded with zeros to
+/// 96 bytes. The scalar is not required to be smaller than the group order.
+pub fn mul(input: &[u8]) -> Option<[u8; 64]> {
+	let input = padded::<{ G1_LEN + 32 }>(input);
+	let point = read_g1(&input[..G1_LEN])?;
+	Some(write_g1(point.mul_bigint(read_bigint(&input[G1_LEN..])).into_affine()))
+}
+
+/// The `ECPAIRING` precompile of EIP-197.
+///
+/// `input` is read as a sequence of G1 and G2 point pairs and its length must be a multiple of
+/// 192 bytes. The result is one as a 32-byte big-endian integer if the product of the pairings
+/// of all pairs is one, including for empty input, and zero otherwise. A Miller loop of zero,
+/// which has no final exponentiation, fails like invalid input instead of panicking.
+pub fn pairing(input: &[u8]) -> Option<[u8; 32]> {
+	if input.len() % PAIR_LEN != 0 {
+		return None
+	}
+
+	let (a, b): (Vec<_>, Vec<_>) = input
+		.chunks(PAIR_LEN)
+		.map(|pair| Some((read_g1(&pair[..G1_LEN])?, read_g2(&pair[G1_LEN..])?)))
+		.collect::<Option<Vec<_>>>()?
+		.into_iter()
+		.unzip();
+
+	let product = Bn254::final_exponentiation(Bn254::multi_miller_loop(a, b))?;
+	let mut result = [0u8; 32];
+	result[31] = product.0.is_one().into();
+	Some(result)
+}
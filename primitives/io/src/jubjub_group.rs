@@ -0,0 +1,152 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Arithmetic, Pedersen commitments and ElGamal encryption on the prime order subgroup of
+//! Jubjub.
+//!
+//! Points are 32 bytes in the encoding of Zcash and must lie in the prime order subgroup, which
+//! keeps the homomorphic operations free of small order components. Scalars are 32 bytes
+//! little-endian and must be smaller than the order of the subgroup.
+//!
+//! Commitments use the generators of the Sapling value commitment, `FindGroupHash("Zcash_cv",
+//! "v")` for the value and `FindGroupHash("Zcash_cv", "r")` for the blinding factor. ElGamal
+//! keys use the Sapling spend authorization generator `FindGroupHash("Zcash_G_", "")`.
+
+use crate::sapling::find_group_hash;
+use jubjub::{AffinePoint, ExtendedPoint, Fr};
+
+/// The BLAKE2s personalization of the commitment generators.
+const VALUE_COMMITMENT_PERSONALIZATION: &[u8; 8] = b"Zcash_cv";
+
+/// Decode a point of the prime order subgroup.
+fn read_point(bytes: &[u8; 32]) -> Option<ExtendedPoint> {
+	let point: Option<AffinePoint> = AffinePoint::from_bytes(*bytes).into();
+	let point = ExtendedPoint::from(point?);
+	bool::from(point.is_torsion_free()).then_some(point)
+}
+
+/// Decode a scalar.
+fn read_scalar(bytes: &[u8; 32]) -> Option<Fr> {
+	Fr::from_bytes(bytes).into()
+}
+
+/// Encode `point`.
+fn write_point(point: ExtendedPoint) -> [u8; 32] {
+	AffinePoint::from(point).to_bytes()
+}
+
+/// The sum of the points `a` and `b`.
+pub fn add(a: &[u8; 32], b: &[u8; 32]) -> Option<[u8; 32]> {
+	Some(write_point(read_point(a)? + read_point(b)?))
+}
+
+/// The difference of the points `a` and `b`.
+pub fn sub(a: &[u8; 32], b: &[u8; 32]) -> Option<[u8; 32]> {
+	Some(write_point(read_point(a)? - read_point(b)?))
+}
+
+/// The product of `point` and `scalar`.
+pub fn mul(point: &[u8; 32], scalar: &[u8; 32]) -> Option<[u8; 32]> {
+	Some(write_point(read_point(point)? * read_scalar(scalar)?))
+}
+
+/// The Pedersen commitment to `value` with the blinding factor `blinding`.
+pub fn pedersen_commit(value: u64, blinding: &[u8; 32]) -> Option<[u8; 32]> {
+	let value_base = find_group_hash(VALUE_COMMITMENT_PERSONALIZATION, b"v");
+	let blinding_base = find_group_hash(VALUE_COMMITMENT_PERSONALIZATION, b"r");
+	Some(write_point(value_base * Fr::from(value) + blinding_base * read_scalar(blinding)?))
+}
+
+/// The BLAKE2s personalization of the ElGamal key generator.
+const KEY_PERSONALIZATION: &[u8; 8] = b"Zcash_G_";
+/// The domain separator of the challenges of discrete log equality proofs.
+const DLEQ_DOMAIN: &[u8] = b"JubjubDLEQ";
+
+/// Decode an ElGamal ciphertext.
+fn read_ciphertext(bytes: &[u8; 64]) -> Option<(ExtendedPoint, ExtendedPoint)> {
+	let (c1, c2) = bytes.split_at(32);
+	let point = |bytes: &[u8]| read_point(bytes.try_into().expect("Both halves are 32 bytes; qed"));
+	Some((point(c1)?, point(c2)?))
+}
+
+/// Encode an ElGamal ciphertext.
+fn write_ciphertext(c1: ExtendedPoint, c2: ExtendedPoint) -> [u8; 64] {
+	let mut bytes = [0; 64];
+	bytes[..32].copy_from_slice(&write_point(c1));
+	bytes[32..].copy_from_slice(&write_point(c2));
+	bytes
+}
+
+/// The ElGamal encryption of the point `message` to `public_key` with `randomness`.
+pub fn elgamal_encrypt(
+	public_key: &[u8; 32],
+	message: &[u8; 32],
+	randomness: &[u8; 32],
+) -> Option<[u8; 64]> {
+	let randomness = read_scalar(randomness)?;
+	let generator = find_group_hash(KEY_PERSONALIZATION, b"");
+	Some(write_ciphertext(
+		generator * randomness,
+		read_point(message)? + read_point(public_key)? * randomness,
+	))
+}
+
+/// The point encrypted in `ciphertext` to the public key of `secret_key`.
+pub fn elgamal_decrypt(secret_key: &[u8; 32], ciphertext: &[u8; 64]) -> Option<[u8; 32]> {
+	let (c1, c2) = read_ciphertext(ciphertext)?;
+	Some(write_point(c2 - c1 * read_scalar(secret_key)?))
+}
+
+/// `ciphertext` to `public_key` rerandomized with `randomness`.
+pub fn elgamal_rerandomize(
+	public_key: &[u8; 32],
+	ciphertext: &[u8; 64],
+	randomness: &[u8; 32],
+) -> Option<[u8; 64]> {
+	let (c1, c2) = read_ciphertext(ciphertext)?;
+	let randomness = read_scalar(randomness)?;
+	let generator = find_group_hash(KEY_PERSONALIZATION, b"");
+	Some(write_ciphertext(c1 + generator * randomness, c2 + read_point(public_key)? * randomness))
+}
+
+/// Verify the proof that `point_1` and `point_2` have the same discrete log to `base_1` and
+/// `base_2`.
+pub fn dleq_verify(
+	base_1: &[u8; 32],
+	point_1: &[u8; 32],
+	base_2: &[u8; 32],
+	point_2: &[u8; 32],
+	proof: &[u8; 64],
+) -> bool {
+	let verify = || -> Option<bool> {
+		let (challenge, response) = proof.split_at(32);
+		let scalar =
+			|bytes: &[u8]| read_scalar(bytes.try_into().expect("Both halves are 32 bytes; qed"));
+		let (challenge, response) = (scalar(challenge)?, scalar(response)?);
+		let commitment_1 = read_point(base_1)? * response - read_point(point_1)? * challenge;
+		let commitment_2 = read_point(base_2)? * response - read_point(point_2)? * challenge;
+
+		let mut transcript = DLEQ_DOMAIN.to_vec();
+		for point in [base_1, point_1, base_2, point_2] {
+			transcript.extend_from_slice(point);
+		}
+		transcript.extend_from_slice(&write_point(commitment_1));
+		transcript.extend_from_slice(&write_point(commitment_2));
+		Some(Fr::from_bytes_wide(&sp_core::hashing::blake2_512(&transcript)) == challenge)
+	};
+	verify().unwrap_or(false)
+}
@@ -46,6 +46,7 @@ use sp_core::{
 use sp_keystore::{KeystoreExt, SyncCryptoStore};
 
 use sp_core::{
+	bandersnatch,
 	crypto::KeyTypeId,
 	ecdsa, ed25519,
 	offchain::{
@@ -81,11 +82,111 @@ mod batch_verifier;
 #[cfg(feature = "std")]
 use batch_verifier::BatchVerifier;
 
+#[cfg(feature = "std")]
+mod bandersnatch_vrf;
+
+// The signature verification of the backend not selected by `blst-signatures` is only used to
+// cross-check the other in tests.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "blst-signatures", allow(dead_code))]
+mod bls12_381;
+
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "blst-signatures"), allow(dead_code))]
+mod bls12_381_blst;
+
+#[cfg(all(feature = "std", not(feature = "blst-signatures")))]
+use bls12_381 as bls12_381_signatures;
+#[cfg(all(feature = "std", feature = "blst-signatures"))]
+use bls12_381_blst as bls12_381_signatures;
+
+#[cfg(feature = "std")]
+mod bn254;
+
+#[cfg(feature = "std")]
+mod jubjub_group;
+
+#[cfg(feature = "std")]
+mod mimc;
+
+#[cfg(feature = "std")]
+mod range_proof;
+
+#[cfg(feature = "std")]
+mod redjubjub;
+
+#[cfg(feature = "std")]
+mod sapling;
+
+#[cfg(feature = "std")]
+mod scalar_field;
+
+#[cfg(feature = "std")]
+mod thread_pool;
+
+#[cfg(feature = "std")]
+pub use thread_pool::set_crypto_threads;
+
+#[cfg(feature = "std")]
+mod vdf;
+
 pub use sp_externalities::MultiRemovalResults;
 
 #[cfg(feature = "std")]
 const LOG_TARGET: &str = "runtime::io";
 
+/// The maximum number of public inputs of a proof accepted by
+/// [`crypto::bls12_381_groth16_verify`].
+pub const BLS12_381_GROTH16_MAX_PUBLIC_INPUTS: u32 = 64;
+
+/// The maximum number of openings accepted by [`crypto::bls12_381_kzg_batch_verify`].
+pub const BLS12_381_KZG_MAX_OPENINGS: u32 = 128;
+
+/// The `τ·G2` point of the KZG setup of the EIP-4844 ceremony, compressed in the zcash encoding.
+pub const BLS12_381_KZG_SETUP_G2: [u8; 96] = [
+	0xb5, 0xbf, 0xd7, 0xdd, 0x8c, 0xde, 0xb1, 0x28, 0x84, 0x3b, 0xc2, 0x87, 0x23, 0x0a, 0xf3, 0x89,
+	0x26, 0x18, 0x70, 0x75, 0xcb, 0xfb, 0xef, 0xa8, 0x10, 0x09, 0xa2, 0xce, 0x61, 0x5a, 0xc5, 0x3d,
+	0x29, 0x14, 0xe5, 0x87, 0x0c, 0xb4, 0x52, 0xd2, 0xaf, 0xaa, 0xab, 0x24, 0xf3, 0x49, 0x9f, 0x72,
+	0x18, 0x5c, 0xbf, 0xee, 0x53, 0x49, 0x27, 0x14, 0x73, 0x44, 0x29, 0xb7, 0xb3, 0x86, 0x08, 0xe2,
+	0x39, 0x26, 0xc9, 0x11, 0xcc, 0xec, 0xea, 0xc9, 0xa3, 0x68, 0x51, 0x47, 0x7b, 0xa4, 0xc6, 0x0b,
+	0x08, 0x70, 0x41, 0xde, 0x62, 0x10, 0x00, 0xed, 0xc9, 0x8e, 0xda, 0xda, 0x20, 0xc1, 0xde, 0xf2,
+];
+
+/// The maximum number of public keys or signatures accepted by the BLS12-381 aggregation host
+/// functions, such as [`crypto::bls12_381_aggregate_verify`].
+pub const BLS12_381_MAX_AGGREGATION: u32 = 1024;
+
+/// The maximum number of pairings accepted by [`crypto::bls12_381_multi_pairing_equals`].
+pub const BLS12_381_MAX_PAIRINGS: u32 = 64;
+
+/// The maximum length in bytes of the exponent accepted by [`crypto::bls12_381_fq12_pow`].
+pub const BLS12_381_FQ12_POW_MAX_EXPONENT_LEN: u32 = 128;
+
+/// The maximum number of G1 powers accepted by [`crypto::bls12_381_powers_of_tau_verify`].
+pub const BLS12_381_POWERS_OF_TAU_MAX_POWERS: u32 = 4096;
+
+/// The maximum number of public keys of a ring accepted by
+/// [`crypto::bandersnatch_ring_commitment`].
+pub const BANDERSNATCH_MAX_RING_SIZE: u32 = 1024;
+
+/// The maximum number of inputs or outputs of [`hashing::mimc_sponge`].
+pub const MIMC_SPONGE_MAX_LEN: u32 = 256;
+
+/// The maximum length of the vectors of the scalar field vector arithmetic host functions, such
+/// as [`crypto::fr_vector_add`].
+pub const FR_VECTOR_MAX_LEN: u32 = 4096;
+
+/// The maximum number of message bits accepted by [`hashing::sapling_pedersen_hash`], six
+/// segments of 189 bits.
+pub const SAPLING_PEDERSEN_HASH_MAX_BITS: u32 = 6 * 189;
+
+/// The maximum length in bytes of each input of [`crypto::modexp`].
+pub const MODEXP_MAX_INPUT_LEN: u32 = 1024;
+
+/// The maximum number of commitments of a range proof accepted by
+/// [`crypto::bulletproofs_verify_range_proof`].
+pub const BULLETPROOFS_MAX_COMMITMENTS: u32 = 64;
+
 /// Error verifying ECDSA signature
 #[derive(Encode, Decode)]
 pub enum EcdsaVerifyError {
@@ -97,6 +198,55 @@ pub enum EcdsaVerifyError {
 	BadSignature,
 }
 
+/// Error verifying a Groth16 proof
+#[derive(Encode, Decode)]
+pub enum Groth16VerifyError {
+	/// Malformed verifying key
+	BadVerifyingKey,
+	/// Malformed proof
+	BadProof,
+	/// Public inputs that are not scalars or do not match the verifying key in number
+	BadPublicInputs,
+}
+
+/// Error checking the inputs of an aggregate BLS signature verification
+#[derive(Encode, Decode)]
+pub enum BlsAggregateVerifyError {
+	/// No public keys
+	Empty,
+	/// More than [`BLS12_381_MAX_AGGREGATION`] public keys
+	TooMany,
+	/// Different numbers of public keys and messages
+	LengthMismatch,
+	/// A public key that is malformed or the point at infinity
+	InvalidPublicKey,
+	/// Malformed signature
+	InvalidSignature,
+	/// The same message more than once
+	DuplicateMessage,
+}
+
+/// An ECVRF ciphersuite accepted by [`crypto::ecvrf_verify`]
+#[derive(Clone, Copy, PassByCodec, Encode, Decode)]
+pub enum EcvrfSuite {
+	/// `Bandersnatch_SHA-512_ELL2`, over Bandersnatch in twisted Edwards form, hashing to the
+	/// curve with Elligator 2
+	BandersnatchSha512Ell2,
+	/// `Bandersnatch_SW_SHA-512_TAI`, over Bandersnatch in short Weierstrass form, hashing to the
+	/// curve with try-and-increment
+	BandersnatchSwSha512Tai,
+}
+
+/// A scalar field of [`hashing::mimc_sponge`] and the scalar field vector arithmetic of
+/// [`crypto`], such as [`crypto::fr_vector_add`]
+#[derive(Clone, Copy, PassByCodec, Encode, Decode)]
+pub enum ScalarField {
+	/// The scalar field of BN254
+	Bn254,
+	/// The scalar field of BLS12-381
+	Bls12_381,
+}
+
 /// The outcome of calling `storage_kill`. Returned value is the number of storage items
 /// removed from the backend from making the `storage_kill` call.
 #[derive(PassByCodec, Encode, Decode)]
@@ -1073,6 +1223,859 @@ pub trait Crypto {
 			.map_err(|_| EcdsaVerifyError::BadSignature)?;
 		Ok(pubkey.serialize())
 	}
+
+	/// Verify an aggregated Bulletproofs range proof over Ristretto Pedersen commitments.
+	///
+	/// - `proof` is the serialized range proof.
+	/// - `commitments` are the compressed Ristretto value commitments. Their number must be a power
+	///   of two and at most [`BULLETPROOFS_MAX_COMMITMENTS`].
+	/// - `bits` is the bit size of the proven range and must be one of 8, 16, 32 or 64.
+	/// - `domain` is appended to the `Substrate Bulletproofs` transcript under the `domain` label
+	///   and must match the one used by the prover.
+	///
+	/// The commitments are expected to use the default Pedersen generators.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bulletproofs_verify_range_proof(
+		proof: &[u8],
+		commitments: Vec<[u8; 32]>,
+		bits: u32,
+		domain: &[u8],
+	) -> bool {
+		range_proof::verify(proof, commitments, bits, domain)
+	}
+
+	/// Compute `base ^ exponent mod modulus` over arbitrary length unsigned integers.
+	///
+	/// All integers are passed big-endian. The result is big-endian as well and left-padded
+	/// with zeros to the length of `modulus`, which matches the semantics of the EVM `MODEXP`
+	/// precompile. A zero `modulus` yields an all-zero result.
+	///
+	/// Returns `None` if any input is longer than [`MODEXP_MAX_INPUT_LEN`] bytes. Below that,
+	/// the cost still grows with the length of all three inputs, and callers are responsible
+	/// for charging weight accordingly.
+	fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Option<Vec<u8>> {
+		if [base, exponent, modulus]
+			.iter()
+			.any(|input| input.len() > MODEXP_MAX_INPUT_LEN as usize)
+		{
+			return None
+		}
+
+		let mut result = vec![0u8; modulus.len()];
+		let modulus = num_bigint::BigUint::from_bytes_be(modulus);
+		if modulus.bits() == 0 {
+			return Some(result)
+		}
+
+		let value = num_bigint::BigUint::from_bytes_be(base)
+			.modpow(&num_bigint::BigUint::from_bytes_be(exponent), &modulus)
+			.to_bytes_be();
+		let offset = result.len() - value.len();
+		result[offset..].copy_from_slice(&value);
+		Some(result)
+	}
+
+	/// Verify a Groth16 proof over BLS12-381.
+	///
+	/// `verifying_key` and `proof` are in the compressed arkworks serialization of
+	/// `ark_groth16::VerifyingKey` and `ark_groth16::Proof`, with points compressed in the zcash
+	/// encoding. Points must be in the prime order subgroup. `public_inputs` are big-endian
+	/// scalars, which must be smaller than the group order. Their number must match the
+	/// verifying key and be at most [`BLS12_381_GROTH16_MAX_PUBLIC_INPUTS`].
+	///
+	/// Returns `Ok(true)` when the verification was successful.
+	fn bls12_381_groth16_verify(
+		verifying_key: &[u8],
+		proof: &[u8],
+		public_inputs: Vec<[u8; 32]>,
+	) -> Result<bool, Groth16VerifyError> {
+		thread_pool::install(|| bls12_381::groth16_verify(verifying_key, proof, &public_inputs))
+	}
+
+	/// Prepare a Groth16 verifying key over BLS12-381 for
+	/// [`bls12_381_groth16_verify_prepared`].
+	///
+	/// `verifying_key` is encoded as in [`bls12_381_groth16_verify`]. Returns the compressed
+	/// arkworks serialization of the `ark_groth16::PreparedVerifyingKey`, or `None` if the key
+	/// is invalid.
+	fn bls12_381_groth16_prepare_verifying_key(verifying_key: &[u8]) -> Option<Vec<u8>> {
+		bls12_381::groth16_prepare_verifying_key(verifying_key)
+	}
+
+	/// Verify a Groth16 proof over BLS12-381 against a prepared verifying key.
+	///
+	/// `prepared_verifying_key` must be the unmodified output of
+	/// [`bls12_381_groth16_prepare_verifying_key`], since its precomputed values are not checked
+	/// against the key. Otherwise behaves like [`bls12_381_groth16_verify`], while saving the
+	/// pairing and line computations of the preparation.
+	fn bls12_381_groth16_verify_prepared(
+		prepared_verifying_key: &[u8],
+		proof: &[u8],
+		public_inputs: Vec<[u8; 32]>,
+	) -> Result<bool, Groth16VerifyError> {
+		thread_pool::install(|| {
+			bls12_381::groth16_verify_prepared(prepared_verifying_key, proof, &public_inputs)
+		})
+	}
+
+	/// Verify a KZG opening over BLS12-381.
+	///
+	/// Checks that `proof` shows that the polynomial committed to in `commitment` evaluates to
+	/// `value` at `point`, using the single pairing equality
+	/// `e(commitment - value·G1 + point·proof, G2) = e(proof, srs_g2)`, where `srs_g2` is `τ·G2`
+	/// for the secret `τ` of the setup.
+	///
+	/// Points are compressed in the zcash encoding and must be in the prime order subgroup.
+	/// `point` and `value` are big-endian scalars, which must be smaller than the group order.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_kzg_verify(
+		commitment: &[u8; 48],
+		point: &[u8; 32],
+		value: &[u8; 32],
+		proof: &[u8; 48],
+		srs_g2: &[u8; 96],
+	) -> bool {
+		bls12_381::kzg_verify(commitment, point, value, proof, srs_g2).unwrap_or(false)
+	}
+
+	/// Verify several KZG openings over BLS12-381 with two pairings.
+	///
+	/// Checks that each of `proofs` opens the commitment at the same index to the value at the
+	/// same index at the point at the same index, encoded as in [`bls12_381_kzg_verify`]. The
+	/// openings are checked in a random linear combination, whose coefficients are the powers
+	/// of the SHA-256 hash of `seed` followed by all commitments, points, values and proofs.
+	/// The vectors must have the same length, which must be at most
+	/// [`BLS12_381_KZG_MAX_OPENINGS`].
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_kzg_batch_verify(
+		commitments: Vec<[u8; 48]>,
+		points: Vec<[u8; 32]>,
+		values: Vec<[u8; 32]>,
+		proofs: Vec<[u8; 48]>,
+		srs_g2: &[u8; 96],
+		seed: &[u8; 32],
+	) -> bool {
+		thread_pool::install(|| {
+			bls12_381::kzg_batch_verify(&commitments, &points, &values, &proofs, srs_g2, seed)
+		})
+		.unwrap_or(false)
+	}
+
+	/// Check that a product of BLS12-381 pairings is an expected element of the target group.
+	///
+	/// Checks that the product of the pairings of the points of `g1` and `g2` at the same index
+	/// is `expected`, so that the product is compared natively instead of being returned to the
+	/// runtime. `expected` is an element of Fq12 in the arkworks serialization, 576 bytes: its
+	/// twelve coefficients from `c0.c0.c0` to `c1.c2.c1`, each a 48-byte little-endian integer
+	/// smaller than the field modulus. Points are compressed in the zcash encoding and must be in
+	/// the prime order subgroup. The vectors must have the same length, which must be at most
+	/// [`BLS12_381_MAX_PAIRINGS`].
+	///
+	/// Returns `None` if an input is invalid.
+	fn bls12_381_multi_pairing_equals(
+		g1: Vec<[u8; 48]>,
+		g2: Vec<[u8; 96]>,
+		expected: &[u8],
+	) -> Option<bool> {
+		thread_pool::install(|| bls12_381::multi_pairing_equals(&g1, &g2, expected))
+	}
+
+	/// Raise an element of the BLS12-381 Fq12 to an arbitrary power.
+	///
+	/// `f` is encoded as `expected` of [`bls12_381_multi_pairing_equals`], and need not be in the
+	/// target group. `exponent` is a big-endian integer of at most
+	/// [`BLS12_381_FQ12_POW_MAX_EXPONENT_LEN`] bytes, the empty exponent being zero.
+	///
+	/// Returns the encoded power, or `None` if an input is invalid.
+	fn bls12_381_fq12_pow(f: &[u8], exponent: &[u8]) -> Option<Vec<u8>> {
+		bls12_381::fq12_pow(f, exponent)
+	}
+
+	/// Verify a KZG opening with the setup of the EIP-4844 ceremony, as the `verify_kzg_proof`
+	/// function of the EIP-4844 specification.
+	///
+	/// Behaves like [`bls12_381_kzg_verify`] with [`BLS12_381_KZG_SETUP_G2`] as `srs_g2`.
+	fn bls12_381_eip4844_verify_kzg_proof(
+		commitment: &[u8; 48],
+		point: &[u8; 32],
+		value: &[u8; 32],
+		proof: &[u8; 48],
+	) -> bool {
+		bls12_381::kzg_verify(commitment, point, value, proof, &BLS12_381_KZG_SETUP_G2)
+			.unwrap_or(false)
+	}
+
+	/// Verify a KZG proof of a blob with the setup of the EIP-4844 ceremony, as the
+	/// `verify_blob_kzg_proof` function of the EIP-4844 specification.
+	///
+	/// `blob` must be 4096 big-endian scalars, the evaluations of the committed polynomial over
+	/// the roots of unity of that order in bit-reversed order. The opening point is derived from
+	/// the blob and the commitment by the Fiat-Shamir challenge of the specification.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_eip4844_verify_blob_kzg_proof(
+		blob: &[u8],
+		commitment: &[u8; 48],
+		proof: &[u8; 48],
+	) -> bool {
+		bls12_381::kzg_verify_blob(blob, commitment, proof, &BLS12_381_KZG_SETUP_G2)
+			.unwrap_or(false)
+	}
+
+	/// Verify a KZG opening with the semantics of the EIP-4844 point evaluation precompile.
+	///
+	/// Checks that `versioned_hash` is the SHA-256 hash of `commitment` with the first byte
+	/// replaced by the version `0x01`, and that the opening is valid as checked by
+	/// [`bls12_381_eip4844_verify_kzg_proof`].
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_eip4844_point_evaluation(
+		versioned_hash: &[u8; 32],
+		point: &[u8; 32],
+		value: &[u8; 32],
+		commitment: &[u8; 48],
+		proof: &[u8; 48],
+	) -> bool {
+		bls12_381::kzg_point_evaluation(
+			versioned_hash,
+			point,
+			value,
+			commitment,
+			proof,
+			&BLS12_381_KZG_SETUP_G2,
+		)
+		.unwrap_or(false)
+	}
+
+	/// Verify a BLS signature over BLS12-381, with the public key in G1 and the signature in G2.
+	///
+	/// Hashes `message` to G2 with the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of RFC 9380 and
+	/// the domain separation tag `dst`, and checks that
+	/// `e(public_key, H(message)) = e(G1, signature)`.
+	///
+	/// Points are compressed in the zcash encoding and must be in the prime order subgroup. The
+	/// public key must not be the point at infinity.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_verify(
+		public_key: &[u8; 48],
+		message: &[u8],
+		signature: &[u8; 96],
+		dst: &[u8],
+	) -> bool {
+		bls12_381_signatures::verify::<bls12_381::MinPk>(public_key, message, signature, dst)
+			.unwrap_or(false)
+	}
+
+	/// Verify an aggregate BLS signature over BLS12-381 of distinct messages, with the public
+	/// keys in G1 and the signature in G2.
+	///
+	/// Checks that `signature` is the sum of the signatures of each of `messages` by the public
+	/// key at the same index, with the messages hashed as in [`bls12_381_verify`], using one
+	/// pairing per message and one more. The messages must be distinct, which makes the
+	/// aggregation secure without proofs of possession.
+	///
+	/// Returns `Ok(true)` when the verification was successful, and an error if the inputs fail
+	/// one of the checks before the pairings.
+	fn bls12_381_aggregate_verify(
+		public_keys: Vec<[u8; 48]>,
+		messages: Vec<Vec<u8>>,
+		signature: &[u8; 96],
+		dst: &[u8],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		thread_pool::install(|| {
+			bls12_381_signatures::aggregate_verify::<bls12_381::MinPk>(
+				&public_keys,
+				&messages,
+				signature,
+				dst,
+			)
+		})
+	}
+
+	/// Verify an aggregate BLS signature over BLS12-381 of a single message, with the public
+	/// keys in G1 and the signature in G2.
+	///
+	/// Checks the signature against the sum of the public keys as in [`bls12_381_verify`],
+	/// using two pairings. This is only secure if each public key came with a valid proof of
+	/// possession, see [`bls12_381_pop_verify`].
+	///
+	/// Returns `Ok(true)` when the verification was successful, and an error if the inputs fail
+	/// one of the checks before the pairings.
+	fn bls12_381_fast_aggregate_verify(
+		public_keys: Vec<[u8; 48]>,
+		message: &[u8],
+		signature: &[u8; 96],
+		dst: &[u8],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		bls12_381_signatures::fast_aggregate_verify::<bls12_381::MinPk>(
+			&public_keys,
+			message,
+			signature,
+			dst,
+		)
+	}
+
+	/// Sum BLS12-381 G1 points, such as public keys.
+	///
+	/// Points are compressed in the zcash encoding and must be in the prime order subgroup.
+	///
+	/// Returns the compressed sum, or `None` if a point is invalid or there are more than
+	/// [`BLS12_381_MAX_AGGREGATION`] points.
+	fn bls12_381_aggregate_g1(points: Vec<[u8; 48]>) -> Option<[u8; 48]> {
+		bls12_381::aggregate::<ark_bls12_381::G1Affine, 48>(&points)
+	}
+
+	/// Sum BLS12-381 G2 points, such as signatures.
+	///
+	/// Behaves like [`bls12_381_aggregate_g1`].
+	fn bls12_381_aggregate_g2(points: Vec<[u8; 96]>) -> Option<[u8; 96]> {
+		bls12_381::aggregate::<ark_bls12_381::G2Affine, 96>(&points)
+	}
+
+	/// Multiply a BLS12-381 G1 point by a secret scalar in constant time.
+	///
+	/// The point is compressed in the zcash encoding and must be in the prime order subgroup,
+	/// and the scalar is 32 bytes big-endian and must be less than the group order.
+	///
+	/// The time taken depends on whether the inputs are valid but not on the scalar, so this is
+	/// meant for operations on secret keys, such as signing in offchain workers. All other
+	/// BLS12-381 host functions are variable-time and must only be given public values. The
+	/// scalar still passes through the memory of the runtime, which is up to the caller.
+	///
+	/// Returns the compressed product, or `None` if an input is invalid.
+	fn bls12_381_mul_g1_ct(point: &[u8; 48], scalar: &[u8; 32]) -> Option<[u8; 48]> {
+		bls12_381_blst::mul_g1_ct(point, scalar)
+	}
+
+	/// Multiply a BLS12-381 G2 point by a secret scalar in constant time.
+	///
+	/// Behaves like [`bls12_381_mul_g1_ct`].
+	fn bls12_381_mul_g2_ct(point: &[u8; 96], scalar: &[u8; 32]) -> Option<[u8; 96]> {
+		bls12_381_blst::mul_g2_ct(point, scalar)
+	}
+
+	/// Verify a BLS proof of possession over BLS12-381, with the public key in G1 and the proof
+	/// in G2.
+	///
+	/// The proof is the signature of the compressed `public_key` with the
+	/// `BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` domain separation tag of the proof of
+	/// possession ciphersuite, checked as in [`bls12_381_verify`].
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_pop_verify(public_key: &[u8; 48], proof: &[u8; 96]) -> bool {
+		bls12_381_signatures::pop_verify::<bls12_381::MinPk>(public_key, proof).unwrap_or(false)
+	}
+
+	/// Verify a BLS signature over BLS12-381, with the public key in G2 and the signature in G1.
+	///
+	/// Behaves like [`bls12_381_verify`] with the groups swapped, hashing `message` to G1 with
+	/// the `BLS12381G1_XMD:SHA-256_SSWU_RO_` suite.
+	fn bls12_381_verify_min_sig(
+		public_key: &[u8; 96],
+		message: &[u8],
+		signature: &[u8; 48],
+		dst: &[u8],
+	) -> bool {
+		bls12_381_signatures::verify::<bls12_381::MinSig>(public_key, message, signature, dst)
+			.unwrap_or(false)
+	}
+
+	/// Verify an aggregate BLS signature over BLS12-381 of distinct messages, with the public
+	/// keys in G2 and the signature in G1.
+	///
+	/// Behaves like [`bls12_381_aggregate_verify`] with the groups swapped.
+	fn bls12_381_aggregate_verify_min_sig(
+		public_keys: Vec<[u8; 96]>,
+		messages: Vec<Vec<u8>>,
+		signature: &[u8; 48],
+		dst: &[u8],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		thread_pool::install(|| {
+			bls12_381_signatures::aggregate_verify::<bls12_381::MinSig>(
+				&public_keys,
+				&messages,
+				signature,
+				dst,
+			)
+		})
+	}
+
+	/// Verify an aggregate BLS signature over BLS12-381 of a single message, with the public
+	/// keys in G2 and the signature in G1.
+	///
+	/// Behaves like [`bls12_381_fast_aggregate_verify`] with the groups swapped.
+	fn bls12_381_fast_aggregate_verify_min_sig(
+		public_keys: Vec<[u8; 96]>,
+		message: &[u8],
+		signature: &[u8; 48],
+		dst: &[u8],
+	) -> Result<bool, BlsAggregateVerifyError> {
+		bls12_381_signatures::fast_aggregate_verify::<bls12_381::MinSig>(
+			&public_keys,
+			message,
+			signature,
+			dst,
+		)
+	}
+
+	/// Verify a BLS proof of possession over BLS12-381, with the public key in G2 and the proof
+	/// in G1.
+	///
+	/// Behaves like [`bls12_381_pop_verify`] with the groups swapped, using the
+	/// `BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_` domain separation tag.
+	fn bls12_381_pop_verify_min_sig(public_key: &[u8; 96], proof: &[u8; 48]) -> bool {
+		bls12_381_signatures::pop_verify::<bls12_381::MinSig>(public_key, proof).unwrap_or(false)
+	}
+
+	/// Evaluate at `index` the polynomial committed to by `commitments` over BLS12-381, giving the
+	/// public key of a share of a threshold key.
+	///
+	/// `commitments` are the coefficients of the polynomial times the generator of G1, from the
+	/// constant term up, as published by a dealer of a Feldman secret sharing. The public key of
+	/// the share at index zero is that of the shared secret.
+	///
+	/// Points are compressed in the zcash encoding and must be in the prime order subgroup.
+	///
+	/// Returns the compressed public key, or `None` if a commitment is invalid or there are none
+	/// or more than [`BLS12_381_MAX_AGGREGATION`].
+	fn bls12_381_threshold_public_key(commitments: Vec<[u8; 48]>, index: u32) -> Option<[u8; 48]> {
+		bls12_381::threshold_public_key(&commitments, index)
+	}
+
+	/// Verify that `share`, a 32-byte big-endian scalar, is the evaluation at `index` of the
+	/// polynomial committed to by `commitments`, as in [`bls12_381_threshold_public_key`].
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_threshold_verify_share(
+		commitments: Vec<[u8; 48]>,
+		index: u32,
+		share: &[u8; 32],
+	) -> bool {
+		bls12_381::threshold_verify_share(&commitments, index, share).unwrap_or(false)
+	}
+
+	/// Combine BLS12-381 signatures in G2 by shares of a threshold key into the signature by the
+	/// shared secret.
+	///
+	/// Each of `signatures` is by the share at the same index of `indices`, which must be
+	/// distinct and not zero. Interpolates the shares at zero, so any threshold number of
+	/// signatures of the same message combine to the same signature. The result is only a valid
+	/// signature if the partial signatures were, which the caller should check.
+	///
+	/// Returns the compressed signature, or `None` if a signature is invalid, the indices are
+	/// not valid or there are none or more than [`BLS12_381_MAX_AGGREGATION`].
+	fn bls12_381_threshold_combine(
+		indices: Vec<u32>,
+		signatures: Vec<[u8; 96]>,
+	) -> Option<[u8; 96]> {
+		thread_pool::install(|| bls12_381::threshold_combine(&indices, &signatures))
+	}
+
+	/// Verify the signature of a drand beacon round by the group public key in G1.
+	///
+	/// The signed message is the SHA-256 hash of `previous_signature` followed by the big-endian
+	/// `round`, hashed to G2 with the `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_` domain
+	/// separation tag. Unchained beacons pass an empty `previous_signature`.
+	///
+	/// Points are compressed in the zcash encoding and must be in the prime order subgroup.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_beacon_verify(
+		group_public_key: &[u8; 48],
+		round: u64,
+		previous_signature: &[u8],
+		signature: &[u8; 96],
+	) -> bool {
+		bls12_381_signatures::beacon_verify(group_public_key, round, previous_signature, signature)
+			.unwrap_or(false)
+	}
+
+	/// Verify a contribution to a BLS12-381 powers-of-tau ceremony.
+	///
+	/// `g1_powers` and `g2_powers` are the products of the powers of the new secret `τ`, from
+	/// `τ⁰` up, with the generators of G1 and G2. The contribution multiplies the previous secret,
+	/// whose first power is `previous_tau_g1`, by a secret `τ'`, and `witness` is `τ'·G2`. The
+	/// powers are checked in a random linear combination, whose coefficients are the powers of
+	/// the SHA-256 hash of all inputs. There must be at least two powers in G2, at most as many
+	/// as in G1, and at most [`BLS12_381_POWERS_OF_TAU_MAX_POWERS`] in G1.
+	///
+	/// Points are compressed in the zcash encoding and must be in the prime order subgroup.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls12_381_powers_of_tau_verify(
+		previous_tau_g1: &[u8; 48],
+		g1_powers: Vec<[u8; 48]>,
+		g2_powers: Vec<[u8; 96]>,
+		witness: &[u8; 96],
+	) -> bool {
+		thread_pool::install(|| {
+			bls12_381::powers_of_tau_verify(previous_tau_g1, &g1_powers, &g2_powers, witness)
+		})
+		.unwrap_or(false)
+	}
+
+	/// Verify a Bandersnatch Pedersen VRF proof.
+	///
+	/// The proof shows that `output` is the VRF output of `input` and `aux_data` by the secret
+	/// key of the blinded key commitment in the proof, without revealing the key.
+	///
+	/// Returns the SHA-512 hash of the output, the VRF randomness, when the verification was
+	/// successful.
+	fn bandersnatch_pedersen_vrf_verify(
+		input: &[u8],
+		aux_data: &[u8],
+		output: &bandersnatch::VrfOutput,
+		proof: &bandersnatch::PedersenVrfProof,
+	) -> Option<[u8; 64]> {
+		bandersnatch_vrf::pedersen_verify(input, aux_data, output, proof)
+	}
+
+	/// Commit to a ring of Bandersnatch public keys, for verifying ring VRF proofs with
+	/// [`bandersnatch_ring_vrf_verify`].
+	///
+	/// `srs` is the compressed arkworks serialization of the KZG setup over BLS12-381, which
+	/// must have at least `3 · d + 1` powers of tau in G1, with `d` the smallest power of two of
+	/// at least the number of keys plus 257, and two in G2. It may have at most 6145 powers in
+	/// G1, enough for the largest rings.
+	///
+	/// Returns `None` if a key or the setup is invalid, or there are no keys or more than
+	/// [`BANDERSNATCH_MAX_RING_SIZE`].
+	fn bandersnatch_ring_commitment(
+		srs: &[u8],
+		public_keys: Vec<bandersnatch::Public>,
+	) -> Option<bandersnatch::RingCommitment> {
+		bandersnatch_vrf::ring_commitment(srs, &public_keys)
+	}
+
+	/// Verify a Bandersnatch ring VRF proof.
+	///
+	/// The proof shows that `output` is the VRF output of `input` and `aux_data` by the secret
+	/// key of one of the public keys committed to in `commitment`, without revealing which.
+	///
+	/// Returns the SHA-512 hash of the output, the VRF randomness, when the verification was
+	/// successful.
+	fn bandersnatch_ring_vrf_verify(
+		commitment: &bandersnatch::RingCommitment,
+		input: &[u8],
+		aux_data: &[u8],
+		output: &bandersnatch::VrfOutput,
+		proof: &bandersnatch::RingVrfProof,
+	) -> Option<[u8; 64]> {
+		bandersnatch_vrf::ring_verify(commitment, input, aux_data, output, proof)
+	}
+
+	/// Verify an RFC 9381 ECVRF proof of the ciphersuite `suite`.
+	///
+	/// The proof shows that `output` is the VRF output of `input` and `aux_data` by the secret
+	/// key of `public_key`. Points are compressed in the arkworks encoding, which takes 32 bytes
+	/// in twisted Edwards and 33 in short Weierstrass form. The proof is the 32-byte challenge
+	/// followed by the 32-byte response, as encoded by ark-vrf.
+	///
+	/// Returns the SHA-512 hash of the output, the VRF randomness, when the verification was
+	/// successful.
+	fn ecvrf_verify(
+		suite: EcvrfSuite,
+		public_key: &[u8],
+		input: &[u8],
+		aux_data: &[u8],
+		output: &[u8],
+		proof: &[u8; 64],
+	) -> Option<[u8; 64]> {
+		bandersnatch_vrf::ecvrf_verify(suite, public_key, input, aux_data, output, proof)
+	}
+
+	/// Verify a Wesolowski VDF proof over the RSA group of `modulus`.
+	///
+	/// Works in the group of signed quadratic residues, where an integer `v` and `modulus - v`
+	/// are identified and represented by `|v| = min(v, modulus - v)`. Checks that
+	/// `output = |challenge ^ (2 ^ iterations) mod modulus|` by verifying
+	/// `|proof ^ l * challenge ^ (2 ^ iterations mod l)| = output`. The 128-bit prime `l` is the
+	/// first candidate passing the Miller-Rabin test with the first 20 primes as bases, where
+	/// the candidates are the first 16 bytes of the blake2-256 hash of the SCALE encoded
+	/// `(b"wesolowski", modulus, iterations, challenge, output, nonce)` with their highest and
+	/// lowest bit set, for `nonce: u64` counting up from zero.
+	///
+	/// All integers are big-endian and hashed as passed. `challenge` must lie in `[1, modulus)`,
+	/// `output` and `proof` in `[1, modulus / 2]`, so that each output has a single valid
+	/// encoding.
+	///
+	/// Returns `true` when the verification was successful.
+	fn vdf_wesolowski_verify(
+		modulus: &[u8],
+		challenge: &[u8],
+		iterations: u64,
+		output: &[u8],
+		proof: &[u8],
+	) -> bool {
+		vdf::verify(modulus, challenge, iterations, output, proof)
+	}
+
+	/// Add the Jubjub points `a` and `b`.
+	///
+	/// Points are 32 bytes in the encoding of Zcash and must lie in the prime order subgroup.
+	///
+	/// Returns `None` if a point is not valid.
+	fn jubjub_add(a: &[u8; 32], b: &[u8; 32]) -> Option<[u8; 32]> {
+		jubjub_group::add(a, b)
+	}
+
+	/// Subtract the Jubjub point `b` from `a`.
+	///
+	/// Points are 32 bytes in the encoding of Zcash and must lie in the prime order subgroup.
+	///
+	/// Returns `None` if a point is not valid.
+	fn jubjub_sub(a: &[u8; 32], b: &[u8; 32]) -> Option<[u8; 32]> {
+		jubjub_group::sub(a, b)
+	}
+
+	/// Multiply the Jubjub point `point` by `scalar`.
+	///
+	/// The point is 32 bytes in the encoding of Zcash and must lie in the prime order subgroup.
+	/// The scalar is 32 bytes little-endian and must be smaller than the order of the subgroup.
+	///
+	/// Returns `None` if the point or the scalar is not valid.
+	fn jubjub_mul(point: &[u8; 32], scalar: &[u8; 32]) -> Option<[u8; 32]> {
+		jubjub_group::mul(point, scalar)
+	}
+
+	/// Commit to `value` with the blinding factor `blinding` in a Pedersen commitment over
+	/// Jubjub.
+	///
+	/// The commitment is `value·V + blinding·R` for the generators `V` and `R` of the Sapling
+	/// value commitment, so commitments can be added and subtracted with [`jubjub_add`] and
+	/// [`jubjub_sub`] to commit to the sum and difference of their values and blinding factors.
+	/// The blinding factor is a scalar, 32 bytes little-endian.
+	///
+	/// Returns `None` if the blinding factor is not a scalar.
+	fn jubjub_pedersen_commit(value: u64, blinding: &[u8; 32]) -> Option<[u8; 32]> {
+		jubjub_group::pedersen_commit(value, blinding)
+	}
+
+	/// Encrypt the Jubjub point `message` to `public_key` with ElGamal.
+	///
+	/// The ciphertext is `randomness·G` followed by `message + randomness·public_key`, where `G`
+	/// is the Sapling spend authorization generator and the public key of the secret key `s` is
+	/// `s·G`. Points and scalars are encoded as for [`jubjub_mul`].
+	///
+	/// Returns `None` if a point or the randomness is not valid.
+	fn jubjub_elgamal_encrypt(
+		public_key: &[u8; 32],
+		message: &[u8; 32],
+		randomness: &[u8; 32],
+	) -> Option<[u8; 64]> {
+		jubjub_group::elgamal_encrypt(public_key, message, randomness)
+	}
+
+	/// Decrypt the ElGamal ciphertext `ciphertext` of [`jubjub_elgamal_encrypt`] with
+	/// `secret_key`.
+	///
+	/// Returns `None` if the ciphertext or the secret key is not valid.
+	fn jubjub_elgamal_decrypt(secret_key: &[u8; 32], ciphertext: &[u8; 64]) -> Option<[u8; 32]> {
+		jubjub_group::elgamal_decrypt(secret_key, ciphertext)
+	}
+
+	/// Rerandomize the ElGamal ciphertext `ciphertext` to `public_key` with `randomness`.
+	///
+	/// The result is an encryption of the same message which cannot be linked to `ciphertext`
+	/// without the secret key.
+	///
+	/// Returns `None` if the ciphertext, the public key or the randomness is not valid.
+	fn jubjub_elgamal_rerandomize(
+		public_key: &[u8; 32],
+		ciphertext: &[u8; 64],
+		randomness: &[u8; 32],
+	) -> Option<[u8; 64]> {
+		jubjub_group::elgamal_rerandomize(public_key, ciphertext, randomness)
+	}
+
+	/// Verify a Chaum-Pedersen proof that `point_1 = x·base_1` and `point_2 = x·base_2` for the
+	/// same scalar `x`.
+	///
+	/// The proof is the challenge `c` followed by the response `s`. It is valid if `c` is the
+	/// blake2-512 hash of `b"JubjubDLEQ"`, the four points and `s·base_1 - c·point_1` and
+	/// `s·base_2 - c·point_2`, reduced modulo the order of the subgroup. Points and scalars are
+	/// encoded as for [`jubjub_mul`].
+	///
+	/// With `base_1 = G`, `point_1` the public key, `base_2` the first half of an ElGamal
+	/// ciphertext and `point_2` its second half minus the message, this proves the decryption
+	/// of a ciphertext.
+	///
+	/// Returns `true` when the verification was successful.
+	fn jubjub_dleq_verify(
+		base_1: &[u8; 32],
+		point_1: &[u8; 32],
+		base_2: &[u8; 32],
+		point_2: &[u8; 32],
+		proof: &[u8; 64],
+	) -> bool {
+		jubjub_group::dleq_verify(base_1, point_1, base_2, point_2, proof)
+	}
+
+	/// Verify a Schnorr signature over Jubjub.
+	///
+	/// This is the RedJubjub scheme of Zcash with the Sapling spend authorization generator
+	/// `G`, the generator of the keys of [`jubjub_elgamal_encrypt`]. The signature is the
+	/// encoded point `R` followed by the scalar `s`. It is valid if
+	/// `s·G = R + c·public_key`, where the challenge `c` is the BLAKE2b-512 hash of `R`, the
+	/// public key and `message` personalized with `Zcash_RedJubjubH`, reduced modulo the order
+	/// of the subgroup.
+	///
+	/// Returns `true` when the verification was successful.
+	fn jubjub_schnorr_verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+		reddsa::VerificationKey::<reddsa::sapling::SpendAuth>::try_from(*public_key)
+			.and_then(|public_key| public_key.verify(message, &(*signature).into()))
+			.is_ok()
+	}
+
+	/// Verify the spend authorization signature of a Sapling spend as zcashd does.
+	///
+	/// `signature` is a RedJubjub signature of `sighash`, the signature hash of the transaction,
+	/// by the randomized key `rk` of the spend. The signed message is `rk || sighash`, and `rk`
+	/// must not have small order.
+	///
+	/// Returns `true` when the verification was successful.
+	fn redjubjub_spend_auth_verify(
+		rk: &[u8; 32],
+		sighash: &[u8; 32],
+		signature: &[u8; 64],
+	) -> bool {
+		redjubjub::spend_auth_verify(rk, sighash, signature)
+	}
+
+	/// Verify the binding signature of a Sapling transaction as zcashd does.
+	///
+	/// `signature` is a RedJubjub signature of `sighash` by the binding verification key
+	/// `bvk`, on the generator of the blinding factors of [`jubjub_pedersen_commit`]. The
+	/// signed message is `bvk || sighash`. `bvk` is the sum of the value commitments of the
+	/// spends minus those of the outputs and of the value balance, with a blinding factor of
+	/// zero.
+	///
+	/// Returns `true` when the verification was successful.
+	fn redjubjub_binding_verify(bvk: &[u8; 32], sighash: &[u8; 32], signature: &[u8; 64]) -> bool {
+		redjubjub::binding_verify(bvk, sighash, signature)
+	}
+
+	/// Randomize the spend authorization key `ak` with the randomizer `alpha`.
+	///
+	/// Returns `rk = ak + alpha·G` for the spend authorization generator `G`, to check that a
+	/// spend is authorized by the randomization of a known key. `alpha` is a scalar, 32 bytes
+	/// little-endian.
+	///
+	/// Returns `None` if `ak` is not a point or `alpha` is not a scalar.
+	fn redjubjub_randomize(ak: &[u8; 32], alpha: &[u8; 32]) -> Option<[u8; 32]> {
+		redjubjub::randomize(ak, alpha)
+	}
+
+	/// Add two BN254 G1 points with the semantics of the EIP-196 `ECADD` precompile.
+	///
+	/// `input` is read as two points, each the 32-byte big-endian `x` and `y` coordinates, and
+	/// is padded with zeros to 128 bytes, ignoring any bytes beyond. The point at infinity is
+	/// encoded as all zeros.
+	///
+	/// Returns the encoded sum, or `None` if a coordinate is not smaller than the field modulus
+	/// or a point is not on the curve.
+	fn bn254_ec_add(input: &[u8]) -> Option<[u8; 64]> {
+		bn254::add(input)
+	}
+
+	/// Multiply a BN254 G1 point by a scalar with the semantics of the EIP-196 `ECMUL`
+	/// precompile.
+	///
+	/// `input` is read as a point, encoded as in [`bn254_ec_add`], followed by a 32-byte
+	/// big-endian scalar, and is padded with zeros to 96 bytes, ignoring any bytes beyond.
+	///
+	/// Returns the encoded product, or `None` if the point is invalid.
+	fn bn254_ec_mul(input: &[u8]) -> Option<[u8; 64]> {
+		bn254::mul(input)
+	}
+
+	/// Check a product of BN254 pairings with the semantics of the EIP-197 `ECPAIRING`
+	/// precompile.
+	///
+	/// `input` is read as a sequence of 192-byte pairs of a G1 point, encoded as in
+	/// [`bn254_ec_add`], and a G2 point, encoded as the `x` and `y` coordinates with the
+	/// imaginary part of each before its real part.
+	///
+	/// Returns one as a 32-byte big-endian integer if the product of the pairings of all pairs
+	/// is one, which includes empty input, and zero otherwise. Returns `None` if the length of
+	/// `input` is not a multiple of 192 or a point is invalid, which includes G2 points that are
+	/// not in the prime order subgroup, or if the Miller loop is zero and has no final
+	/// exponentiation.
+	///
+	/// The cost of this function grows linearly with the number of pairs. Callers are
+	/// responsible for bounding it and charging weight accordingly.
+	fn bn254_ec_pairing(input: &[u8]) -> Option<[u8; 32]> {
+		bn254::pairing(input)
+	}
+
+	/// Add the elements of `a` and `b` at the same index in the scalar field `field`.
+	///
+	/// Field elements are 32-byte big-endian integers, which must be smaller than the modulus.
+	///
+	/// Returns `None` if an element is not in the field, or if the vectors have different
+	/// lengths or are longer than [`FR_VECTOR_MAX_LEN`].
+	fn fr_vector_add(
+		field: ScalarField,
+		a: Vec<[u8; 32]>,
+		b: Vec<[u8; 32]>,
+	) -> Option<Vec<[u8; 32]>> {
+		scalar_field::vector_add(field, &a, &b)
+	}
+
+	/// Subtract the elements of `b` from those of `a` at the same index in the scalar field
+	/// `field`.
+	///
+	/// Behaves like [`fr_vector_add`].
+	fn fr_vector_sub(
+		field: ScalarField,
+		a: Vec<[u8; 32]>,
+		b: Vec<[u8; 32]>,
+	) -> Option<Vec<[u8; 32]>> {
+		scalar_field::vector_sub(field, &a, &b)
+	}
+
+	/// Multiply the elements of `a` and `b` at the same index in the scalar field `field`.
+	///
+	/// Behaves like [`fr_vector_add`].
+	fn fr_vector_mul(
+		field: ScalarField,
+		a: Vec<[u8; 32]>,
+		b: Vec<[u8; 32]>,
+	) -> Option<Vec<[u8; 32]>> {
+		scalar_field::vector_mul(field, &a, &b)
+	}
+
+	/// Compute the inner product of `a` and `b` in the scalar field `field`, the sum of the
+	/// products of their elements at the same index.
+	///
+	/// Behaves like [`fr_vector_add`].
+	fn fr_inner_product(
+		field: ScalarField,
+		a: Vec<[u8; 32]>,
+		b: Vec<[u8; 32]>,
+	) -> Option<[u8; 32]> {
+		scalar_field::vector_inner_product(field, &a, &b)
+	}
+
+	/// Evaluate the polynomial with `coefficients`, from the constant term up, at `point` in the
+	/// scalar field `field`, with Horner's rule.
+	///
+	/// Field elements are encoded as in [`fr_vector_add`]. The polynomial without coefficients
+	/// is zero.
+	///
+	/// Returns `None` if an element is not in the field, or if there are more than
+	/// [`FR_VECTOR_MAX_LEN`] coefficients.
+	fn fr_horner(
+		field: ScalarField,
+		coefficients: Vec<[u8; 32]>,
+		point: &[u8; 32],
+	) -> Option<[u8; 32]> {
+		scalar_field::evaluate(field, &coefficients, point)
+	}
 }
 
 /// Interface that provides functions for hashing with different algorithms.
@@ -1117,6 +2120,69 @@ pub trait Hashing {
 	fn twox_64(data: &[u8]) -> [u8; 8] {
 		sp_core::hashing::twox_64(data)
 	}
+
+	/// Hash `inputs` with the MiMC sponge of circomlib over the scalar field `field`.
+	///
+	/// Each input is added to the left half of the state, which is then permuted with the 220
+	/// round MiMC Feistel network keyed with `key`. The outputs are the left half after
+	/// absorbing, followed by the left half after each further permutation. Over BN254 this is
+	/// `MiMCSponge(inputs.len(), 220, outputs)` of circomlib with its round constants, as used by
+	/// Tornado Cash. Over BLS12-381 the round constants are derived in the same way.
+	///
+	/// Field elements are 32-byte big-endian integers, which must be smaller than the modulus.
+	///
+	/// Returns `None` if an element is not in the field, or if there are more than
+	/// [`MIMC_SPONGE_MAX_LEN`] inputs or no or more outputs.
+	fn mimc_sponge(
+		field: ScalarField,
+		inputs: Vec<[u8; 32]>,
+		key: &[u8; 32],
+		outputs: u32,
+	) -> Option<Vec<[u8; 32]>> {
+		mimc::mimc_sponge(field, &inputs, key, outputs)
+	}
+
+	/// Hash the first `bits` bits of `message` with the Sapling Pedersen hash over Jubjub.
+	///
+	/// This is `PedersenHash("Zcash_PH", message)` of the Zcash protocol specification, with
+	/// the message bits read least significant bit first from each byte. `message` must be
+	/// exactly as long as needed to hold `bits` bits.
+	///
+	/// Returns the u-coordinate of the hash in the little-endian encoding of Zcash, or `None`
+	/// if the length of `message` does not match or `bits` is more than
+	/// [`SAPLING_PEDERSEN_HASH_MAX_BITS`].
+	fn sapling_pedersen_hash(message: &[u8], bits: u32) -> Option<[u8; 32]> {
+		sapling::pedersen_hash(message, bits)
+	}
+
+	/// Hash the sibling nodes `left` and `right` at `depth` of a Sapling note commitment tree.
+	///
+	/// This is `MerkleCRH(depth, left, right)` of the Zcash protocol specification, where the
+	/// leaves have depth zero. Nodes are field elements of BLS12-381, encoded in 32 bytes
+	/// little-endian.
+	///
+	/// Returns `None` if a node is not in the field or `depth` does not fit in six bits.
+	fn sapling_merkle_hash(depth: u8, left: &[u8; 32], right: &[u8; 32]) -> Option<[u8; 32]> {
+		sapling::merkle_hash(depth, left, right)
+	}
+
+	/// Compute the Sapling note commitment of a note of `value` to the diversified base `g_d`
+	/// and transmission key `pk_d` with the randomness `rcm`.
+	///
+	/// This is `NoteCommit(rcm, g_d, pk_d, value)` of the Zcash protocol specification, the
+	/// Pedersen hash of `[1; 6] || value || g_d || pk_d` plus `rcm` times the generator
+	/// `FindGroupHash("Zcash_PH", "r")`. `g_d` and `pk_d` are the encoded points and are hashed
+	/// as passed, `rcm` is a scalar of Jubjub encoded in 32 bytes little-endian.
+	///
+	/// Returns the note commitment `cmu`, or `None` if `rcm` is not a scalar.
+	fn sapling_note_commitment(
+		g_d: &[u8; 32],
+		pk_d: &[u8; 32],
+		value: u64,
+		rcm: &[u8; 32],
+	) -> Option<[u8; 32]> {
+		sapling::note_commitment(g_d, pk_d, value, rcm)
+	}
 }
 
 /// Interface that provides transaction indexing API.
@@ -1919,6 +2985,1549 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn modexp_works() {
+		// 3 ^ 5 mod 7 = 5, padded to the length of the modulus.
+		assert_eq!(crypto::modexp(&[3], &[5], &[0, 7]), Some(vec![0, 5]));
+		// Empty exponent is zero.
+		assert_eq!(crypto::modexp(&[3], &[], &[7]), Some(vec![1]));
+		assert_eq!(crypto::modexp(&[3], &[], &[1]), Some(vec![0]));
+		// Zero modulus.
+		assert_eq!(crypto::modexp(&[3], &[5], &[0, 0]), Some(vec![0, 0]));
+		assert_eq!(crypto::modexp(&[3], &[5], &[]), Some(Vec::new()));
+		// 2 ^ 256 mod (2 ^ 255 + 1) = 2 ^ 255 - 1
+		let mut modulus = vec![0u8; 32];
+		modulus[0] = 0x80;
+		modulus[31] = 0x01;
+		let mut expected = vec![0xffu8; 32];
+		expected[0] = 0x7f;
+		assert_eq!(crypto::modexp(&[2], &[1, 0], &modulus), Some(expected));
+
+		// Inputs are limited in length, leading zeros included.
+		let max = MODEXP_MAX_INPUT_LEN as usize;
+		let mut modulus = vec![0u8; max];
+		modulus[max - 1] = 7;
+		assert_eq!(crypto::modexp(&[3], &[5], &modulus).unwrap()[max - 1], 5);
+		assert_eq!(crypto::modexp(&[3], &[5], &vec![0u8; max + 1]), None);
+		assert_eq!(crypto::modexp(&vec![0u8; max + 1], &[5], &[7]), None);
+		assert_eq!(crypto::modexp(&[3], &vec![0u8; max + 1], &[7]), None);
+	}
+
+	fn bls12_381_compressed(point: impl ark_serialize::CanonicalSerialize) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		point.serialize_compressed(&mut bytes).unwrap();
+		bytes
+	}
+
+	fn bls12_381_scalar(scalar: ark_bls12_381::Fr) -> [u8; 32] {
+		use ark_ff::{BigInteger, PrimeField};
+
+		scalar.into_bigint().to_bytes_be().try_into().unwrap()
+	}
+
+	/// A proof of knowledge of `a` and `b` with `a · b = c` for the public input `c`.
+	#[derive(Clone)]
+	struct MultiplierCircuit(ark_bls12_381::Fr, ark_bls12_381::Fr);
+
+	impl ark_relations::r1cs::ConstraintSynthesizer<ark_bls12_381::Fr> for MultiplierCircuit {
+		fn generate_constraints(
+			self,
+			cs: ark_relations::r1cs::ConstraintSystemRef<ark_bls12_381::Fr>,
+		) -> ark_relations::r1cs::Result<()> {
+			use ark_relations::lc;
+
+			let a = cs.new_witness_variable(|| Ok(self.0))?;
+			let b = cs.new_witness_variable(|| Ok(self.1))?;
+			let c = cs.new_input_variable(|| Ok(self.0 * self.1))?;
+			cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + c)
+		}
+	}
+
+	/// The verifying key and proof of `3 · 11 = 33`, with the public input `33`.
+	fn bls12_381_groth16_fixture() -> (Vec<u8>, Vec<u8>, [u8; 32]) {
+		use ark_bls12_381::{Bls12_381, Fr};
+		use ark_groth16::Groth16;
+
+		let mut rng = ark_std::test_rng();
+		let circuit = MultiplierCircuit(Fr::from(3u8), Fr::from(11u8));
+		let proving_key = Groth16::<Bls12_381>::generate_random_parameters_with_reduction(
+			circuit.clone(),
+			&mut rng,
+		)
+		.unwrap();
+		let proof = Groth16::<Bls12_381>::create_random_proof_with_reduction(
+			circuit,
+			&proving_key,
+			&mut rng,
+		)
+		.unwrap();
+		(
+			bls12_381_compressed(proving_key.vk),
+			bls12_381_compressed(proof),
+			bls12_381_scalar(Fr::from(33u8)),
+		)
+	}
+
+	#[test]
+	fn bls12_381_groth16_verify_works() {
+		let (verifying_key, proof, input) = bls12_381_groth16_fixture();
+		let verify = |verifying_key: &[u8], proof: &[u8], inputs: Vec<[u8; 32]>| {
+			crypto::bls12_381_groth16_verify(verifying_key, proof, inputs)
+		};
+
+		assert!(matches!(verify(&verifying_key, &proof, vec![input]), Ok(true)));
+		let other = bls12_381_scalar(34u8.into());
+		assert!(matches!(verify(&verifying_key, &proof, vec![other]), Ok(false)));
+		// The number of public inputs must match the verifying key.
+		assert!(matches!(
+			verify(&verifying_key, &proof, vec![]),
+			Err(Groth16VerifyError::BadPublicInputs)
+		));
+		assert!(matches!(
+			verify(&verifying_key, &proof, vec![input, input]),
+			Err(Groth16VerifyError::BadPublicInputs)
+		));
+		// Scalars must be smaller than the group order.
+		assert!(matches!(
+			verify(&verifying_key, &proof, vec![[0xff; 32]]),
+			Err(Groth16VerifyError::BadPublicInputs)
+		));
+		// Encodings must not have trailing bytes.
+		let trailing = |bytes: &[u8]| [bytes, &[0]].concat();
+		assert!(matches!(
+			verify(&trailing(&verifying_key), &proof, vec![input]),
+			Err(Groth16VerifyError::BadVerifyingKey)
+		));
+		assert!(matches!(
+			verify(&verifying_key, &trailing(&proof), vec![input]),
+			Err(Groth16VerifyError::BadProof)
+		));
+	}
+
+	#[test]
+	fn bls12_381_groth16_verify_prepared_works() {
+		let (verifying_key, proof, input) = bls12_381_groth16_fixture();
+		let prepared = crypto::bls12_381_groth16_prepare_verifying_key(&verifying_key).unwrap();
+		let verify = |prepared: &[u8], inputs: Vec<[u8; 32]>| {
+			crypto::bls12_381_groth16_verify_prepared(prepared, &proof, inputs)
+		};
+
+		assert!(matches!(verify(&prepared, vec![input]), Ok(true)));
+		let other = bls12_381_scalar(34u8.into());
+		assert!(matches!(verify(&prepared, vec![other]), Ok(false)));
+		assert!(matches!(verify(&prepared, vec![]), Err(Groth16VerifyError::BadPublicInputs)));
+		assert!(matches!(
+			verify(&[&prepared[..], &[0]].concat(), vec![input]),
+			Err(Groth16VerifyError::BadVerifyingKey)
+		));
+		assert!(crypto::bls12_381_groth16_prepare_verifying_key(&verifying_key[1..]).is_none());
+	}
+
+	#[test]
+	fn bls12_381_kzg_verify_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::{BigInteger, One, PrimeField};
+
+		let scalar = bls12_381_scalar;
+		// `p(X) = 3X² + 2X + 1` opened at 5, for the setup secret 7.
+		let (tau, z) = (Fr::from(7u8), Fr::from(5u8));
+		let p = |x: Fr| Fr::from(3u8) * x * x + Fr::from(2u8) * x + Fr::one();
+		let y = p(z);
+		// The quotient `(p(X) - y) / (X - z)` at the secret.
+		let q = (p(tau) - y) / (tau - z);
+
+		let g1 = |s: Fr| bls12_381_compressed(G1Affine::generator() * s);
+		let commitment: [u8; 48] = g1(p(tau)).try_into().unwrap();
+		let proof: [u8; 48] = g1(q).try_into().unwrap();
+		let srs_g2: [u8; 96] =
+			bls12_381_compressed(G2Affine::generator() * tau).try_into().unwrap();
+		let verify = |point: &[u8; 32], value: &[u8; 32], proof: &[u8; 48]| {
+			crypto::bls12_381_kzg_verify(&commitment, point, value, proof, &srs_g2)
+		};
+
+		assert!(verify(&scalar(z), &scalar(y), &proof));
+		assert!(!verify(&scalar(z), &scalar(y + Fr::one()), &proof));
+		assert!(!verify(&scalar(z + Fr::one()), &scalar(y), &proof));
+		assert!(!verify(&scalar(z), &scalar(y), &commitment));
+		// Scalars must be smaller than the group order.
+		let order = Fr::MODULUS.to_bytes_be().try_into().unwrap();
+		assert!(!verify(&order, &scalar(y), &proof));
+		// Points must be compressed.
+		assert!(!verify(&scalar(z), &scalar(y), &[0; 48]));
+	}
+
+	#[test]
+	fn bls12_381_kzg_batch_verify_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::One;
+
+		// `pᵢ(X) = (i + 1)X² + 2X + i` opened at `i + 2`, for the setup secret 7.
+		let tau = Fr::from(7u8);
+		let p = |i: u8, x: Fr| Fr::from(i + 1) * x * x + Fr::from(2u8) * x + Fr::from(i);
+		let g1 = |s: Fr| -> [u8; 48] {
+			bls12_381_compressed(G1Affine::generator() * s).try_into().unwrap()
+		};
+		let (mut commitments, mut points, mut values, mut proofs) =
+			(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+		for i in 0..3 {
+			let z = Fr::from(i + 2);
+			commitments.push(g1(p(i, tau)));
+			points.push(bls12_381_scalar(z));
+			values.push(bls12_381_scalar(p(i, z)));
+			proofs.push(g1((p(i, tau) - p(i, z)) / (tau - z)));
+		}
+		let srs_g2: [u8; 96] =
+			bls12_381_compressed(G2Affine::generator() * tau).try_into().unwrap();
+		let verify = |values: Vec<[u8; 32]>, proofs: Vec<[u8; 48]>| {
+			crypto::bls12_381_kzg_batch_verify(
+				commitments.clone(),
+				points.clone(),
+				values,
+				proofs,
+				&srs_g2,
+				&[0; 32],
+			)
+		};
+
+		assert!(verify(values.clone(), proofs.clone()));
+		let mut wrong_values = values.clone();
+		wrong_values[1] = bls12_381_scalar(p(1, Fr::from(3u8)) + Fr::one());
+		assert!(!verify(wrong_values, proofs.clone()));
+		// Errors must not cancel out in the combination.
+		let mut swapped_proofs = proofs.clone();
+		swapped_proofs.swap(0, 1);
+		assert!(!verify(values.clone(), swapped_proofs));
+		// All vectors must have the same length.
+		assert!(!verify(values[..2].to_vec(), proofs[..2].to_vec()));
+		assert!(!crypto::bls12_381_kzg_batch_verify(
+			vec![commitments[0]; BLS12_381_KZG_MAX_OPENINGS as usize + 1],
+			vec![points[0]; BLS12_381_KZG_MAX_OPENINGS as usize + 1],
+			vec![values[0]; BLS12_381_KZG_MAX_OPENINGS as usize + 1],
+			vec![proofs[0]; BLS12_381_KZG_MAX_OPENINGS as usize + 1],
+			&srs_g2,
+			&[0; 32],
+		));
+	}
+
+	#[test]
+	fn bls12_381_eip4844_verify_kzg_proof_works() {
+		// The `verify_kzg_proof_case_correct_proof_31ebd010e6098750` case of the c-kzg-4844 tests.
+		let commitment = array_bytes::hex2array_unchecked("8f59a8d2a1a625a17f3fea0fe5eb8c896db3764f3185481bc22f91b4aaffcca25f26936857bc3a7c2539ea8ec3a952b7");
+		let point = array_bytes::hex2array_unchecked(
+			"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000",
+		);
+		let value = array_bytes::hex2array_unchecked(
+			"1522a4a7f34e1ea350ae07c29c96c7e79655aa926122e95fe69fcbd932ca49e9",
+		);
+		let proof = array_bytes::hex2array_unchecked("a62ad71d14c5719385c0686f1871430475bf3a00f0aa3f7b8dd99a9abc2160744faf0070725e00b60ad9a026a15b1a8c");
+
+		assert!(crypto::bls12_381_eip4844_verify_kzg_proof(&commitment, &point, &value, &proof));
+		assert!(!crypto::bls12_381_eip4844_verify_kzg_proof(&commitment, &value, &point, &proof));
+
+		let mut versioned_hash = sp_core::hashing::sha2_256(&commitment);
+		versioned_hash[0] = 1;
+		assert!(crypto::bls12_381_eip4844_point_evaluation(
+			&versioned_hash,
+			&point,
+			&value,
+			&commitment,
+			&proof
+		));
+		versioned_hash[0] = 0;
+		assert!(!crypto::bls12_381_eip4844_point_evaluation(
+			&versioned_hash,
+			&point,
+			&value,
+			&commitment,
+			&proof
+		));
+	}
+
+	#[test]
+	fn bls12_381_eip4844_verify_blob_kzg_proof_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::{FftField, Field, One};
+
+		let g1 = |s: Fr| -> [u8; 48] {
+			bls12_381_compressed(G1Affine::generator() * s).try_into().unwrap()
+		};
+
+		// The constant polynomial one commits to the generator, and its opening proofs at any
+		// point are the point at infinity.
+		let blob = bls12_381_scalar(Fr::one()).repeat(4096);
+		let (generator, infinity) = (g1(Fr::one()), g1(0u8.into()));
+		assert!(crypto::bls12_381_eip4844_verify_blob_kzg_proof(&blob, &generator, &infinity));
+		assert!(!crypto::bls12_381_eip4844_verify_blob_kzg_proof(&blob, &infinity, &infinity));
+		assert!(!crypto::bls12_381_eip4844_verify_blob_kzg_proof(
+			&blob[32..],
+			&generator,
+			&infinity
+		));
+
+		// The roots of unity in bit-reversed order evaluate `p(X) = X`, which commits to `τ·G1`
+		// and whose opening proofs at any point are the generator.
+		let root = Fr::get_root_of_unity(4096).unwrap();
+		let mut blob: Vec<u8> = (0..4096usize)
+			.flat_map(|i| {
+				bls12_381_scalar(root.pow([(i.reverse_bits() >> (usize::BITS - 12)) as u64]))
+			})
+			.collect();
+		let tau = Fr::from(7u8);
+		let srs_g2: [u8; 96] =
+			bls12_381_compressed(G2Affine::generator() * tau).try_into().unwrap();
+		assert_eq!(bls12_381::kzg_verify_blob(&blob, &g1(tau), &generator, &srs_g2), Some(true));
+		blob[4095] ^= 1;
+		assert_eq!(bls12_381::kzg_verify_blob(&blob, &g1(tau), &generator, &srs_g2), Some(false));
+		// Scalars must be smaller than the group order.
+		blob[..32].copy_from_slice(&[0xff; 32]);
+		assert_eq!(bls12_381::kzg_verify_blob(&blob, &g1(tau), &generator, &srs_g2), None);
+	}
+
+	#[test]
+	fn bls12_381_verify_works() {
+		use ark_bls12_381::{Fq, Fq2, Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::PrimeField;
+
+		let fq = |hex: &str| Fq::from_be_bytes_mod_order(&array_bytes::hex2bytes_unchecked(hex));
+
+		// The hash of `abc` from the test vectors of RFC 9380 is its signature by the secret key
+		// one.
+		let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+		let hash = G2Affine::new(
+			Fq2::new(
+				fq("02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+				fq("139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8"),
+			),
+			Fq2::new(
+				fq("1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48"),
+				fq("00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16"),
+			),
+		);
+		let generator: [u8; 48] = bls12_381_compressed(G1Affine::generator()).try_into().unwrap();
+		let signature: [u8; 96] = bls12_381_compressed(hash).try_into().unwrap();
+		assert!(crypto::bls12_381_verify(&generator, b"abc", &signature, dst));
+
+		let secret = Fr::from(42u8);
+		let public_key: [u8; 48] =
+			bls12_381_compressed(G1Affine::generator() * secret).try_into().unwrap();
+		let signature: [u8; 96] = bls12_381_compressed(hash * secret).try_into().unwrap();
+		assert!(crypto::bls12_381_verify(&public_key, b"abc", &signature, dst));
+		assert!(!crypto::bls12_381_verify(&public_key, b"abd", &signature, dst));
+		assert!(!crypto::bls12_381_verify(&public_key, b"abc", &signature, b"other"));
+		assert!(!crypto::bls12_381_verify(&generator, b"abc", &signature, dst));
+
+		// The point at infinity is not a valid public key, even with the signature at infinity.
+		let infinity: [u8; 48] = bls12_381_compressed(G1Affine::zero()).try_into().unwrap();
+		let signature: [u8; 96] = bls12_381_compressed(G2Affine::zero()).try_into().unwrap();
+		assert!(!crypto::bls12_381_verify(&infinity, b"abc", &signature, dst));
+	}
+
+	#[test]
+	fn bls12_381_aggregate_verify_works() {
+		use ark_bls12_381::{Fr, G1Affine};
+		use ark_ec::AffineRepr;
+		use bls12_381::Variant;
+
+		let dst = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+		let hash = |message: &[u8]| bls12_381::MinPk::hash_to_curve(message, dst).unwrap();
+		let secrets = [1u8, 2, 3].map(Fr::from);
+		let public_keys: Vec<[u8; 48]> = secrets
+			.iter()
+			.map(|secret| bls12_381_compressed(G1Affine::generator() * secret).try_into().unwrap())
+			.collect();
+		let messages = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+		let signature: [u8; 96] = bls12_381_compressed(
+			secrets
+				.iter()
+				.zip(&messages)
+				.map(|(secret, message)| hash(message) * secret)
+				.sum::<ark_bls12_381::G2Projective>(),
+		)
+		.try_into()
+		.unwrap();
+		let verify = |public_keys: Vec<[u8; 48]>, messages: Vec<Vec<u8>>, signature: &[u8; 96]| {
+			crypto::bls12_381_aggregate_verify(public_keys, messages, signature, dst)
+		};
+
+		assert!(matches!(verify(public_keys.clone(), messages.clone(), &signature), Ok(true)));
+		let swapped = vec![b"b".to_vec(), b"a".to_vec(), b"c".to_vec()];
+		assert!(matches!(verify(public_keys.clone(), swapped, &signature), Ok(false)));
+		let duplicate = vec![b"a".to_vec(), b"a".to_vec(), b"c".to_vec()];
+		assert!(matches!(
+			verify(public_keys.clone(), duplicate, &signature),
+			Err(BlsAggregateVerifyError::DuplicateMessage)
+		));
+		assert!(matches!(verify(vec![], vec![], &signature), Err(BlsAggregateVerifyError::Empty)));
+		assert!(matches!(
+			verify(public_keys[..2].to_vec(), messages.clone(), &signature),
+			Err(BlsAggregateVerifyError::LengthMismatch)
+		));
+		let infinity: [u8; 48] = bls12_381_compressed(G1Affine::zero()).try_into().unwrap();
+		assert!(matches!(
+			verify(vec![infinity, public_keys[1], public_keys[2]], messages.clone(), &signature),
+			Err(BlsAggregateVerifyError::InvalidPublicKey)
+		));
+		assert!(matches!(
+			verify(public_keys.clone(), messages, &[0; 96]),
+			Err(BlsAggregateVerifyError::InvalidSignature)
+		));
+
+		let signature: [u8; 96] =
+			bls12_381_compressed(hash(b"m") * Fr::from(6u8)).try_into().unwrap();
+		let fast_verify = |public_keys: Vec<[u8; 48]>| {
+			crypto::bls12_381_fast_aggregate_verify(public_keys, b"m", &signature, dst)
+		};
+		assert!(matches!(fast_verify(public_keys.clone()), Ok(true)));
+		assert!(matches!(fast_verify(public_keys[..2].to_vec()), Ok(false)));
+		assert!(matches!(fast_verify(vec![]), Err(BlsAggregateVerifyError::Empty)));
+		assert!(matches!(
+			fast_verify(vec![public_keys[0]; BLS12_381_MAX_AGGREGATION as usize + 1]),
+			Err(BlsAggregateVerifyError::TooMany)
+		));
+	}
+
+	#[test]
+	fn bls12_381_aggregate_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+
+		let g1 = |s: u8| -> [u8; 48] {
+			bls12_381_compressed(G1Affine::generator() * Fr::from(s)).try_into().unwrap()
+		};
+		let g2 = |s: u8| -> [u8; 96] {
+			bls12_381_compressed(G2Affine::generator() * Fr::from(s)).try_into().unwrap()
+		};
+
+		assert_eq!(crypto::bls12_381_aggregate_g1(vec![g1(1), g1(2), g1(3)]), Some(g1(6)));
+		assert_eq!(crypto::bls12_381_aggregate_g1(vec![]), Some(g1(0)));
+		assert_eq!(crypto::bls12_381_aggregate_g1(vec![g1(1), [0; 48]]), None);
+		assert_eq!(
+			crypto::bls12_381_aggregate_g1(vec![g1(1); BLS12_381_MAX_AGGREGATION as usize + 1]),
+			None
+		);
+		assert_eq!(crypto::bls12_381_aggregate_g2(vec![g2(1), g2(2), g2(3)]), Some(g2(6)));
+		assert_eq!(crypto::bls12_381_aggregate_g2(vec![g2(1), [0; 96]]), None);
+	}
+
+	#[test]
+	fn bls12_381_multi_pairing_equals_works() {
+		use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+		use ark_ec::{pairing::Pairing, AffineRepr};
+
+		let g1 = |s: u8| -> [u8; 48] {
+			bls12_381_compressed(G1Affine::generator() * Fr::from(s)).try_into().unwrap()
+		};
+		let g2 = |s: u8| -> [u8; 96] {
+			bls12_381_compressed(G2Affine::generator() * Fr::from(s)).try_into().unwrap()
+		};
+		// e(2·G1, 3·G2) · e(G1, G2) = e(G1, G2)⁷
+		let expected: [u8; 576] = bls12_381_compressed(
+			Bls12_381::pairing(G1Affine::generator(), G2Affine::generator()) * Fr::from(7u8),
+		)
+		.try_into()
+		.unwrap();
+
+		assert_eq!(
+			crypto::bls12_381_multi_pairing_equals(
+				vec![g1(2), g1(1)],
+				vec![g2(3), g2(1)],
+				&expected
+			),
+			Some(true)
+		);
+		assert_eq!(
+			crypto::bls12_381_multi_pairing_equals(
+				vec![g1(2), g1(1)],
+				vec![g2(3), g2(2)],
+				&expected
+			),
+			Some(false)
+		);
+		// The empty product is one.
+		let mut one = [0; 576];
+		one[0] = 1;
+		assert_eq!(crypto::bls12_381_multi_pairing_equals(vec![], vec![], &one), Some(true));
+
+		assert_eq!(crypto::bls12_381_multi_pairing_equals(vec![g1(2)], vec![], &expected), None);
+		assert_eq!(
+			crypto::bls12_381_multi_pairing_equals(vec![[0; 48]], vec![g2(1)], &expected),
+			None
+		);
+		assert_eq!(
+			crypto::bls12_381_multi_pairing_equals(vec![g1(1)], vec![g2(1)], &[0xff; 576]),
+			None
+		);
+		assert_eq!(
+			crypto::bls12_381_multi_pairing_equals(vec![], vec![], &[one, [0; 576]].concat()),
+			None
+		);
+		assert_eq!(
+			crypto::bls12_381_multi_pairing_equals(
+				vec![g1(1); BLS12_381_MAX_PAIRINGS as usize + 1],
+				vec![g2(1); BLS12_381_MAX_PAIRINGS as usize + 1],
+				&expected,
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn bls12_381_fq12_pow_works() {
+		use ark_bls12_381::{Bls12_381, Fq12, Fr, G1Affine, G2Affine};
+		use ark_ec::{pairing::Pairing, AffineRepr};
+		use ark_ff::{BigInteger, Field, PrimeField};
+
+		let gt = Bls12_381::pairing(G1Affine::generator(), G2Affine::generator());
+		let f = bls12_381_compressed(gt);
+		assert_eq!(
+			crypto::bls12_381_fq12_pow(&f, &[7]),
+			Some(bls12_381_compressed(gt * Fr::from(7u8)))
+		);
+		// Leading zeros do not matter, and the order of the target group is its exponent.
+		assert_eq!(
+			crypto::bls12_381_fq12_pow(&f, &[0, 0, 7]),
+			crypto::bls12_381_fq12_pow(&f, &[7])
+		);
+		let one = bls12_381_compressed(Fq12::ONE);
+		assert_eq!(crypto::bls12_381_fq12_pow(&f, &Fr::MODULUS.to_bytes_be()), Some(one.clone()));
+		assert_eq!(crypto::bls12_381_fq12_pow(&f, &[]), Some(one));
+
+		// An element outside of the target group, and an exponent of more than one limb.
+		let g = Fq12::from(3u8) + gt.0;
+		assert_eq!(
+			crypto::bls12_381_fq12_pow(&bls12_381_compressed(g), &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+			Some(bls12_381_compressed(g.pow([0x0304_0506_0708_090a, 0x0102]))),
+		);
+
+		assert_eq!(crypto::bls12_381_fq12_pow(&f[1..], &[7]), None);
+		assert_eq!(crypto::bls12_381_fq12_pow(&[0xff; 576], &[7]), None);
+		assert_eq!(
+			crypto::bls12_381_fq12_pow(&f, &[1; BLS12_381_FQ12_POW_MAX_EXPONENT_LEN as usize + 1]),
+			None
+		);
+	}
+
+	#[test]
+	fn bls12_381_mul_ct_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::UniformRand;
+
+		let point = G1Affine::generator() * Fr::from(3u8);
+		let g1 = |point| -> [u8; 48] { bls12_381_compressed(point).try_into().unwrap() };
+		let g2 = |point| -> [u8; 96] { bls12_381_compressed(point).try_into().unwrap() };
+		let mut rng = ark_std::test_rng();
+		for scalar in [Fr::from(0u8), Fr::from(1u8), -Fr::from(1u8), Fr::rand(&mut rng)] {
+			assert_eq!(
+				crypto::bls12_381_mul_g1_ct(&g1(point), &bls12_381_scalar(scalar)),
+				Some(g1(point * scalar)),
+			);
+			let point = G2Affine::generator() * Fr::from(5u8);
+			assert_eq!(
+				crypto::bls12_381_mul_g2_ct(&g2(point), &bls12_381_scalar(scalar)),
+				Some(g2(point * scalar)),
+			);
+		}
+
+		// The group order is not a valid scalar.
+		let mut order = bls12_381_scalar(-Fr::from(1u8));
+		order[31] += 1;
+		assert_eq!(crypto::bls12_381_mul_g1_ct(&g1(point), &order), None);
+		assert_eq!(crypto::bls12_381_mul_g1_ct(&[0; 48], &[1; 32]), None);
+		assert_eq!(crypto::bls12_381_mul_g2_ct(&[0; 96], &[1; 32]), None);
+	}
+
+	#[test]
+	fn bls12_381_pop_verify_works() {
+		use ark_bls12_381::{Fr, G1Affine};
+		use ark_ec::AffineRepr;
+		use bls12_381::Variant;
+
+		let secret = Fr::from(42u8);
+		let public_key: [u8; 48] =
+			bls12_381_compressed(G1Affine::generator() * secret).try_into().unwrap();
+		let sign = |dst: &[u8]| -> [u8; 96] {
+			let hash = bls12_381::MinPk::hash_to_curve(&public_key, dst).unwrap();
+			bls12_381_compressed(hash * secret).try_into().unwrap()
+		};
+
+		assert!(crypto::bls12_381_pop_verify(&public_key, &sign(bls12_381::MinPk::POP_DST)));
+		// A signature of the public key as message is not a proof of possession.
+		assert!(!crypto::bls12_381_pop_verify(
+			&public_key,
+			&sign(b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_")
+		));
+		let other: [u8; 48] = bls12_381_compressed(G1Affine::generator()).try_into().unwrap();
+		assert!(!crypto::bls12_381_pop_verify(&other, &sign(bls12_381::MinPk::POP_DST)));
+	}
+
+	#[test]
+	fn bls12_381_verify_min_sig_works() {
+		use ark_bls12_381::{Fq, Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::PrimeField;
+		use bls12_381::Variant;
+
+		let fq = |hex: &str| Fq::from_be_bytes_mod_order(&array_bytes::hex2bytes_unchecked(hex));
+
+		// The hash of `abc` from the test vectors of RFC 9380 is its signature by the secret key
+		// one.
+		let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+		let hash = G1Affine::new(
+			fq("03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"),
+			fq("0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d"),
+		);
+		let generator: [u8; 96] = bls12_381_compressed(G2Affine::generator()).try_into().unwrap();
+		let signature: [u8; 48] = bls12_381_compressed(hash).try_into().unwrap();
+		assert!(crypto::bls12_381_verify_min_sig(&generator, b"abc", &signature, dst));
+		assert!(!crypto::bls12_381_verify_min_sig(&generator, b"abd", &signature, dst));
+
+		let secrets = [Fr::from(2u8), Fr::from(3u8)];
+		let public_keys: Vec<[u8; 96]> = secrets
+			.iter()
+			.map(|secret| bls12_381_compressed(G2Affine::generator() * secret).try_into().unwrap())
+			.collect();
+		let signature: [u8; 48] = bls12_381_compressed(hash * Fr::from(5u8)).try_into().unwrap();
+		assert!(matches!(
+			crypto::bls12_381_fast_aggregate_verify_min_sig(
+				public_keys.clone(),
+				b"abc",
+				&signature,
+				dst
+			),
+			Ok(true)
+		));
+		let hash_d = bls12_381::MinSig::hash_to_curve(b"abd", dst).unwrap();
+		let signature: [u8; 48] = bls12_381_compressed(hash * secrets[0] + hash_d * secrets[1])
+			.try_into()
+			.unwrap();
+		assert!(matches!(
+			crypto::bls12_381_aggregate_verify_min_sig(
+				public_keys.clone(),
+				vec![b"abc".to_vec(), b"abd".to_vec()],
+				&signature,
+				dst
+			),
+			Ok(true)
+		));
+
+		let proof: [u8; 48] = bls12_381_compressed(
+			bls12_381::MinSig::hash_to_curve(&public_keys[0], bls12_381::MinSig::POP_DST).unwrap() *
+				secrets[0],
+		)
+		.try_into()
+		.unwrap();
+		assert!(crypto::bls12_381_pop_verify_min_sig(&public_keys[0], &proof));
+		assert!(!crypto::bls12_381_pop_verify_min_sig(&public_keys[1], &proof));
+	}
+
+	#[test]
+	fn bls12_381_threshold_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use bls12_381::Variant;
+
+		let dst = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+		// The polynomial `7 + 3x + 5x²`, whose shares of the secret 7 have a threshold of three.
+		let coefficients = [7u8, 3, 5].map(Fr::from);
+		let commitments: Vec<[u8; 48]> = coefficients
+			.iter()
+			.map(|coefficient| {
+				bls12_381_compressed(G1Affine::generator() * coefficient).try_into().unwrap()
+			})
+			.collect();
+		let share = |index: u8| {
+			let x = Fr::from(index);
+			coefficients[0] + coefficients[1] * x + coefficients[2] * x * x
+		};
+
+		assert_eq!(
+			crypto::bls12_381_threshold_public_key(commitments.clone(), 0).unwrap().to_vec(),
+			bls12_381_compressed(G1Affine::generator() * coefficients[0]),
+		);
+		assert_eq!(
+			crypto::bls12_381_threshold_public_key(commitments.clone(), 2).unwrap().to_vec(),
+			bls12_381_compressed(G1Affine::generator() * share(2)),
+		);
+		assert!(crypto::bls12_381_threshold_verify_share(
+			commitments.clone(),
+			2,
+			&bls12_381_scalar(share(2))
+		));
+		assert!(!crypto::bls12_381_threshold_verify_share(
+			commitments.clone(),
+			3,
+			&bls12_381_scalar(share(2))
+		));
+		assert_eq!(crypto::bls12_381_threshold_public_key(Vec::new(), 1), None);
+
+		// Any three partial signatures combine to the signature by the secret.
+		let hash = bls12_381::MinPk::hash_to_curve(b"abc", dst).unwrap();
+		let sign =
+			|secret: Fr| -> [u8; 96] { bls12_381_compressed(hash * secret).try_into().unwrap() };
+		let signature = crypto::bls12_381_threshold_combine(
+			vec![4, 1, 3],
+			vec![sign(share(4)), sign(share(1)), sign(share(3))],
+		)
+		.unwrap();
+		assert_eq!(signature, sign(coefficients[0]));
+		let public_key: [u8; 48] = commitments[0];
+		assert!(crypto::bls12_381_verify(&public_key, b"abc", &signature, dst));
+		assert_ne!(
+			crypto::bls12_381_threshold_combine(vec![4, 1], vec![sign(share(4)), sign(share(1))]),
+			Some(signature),
+		);
+
+		assert_eq!(crypto::bls12_381_threshold_combine(vec![0], vec![sign(share(0))]), None);
+		assert_eq!(
+			crypto::bls12_381_threshold_combine(vec![1, 1], vec![sign(share(1)), sign(share(1))]),
+			None
+		);
+		let infinity: [u8; 96] = bls12_381_compressed(G2Affine::zero()).try_into().unwrap();
+		assert_eq!(crypto::bls12_381_threshold_combine(vec![1, 2], vec![infinity]), None);
+	}
+
+	#[test]
+	fn bls12_381_blst_matches_arkworks() {
+		use ark_bls12_381::{g1, Fr, G1Affine, G2Affine};
+		use ark_ec::{short_weierstrass::Affine, AffineRepr};
+		use bls12_381::{MinPk, MinSig, Variant};
+
+		let dst = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+		let long_dst = [7u8; 300];
+		let g1 = |secret: i64| -> [u8; 48] {
+			let secret =
+				if secret < 0 { -Fr::from(-secret as u64) } else { Fr::from(secret as u64) };
+			bls12_381_compressed(G1Affine::generator() * secret).try_into().unwrap()
+		};
+		let sign = |secret: u8, message: &[u8], dst: &[u8]| -> [u8; 96] {
+			bls12_381_compressed(MinPk::hash_to_curve(message, dst).unwrap() * Fr::from(secret))
+				.try_into()
+				.unwrap()
+		};
+		// A point on the curve outside of the prime order subgroup.
+		let outside: [u8; 48] = (1u64..)
+			.filter_map(|x| Affine::<g1::Config>::get_point_from_x_unchecked(x.into(), false))
+			.find(|point| !point.is_in_correct_subgroup_assuming_on_curve())
+			.map(|point| bls12_381_compressed(point).try_into().unwrap())
+			.unwrap();
+		let infinity_g1: [u8; 48] = bls12_381_compressed(G1Affine::zero()).try_into().unwrap();
+		let infinity_g2: [u8; 96] = bls12_381_compressed(G2Affine::zero()).try_into().unwrap();
+
+		let public_keys = [g1(2), g1(3), g1(-2), infinity_g1, outside, [0; 48]];
+		let signatures = [
+			sign(2, b"m", dst),
+			sign(2, b"m", &long_dst),
+			sign(3, b"n", dst),
+			sign(5, b"m", dst),
+			infinity_g2,
+			[0; 96],
+		];
+		for public_key in &public_keys {
+			for signature in &signatures {
+				for (message, dst) in [(&b"m"[..], &dst[..]), (b"n", dst), (b"m", &long_dst)] {
+					assert_eq!(
+						bls12_381::verify::<MinPk>(public_key, message, signature, dst),
+						bls12_381_blst::verify::<MinPk>(public_key, message, signature, dst),
+					);
+				}
+				let keys = [*public_key, g1(3)];
+				let messages = [b"m".to_vec(), b"n".to_vec()];
+				assert_eq!(
+					bls12_381::aggregate_verify::<MinPk>(&keys, &messages, signature, dst).encode(),
+					bls12_381_blst::aggregate_verify::<MinPk>(&keys, &messages, signature, dst)
+						.encode(),
+				);
+				assert_eq!(
+					bls12_381::fast_aggregate_verify::<MinPk>(&keys, b"m", signature, dst).encode(),
+					bls12_381_blst::fast_aggregate_verify::<MinPk>(&keys, b"m", signature, dst)
+						.encode(),
+				);
+			}
+		}
+		assert_eq!(bls12_381_blst::verify::<MinPk>(&g1(2), b"m", &signatures[0], dst), Some(true));
+		// Keys that sum to the point at infinity.
+		assert_eq!(
+			bls12_381_blst::fast_aggregate_verify::<MinPk>(
+				&[g1(2), g1(-2)],
+				b"m",
+				&infinity_g2,
+				dst
+			)
+			.encode(),
+			Ok::<_, BlsAggregateVerifyError>(false).encode(),
+		);
+
+		let public_key: [u8; 96] =
+			bls12_381_compressed(G2Affine::generator() * Fr::from(2u8)).try_into().unwrap();
+		let proof: [u8; 48] = bls12_381_compressed(
+			MinSig::hash_to_curve(&public_key, MinSig::POP_DST).unwrap() * Fr::from(2u8),
+		)
+		.try_into()
+		.unwrap();
+		for proof in [proof, g1(2), infinity_g1, outside] {
+			assert_eq!(
+				bls12_381::pop_verify::<MinSig>(&public_key, &proof),
+				bls12_381_blst::pop_verify::<MinSig>(&public_key, &proof),
+			);
+		}
+		assert_eq!(bls12_381_blst::pop_verify::<MinSig>(&public_key, &proof), Some(true));
+	}
+
+	#[test]
+	fn bls12_381_beacon_verify_works() {
+		// Round 72785 of the drand mainnet chain.
+		let group_public_key = array_bytes::hex2array_unchecked("868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31");
+		let previous_signature = array_bytes::hex2bytes_unchecked("a609e19a03c2fcc559e8dae14900aaefe517cb55c840f6e69bc8e4f66c8d18e8a609685d9917efbfb0c37f058c2de88f13d297c7e19e0ab24813079efe57a182554ff054c7638153f9b26a60e7111f71a0ff63d9571704905d3ca6df0b031747");
+		let signature = array_bytes::hex2array_unchecked("82f5d3d2de4db19d40a6980e8aa37842a0e55d1df06bd68bddc8d60002e8e959eb9cfa368b3c1b77d18f02a54fe047b80f0989315f83b12a74fd8679c4f12aae86eaf6ab5690b34f1fddd50ee3cc6f6cdf59e95526d5a5d82aaa84fa6f181e42");
+
+		assert!(crypto::bls12_381_beacon_verify(
+			&group_public_key,
+			72785,
+			&previous_signature,
+			&signature
+		));
+		assert!(!crypto::bls12_381_beacon_verify(
+			&group_public_key,
+			72786,
+			&previous_signature,
+			&signature
+		));
+		assert!(!crypto::bls12_381_beacon_verify(&group_public_key, 72785, &[], &signature));
+	}
+
+	#[test]
+	fn bls12_381_powers_of_tau_verify_works() {
+		use ark_bls12_381::{Fr, G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::Field;
+
+		let powers = |tau: u64, len: u64| (0..len).map(move |i| Fr::from(tau).pow([i]));
+		let g1 = |tau, len| -> Vec<[u8; 48]> {
+			powers(tau, len)
+				.map(|power| {
+					bls12_381_compressed(G1Affine::generator() * power).try_into().unwrap()
+				})
+				.collect()
+		};
+		let g2 = |tau, len| -> Vec<[u8; 96]> {
+			powers(tau, len)
+				.map(|power| {
+					bls12_381_compressed(G2Affine::generator() * power).try_into().unwrap()
+				})
+				.collect()
+		};
+		let (previous, witness) = (g1(3, 2)[1], g2(5, 2)[1]);
+
+		assert!(crypto::bls12_381_powers_of_tau_verify(&previous, g1(15, 8), g2(15, 3), &witness));
+		// The G2 powers may not be of another secret.
+		assert!(!crypto::bls12_381_powers_of_tau_verify(&previous, g1(15, 8), g2(14, 3), &witness));
+		// Nor may the contribution drop the previous secret.
+		assert!(!crypto::bls12_381_powers_of_tau_verify(&previous, g1(5, 8), g2(5, 3), &witness));
+		let mut tampered = g1(15, 8);
+		tampered.swap(5, 6);
+		assert!(!crypto::bls12_381_powers_of_tau_verify(&previous, tampered, g2(15, 3), &witness));
+		assert!(!crypto::bls12_381_powers_of_tau_verify(&previous, g1(15, 2), g2(15, 3), &witness));
+		// A zero secret would erase the setup.
+		assert!(!crypto::bls12_381_powers_of_tau_verify(
+			&previous,
+			g1(0, 8),
+			g2(0, 3),
+			&g2(0, 2)[1]
+		));
+	}
+
+	fn bandersnatch_compressed<const N: usize>(
+		value: impl ark_vrf::reexports::ark_serialize::CanonicalSerialize,
+	) -> [u8; N] {
+		let mut bytes = [0; N];
+		value.serialize_compressed(&mut bytes[..]).unwrap();
+		bytes
+	}
+
+	#[test]
+	fn bandersnatch_pedersen_vrf_verify_works() {
+		use ark_vrf::{pedersen::Prover, suites::bandersnatch::*};
+
+		let secret = Secret::from_seed(b"secret");
+		let input = Input::new(b"input").unwrap();
+		let output = secret.output(input);
+		let (proof, _) = secret.prove(input, output, b"data");
+		let vrf_output = bandersnatch::VrfOutput(bandersnatch_compressed(output));
+		let proof = bandersnatch::PedersenVrfProof(bandersnatch_compressed(proof));
+
+		assert_eq!(
+			crypto::bandersnatch_pedersen_vrf_verify(b"input", b"data", &vrf_output, &proof),
+			Some(output.hash().into()),
+		);
+		assert_eq!(
+			crypto::bandersnatch_pedersen_vrf_verify(b"other", b"data", &vrf_output, &proof),
+			None,
+		);
+		assert_eq!(
+			crypto::bandersnatch_pedersen_vrf_verify(b"input", b"other", &vrf_output, &proof),
+			None,
+		);
+		let other_output = bandersnatch::VrfOutput(bandersnatch_compressed(
+			Secret::from_seed(b"other").output(input),
+		));
+		assert_eq!(
+			crypto::bandersnatch_pedersen_vrf_verify(b"input", b"data", &other_output, &proof),
+			None,
+		);
+	}
+
+	#[test]
+	fn bandersnatch_ring_vrf_verify_works() {
+		use ark_vrf::{
+			reexports::ark_serialize::CanonicalSerialize, ring::Prover, suites::bandersnatch::*,
+		};
+
+		let params = RingProofParams::from_seed(4, [0; 32]);
+		let mut srs = Vec::new();
+		params.pcs.serialize_compressed(&mut srs).unwrap();
+		let secrets = (0..4u8).map(|seed| Secret::from_seed(&[seed])).collect::<Vec<_>>();
+		let ring = secrets.iter().map(|secret| secret.public().0).collect::<Vec<_>>();
+		let public_keys = ring
+			.iter()
+			.map(|public_key| bandersnatch::Public(bandersnatch_compressed(*public_key)))
+			.collect::<Vec<_>>();
+
+		let commitment = crypto::bandersnatch_ring_commitment(&srs, public_keys.clone()).unwrap();
+		assert_eq!(commitment.ring_size, 4);
+		assert_eq!(commitment.verifier_key, bandersnatch_compressed(params.verifier_key(&ring)));
+
+		let input = Input::new(b"input").unwrap();
+		let output = secrets[2].output(input);
+		let prover = params.prover(params.prover_key(&ring), 2);
+		let proof = secrets[2].prove(input, output, b"data", &prover);
+		let vrf_output = bandersnatch::VrfOutput(bandersnatch_compressed(output));
+		let proof = bandersnatch::RingVrfProof(bandersnatch_compressed(proof));
+
+		assert_eq!(
+			crypto::bandersnatch_ring_vrf_verify(
+				&commitment,
+				b"input",
+				b"data",
+				&vrf_output,
+				&proof
+			),
+			Some(output.hash().into()),
+		);
+		assert_eq!(
+			crypto::bandersnatch_ring_vrf_verify(
+				&commitment,
+				b"input",
+				b"other",
+				&vrf_output,
+				&proof
+			),
+			None,
+		);
+		// The proof is not valid for a ring without the key.
+		let mut other_keys = public_keys.clone();
+		other_keys[2] =
+			bandersnatch::Public(bandersnatch_compressed(Secret::from_seed(b"other").public().0));
+		let other_commitment = crypto::bandersnatch_ring_commitment(&srs, other_keys).unwrap();
+		assert_eq!(
+			crypto::bandersnatch_ring_vrf_verify(
+				&other_commitment,
+				b"input",
+				b"data",
+				&vrf_output,
+				&proof
+			),
+			None,
+		);
+
+		assert_eq!(crypto::bandersnatch_ring_commitment(&srs, Vec::new()), None);
+		assert_eq!(crypto::bandersnatch_ring_commitment(&srs[1..], public_keys), None);
+	}
+
+	#[test]
+	fn ecvrf_verify_works() {
+		use ark_vrf::{
+			ietf::{IetfSuite, Prover},
+			reexports::ark_serialize::CanonicalSerialize,
+			suites::{bandersnatch, bandersnatch_sw},
+			Input, Secret,
+		};
+
+		fn prove<S: IetfSuite>() -> (Vec<u8>, Vec<u8>, [u8; 64], [u8; 64]) {
+			let secret = Secret::<S>::from_seed(b"secret");
+			let input = Input::<S>::new(b"input").unwrap();
+			let output = secret.output(input);
+			let proof = secret.prove(input, output, b"data");
+			let mut public_key = Vec::new();
+			secret.public().serialize_compressed(&mut public_key).unwrap();
+			let mut output_bytes = Vec::new();
+			output.serialize_compressed(&mut output_bytes).unwrap();
+			(
+				public_key,
+				output_bytes,
+				bandersnatch_compressed(proof),
+				output.hash().as_slice().try_into().unwrap(),
+			)
+		}
+
+		for (suite, (public_key, output, proof, hash)) in [
+			(EcvrfSuite::BandersnatchSha512Ell2, prove::<bandersnatch::BandersnatchSha512Ell2>()),
+			(
+				EcvrfSuite::BandersnatchSwSha512Tai,
+				prove::<bandersnatch_sw::BandersnatchSha512Tai>(),
+			),
+		] {
+			assert_eq!(
+				crypto::ecvrf_verify(suite, &public_key, b"input", b"data", &output, &proof),
+				Some(hash),
+			);
+			assert_eq!(
+				crypto::ecvrf_verify(suite, &public_key, b"other", b"data", &output, &proof),
+				None,
+			);
+			assert_eq!(
+				crypto::ecvrf_verify(suite, &public_key, b"input", b"other", &output, &proof),
+				None,
+			);
+			let mut long_output = output.clone();
+			long_output.push(0);
+			assert_eq!(
+				crypto::ecvrf_verify(suite, &public_key, b"input", b"data", &long_output, &proof),
+				None,
+			);
+		}
+
+		// A proof of one suite does not verify in the other.
+		let (public_key, output, proof, _) = prove::<bandersnatch::BandersnatchSha512Ell2>();
+		assert_eq!(
+			crypto::ecvrf_verify(
+				EcvrfSuite::BandersnatchSwSha512Tai,
+				&public_key,
+				b"input",
+				b"data",
+				&output,
+				&proof
+			),
+			None,
+		);
+	}
+
+	#[test]
+	fn vdf_wesolowski_verify_works() {
+		use num_bigint::BigUint;
+
+		// 1_000_000_007 * 998_244_353
+		let modulus = 998_244_359_987_710_471u64.to_be_bytes();
+		let n = BigUint::from_bytes_be(&modulus);
+		let challenge = [42u8];
+		let iterations = 100;
+
+		let x = BigUint::from_bytes_be(&challenge);
+		let exponent = BigUint::from(1u32) << iterations;
+		let signed = |value: BigUint| value.clone().min(&n - value);
+		let output = signed(x.modpow(&exponent, &n)).to_bytes_be();
+		let l = vdf::hash_to_prime(&modulus, iterations, &challenge, &output);
+		let proof = signed(x.modpow(&(&exponent / l), &n)).to_bytes_be();
+
+		assert!(crypto::vdf_wesolowski_verify(&modulus, &challenge, iterations, &output, &proof));
+		assert!(!crypto::vdf_wesolowski_verify(&modulus, &challenge, 99, &output, &proof));
+		assert!(!crypto::vdf_wesolowski_verify(&modulus, &[43], iterations, &output, &proof));
+		assert!(!crypto::vdf_wesolowski_verify(&modulus, &challenge, iterations, &proof, &output));
+		assert!(!crypto::vdf_wesolowski_verify(&modulus, &modulus, iterations, &output, &proof));
+		assert!(!crypto::vdf_wesolowski_verify(&[], &challenge, iterations, &output, &proof));
+	}
+
+	#[test]
+	fn vdf_wesolowski_verify_rejects_negated_outputs() {
+		use num_bigint::BigUint;
+
+		let modulus = 998_244_359_987_710_471u64.to_be_bytes();
+		let n = BigUint::from_bytes_be(&modulus);
+		let challenge = [42u8];
+		let iterations = 100;
+		let x = BigUint::from_bytes_be(&challenge);
+		let exponent = BigUint::from(1u32) << iterations;
+
+		// Either the output or its negation, each with the proof for its own challenge prime.
+		let candidates = [x.modpow(&exponent, &n), &n - x.modpow(&exponent, &n)].map(|y| {
+			let output = y.to_bytes_be();
+			let l = vdf::hash_to_prime(&modulus, iterations, &challenge, &output);
+			let r = BigUint::from(2u32).modpow(&BigUint::from(iterations), &l);
+			let mut pi = x.modpow(&(&exponent / &l), &n);
+			// `l` is odd, so negating the proof negates the result.
+			if pi.modpow(&l, &n) * x.modpow(&r, &n) % &n != y {
+				pi = &n - pi;
+			}
+			assert_eq!(pi.modpow(&l, &n) * x.modpow(&r, &n) % &n, y);
+			(y, pi)
+		});
+
+		// Both satisfy the equation in `Z_n*`, but only the smaller representative is accepted.
+		for (y, pi) in candidates {
+			let canonical = y <= &n >> 1u32;
+			let signed_pi = pi.clone().min(&n - &pi);
+			assert_eq!(
+				crypto::vdf_wesolowski_verify(
+					&modulus,
+					&challenge,
+					iterations,
+					&y.to_bytes_be(),
+					&signed_pi.to_bytes_be(),
+				),
+				canonical,
+			);
+			assert!(!crypto::vdf_wesolowski_verify(
+				&modulus,
+				&challenge,
+				iterations,
+				&y.to_bytes_be(),
+				&(&n - &signed_pi).to_bytes_be(),
+			));
+		}
+	}
+
+	#[test]
+	fn fr_vector_arithmetic_works() {
+		use ark_ff::{BigInteger, PrimeField};
+
+		fn scalar<F: PrimeField>(value: F) -> [u8; 32] {
+			value.into_bigint().to_bytes_be().try_into().unwrap()
+		}
+		fn check<F: PrimeField>(field: ScalarField) {
+			let a = [F::from(3u8), -F::from(1u8), F::from(7u8)];
+			let b = [F::from(5u8), F::from(2u8), -F::from(4u8)];
+			let (ea, eb) = (a.map(scalar).to_vec(), b.map(scalar).to_vec());
+			let expect = |op: fn(F, F) -> F| Some((0..3).map(|i| scalar(op(a[i], b[i]))).collect());
+
+			assert_eq!(crypto::fr_vector_add(field, ea.clone(), eb.clone()), expect(|a, b| a + b));
+			assert_eq!(crypto::fr_vector_sub(field, ea.clone(), eb.clone()), expect(|a, b| a - b));
+			assert_eq!(crypto::fr_vector_mul(field, ea.clone(), eb.clone()), expect(|a, b| a * b));
+			// 3·5 - 2 - 28
+			assert_eq!(
+				crypto::fr_inner_product(field, ea.clone(), eb.clone()),
+				Some(scalar(-F::from(15u8)))
+			);
+			// 3 - x + 7·x² at 2
+			assert_eq!(
+				crypto::fr_horner(field, ea.clone(), &scalar(F::from(2u8))),
+				Some(scalar(F::from(29u8)))
+			);
+			assert_eq!(crypto::fr_horner(field, vec![], &ea[0]), Some([0; 32]));
+
+			assert_eq!(crypto::fr_vector_add(field, ea.clone(), eb[..2].to_vec()), None);
+			assert_eq!(crypto::fr_inner_product(field, ea.clone(), vec![[0xff; 32]; 3]), None);
+			assert_eq!(crypto::fr_horner(field, ea.clone(), &[0xff; 32]), None);
+			let long = vec![[0; 32]; FR_VECTOR_MAX_LEN as usize + 1];
+			assert_eq!(crypto::fr_vector_mul(field, long.clone(), long.clone()), None);
+			assert_eq!(crypto::fr_horner(field, long, &ea[0]), None);
+		}
+
+		check::<ark_bn254::Fr>(ScalarField::Bn254);
+		check::<ark_bls12_381::Fr>(ScalarField::Bls12_381);
+	}
+
+	#[test]
+	fn mimc_sponge_works() {
+		use ark_ff::{BigInteger, PrimeField};
+
+		// The zero leaf of the Tornado Cash Merkle tree and the roots of its empty subtrees of
+		// depth one and two, as hardcoded in its `MerkleTreeWithHistory` contract.
+		let zeros: [[u8; 32]; 3] = [
+			array_bytes::hex2array_unchecked(
+				"2fe54c60d3acabf3343a35b6eba15db4821b340f76e741e2249685ed4899af6c",
+			),
+			array_bytes::hex2array_unchecked(
+				"256a6135777eee2fd26f54b8b7037a25439d5235caee224154186d2b8a52e31d",
+			),
+			array_bytes::hex2array_unchecked(
+				"1151949895e82ab19924de92c40a3d6f7bcb60d92b00504b8199613683f0c200",
+			),
+		];
+		// The zero leaf is keccak-256 of `tornado` modulo the field.
+		assert_eq!(
+			ark_bn254::Fr::from_be_bytes_mod_order(&hashing::keccak_256(b"tornado"))
+				.into_bigint()
+				.to_bytes_be(),
+			zeros[0],
+		);
+		// Tornado Cash hashes the children of a node with `MiMCSponge(2, 220, 1)`.
+		let hash = |field, left, right| {
+			hashing::mimc_sponge(field, vec![left, right], &[0; 32], 1).map(|outputs| outputs[0])
+		};
+		assert_eq!(hash(ScalarField::Bn254, zeros[0], zeros[0]), Some(zeros[1]));
+		assert_eq!(hash(ScalarField::Bn254, zeros[1], zeros[1]), Some(zeros[2]));
+
+		// Squeezing more outputs starts with the same one.
+		let outputs =
+			hashing::mimc_sponge(ScalarField::Bn254, vec![zeros[0], zeros[0]], &[0; 32], 3)
+				.unwrap();
+		assert_eq!(outputs.len(), 3);
+		assert_eq!(outputs[0], zeros[1]);
+		// The key changes the hash, and so does the field.
+		assert_ne!(
+			hashing::mimc_sponge(ScalarField::Bn254, vec![zeros[0], zeros[0]], &[1; 32], 1),
+			Some(vec![zeros[1]]),
+		);
+		assert_ne!(hash(ScalarField::Bls12_381, zeros[0], zeros[0]), Some(zeros[1]));
+
+		let modulus: [u8; 32] = ark_bn254::Fr::MODULUS.to_bytes_be().try_into().unwrap();
+		assert_eq!(hash(ScalarField::Bn254, modulus, zeros[0]), None);
+		assert_eq!(hashing::mimc_sponge(ScalarField::Bn254, vec![zeros[0]], &[0; 32], 0), None);
+		assert_eq!(hashing::mimc_sponge(ScalarField::Bn254, vec![[0; 32]; 257], &[0; 32], 1), None);
+	}
+
+	#[test]
+	fn sapling_pedersen_hash_works() {
+		// The uncommitted leaf of the Sapling note commitment tree and the roots of its empty
+		// subtrees of depth one to three, as in zcashd.
+		let roots: [[u8; 32]; 4] = [
+			array_bytes::hex2array_unchecked(
+				"0100000000000000000000000000000000000000000000000000000000000000",
+			),
+			array_bytes::hex2array_unchecked(
+				"817de36ab2d57feb077634bca77819c8e0bd298c04f6fed0e6a83cc1356ca155",
+			),
+			array_bytes::hex2array_unchecked(
+				"ffe9fc03f18b176c998806439ff0bb8ad193afdb27b2ccbc88856916dd804e34",
+			),
+			array_bytes::hex2array_unchecked(
+				"d8283386ef2ef07ebdbb4383c12a739a953a4d6e0d6fb1139a4036d693bfbb6c",
+			),
+		];
+		for depth in 0..3 {
+			assert_eq!(
+				hashing::sapling_merkle_hash(depth, &roots[depth as usize], &roots[depth as usize]),
+				Some(roots[depth as usize + 1]),
+			);
+		}
+		assert_eq!(hashing::sapling_merkle_hash(64, &roots[0], &roots[0]), None);
+		assert_eq!(hashing::sapling_merkle_hash(0, &[0xff; 32], &roots[0]), None);
+
+		// Pack bits least significant bit first.
+		let pack = |bits: &[bool]| {
+			let mut bytes = vec![0u8; (bits.len() + 7) / 8];
+			for (index, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+				bytes[index / 8] |= 1 << (index % 8);
+			}
+			(bytes, bits.len() as u32)
+		};
+		let unpack = |bytes: &[u8], len: usize| {
+			bytes
+				.iter()
+				.flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1 == 1))
+				.take(len)
+				.collect::<Vec<_>>()
+		};
+
+		// The Merkle tree hash is the Pedersen hash of the depth and both nodes.
+		let mut bits = unpack(&[2], 6);
+		bits.extend(unpack(&roots[2], 255));
+		bits.extend(unpack(&roots[1], 255));
+		let (message, len) = pack(&bits);
+		assert_eq!(
+			hashing::sapling_pedersen_hash(&message, len),
+			hashing::sapling_merkle_hash(2, &roots[2], &roots[1]),
+		);
+		assert_eq!(hashing::sapling_pedersen_hash(&message, len + 8), None);
+		assert_eq!(hashing::sapling_pedersen_hash(&[0; 142], 1135), None);
+		assert!(hashing::sapling_pedersen_hash(&[0; 142], 1134).is_some());
+
+		// Without randomness the note commitment is the Pedersen hash of the note.
+		let (g_d, pk_d) = (roots[1], roots[3]);
+		let mut bits = vec![true; 6];
+		bits.extend(unpack(&7u64.to_le_bytes(), 64));
+		bits.extend(unpack(&g_d, 256));
+		bits.extend(unpack(&pk_d, 256));
+		let (message, len) = pack(&bits);
+		assert_eq!(
+			hashing::sapling_note_commitment(&g_d, &pk_d, 7, &[0; 32]),
+			hashing::sapling_pedersen_hash(&message, len),
+		);
+		let commitment = hashing::sapling_note_commitment(&g_d, &pk_d, 7, &[1; 32]).unwrap();
+		assert_ne!(Some(commitment), hashing::sapling_pedersen_hash(&message, len));
+		assert_ne!(Some(commitment), hashing::sapling_note_commitment(&g_d, &pk_d, 8, &[1; 32]));
+		assert_eq!(hashing::sapling_note_commitment(&g_d, &pk_d, 7, &[0xff; 32]), None);
+	}
+
+	#[test]
+	fn jubjub_pedersen_commit_works() {
+		let scalar = |value: u64| {
+			let mut bytes = [0; 32];
+			bytes[..8].copy_from_slice(&value.to_le_bytes());
+			bytes
+		};
+		let commit = |value, blinding| crypto::jubjub_pedersen_commit(value, &scalar(blinding));
+
+		// The blinding generator is the Sapling binding signature base point.
+		assert_eq!(
+			commit(0, 1),
+			Some(array_bytes::hex2array_unchecked(
+				"8b6a0b38b9faae3c3b803b47b0f146ad50ab221e6e2afbe6dbde45cba9d381ed"
+			)),
+		);
+
+		// Commitments add and subtract homomorphically.
+		let (a, b) = (commit(5, 11).unwrap(), commit(3, 7).unwrap());
+		assert_eq!(crypto::jubjub_add(&a, &b), commit(8, 18));
+		assert_eq!(crypto::jubjub_sub(&a, &b), commit(2, 4));
+		assert_eq!(crypto::jubjub_mul(&a, &scalar(3)), commit(15, 33));
+		assert_ne!(commit(5, 11), commit(5, 12));
+		assert_ne!(commit(5, 11), commit(6, 11));
+
+		// The identity is a commitment to zero with no blinding.
+		let identity = crypto::jubjub_sub(&a, &a).unwrap();
+		assert_eq!(commit(0, 0), Some(identity));
+		assert_eq!(crypto::jubjub_add(&a, &identity), Some(a));
+
+		// Scalars must be reduced and points must lie in the prime order subgroup.
+		assert_eq!(crypto::jubjub_pedersen_commit(1, &[0xff; 32]), None);
+		assert_eq!(crypto::jubjub_mul(&a, &[0xff; 32]), None);
+		// The point of order two, `(0, -1)`.
+		let small_order = (-jubjub::Fq::one()).to_bytes();
+		assert_eq!(crypto::jubjub_add(&a, &small_order), None);
+		assert_eq!(crypto::jubjub_add(&a, &[0xff; 32]), None);
+	}
+
+	#[test]
+	fn jubjub_elgamal_works() {
+		use jubjub::{AffinePoint, ExtendedPoint, Fr};
+
+		let point = |point: ExtendedPoint| AffinePoint::from(point).to_bytes();
+		// The Sapling spend authorization generator.
+		let generator: ExtendedPoint = AffinePoint::from_bytes(array_bytes::hex2array_unchecked(
+			"30b5f2aaad325630bcdddbce4d67656d05fd1cc2d037bb5375b6e96d9e01a1d7",
+		))
+		.unwrap()
+		.into();
+		let (secret_key, randomness) = (Fr::from(7u64), Fr::from(11u64));
+		let public_key = point(generator * secret_key);
+		let message = point(generator * Fr::from(42u64));
+
+		let ciphertext =
+			crypto::jubjub_elgamal_encrypt(&public_key, &message, &randomness.to_bytes()).unwrap();
+		assert_eq!(&ciphertext[..32], &point(generator * randomness)[..]);
+		assert_eq!(
+			crypto::jubjub_elgamal_decrypt(&secret_key.to_bytes(), &ciphertext),
+			Some(message),
+		);
+		assert_ne!(
+			crypto::jubjub_elgamal_decrypt(&Fr::from(8u64).to_bytes(), &ciphertext),
+			Some(message),
+		);
+
+		let rerandomized = crypto::jubjub_elgamal_rerandomize(
+			&public_key,
+			&ciphertext,
+			&Fr::from(3u64).to_bytes(),
+		)
+		.unwrap();
+		assert_ne!(rerandomized, ciphertext);
+		assert_eq!(
+			crypto::jubjub_elgamal_decrypt(&secret_key.to_bytes(), &rerandomized),
+			Some(message),
+		);
+		assert_eq!(crypto::jubjub_elgamal_encrypt(&public_key, &[0xff; 32], &[0; 32]), None);
+		assert_eq!(crypto::jubjub_elgamal_decrypt(&[0xff; 32], &ciphertext), None);
+
+		// Prove the decryption: the public key and the second half of the ciphertext minus the
+		// message have the same discrete log to the generator and the first half.
+		let c1: [u8; 32] = ciphertext[..32].try_into().unwrap();
+		let shared = crypto::jubjub_sub(ciphertext[32..].try_into().unwrap(), &message).unwrap();
+		let statement = [point(generator), public_key, c1, shared];
+		let prove = |secret_key: Fr| {
+			let nonce = Fr::from(5u64);
+			let mut transcript = b"JubjubDLEQ".to_vec();
+			statement.iter().for_each(|point| transcript.extend_from_slice(point));
+			transcript.extend_from_slice(&point(generator * nonce));
+			transcript.extend_from_slice(&point(
+				ExtendedPoint::from(AffinePoint::from_bytes(c1).unwrap()) * nonce,
+			));
+			let challenge = Fr::from_bytes_wide(&sp_core::hashing::blake2_512(&transcript));
+			let mut proof = [0; 64];
+			proof[..32].copy_from_slice(&challenge.to_bytes());
+			proof[32..].copy_from_slice(&(nonce + challenge * secret_key).to_bytes());
+			proof
+		};
+		let verify = |proof| {
+			crypto::jubjub_dleq_verify(
+				&statement[0],
+				&statement[1],
+				&statement[2],
+				&statement[3],
+				&proof,
+			)
+		};
+		assert!(verify(prove(secret_key)));
+		assert!(!verify(prove(Fr::from(8u64))));
+		assert!(!verify([0xff; 64]));
+	}
+
+	#[test]
+	fn jubjub_schnorr_verify_works() {
+		// A spend authorization signature of the librustzcash test vectors.
+		let message: [u8; 32] = array_bytes::hex2array_unchecked(
+			"101cbe4b9c42604f04c703c396f788c6cb2d6d7d58f45430b12eb2edd640076c",
+		);
+		let signature: [u8; 64] = array_bytes::hex2array_unchecked(
+			"b8489300b82a701784a1929a60e4791339393b8ed1e9971d09378e99477ccb4c\
+			cbb7a5271431e618a2e89cb073b8bf7a1b67f30fe248b1db43b05f1b6c28450a",
+		);
+		let public_key: [u8; 32] = array_bytes::hex2array_unchecked(
+			"b5f1895d2e2a4dec2af0fe9c6692ec2ca65d55e4765a6d8a19462eca3dffd804",
+		);
+
+		assert!(crypto::jubjub_schnorr_verify(&public_key, &message, &signature));
+		assert!(!crypto::jubjub_schnorr_verify(&public_key, &message[1..], &signature));
+		let mut tampered = signature;
+		tampered[40] ^= 1;
+		assert!(!crypto::jubjub_schnorr_verify(&public_key, &message, &tampered));
+		assert!(!crypto::jubjub_schnorr_verify(&[0xff; 32], &message, &signature));
+	}
+
+	#[test]
+	fn redjubjub_verify_works() {
+		use ark_std::rand::{rngs::StdRng, SeedableRng};
+		use reddsa::{
+			sapling::{Binding, SpendAuth},
+			SigningKey, VerificationKey,
+		};
+
+		let sighash = [7; 32];
+		let sign = |key: &[u8; 32], message: &[u8]| -> [u8; 64] {
+			SigningKey::<SpendAuth>::try_from(*key)
+				.unwrap()
+				.sign(StdRng::seed_from_u64(0), message)
+				.into()
+		};
+
+		// The spend is authorized by `ask` randomized with `alpha`.
+		let (ask, alpha) = ([3; 32], jubjub::Fr::from(5u64));
+		let ak: [u8; 32] =
+			VerificationKey::from(&SigningKey::<SpendAuth>::try_from(ask).unwrap()).into();
+		let rsk: [u8; 32] =
+			SigningKey::<SpendAuth>::try_from(ask).unwrap().randomize(&alpha).into();
+		let rk = crypto::redjubjub_randomize(&ak, &alpha.to_bytes()).unwrap();
+		assert_eq!(
+			rk,
+			<[u8; 32]>::from(VerificationKey::from(
+				&SigningKey::<SpendAuth>::try_from(rsk).unwrap()
+			)),
+		);
+
+		let message = [&rk[..], &sighash[..]].concat();
+		assert!(crypto::redjubjub_spend_auth_verify(&rk, &sighash, &sign(&rsk, &message)));
+		// The signature must cover the key, and be made by the randomized key.
+		assert!(!crypto::redjubjub_spend_auth_verify(&rk, &sighash, &sign(&rsk, &sighash)));
+		assert!(!crypto::redjubjub_spend_auth_verify(&rk, &[8; 32], &sign(&rsk, &message)));
+		let message = [&ak[..], &sighash[..]].concat();
+		assert!(!crypto::redjubjub_spend_auth_verify(&rk, &sighash, &sign(&ask, &message)));
+		assert!(crypto::redjubjub_spend_auth_verify(&ak, &sighash, &sign(&ask, &message)));
+		assert_eq!(crypto::redjubjub_randomize(&ak, &[0xff; 32]), None);
+
+		// Binding keys are on the value commitment generator of the blinding factor.
+		let bsk = jubjub::Fr::from(11u64);
+		let bvk = crypto::jubjub_pedersen_commit(0, &bsk.to_bytes()).unwrap();
+		let message = [&bvk[..], &sighash[..]].concat();
+		let signature: [u8; 64] = SigningKey::<Binding>::try_from(bsk.to_bytes())
+			.unwrap()
+			.sign(StdRng::seed_from_u64(0), &message)
+			.into();
+		assert!(crypto::redjubjub_binding_verify(&bvk, &sighash, &signature));
+		assert!(!crypto::redjubjub_binding_verify(&bvk, &[8; 32], &signature));
+		assert!(!crypto::redjubjub_spend_auth_verify(&bvk, &sighash, &signature));
+	}
+
+	fn bn254_g1(point: ark_bn254::G1Affine) -> Vec<u8> {
+		use ark_ff::{BigInteger, PrimeField};
+
+		let (x, y) = ark_ec::AffineRepr::xy(&point).unwrap();
+		[x.into_bigint().to_bytes_be(), y.into_bigint().to_bytes_be()].concat()
+	}
+
+	fn bn254_g2(point: ark_bn254::G2Affine) -> Vec<u8> {
+		use ark_ff::{BigInteger, PrimeField};
+
+		let (x, y) = ark_ec::AffineRepr::xy(&point).unwrap();
+		[x.c1, x.c0, y.c1, y.c0]
+			.iter()
+			.flat_map(|c| c.into_bigint().to_bytes_be())
+			.collect()
+	}
+
+	#[test]
+	fn bn254_ec_add_works() {
+		use ark_bn254::G1Affine;
+		use ark_ec::{AffineRepr, CurveGroup};
+
+		let generator = bn254_g1(G1Affine::generator());
+		let doubled = bn254_g1((G1Affine::generator() + G1Affine::generator()).into_affine());
+
+		assert_eq!(
+			crypto::bn254_ec_add(&[&generator[..], &generator].concat()).unwrap(),
+			doubled[..]
+		);
+		// Missing input is zero, which encodes the point at infinity, and extra input is
+		// ignored.
+		assert_eq!(crypto::bn254_ec_add(&generator).unwrap(), generator[..]);
+		assert_eq!(crypto::bn254_ec_add(&[]), Some([0; 64]));
+		assert_eq!(
+			crypto::bn254_ec_add(&[&generator[..], &generator, &[1]].concat()).unwrap(),
+			doubled[..],
+		);
+		// (1, 3) is not on the curve.
+		let mut invalid = generator;
+		invalid[63] = 3;
+		assert_eq!(crypto::bn254_ec_add(&invalid), None);
+	}
+
+	#[test]
+	fn bn254_ec_mul_works() {
+		use ark_bn254::{Fr, G1Affine};
+		use ark_ec::AffineRepr;
+		use ark_ff::{BigInteger, PrimeField};
+
+		let generator = bn254_g1(G1Affine::generator());
+		let mut two = [0u8; 32];
+		two[31] = 2;
+
+		assert_eq!(
+			crypto::bn254_ec_mul(&[&generator[..], &two].concat()),
+			crypto::bn254_ec_add(&[&generator[..], &generator].concat()),
+		);
+		// The scalar is not reduced modulo the group order.
+		let order = Fr::MODULUS.to_bytes_be();
+		assert_eq!(crypto::bn254_ec_mul(&[&generator[..], &order].concat()), Some([0; 64]));
+		assert_eq!(crypto::bn254_ec_mul(&generator), Some([0; 64]));
+		// Coordinates larger than the field modulus.
+		assert_eq!(crypto::bn254_ec_mul(&[0xff; 96]), None);
+	}
+
+	#[test]
+	fn bn254_ec_pairing_works() {
+		use ark_bn254::{G1Affine, G2Affine};
+		use ark_ec::AffineRepr;
+
+		let mut one = [0u8; 32];
+		one[31] = 1;
+		let (a, b) = (G1Affine::generator(), G2Affine::generator());
+
+		// e(a, b) * e(-a, b) = 1
+		let input = [bn254_g1(a), bn254_g2(b), bn254_g1(-a), bn254_g2(b)].concat();
+		assert_eq!(crypto::bn254_ec_pairing(&input), Some(one));
+		assert_eq!(crypto::bn254_ec_pairing(&input[..192]), Some([0; 32]));
+		assert_eq!(crypto::bn254_ec_pairing(&[]), Some(one));
+		assert_eq!(crypto::bn254_ec_pairing(&[0; 192]), Some(one));
+
+		assert_eq!(crypto::bn254_ec_pairing(&input[..191]), None);
+		let mut invalid = input;
+		invalid[64] ^= 1;
+		assert_eq!(crypto::bn254_ec_pairing(&invalid), None);
+	}
+
 	fn zero_ed_pub() -> ed25519::Public {
 		[0u8; 32].unchecked_into()
 	}
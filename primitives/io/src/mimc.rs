@@ -0,0 +1,102 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The MiMC sponge of circomlib over the scalar fields of BN254 and BLS12-381.
+//!
+//! The permutation is the 220 round Feistel network with the `x⁵` round function of
+//! circomlib's `MiMCFeistel`. Its round constants are derived from the seed `mimcsponge` by
+//! iterating keccak-256 and reducing each hash modulo the field, with the first and last round
+//! constants zero. Over BN254 these are the constants of circomlib and the Tornado Cash
+//! contracts, while BLS12-381 uses the same derivation for its own modulus.
+//!
+//! Field elements are 32-byte big-endian integers, which must be smaller than the modulus.
+
+use crate::{
+	scalar_field::{read_element, write_element},
+	ScalarField, MIMC_SPONGE_MAX_LEN,
+};
+use ark_ff::PrimeField;
+
+/// The number of rounds of the Feistel network.
+const ROUNDS: usize = 220;
+/// The seed of the round constants.
+const SEED: &[u8] = b"mimcsponge";
+
+/// The round constants over `F`.
+fn round_constants<F: PrimeField>() -> Vec<F> {
+	let mut constants = vec![F::zero(); ROUNDS];
+	let mut hash = sp_core::hashing::keccak_256(SEED);
+	for constant in constants.iter_mut().take(ROUNDS - 1).skip(1) {
+		hash = sp_core::hashing::keccak_256(&hash);
+		*constant = F::from_be_bytes_mod_order(&hash);
+	}
+	constants
+}
+
+/// Apply the Feistel network keyed with `key` to `(left, right)`.
+fn permute<F: PrimeField>(constants: &[F], key: F, left: &mut F, right: &mut F) {
+	for (round, constant) in constants.iter().enumerate() {
+		let t = *left + key + constant;
+		let t5 = t.square().square() * t;
+		if round < ROUNDS - 1 {
+			let new_left = *right + t5;
+			*right = *left;
+			*left = new_left;
+		} else {
+			*right += t5;
+		}
+	}
+}
+
+/// Absorb `inputs` into the sponge keyed with `key` and squeeze `outputs` elements.
+fn sponge<F: PrimeField>(
+	inputs: &[[u8; 32]],
+	key: &[u8; 32],
+	outputs: u32,
+) -> Option<Vec<[u8; 32]>> {
+	let constants = round_constants::<F>();
+	let key = read_element::<F>(key)?;
+	let (mut left, mut right) = (F::zero(), F::zero());
+	for input in inputs {
+		left += read_element::<F>(input)?;
+		permute(&constants, key, &mut left, &mut right);
+	}
+
+	let mut squeezed = vec![write_element(left)];
+	for _ in 1..outputs {
+		permute(&constants, key, &mut left, &mut right);
+		squeezed.push(write_element(left));
+	}
+	Some(squeezed)
+}
+
+/// Hash `inputs` with the MiMC sponge over `field`, keyed with `key`, into `outputs` elements.
+pub fn mimc_sponge(
+	field: ScalarField,
+	inputs: &[[u8; 32]],
+	key: &[u8; 32],
+	outputs: u32,
+) -> Option<Vec<[u8; 32]>> {
+	if inputs.len() > MIMC_SPONGE_MAX_LEN as usize || outputs == 0 || outputs > MIMC_SPONGE_MAX_LEN
+	{
+		return None
+	}
+	match field {
+		ScalarField::Bn254 => sponge::<ark_bn254::Fr>(inputs, key, outputs),
+		ScalarField::Bls12_381 => sponge::<ark_bls12_381::Fr>(inputs, key, outputs),
+	}
+}
@@ -0,0 +1,53 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bulletproofs range proof verification.
+//!
+//! The generators are derived once for [`crate::BULLETPROOFS_MAX_COMMITMENTS`] parties and shared
+//! by all calls, as deriving them costs about as much as the verification itself.
+
+use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
+use curve25519_dalek_ng::ristretto::CompressedRistretto;
+
+static GENERATORS: once_cell::sync::Lazy<BulletproofGens> = once_cell::sync::Lazy::new(|| {
+	BulletproofGens::new(64, crate::BULLETPROOFS_MAX_COMMITMENTS as usize)
+});
+
+/// Verify an aggregated range proof of `bits` bits for `commitments`.
+pub fn verify(proof: &[u8], commitments: Vec<[u8; 32]>, bits: u32, domain: &[u8]) -> bool {
+	if commitments.len() > crate::BULLETPROOFS_MAX_COMMITMENTS as usize {
+		return false
+	}
+	let proof = match RangeProof::from_bytes(proof) {
+		Ok(proof) => proof,
+		Err(_) => return false,
+	};
+	let commitments = commitments.into_iter().map(CompressedRistretto).collect::<Vec<_>>();
+
+	let mut transcript = merlin::Transcript::new(b"Substrate Bulletproofs");
+	transcript.append_message(b"domain", domain);
+
+	proof
+		.verify_multiple(
+			&GENERATORS,
+			&PedersenGens::default(),
+			&mut transcript,
+			&commitments,
+			bits as usize,
+		)
+		.is_ok()
+}
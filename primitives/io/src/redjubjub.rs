@@ -0,0 +1,59 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RedJubjub signatures of Sapling spends and transactions.
+//!
+//! Signatures are verified as in zcashd: the signed message is the encoded verification key
+//! followed by the 32-byte signature hash of the transaction, and randomized spend
+//! authorization keys of small order are rejected.
+
+use jubjub::AffinePoint;
+use reddsa::{
+	sapling::{Binding, SpendAuth},
+	SigType, VerificationKey,
+};
+
+/// Verify `signature` of `sighash` by `key`.
+fn verify<T: SigType>(key: &[u8; 32], sighash: &[u8; 32], signature: &[u8; 64]) -> bool {
+	let mut message = [0; 64];
+	message[..32].copy_from_slice(key);
+	message[32..].copy_from_slice(sighash);
+	VerificationKey::<T>::try_from(*key)
+		.and_then(|key| key.verify(&message, &(*signature).into()))
+		.is_ok()
+}
+
+/// Verify the spend authorization `signature` of `sighash` by the randomized key `rk`.
+pub fn spend_auth_verify(rk: &[u8; 32], sighash: &[u8; 32], signature: &[u8; 64]) -> bool {
+	match Option::<AffinePoint>::from(AffinePoint::from_bytes(*rk)) {
+		Some(point) if !bool::from(point.is_small_order()) =>
+			verify::<SpendAuth>(rk, sighash, signature),
+		_ => false,
+	}
+}
+
+/// Verify the binding `signature` of `sighash` by the binding verification key `bvk`.
+pub fn binding_verify(bvk: &[u8; 32], sighash: &[u8; 32], signature: &[u8; 64]) -> bool {
+	verify::<Binding>(bvk, sighash, signature)
+}
+
+/// The spend authorization key `ak` randomized with `alpha`.
+pub fn randomize(ak: &[u8; 32], alpha: &[u8; 32]) -> Option<[u8; 32]> {
+	let alpha = Option::<jubjub::Fr>::from(jubjub::Fr::from_bytes(alpha))?;
+	let ak = VerificationKey::<SpendAuth>::try_from(*ak).ok()?;
+	Some(ak.randomize(&alpha).into())
+}
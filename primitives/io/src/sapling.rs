@@ -0,0 +1,145 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sapling Pedersen hashes over Jubjub.
+//!
+//! Implements `PedersenHash` of the Zcash protocol specification and the Sapling functions built
+//! on it, the Merkle tree hash `MerkleCRH` and the note commitment `NoteCommit`. The generators
+//! are derived with `FindGroupHash` from the personalization `Zcash_PH`, as in zcashd.
+//!
+//! Bit strings are read least significant bit first from each byte. Field elements, scalars and
+//! points are 32 bytes in the little-endian encoding of Zcash, and hashes and commitments are
+//! the u-coordinate of the resulting point.
+
+use crate::SAPLING_PEDERSEN_HASH_MAX_BITS;
+use jubjub::{AffinePoint, ExtendedPoint, Fq, Fr};
+
+/// The uniform random string hashed into every group hash.
+const URS: &[u8] = b"096b36a5804bfacef1691e173c366a47ff5ba84a44f26ddd7e8d9f79d5b42df0";
+/// The BLAKE2s personalization of the Pedersen hash generators.
+const PERSONALIZATION: &[u8; 8] = b"Zcash_PH";
+/// The number of bits hashed with each generator, 63 chunks of three bits.
+const SEGMENT_BITS: usize = 189;
+/// The number of bits of a field element in a Merkle tree hash.
+const FIELD_BITS: usize = 255;
+
+/// `GroupHash`, failing if the hash of `tag` is not a point or has small order.
+fn group_hash(personalization: &[u8; 8], tag: &[u8]) -> Option<ExtendedPoint> {
+	let hash = blake2s_simd::Params::new()
+		.hash_length(32)
+		.personal(personalization)
+		.to_state()
+		.update(URS)
+		.update(tag)
+		.finalize();
+	let point: Option<AffinePoint> = AffinePoint::from_bytes(*hash.as_array()).into();
+	let point = point?.mul_by_cofactor();
+	(!bool::from(point.is_identity())).then_some(point)
+}
+
+/// `FindGroupHash`, the first group hash of `message` followed by a counter byte.
+pub fn find_group_hash(personalization: &[u8; 8], message: &[u8]) -> ExtendedPoint {
+	let mut tag = message.to_vec();
+	tag.push(0);
+	loop {
+		if let Some(point) = group_hash(personalization, &tag) {
+			return point
+		}
+		*tag.last_mut().expect("The tag ends with the counter; qed") += 1;
+	}
+}
+
+/// The first `len` bits of `bytes`.
+fn bits(bytes: &[u8], len: usize) -> impl Iterator<Item = bool> + '_ {
+	bytes
+		.iter()
+		.flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1 == 1))
+		.take(len)
+}
+
+/// `PedersenHashToPoint` of `bits`.
+fn hash_to_point(bits: impl Iterator<Item = bool>) -> ExtendedPoint {
+	let bits = bits.collect::<Vec<_>>();
+	let mut point = ExtendedPoint::identity();
+	for (index, segment) in bits.chunks(SEGMENT_BITS).enumerate() {
+		// Each chunk `(s₀, s₁, s₂)` encodes `(1 - 2·s₂)·(1 + s₀ + 2·s₁)`, scaled by `2⁴` for
+		// each chunk before it.
+		let mut scalar = Fr::zero();
+		let mut base = Fr::one();
+		for chunk in segment.chunks(3) {
+			let bit = |index: usize| chunk.get(index).copied().unwrap_or(false);
+			let mut encoding = base;
+			if bit(0) {
+				encoding += base;
+			}
+			if bit(1) {
+				encoding += base.double();
+			}
+			if bit(2) {
+				encoding = -encoding;
+			}
+			scalar += encoding;
+			base = base.double().double().double().double();
+		}
+		point += find_group_hash(PERSONALIZATION, &(index as u32).to_le_bytes()) * scalar;
+	}
+	point
+}
+
+/// The u-coordinate of `point`.
+fn extract(point: ExtendedPoint) -> [u8; 32] {
+	AffinePoint::from(point).get_u().to_bytes()
+}
+
+/// `PedersenHash` of the first `len` bits of `message`.
+pub fn pedersen_hash(message: &[u8], len: u32) -> Option<[u8; 32]> {
+	if len > SAPLING_PEDERSEN_HASH_MAX_BITS || message.len() != (len as usize + 7) / 8 {
+		return None
+	}
+	Some(extract(hash_to_point(bits(message, len as usize))))
+}
+
+/// `MerkleCRH` of the nodes `left` and `right` at `depth`, counted from the leaves.
+pub fn merkle_hash(depth: u8, left: &[u8; 32], right: &[u8; 32]) -> Option<[u8; 32]> {
+	if depth >= 64 {
+		return None
+	}
+	for node in [left, right] {
+		Option::<Fq>::from(Fq::from_bytes(node))?;
+	}
+	let depth = [depth];
+	let bits = bits(&depth, 6).chain(bits(left, FIELD_BITS)).chain(bits(right, FIELD_BITS));
+	Some(extract(hash_to_point(bits)))
+}
+
+/// `NoteCommit` of a note of `value` to the diversified base `g_d` and transmission key `pk_d`
+/// with the randomness `rcm`.
+pub fn note_commitment(
+	g_d: &[u8; 32],
+	pk_d: &[u8; 32],
+	value: u64,
+	rcm: &[u8; 32],
+) -> Option<[u8; 32]> {
+	let rcm = Option::<Fr>::from(Fr::from_bytes(rcm))?;
+	let value = value.to_le_bytes();
+	let bits = [true; 6]
+		.into_iter()
+		.chain(bits(&value, 64))
+		.chain(bits(g_d, 256))
+		.chain(bits(pk_d, 256));
+	Some(extract(hash_to_point(bits) + find_group_hash(PERSONALIZATION, b"r") * rcm))
+}
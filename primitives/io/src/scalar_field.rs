@@ -0,0 +1,130 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Vector arithmetic over the scalar fields of BN254 and BLS12-381.
+//!
+//! Field elements are 32-byte big-endian integers, which must be smaller than the modulus.
+
+use crate::{ScalarField, FR_VECTOR_MAX_LEN};
+use ark_ff::{BigInteger, PrimeField};
+
+/// Read a big-endian field element, failing if it is not smaller than the modulus.
+pub(crate) fn read_element<F: PrimeField>(bytes: &[u8; 32]) -> Option<F> {
+	let element = F::from_be_bytes_mod_order(bytes);
+	(write_element(element) == *bytes).then_some(element)
+}
+
+/// Write `element` as 32 big-endian bytes.
+pub(crate) fn write_element<F: PrimeField>(element: F) -> [u8; 32] {
+	let bytes = element.into_bigint().to_bytes_be();
+	let mut element = [0; 32];
+	element[32 - bytes.len()..].copy_from_slice(&bytes);
+	element
+}
+
+/// Read a vector of field elements, failing if it is longer than [`FR_VECTOR_MAX_LEN`].
+fn read_vector<F: PrimeField>(elements: &[[u8; 32]]) -> Option<Vec<F>> {
+	if elements.len() > FR_VECTOR_MAX_LEN as usize {
+		return None
+	}
+	elements.iter().map(read_element).collect()
+}
+
+/// Apply `op` to the elements of `a` and `b` at the same index.
+fn elementwise<F: PrimeField>(
+	a: &[[u8; 32]],
+	b: &[[u8; 32]],
+	op: impl Fn(F, F) -> F,
+) -> Option<Vec<[u8; 32]>> {
+	if a.len() != b.len() {
+		return None
+	}
+	let (a, b) = (read_vector::<F>(a)?, read_vector::<F>(b)?);
+	Some(a.into_iter().zip(b).map(|(a, b)| write_element(op(a, b))).collect())
+}
+
+/// The sum of the products of the elements of `a` and `b` at the same index.
+fn inner_product<F: PrimeField>(a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<[u8; 32]> {
+	if a.len() != b.len() {
+		return None
+	}
+	let (a, b) = (read_vector::<F>(a)?, read_vector::<F>(b)?);
+	Some(write_element(a.into_iter().zip(b).map(|(a, b)| a * b).sum::<F>()))
+}
+
+/// Evaluate the polynomial with `coefficients`, from the constant term up, at `point` with
+/// Horner's rule.
+fn horner<F: PrimeField>(coefficients: &[[u8; 32]], point: &[u8; 32]) -> Option<[u8; 32]> {
+	let point = read_element::<F>(point)?;
+	let value = read_vector::<F>(coefficients)?
+		.into_iter()
+		.rev()
+		.fold(F::zero(), |value, coefficient| value * point + coefficient);
+	Some(write_element(value))
+}
+
+/// Dispatch a generic function to the field of a [`ScalarField`].
+macro_rules! with_field {
+	($field:expr, $function:ident($($arg:expr),*)) => {
+		match $field {
+			ScalarField::Bn254 => $function::<ark_bn254::Fr>($($arg),*),
+			ScalarField::Bls12_381 => $function::<ark_bls12_381::Fr>($($arg),*),
+		}
+	};
+}
+
+/// The sums of the elements of `a` and `b` at the same index.
+pub fn vector_add(field: ScalarField, a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<Vec<[u8; 32]>> {
+	fn add<F: PrimeField>(a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<Vec<[u8; 32]>> {
+		elementwise::<F>(a, b, |a, b| a + b)
+	}
+	with_field!(field, add(a, b))
+}
+
+/// The differences of the elements of `a` and `b` at the same index.
+pub fn vector_sub(field: ScalarField, a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<Vec<[u8; 32]>> {
+	fn sub<F: PrimeField>(a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<Vec<[u8; 32]>> {
+		elementwise::<F>(a, b, |a, b| a - b)
+	}
+	with_field!(field, sub(a, b))
+}
+
+/// The products of the elements of `a` and `b` at the same index.
+pub fn vector_mul(field: ScalarField, a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<Vec<[u8; 32]>> {
+	fn mul<F: PrimeField>(a: &[[u8; 32]], b: &[[u8; 32]]) -> Option<Vec<[u8; 32]>> {
+		elementwise::<F>(a, b, |a, b| a * b)
+	}
+	with_field!(field, mul(a, b))
+}
+
+/// The inner product of `a` and `b`.
+pub fn vector_inner_product(
+	field: ScalarField,
+	a: &[[u8; 32]],
+	b: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+	with_field!(field, inner_product(a, b))
+}
+
+/// The evaluation at `point` of the polynomial with `coefficients`, from the constant term up.
+pub fn evaluate(
+	field: ScalarField,
+	coefficients: &[[u8; 32]],
+	point: &[u8; 32],
+) -> Option<[u8; 32]> {
+	with_field!(field, horner(coefficients, point))
+}
@@ -0,0 +1,66 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The thread pool of the parallel cryptographic host function implementations.
+//!
+//! With the `parallel` feature, the arkworks-based host functions decode points and compute
+//! multi-scalar multiplications and Miller loops in parallel, on a dedicated rayon pool whose
+//! number of threads is bounded by [`set_crypto_threads`]. This keeps the share of the machine
+//! taken by a verification predictable while blocks are imported concurrently. Without the
+//! feature they run on the calling thread.
+
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of threads of the pool, zero for one per CPU.
+#[cfg(feature = "parallel")]
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Bound the number of threads of the parallel host function implementations, zero meaning
+/// one per CPU.
+///
+/// Must be called before the first host function runs, as the pool is built once. Has no effect
+/// without the `parallel` feature.
+pub fn set_crypto_threads(threads: usize) {
+	#[cfg(feature = "parallel")]
+	THREADS.store(threads, Ordering::Relaxed);
+	#[cfg(not(feature = "parallel"))]
+	let _ = threads;
+}
+
+/// Run `f`, and the parallel iterators it uses, on the pool.
+pub(crate) fn install<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+	#[cfg(feature = "parallel")]
+	{
+		static POOL: once_cell::sync::OnceCell<Option<rayon::ThreadPool>> =
+			once_cell::sync::OnceCell::new();
+
+		let pool = POOL.get_or_init(|| {
+			rayon::ThreadPoolBuilder::new()
+				.num_threads(THREADS.load(Ordering::Relaxed))
+				.thread_name(|index| format!("crypto-exec-{}", index))
+				.build()
+				.map_err(|error| log::warn!(target: crate::LOG_TARGET, "Failed to build the crypto thread pool: {}", error))
+				.ok()
+		});
+		// Falls back to the global pool.
+		if let Some(pool) = pool {
+			return pool.install(f)
+		}
+	}
+	f()
+}
@@ -0,0 +1,122 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wesolowski VDF verification over RSA groups.
+//!
+//! The proof is verified in the group of signed quadratic residues, where `v` and `modulus - v`
+//! are the same element, represented by the smaller of the two. In `Z_n*` itself, the negation of
+//! a valid output would verify with the negated proof for its own challenge prime, so the
+//! submitter could choose between two outputs.
+
+use codec::Encode;
+use num_bigint::BigUint;
+
+/// Bases of the Miller-Rabin test, the first 20 primes.
+const MILLER_RABIN_BASES: [u32; 20] =
+	[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
+
+/// Whether `n` passes the Miller-Rabin test for all of [`MILLER_RABIN_BASES`].
+fn is_probable_prime(n: &BigUint) -> bool {
+	for base in MILLER_RABIN_BASES {
+		if *n == BigUint::from(base) {
+			return true
+		}
+		if (n % base).bits() == 0 {
+			return false
+		}
+	}
+	if n.bits() < 2 {
+		return false
+	}
+
+	let n_minus_one = n - 1u32;
+	let s = n_minus_one.trailing_zeros().expect("n > 71, so n - 1 is not zero; qed");
+	let d = &n_minus_one >> s;
+
+	'bases: for base in MILLER_RABIN_BASES {
+		let mut x = BigUint::from(base).modpow(&d, n);
+		if x.bits() == 1 || x == n_minus_one {
+			continue
+		}
+		for _ in 1..s {
+			x = &x * &x % n;
+			if x == n_minus_one {
+				continue 'bases
+			}
+		}
+		return false
+	}
+	true
+}
+
+/// Derive the 128-bit challenge prime `l` of the proof from the statement.
+///
+/// For an increasing `nonce: u64`, the first 16 bytes of the blake2-256 hash of the SCALE
+/// encoding of `(b"wesolowski", modulus, iterations, challenge, output, nonce)` are read as a
+/// big-endian integer, its highest and lowest bits are set and the first candidate passing
+/// the Miller-Rabin test is returned.
+pub fn hash_to_prime(modulus: &[u8], iterations: u64, challenge: &[u8], output: &[u8]) -> BigUint {
+	(0u64..)
+		.map(|nonce| {
+			let hash = sp_core::hashing::blake2_256(
+				&(b"wesolowski", modulus, iterations, challenge, output, nonce).encode(),
+			);
+			BigUint::from_bytes_be(&hash[..16]) |
+				(BigUint::from(1u32) << 127u32) |
+				BigUint::from(1u32)
+		})
+		.find(is_probable_prime)
+		.expect("Primes are dense enough to be found long before the nonce overflows; qed")
+}
+
+/// The representative `min(value, modulus - value)` of `value` in the group of signed quadratic
+/// residues.
+fn signed(value: BigUint, modulus: &BigUint) -> BigUint {
+	let negated = modulus - &value;
+	value.min(negated)
+}
+
+/// Verify that `proof` shows `output = ±challenge ^ (2 ^ iterations) mod modulus`.
+///
+/// See [`crate::crypto::vdf_wesolowski_verify`] for details.
+pub fn verify(
+	modulus: &[u8],
+	challenge: &[u8],
+	iterations: u64,
+	output: &[u8],
+	proof: &[u8],
+) -> bool {
+	let n = BigUint::from_bytes_be(modulus);
+	let x = BigUint::from_bytes_be(challenge);
+	let y = BigUint::from_bytes_be(output);
+	let pi = BigUint::from_bytes_be(proof);
+
+	if [&x, &y, &pi].iter().any(|element| element.bits() == 0 || **element >= n) {
+		return false
+	}
+	// Only the representatives of the signed group are accepted, so neither the output nor the
+	// proof can be replaced by its negation.
+	let half = &n >> 1u32;
+	if y > half || pi > half {
+		return false
+	}
+
+	let l = hash_to_prime(modulus, iterations, challenge, output);
+	let r = BigUint::from(2u32).modpow(&BigUint::from(iterations), &l);
+
+	signed(pi.modpow(&l, &n) * x.modpow(&r, &n) % &n, &n) == y
+}
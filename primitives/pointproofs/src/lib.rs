@@ -0,0 +1,448 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pointproofs vector commitments over BN254.
+//!
+//! Implements the vector commitment scheme of Gorbunov, Reyzin, Wee and Zhang, "Pointproofs:
+//! Aggregating Proofs for Multiple Vector Commitments". A vector of up to `N` scalar messages is
+//! committed to with a single G1 point, and so is the proof of the message at any position.
+//! Commitments and proofs can be updated when a message changes without recomputing them.
+//!
+//! Proofs of several positions of one commitment are aggregated into a single point with
+//! [`aggregate`], and such aggregated proofs of several commitments again into a single point
+//! with [`aggregate_across`]. Both are verified with [`verify`] and [`verify_across`] through
+//! the BN254 host functions of `sp-io`: one `bn254_ec_pairing` call with one pair per distinct
+//! opened position plus two, after a `bn254_ec_mul` and `bn254_ec_add` call per opened message.
+//!
+//! The paper uses the target group element `e(G1, G2)^(α^(N+1))` as a public parameter. As the
+//! host function only checks products of pairings, it is expressed as `e(α·G1, α^N·G2)` here.
+//!
+//! The parameters are derived from a secret `α` by [`setup`]. Anyone knowing `α` can forge
+//! proofs, so the parameters of a production deployment must come from a multi-party ceremony
+//! instead. All parameter types implement arkworks' canonical serialization, and can be SCALE
+//! encoded by wrapping them in `sp_ark_scale::ArkScale`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use ark_bn254::{Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{rand::Rng, UniformRand};
+use sp_std::vec::Vec;
+
+mod verification;
+
+pub use verification::{verify, verify_across};
+
+/// Errors of creating or aggregating proofs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+	/// The vector is longer than the parameters support.
+	TooLong,
+	/// An index is out of range, or the indices of an opening are not strictly increasing.
+	InvalidIndex,
+	/// The number of messages or proofs does not match the number of indices or openings.
+	LengthMismatch,
+}
+
+/// The parameters needed to commit, open and update.
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProverParameters {
+	/// `α^i·G1` for `i` in `1..=2N`, with the point at infinity in place of `α^(N+1)·G1`.
+	pub g1_powers: Vec<G1Affine>,
+}
+
+impl ProverParameters {
+	/// The maximum length `N` of a committed vector.
+	pub fn max_len(&self) -> usize {
+		self.g1_powers.len() / 2
+	}
+}
+
+/// The parameters needed to verify.
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifierParameters {
+	/// `α·G1`.
+	pub g1_alpha: G1Affine,
+	/// `α^i·G2` for `i` in `0..=N`.
+	pub g2_powers: Vec<G2Affine>,
+}
+
+impl VerifierParameters {
+	/// The maximum length `N` of a committed vector.
+	pub fn max_len(&self) -> usize {
+		self.g2_powers.len().saturating_sub(1)
+	}
+}
+
+/// The messages at a subset of the positions of a committed vector.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Opening<'a> {
+	/// The commitment to the vector.
+	pub commitment: &'a G1Affine,
+	/// The positions, in strictly increasing order.
+	pub indices: &'a [usize],
+	/// The messages at [`Self::indices`].
+	pub messages: &'a [Fr],
+}
+
+impl<'a> Opening<'a> {
+	/// Ensure the indices are strictly increasing and match the messages.
+	fn validate(&self) -> Result<(), Error> {
+		if self.indices.len() != self.messages.len() {
+			return Err(Error::LengthMismatch)
+		}
+		if self.indices.windows(2).any(|pair| pair[0] >= pair[1]) {
+			return Err(Error::InvalidIndex)
+		}
+		Ok(())
+	}
+
+	/// The coefficients of the proofs at [`Self::indices`] in an aggregated proof.
+	///
+	/// They are derived from the whole opening, so that proofs cannot be combined to open a
+	/// position to a different message.
+	fn coefficients(&self) -> impl Iterator<Item = Fr> + '_ {
+		let seed = self.hash();
+		self.indices.iter().map(move |index| coefficient(&seed, *index))
+	}
+
+	/// The hash of the commitment, indices and messages.
+	fn hash(&self) -> [u8; 32] {
+		let mut input = Vec::with_capacity(64 + 40 * self.indices.len());
+		input.extend(encode_g1(self.commitment));
+		for (index, message) in self.indices.iter().zip(self.messages) {
+			input.extend((*index as u64).to_le_bytes());
+			input.extend(encode_scalar(message));
+		}
+		sp_io::hashing::blake2_256(&input)
+	}
+}
+
+/// Generate parameters for vectors of up to `n` messages from a random secret.
+///
+/// The secret is discarded, but has been in memory. This is only suitable for tests and
+/// experiments.
+pub fn setup<R: Rng + ?Sized>(n: usize, rng: &mut R) -> (ProverParameters, VerifierParameters) {
+	let alpha = Fr::rand(rng);
+	let powers: Vec<_> = ark_std::iter::successors(Some(Fr::one()), |power| Some(*power * alpha))
+		.take(2 * n + 1)
+		.collect();
+
+	let g1_powers: Vec<_> = powers[1..]
+		.iter()
+		.enumerate()
+		.map(|(i, power)| {
+			// `α^(N+1)·G1` would allow forging proofs.
+			if i == n {
+				G1Projective::zero()
+			} else {
+				G1Affine::generator() * power
+			}
+		})
+		.collect();
+	let g2_powers: Vec<_> =
+		powers[..=n].iter().map(|power| G2Affine::generator() * power).collect();
+
+	let g1_powers = G1Projective::normalize_batch(&g1_powers);
+	let verifier = VerifierParameters {
+		g1_alpha: g1_powers[0],
+		g2_powers: G2Projective::normalize_batch(&g2_powers),
+	};
+	(ProverParameters { g1_powers }, verifier)
+}
+
+/// Commit to `messages`.
+pub fn commit(params: &ProverParameters, messages: &[Fr]) -> Result<G1Affine, Error> {
+	if messages.len() > params.max_len() {
+		return Err(Error::TooLong)
+	}
+	Ok(G1Projective::msm_unchecked(&params.g1_powers[..messages.len()], messages).into_affine())
+}
+
+/// Create the proof of the message at `index` of `messages`.
+pub fn open(params: &ProverParameters, messages: &[Fr], index: usize) -> Result<G1Affine, Error> {
+	let n = params.max_len();
+	if messages.len() > n {
+		return Err(Error::TooLong)
+	}
+	if index >= messages.len() {
+		return Err(Error::InvalidIndex)
+	}
+
+	// The message at `j` is multiplied by `α^(N+1-index+j)`. The base of the message at `index`
+	// itself is the point at infinity.
+	let bases = &params.g1_powers[n - index..n - index + messages.len()];
+	Ok(G1Projective::msm_unchecked(bases, messages).into_affine())
+}
+
+/// Update `commitment` for the message at `index` changing from `old` to `new`.
+pub fn update_commitment(
+	params: &ProverParameters,
+	commitment: &G1Affine,
+	index: usize,
+	old: &Fr,
+	new: &Fr,
+) -> Result<G1Affine, Error> {
+	if index >= params.max_len() {
+		return Err(Error::InvalidIndex)
+	}
+	Ok((params.g1_powers[index] * (*new - old) + commitment).into_affine())
+}
+
+/// Update `proof`, the proof of the message at `index`, for the message at `changed` changing
+/// from `old` to `new`.
+pub fn update_proof(
+	params: &ProverParameters,
+	proof: &G1Affine,
+	index: usize,
+	changed: usize,
+	old: &Fr,
+	new: &Fr,
+) -> Result<G1Affine, Error> {
+	let n = params.max_len();
+	if index >= n || changed >= n {
+		return Err(Error::InvalidIndex)
+	}
+	// The proof does not depend on its own message.
+	if index == changed {
+		return Ok(*proof)
+	}
+	Ok((params.g1_powers[n + changed - index] * (*new - old) + proof).into_affine())
+}
+
+/// Aggregate the `proofs` of the messages of `opening` into a single proof.
+pub fn aggregate(opening: &Opening, proofs: &[G1Affine]) -> Result<G1Affine, Error> {
+	opening.validate()?;
+	if proofs.len() != opening.indices.len() {
+		return Err(Error::LengthMismatch)
+	}
+	let coefficients: Vec<_> = opening.coefficients().collect();
+	Ok(G1Projective::msm_unchecked(proofs, &coefficients).into_affine())
+}
+
+/// Aggregate the `proofs` of `openings`, each aggregated by [`aggregate`], into a single proof.
+pub fn aggregate_across(openings: &[Opening], proofs: &[G1Affine]) -> Result<G1Affine, Error> {
+	for opening in openings {
+		opening.validate()?;
+	}
+	if proofs.len() != openings.len() {
+		return Err(Error::LengthMismatch)
+	}
+	let coefficients: Vec<_> = cross_coefficients(openings).collect();
+	Ok(G1Projective::msm_unchecked(proofs, &coefficients).into_affine())
+}
+
+/// The coefficients of the aggregated proofs of `openings` in a proof across commitments.
+fn cross_coefficients<'a>(openings: &'a [Opening]) -> impl Iterator<Item = Fr> + 'a {
+	let hashes: Vec<u8> = openings.iter().flat_map(|opening| opening.hash()).collect();
+	let seed = sp_io::hashing::blake2_256(&hashes);
+	(0..openings.len()).map(move |index| coefficient(&seed, index))
+}
+
+/// Derive the coefficient at `index` from `seed`.
+fn coefficient(seed: &[u8; 32], index: usize) -> Fr {
+	let input = [&seed[..], &(index as u64).to_le_bytes()].concat();
+	Fr::from_be_bytes_mod_order(&sp_io::hashing::blake2_256(&input))
+}
+
+/// Encode a scalar as a 32-byte big-endian integer.
+fn encode_scalar(scalar: &Fr) -> Vec<u8> {
+	scalar.into_bigint().to_bytes_be()
+}
+
+/// Encode a G1 point as expected by the BN254 host functions.
+fn encode_g1(point: &G1Affine) -> Vec<u8> {
+	match point.xy() {
+		Some((x, y)) => [x.into_bigint().to_bytes_be(), y.into_bigint().to_bytes_be()].concat(),
+		None => sp_std::vec![0; 64],
+	}
+}
+
+/// Encode a G2 point as expected by the BN254 host functions.
+fn encode_g2(point: &G2Affine) -> Vec<u8> {
+	match point.xy() {
+		Some((x, y)) => [x.c1, x.c0, y.c1, y.c0]
+			.iter()
+			.flat_map(|c| c.into_bigint().to_bytes_be())
+			.collect(),
+		None => sp_std::vec![0; 128],
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ark_std::test_rng;
+
+	const N: usize = 8;
+
+	fn messages(rng: &mut impl Rng) -> Vec<Fr> {
+		(0..N).map(|_| Fr::rand(rng)).collect()
+	}
+
+	#[test]
+	fn single_proofs_verify() {
+		let rng = &mut test_rng();
+		let (prover, verifier) = setup(N, rng);
+		let messages = messages(rng);
+		let commitment = commit(&prover, &messages).unwrap();
+
+		for index in 0..N {
+			let proof = open(&prover, &messages, index).unwrap();
+			let opening = Opening {
+				commitment: &commitment,
+				indices: &[index],
+				messages: &messages[index..=index],
+			};
+			// A single proof is aggregated with a coefficient as well.
+			let aggregated = aggregate(&opening, &[proof]).unwrap();
+			assert!(verify(&verifier, &opening, &aggregated));
+			assert!(!verify(&verifier, &opening, &proof));
+
+			let wrong = [messages[index] + Fr::one()];
+			assert!(!verify(&verifier, &Opening { messages: &wrong, ..opening }, &aggregated));
+		}
+	}
+
+	#[test]
+	fn aggregated_proofs_verify() {
+		let rng = &mut test_rng();
+		let (prover, verifier) = setup(N, rng);
+		let messages = messages(rng);
+		let commitment = commit(&prover, &messages).unwrap();
+
+		let indices = [1, 4, 5];
+		let subset: Vec<_> = indices.iter().map(|i| messages[*i]).collect();
+		let proofs: Vec<_> =
+			indices.iter().map(|i| open(&prover, &messages, *i).unwrap()).collect();
+		let opening = Opening { commitment: &commitment, indices: &indices, messages: &subset };
+		let proof = aggregate(&opening, &proofs).unwrap();
+		assert!(verify(&verifier, &opening, &proof));
+
+		// Swapping two messages changes the coefficients.
+		let swapped = [subset[1], subset[0], subset[2]];
+		assert!(!verify(&verifier, &Opening { messages: &swapped, ..opening }, &proof));
+		assert!(!verify(
+			&verifier,
+			&Opening { indices: &[1, 4], messages: &subset[..2], ..opening },
+			&proof
+		));
+
+		assert_eq!(
+			aggregate(&Opening { indices: &[4, 1, 5], ..opening }, &proofs),
+			Err(Error::InvalidIndex),
+		);
+		assert_eq!(
+			aggregate(&Opening { indices: &[1, 4, 4], ..opening }, &proofs),
+			Err(Error::InvalidIndex),
+		);
+		assert_eq!(aggregate(&opening, &proofs[..2]), Err(Error::LengthMismatch));
+		assert!(!verify(&verifier, &Opening { indices: &[1, 4, N], ..opening }, &proof));
+	}
+
+	#[test]
+	fn proofs_across_commitments_verify() {
+		let rng = &mut test_rng();
+		let (prover, verifier) = setup(N, rng);
+		let vectors = [messages(rng), messages(rng), messages(rng)];
+		let commitments: Vec<_> = vectors.iter().map(|m| commit(&prover, m).unwrap()).collect();
+		let indices: [&[usize]; 3] = [&[0, 2], &[2], &[3, 6, 7]];
+		let subsets: Vec<Vec<_>> = vectors
+			.iter()
+			.zip(indices)
+			.map(|(messages, indices)| indices.iter().map(|i| messages[*i]).collect())
+			.collect();
+
+		let openings: Vec<_> = (0..3)
+			.map(|j| Opening {
+				commitment: &commitments[j],
+				indices: indices[j],
+				messages: &subsets[j],
+			})
+			.collect();
+		let proofs: Vec<_> = openings
+			.iter()
+			.zip(&vectors)
+			.map(|(opening, messages)| {
+				let proofs: Vec<_> =
+					opening.indices.iter().map(|i| open(&prover, messages, *i).unwrap()).collect();
+				aggregate(opening, &proofs).unwrap()
+			})
+			.collect();
+
+		let proof = aggregate_across(&openings, &proofs).unwrap();
+		assert!(verify_across(&verifier, &openings, &proof));
+		assert!(!verify_across(&verifier, &openings[..2], &proof));
+
+		let mut swapped = openings.clone();
+		swapped.swap(0, 1);
+		assert!(!verify_across(&verifier, &swapped, &proof));
+		assert_eq!(aggregate_across(&openings, &proofs[..2]), Err(Error::LengthMismatch));
+	}
+
+	#[test]
+	fn updates_match_recomputation() {
+		let rng = &mut test_rng();
+		let (prover, verifier) = setup(N, rng);
+		let mut messages = messages(rng);
+		let commitment = commit(&prover, &messages).unwrap();
+		let proofs: Vec<_> = (0..N).map(|i| open(&prover, &messages, i).unwrap()).collect();
+
+		let (changed, old, new) = (3, messages[3], Fr::rand(rng));
+		messages[changed] = new;
+		let updated = update_commitment(&prover, &commitment, changed, &old, &new).unwrap();
+		assert_eq!(updated, commit(&prover, &messages).unwrap());
+		for (index, proof) in proofs.iter().enumerate() {
+			let updated_proof = update_proof(&prover, proof, index, changed, &old, &new).unwrap();
+			assert_eq!(updated_proof, open(&prover, &messages, index).unwrap());
+		}
+
+		let opening = Opening {
+			commitment: &updated,
+			indices: &[changed],
+			messages: &messages[changed..=changed],
+		};
+		let proof = aggregate(&opening, &[open(&prover, &messages, changed).unwrap()]).unwrap();
+		assert!(verify(&verifier, &opening, &proof));
+		assert_eq!(
+			update_commitment(&prover, &commitment, N, &old, &new),
+			Err(Error::InvalidIndex)
+		);
+	}
+
+	#[test]
+	fn short_vectors_and_limits() {
+		let rng = &mut test_rng();
+		let (prover, verifier) = setup(N, rng);
+		assert_eq!((prover.max_len(), verifier.max_len()), (N, N));
+		assert!(prover.g1_powers[N].is_zero());
+
+		// Shorter vectors are padded with zeros.
+		let messages = messages(rng);
+		let mut padded = messages[..3].to_vec();
+		padded.resize(N, Fr::zero());
+		assert_eq!(commit(&prover, &messages[..3]), commit(&prover, &padded));
+		assert_eq!(open(&prover, &messages[..3], 1), open(&prover, &padded, 1));
+
+		let too_long = [Fr::one(); N + 1];
+		assert_eq!(commit(&prover, &too_long), Err(Error::TooLong));
+		assert_eq!(open(&prover, &messages[..3], 3), Err(Error::InvalidIndex));
+	}
+}
@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of aggregated proofs through the BN254 host functions.
+//!
+//! An aggregated proof `π` of the messages `m_i` at the positions `i` of the commitments `C` is
+//! valid if, with `c` the coefficient of each message,
+//!
+//! `Π e(Σ c·C, α^(N-i)·G2) · e(-π, G2) · e(-(Σ c·m)·α·G1, α^N·G2) = 1`,
+//!
+//! where the first product runs over the distinct positions, and the inner sum over the
+//! commitments opened at each of them.
+
+use crate::{cross_coefficients, encode_g1, encode_g2, encode_scalar, Opening, VerifierParameters};
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::Zero;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// The length of an encoded G1 and G2 point pair in the pairing input.
+const PAIR_LEN: usize = 192;
+
+/// Verify the `proof` of `opening`, aggregated by [`crate::aggregate`].
+///
+/// Returns `false` if the opening is malformed or an index is out of range.
+pub fn verify(params: &VerifierParameters, opening: &Opening, proof: &G1Affine) -> bool {
+	if opening.validate().is_err() {
+		return false
+	}
+	let terms =
+		opening.indices.iter().zip(opening.messages).zip(opening.coefficients()).map(
+			|((index, message), coefficient)| (opening.commitment, *index, coefficient, *message),
+		);
+	check(params, terms, proof).unwrap_or(false)
+}
+
+/// Verify the `proof` of `openings`, aggregated by [`crate::aggregate_across`].
+///
+/// Returns `false` if an opening is malformed or an index is out of range.
+pub fn verify_across(params: &VerifierParameters, openings: &[Opening], proof: &G1Affine) -> bool {
+	if openings.iter().any(|opening| opening.validate().is_err()) {
+		return false
+	}
+	let terms = openings.iter().zip(cross_coefficients(openings)).flat_map(|(opening, outer)| {
+		opening.indices.iter().zip(opening.messages).zip(opening.coefficients()).map(
+			move |((index, message), inner)| (opening.commitment, *index, outer * inner, *message),
+		)
+	});
+	check(params, terms, proof).unwrap_or(false)
+}
+
+/// Check the pairing equation for the commitment, index, coefficient and message `terms`.
+///
+/// Returns `None` if the parameters are empty, an index is out of range or a host function rejects
+/// its input.
+fn check<'a>(
+	params: &VerifierParameters,
+	terms: impl Iterator<Item = (&'a G1Affine, usize, Fr, Fr)>,
+	proof: &G1Affine,
+) -> Option<bool> {
+	let n = params.max_len();
+	let generator = params.g2_powers.first()?;
+	let mut bases = BTreeMap::<usize, [u8; 64]>::new();
+	let mut scalar = Fr::zero();
+	for (commitment, index, coefficient, message) in terms {
+		if index >= n {
+			return None
+		}
+		let term = sp_io::crypto::bn254_ec_mul(
+			&[encode_g1(commitment), encode_scalar(&coefficient)].concat(),
+		)?;
+		let base = bases.entry(index).or_insert([0; 64]);
+		*base = sp_io::crypto::bn254_ec_add(&[&base[..], &term].concat())?;
+		scalar += coefficient * message;
+	}
+	let message_term = sp_io::crypto::bn254_ec_mul(
+		&[encode_g1(&params.g1_alpha), encode_scalar(&-scalar)].concat(),
+	)?;
+
+	let mut input = Vec::with_capacity((bases.len() + 2) * PAIR_LEN);
+	for (index, base) in bases {
+		input.extend(base);
+		input.extend(encode_g2(&params.g2_powers[n - index]));
+	}
+	input.extend(encode_g1(&-*proof));
+	input.extend(encode_g2(generator));
+	input.extend(message_term);
+	input.extend(encode_g2(&params.g2_powers[n]));
+
+	Some(sp_io::crypto::bn254_ec_pairing(&input)?[31] == 1)
+}
@@ -0,0 +1,253 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Append-only Merkle trees that only store their frontier.
+
+use crate::{empty_hashes, NodeHasher};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::{bounded::BoundedVec, ConstU32};
+use sp_debug_derive::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The maximum depth of an [`IncrementalMerkleTree`].
+pub const MAX_DEPTH: u32 = 64;
+
+/// An append-only Merkle tree of fixed depth which only stores its frontier.
+///
+/// Leaf `i` is the `i`-th inserted leaf, and leaves that were not inserted yet have the
+/// default value. Empty subtrees and inner nodes are hashed as in the sparse Merkle tree of
+/// this crate, so the tree takes `O(depth)` space regardless of the number of leaves, and is
+/// meant to be kept in runtime storage.
+///
+/// As the tree does not keep its leaves, authentication paths are only available for the
+/// leaves of a batch when it is inserted. Paths are valid against the root right after the
+/// insertion, and witnesses have to update them with the leaves inserted later.
+#[derive(Encode, Decode, MaxEncodedLen, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct IncrementalMerkleTree<Hash> {
+	/// The number of inserted leaves, which is the index of the next leaf.
+	next_index: u64,
+	/// The rightmost node with an even index at every height below the root.
+	///
+	/// Its length is the depth of the tree.
+	frontier: BoundedVec<Hash, ConstU32<MAX_DEPTH>>,
+	/// The current root.
+	root: Hash,
+}
+
+impl<Hash: Copy + Default + AsRef<[u8]>> IncrementalMerkleTree<Hash> {
+	/// Create an empty tree of the given `depth`.
+	///
+	/// Returns `None` if `depth` is larger than [`MAX_DEPTH`].
+	pub fn new<H: NodeHasher<Out = Hash>>(depth: u32) -> Option<Self> {
+		if depth > MAX_DEPTH {
+			return None
+		}
+
+		let mut empty = empty_hashes::<H>(depth);
+		let root = empty.pop().expect("`empty_hashes` returns at least one hash; qed");
+		Some(Self { next_index: 0, frontier: BoundedVec::truncate_from(empty), root })
+	}
+
+	/// The depth of the tree.
+	pub fn depth(&self) -> u32 {
+		self.frontier.len() as u32
+	}
+
+	/// The number of inserted leaves.
+	pub fn len(&self) -> u64 {
+		self.next_index
+	}
+
+	/// Whether no leaves were inserted yet.
+	pub fn is_empty(&self) -> bool {
+		self.next_index == 0
+	}
+
+	/// The number of leaves that can still be inserted.
+	///
+	/// Trees of depth [`MAX_DEPTH`] hold at most `u64::MAX` leaves.
+	pub fn remaining(&self) -> u64 {
+		1u64.checked_shl(self.depth()).unwrap_or(u64::MAX) - self.next_index
+	}
+
+	/// The current root.
+	pub fn root(&self) -> Hash {
+		self.root
+	}
+
+	/// Append `leaves` to the tree.
+	///
+	/// Returns the authentication path of every inserted leaf against the new root, with the
+	/// siblings ordered from the leaf to the root, or `None` if there is not enough room for
+	/// all `leaves`, in which case the tree is left unchanged.
+	pub fn insert<H: NodeHasher<Out = Hash>>(&mut self, leaves: &[Hash]) -> Option<Vec<Vec<Hash>>> {
+		if leaves.len() as u64 > self.remaining() {
+			return None
+		}
+		if leaves.is_empty() {
+			return Some(Vec::new())
+		}
+
+		let depth = self.frontier.len();
+		let empty = empty_hashes::<H>(depth as u32);
+		let start = self.next_index;
+		let last = start + leaves.len() as u64 - 1;
+
+		let mut paths = vec![Vec::with_capacity(depth); leaves.len()];
+		// The nodes above the leaves at the current height, starting at index `first`.
+		let mut level = leaves.to_vec();
+		let mut first = start;
+		for height in 0..depth {
+			// Complete the level with the sibling of its first and last node.
+			if first % 2 == 1 {
+				level.insert(0, self.frontier[height]);
+				first -= 1;
+			}
+			if level.len() % 2 == 1 {
+				level.push(empty[height]);
+			}
+
+			for (index, path) in (start..).zip(paths.iter_mut()) {
+				let sibling = (index >> height) ^ 1;
+				path.push(level[(sibling - first) as usize]);
+			}
+			self.frontier[height] = level[(((last >> height) & !1) - first) as usize];
+
+			level = level.chunks(2).map(|pair| H::hash_node(&pair[0], &pair[1])).collect();
+			first >>= 1;
+		}
+
+		self.root = level[0];
+		self.next_index = last + 1;
+		Some(paths)
+	}
+}
+
+/// Verify that `path` authenticates `leaf` at `index` in an incremental Merkle tree with the
+/// given `root`.
+///
+/// The depth of the tree is the length of the path.
+pub fn verify_path<H: NodeHasher>(
+	root: &H::Out,
+	index: u64,
+	leaf: &H::Out,
+	path: &[H::Out],
+) -> bool {
+	if path.len() > MAX_DEPTH as usize || index.checked_shr(path.len() as u32).unwrap_or(0) != 0 {
+		return false
+	}
+
+	let node = path.iter().enumerate().fold(*leaf, |node, (height, sibling)| {
+		if (index >> height) & 1 == 1 {
+			H::hash_node(sibling, &node)
+		} else {
+			H::hash_node(&node, sibling)
+		}
+	});
+	node == *root
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{Blake2Hasher, H256};
+
+	type Tree = IncrementalMerkleTree<H256>;
+
+	fn leaf(i: u64) -> H256 {
+		H256::from_low_u64_be(i + 1)
+	}
+
+	/// The root of a tree of `depth` with the given leaves, computed from all nodes.
+	fn naive_root(depth: u32, leaves: &[H256]) -> H256 {
+		let mut level = leaves.to_vec();
+		level.resize(1 << depth, H256::zero());
+		while level.len() > 1 {
+			level = level
+				.chunks(2)
+				.map(|pair| Blake2Hasher::hash_node(&pair[0], &pair[1]))
+				.collect();
+		}
+		level[0]
+	}
+
+	#[test]
+	fn new_tree_is_empty() {
+		let tree = Tree::new::<Blake2Hasher>(4).unwrap();
+
+		assert_eq!(tree.depth(), 4);
+		assert!(tree.is_empty());
+		assert_eq!(tree.remaining(), 16);
+		assert_eq!(tree.root(), naive_root(4, &[]));
+		assert_eq!(Tree::new::<Blake2Hasher>(0).unwrap().root(), H256::zero());
+		assert_eq!(Tree::new::<Blake2Hasher>(64).unwrap().remaining(), u64::MAX);
+		assert!(Tree::new::<Blake2Hasher>(65).is_none());
+	}
+
+	#[test]
+	fn insertions_match_full_tree() {
+		for batch in [1, 2, 3, 5, 16] {
+			let mut tree = Tree::new::<Blake2Hasher>(4).unwrap();
+			let leaves = (0..16).map(leaf).collect::<Vec<_>>();
+
+			for (chunk, inserted) in leaves.chunks(batch).zip((batch..).step_by(batch)) {
+				let inserted = inserted.min(16);
+				let start = tree.len();
+				let paths = tree.insert::<Blake2Hasher>(chunk).unwrap();
+
+				assert_eq!(tree.len(), inserted as u64);
+				assert_eq!(tree.root(), naive_root(4, &leaves[..inserted]));
+				assert_eq!(paths.len(), chunk.len());
+				for ((index, leaf), path) in (start..).zip(chunk).zip(&paths) {
+					assert_eq!(path.len(), 4);
+					assert!(verify_path::<Blake2Hasher>(&tree.root(), index, leaf, path));
+				}
+			}
+			assert_eq!(tree.remaining(), 0);
+		}
+	}
+
+	#[test]
+	fn full_tree_rejects_leaves() {
+		let mut tree = Tree::new::<Blake2Hasher>(2).unwrap();
+		assert!(tree.insert::<Blake2Hasher>(&[leaf(0); 5]).is_none());
+		assert!(tree.is_empty());
+
+		tree.insert::<Blake2Hasher>(&[leaf(0); 3]).unwrap();
+		let root = tree.root();
+		assert!(tree.insert::<Blake2Hasher>(&[leaf(3); 2]).is_none());
+		assert_eq!(tree.root(), root);
+
+		assert_eq!(tree.insert::<Blake2Hasher>(&[]), Some(vec![]));
+		tree.insert::<Blake2Hasher>(&[leaf(3)]).unwrap();
+		assert!(tree.insert::<Blake2Hasher>(&[leaf(4)]).is_none());
+	}
+
+	#[test]
+	fn invalid_paths_are_rejected() {
+		let mut tree = Tree::new::<Blake2Hasher>(3).unwrap();
+		let paths = tree.insert::<Blake2Hasher>(&[leaf(0), leaf(1), leaf(2)]).unwrap();
+		let root = tree.root();
+
+		assert!(verify_path::<Blake2Hasher>(&root, 2, &leaf(2), &paths[2]));
+		assert!(!verify_path::<Blake2Hasher>(&root, 2, &leaf(1), &paths[2]));
+		assert!(!verify_path::<Blake2Hasher>(&root, 1, &leaf(2), &paths[2]));
+		assert!(!verify_path::<Blake2Hasher>(&root, 10, &leaf(2), &paths[2]));
+		assert!(!verify_path::<Blake2Hasher>(&root, 2, &leaf(2), &paths[2][..2]));
+	}
+}
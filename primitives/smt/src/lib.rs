@@ -0,0 +1,561 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sparse Merkle tree primitives.
+//!
+//! A sparse Merkle tree of depth `d` commits to a map from keys to values, where both are nodes
+//! of the chosen [`NodeHasher`]. It has a leaf for every `d`-bit integer, and a key maps to the
+//! leaf given by its `d` least significant bits, read as a big-endian integer. The path to the
+//! leaf follows these bits, most significant bit first, with a set bit selecting the right
+//! child. Keys only differing in their other bits share a leaf, so keys should be smaller than
+//! `2^d`, and a tree of the largest depth, the bit length of a node, has a leaf for every key.
+//!
+//! Absent keys have the default (all zero) value. An empty subtree of height `h` hashes to
+//! `E(h)`, where `E(0)` is the default value and `E(h + 1) = H(E(h), E(h))`, and an inner node
+//! hashes to `H(left, right)`. Leaves are the values themselves, so a proof that a key maps to
+//! the default value is a proof of non-membership.
+//!
+//! The structure only relies on the two-to-one hash of [`NodeHasher`], which is implemented for
+//! every [`Hasher`] by hashing the concatenated children, and for every [`FieldHasher`], like the
+//! circuit friendly [`Poseidon`], through [`FieldNodeHasher`]. Trees hashed with the latter can
+//! be proven in zero-knowledge circuits, and proofs can be verified in the runtime with
+//! [`verify_proof`].
+//!
+//! Append-only trees whose leaves are inserted in order can use an [`IncrementalMerkleTree`]
+//! instead, which hashes the same way but only keeps the nodes needed to insert further leaves.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use ark_ff::{BigInteger, PrimeField};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::{Hasher, H256};
+use sp_debug_derive::RuntimeDebug;
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
+
+mod incremental;
+mod poseidon;
+
+pub use incremental::{verify_path, IncrementalMerkleTree, MAX_DEPTH};
+pub use poseidon::Poseidon;
+
+/// A two-to-one hash of the children of inner nodes.
+pub trait NodeHasher {
+	/// A node, which is the hash of its children for inner nodes.
+	type Out: Copy + Default + Eq + AsRef<[u8]> + AsMut<[u8]>;
+
+	/// The length of a node in bytes.
+	const LENGTH: usize;
+
+	/// Hash the inner node with the given children.
+	fn hash_node(left: &Self::Out, right: &Self::Out) -> Self::Out;
+}
+
+impl<H: Hasher> NodeHasher for H {
+	type Out = H::Out;
+
+	const LENGTH: usize = H::LENGTH;
+
+	fn hash_node(left: &H::Out, right: &H::Out) -> H::Out {
+		let mut node = Vec::with_capacity(2 * H::LENGTH);
+		node.extend_from_slice(left.as_ref());
+		node.extend_from_slice(right.as_ref());
+		H::hash(&node)
+	}
+}
+
+/// A two-to-one hash over the elements of a prime field, as used in arithmetic circuits.
+pub trait FieldHasher {
+	/// The field, whose modulus must be less than `2^256`.
+	type Field: PrimeField;
+
+	/// Hash `left` and `right` to a single field element.
+	fn hash(left: &Self::Field, right: &Self::Field) -> Self::Field;
+}
+
+/// The [`NodeHasher`] of the field hasher `F`, with nodes being the big-endian encoding of
+/// elements of its field.
+///
+/// Nodes that are not smaller than the modulus are reduced before hashing, so they hash like a
+/// different node. Values and keys taken from outside of the tree should be checked with
+/// [`FieldNodeHasher::is_canonical`].
+pub struct FieldNodeHasher<F>(PhantomData<F>);
+
+impl<F: FieldHasher> FieldNodeHasher<F> {
+	/// Whether `node` encodes an element of the field, i.e. is smaller than the modulus.
+	pub fn is_canonical(node: &H256) -> bool {
+		Self::encode(&Self::decode(node)) == *node
+	}
+
+	fn decode(node: &H256) -> F::Field {
+		F::Field::from_be_bytes_mod_order(node.as_bytes())
+	}
+
+	fn encode(element: &F::Field) -> H256 {
+		let bytes = element.into_bigint().to_bytes_be();
+		let mut node = H256::zero();
+		node[32 - bytes.len()..].copy_from_slice(&bytes);
+		node
+	}
+}
+
+impl<F: FieldHasher> NodeHasher for FieldNodeHasher<F> {
+	type Out = H256;
+
+	const LENGTH: usize = 32;
+
+	fn hash_node(left: &H256, right: &H256) -> H256 {
+		Self::encode(&F::hash(&Self::decode(left), &Self::decode(right)))
+	}
+}
+
+/// A proof of the value of a key in a sparse Merkle tree.
+///
+/// Only the siblings on the path that are not empty subtrees are included.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct MerkleProof<Hash> {
+	/// Bit `h` (least significant bit of the first byte first) is set if the sibling at height
+	/// `h` is not an empty subtree.
+	pub non_empty: Vec<u8>,
+	/// The siblings that are not empty subtrees, from the leaf to the root.
+	pub siblings: Vec<Hash>,
+}
+
+/// Bit `index` of `key`, counting from the least significant bit of the last byte.
+fn bit(key: &[u8], index: usize) -> bool {
+	key[key.len() - 1 - index / 8] & (1 << (index % 8)) != 0
+}
+
+/// The `key` with the bits that do not select the node at `height` on its path in a tree of
+/// `depth` cleared.
+///
+/// Identifies the node at `height` on the path to `key`.
+fn prefix<Hash: AsMut<[u8]>>(mut key: Hash, height: usize, depth: usize) -> Hash {
+	for (index, byte) in key.as_mut().iter_mut().rev().enumerate() {
+		let kept = (0..8).filter(|bit| (height..depth).contains(&(8 * index + bit)));
+		*byte &= kept.fold(0u8, |mask, bit| mask | 1 << bit);
+	}
+	key
+}
+
+/// The hashes of empty subtrees of height `0` up to and including `depth`.
+///
+/// These only depend on the hasher and the depth, so they can be computed once and used for any
+/// number of trees and proofs.
+pub fn empty_hashes<H: NodeHasher>(depth: u32) -> Vec<H::Out> {
+	let mut hashes = Vec::with_capacity(depth as usize + 1);
+	hashes.push(H::Out::default());
+	for height in 0..depth as usize {
+		let hash = H::hash_node(&hashes[height], &hashes[height]);
+		hashes.push(hash);
+	}
+	hashes
+}
+
+/// The root of a tree of `depth` in which every key has the default value.
+pub fn empty_root<H: NodeHasher>(depth: u32) -> H::Out {
+	empty_hashes::<H>(depth)[depth as usize]
+}
+
+/// Verify that `proof` shows that `key` maps to `value` in the tree with the given `root`.
+///
+/// The depth of the tree is given by its `empty` subtree hashes, as returned by
+/// [`empty_hashes`]. A default `value` makes this a non-membership proof.
+pub fn verify_proof<H: NodeHasher>(
+	empty: &[H::Out],
+	root: &H::Out,
+	key: &H::Out,
+	value: &H::Out,
+	proof: &MerkleProof<H::Out>,
+) -> bool {
+	let depth = match empty.len().checked_sub(1) {
+		Some(depth) if depth <= 8 * H::LENGTH => depth,
+		_ => return false,
+	};
+	if proof.non_empty.len() != (depth + 7) / 8 {
+		return false
+	}
+
+	let mut siblings = proof.siblings.iter();
+	let mut node = *value;
+	for (height, empty) in empty[..depth].iter().enumerate() {
+		let sibling = if proof.non_empty[height / 8] & (1 << (height % 8)) != 0 {
+			match siblings.next() {
+				Some(sibling) => *sibling,
+				None => return false,
+			}
+		} else {
+			*empty
+		};
+		node = if bit(key.as_ref(), height) {
+			H::hash_node(&sibling, &node)
+		} else {
+			H::hash_node(&node, &sibling)
+		};
+	}
+
+	siblings.next().is_none() && node == *root
+}
+
+/// An in-memory sparse Merkle tree.
+///
+/// Only the nodes that are not empty subtrees are stored, so inserting a key costs as many
+/// hashes as the depth of the tree, and the same amount of storage.
+pub struct SparseMerkleTree<H: NodeHasher> {
+	/// The hashes of empty subtrees by height, up to and including the root.
+	empty: Vec<H::Out>,
+	/// The non-empty nodes, by height and the prefix of the keys below them.
+	nodes: BTreeMap<(usize, H::Out), H::Out>,
+}
+
+impl<H: NodeHasher> Default for SparseMerkleTree<H>
+where
+	H::Out: Ord,
+{
+	/// Create a tree of the largest depth, with a leaf for every key.
+	fn default() -> Self {
+		Self::new(8 * H::LENGTH as u32).expect("The depth is the largest one; qed")
+	}
+}
+
+impl<H: NodeHasher> SparseMerkleTree<H>
+where
+	H::Out: Ord,
+{
+	/// Create a tree of the given `depth` in which every key has the default value.
+	///
+	/// Returns `None` if `depth` is larger than the number of bits of a node.
+	pub fn new(depth: u32) -> Option<Self> {
+		if depth as usize > 8 * H::LENGTH {
+			return None
+		}
+		Some(Self { empty: empty_hashes::<H>(depth), nodes: BTreeMap::new() })
+	}
+
+	/// The depth of the tree.
+	pub fn depth(&self) -> u32 {
+		self.empty.len() as u32 - 1
+	}
+
+	/// The hashes of empty subtrees of the tree, for verifying proofs with [`verify_proof`].
+	pub fn empty_hashes(&self) -> &[H::Out] {
+		&self.empty
+	}
+
+	/// The node at `height` on the path to `key`.
+	fn node(&self, key: &H::Out, height: usize) -> H::Out {
+		self.nodes
+			.get(&(height, prefix(*key, height, self.empty.len() - 1)))
+			.copied()
+			.unwrap_or(self.empty[height])
+	}
+
+	/// The sibling of the node at `height` on the path to `key`.
+	fn sibling(&self, key: &H::Out, height: usize) -> H::Out {
+		let mut sibling = *key;
+		let bytes = sibling.as_mut();
+		let index = bytes.len() - 1 - height / 8;
+		bytes[index] ^= 1 << (height % 8);
+		self.node(&sibling, height)
+	}
+
+	/// The root of the tree.
+	pub fn root(&self) -> H::Out {
+		self.node(&H::Out::default(), self.empty.len() - 1)
+	}
+
+	/// The value of `key`, which is the default value if the key is absent.
+	pub fn get(&self, key: &H::Out) -> H::Out {
+		self.node(key, 0)
+	}
+
+	/// Set the value of `key`.
+	///
+	/// Setting the default value removes the key.
+	pub fn insert(&mut self, key: &H::Out, value: H::Out) {
+		let depth = self.empty.len() - 1;
+		let mut node = value;
+		for height in 0..=depth {
+			let position = (height, prefix(*key, height, depth));
+			if node == self.empty[height] {
+				self.nodes.remove(&position);
+			} else {
+				self.nodes.insert(position, node);
+			}
+
+			if height < depth {
+				let sibling = self.sibling(key, height);
+				node = if bit(key.as_ref(), height) {
+					H::hash_node(&sibling, &node)
+				} else {
+					H::hash_node(&node, &sibling)
+				};
+			}
+		}
+	}
+
+	/// Remove `key` from the tree.
+	pub fn remove(&mut self, key: &H::Out) {
+		self.insert(key, H::Out::default());
+	}
+
+	/// Generate a proof of the value of `key`.
+	pub fn prove(&self, key: &H::Out) -> MerkleProof<H::Out> {
+		let depth = self.empty.len() - 1;
+		let mut proof = MerkleProof { non_empty: vec![0; (depth + 7) / 8], siblings: Vec::new() };
+		for height in 0..depth {
+			let sibling = self.sibling(key, height);
+			if sibling != self.empty[height] {
+				proof.non_empty[height / 8] |= 1 << (height % 8);
+				proof.siblings.push(sibling);
+			}
+		}
+		proof
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{Blake2Hasher, H256};
+
+	type Tree = SparseMerkleTree<Blake2Hasher>;
+
+	fn key(i: u8) -> H256 {
+		Blake2Hasher::hash(&[i])
+	}
+
+	fn value(i: u8) -> H256 {
+		H256::repeat_byte(i + 1)
+	}
+
+	/// Verify `proof` for a tree of the largest depth.
+	fn verify(root: &H256, key: &H256, value: &H256, proof: &MerkleProof<H256>) -> bool {
+		verify_proof::<Blake2Hasher>(&empty_hashes::<Blake2Hasher>(256), root, key, value, proof)
+	}
+
+	#[test]
+	fn empty_tree_has_empty_root() {
+		let tree = Tree::default();
+		let empty = empty_hashes::<Blake2Hasher>(256);
+
+		assert_eq!(empty.len(), 257);
+		assert_eq!(empty[1], Blake2Hasher::hash(&[0; 64]));
+		assert_eq!(tree.root(), empty_root::<Blake2Hasher>(256));
+		assert_eq!(tree.get(&key(0)), H256::zero());
+	}
+
+	#[test]
+	fn root_does_not_depend_on_insertion_order() {
+		let mut tree = Tree::default();
+		let mut reversed = Tree::default();
+		for i in 0..20 {
+			tree.insert(&key(i), value(i));
+			reversed.insert(&key(19 - i), value(19 - i));
+		}
+
+		assert_eq!(tree.root(), reversed.root());
+		assert_ne!(tree.root(), empty_root::<Blake2Hasher>(256));
+		for i in 0..20 {
+			assert_eq!(tree.get(&key(i)), value(i));
+		}
+	}
+
+	#[test]
+	fn removing_keys_restores_root() {
+		let mut tree = Tree::default();
+		tree.insert(&key(0), value(0));
+		let root = tree.root();
+
+		tree.insert(&key(1), value(1));
+		tree.insert(&key(0), value(2));
+		tree.remove(&key(1));
+		tree.insert(&key(0), value(0));
+		assert_eq!(tree.root(), root);
+
+		tree.remove(&key(0));
+		assert_eq!(tree.root(), empty_root::<Blake2Hasher>(256));
+		assert!(tree.nodes.is_empty());
+	}
+
+	#[test]
+	fn keys_sharing_a_prefix_work() {
+		let mut tree = Tree::default();
+		let left = H256::zero();
+		let right = H256::from_low_u64_be(1);
+		tree.insert(&left, value(0));
+		tree.insert(&right, value(1));
+
+		let mut expected = Blake2Hasher::hash_node(&value(0), &value(1));
+		for height in 1..256 {
+			expected = Blake2Hasher::hash_node(&expected, &tree.empty[height]);
+		}
+		assert_eq!(tree.root(), expected);
+
+		let proof = tree.prove(&left);
+		assert_eq!(proof.siblings, vec![value(1)]);
+		assert!(verify(&tree.root(), &left, &value(0), &proof));
+	}
+
+	#[test]
+	fn membership_proofs_work() {
+		let mut tree = Tree::default();
+		for i in 0..20 {
+			tree.insert(&key(i), value(i));
+		}
+		let root = tree.root();
+
+		for i in 0..20 {
+			let proof = tree.prove(&key(i));
+			assert!(verify(&root, &key(i), &value(i), &proof));
+			assert!(!verify(&root, &key(i), &value(i + 1), &proof));
+			assert!(!verify(&root, &key(i), &H256::zero(), &proof));
+			assert!(!verify(&root, &key(i + 1), &value(i), &proof));
+		}
+	}
+
+	#[test]
+	fn non_membership_proofs_work() {
+		let mut tree = Tree::default();
+		for i in 0..20 {
+			tree.insert(&key(i), value(i));
+		}
+		let root = tree.root();
+
+		let proof = tree.prove(&key(20));
+		assert!(verify(&root, &key(20), &H256::zero(), &proof));
+		assert!(!verify(&root, &key(20), &value(20), &proof));
+
+		// The proof of an empty tree has no siblings.
+		let proof = Tree::default().prove(&key(0));
+		assert!(proof.siblings.is_empty());
+		assert!(verify(&empty_root::<Blake2Hasher>(256), &key(0), &H256::zero(), &proof));
+	}
+
+	#[test]
+	fn malformed_proofs_are_rejected() {
+		let mut tree = Tree::default();
+		for i in 0..20 {
+			tree.insert(&key(i), value(i));
+		}
+		let root = tree.root();
+		let proof = tree.prove(&key(0));
+
+		let mut missing_sibling = proof.clone();
+		missing_sibling.siblings.pop();
+		assert!(!verify(&root, &key(0), &value(0), &missing_sibling));
+
+		let mut extra_sibling = proof.clone();
+		extra_sibling.siblings.push(H256::zero());
+		assert!(!verify(&root, &key(0), &value(0), &extra_sibling));
+
+		let mut short_bitmap = proof;
+		short_bitmap.non_empty.pop();
+		assert!(!verify(&root, &key(0), &value(0), &short_bitmap));
+	}
+
+	#[test]
+	fn depth_is_configurable() {
+		assert!(Tree::new(257).is_none());
+		assert_eq!(Tree::default().depth(), 256);
+
+		let mut tree = Tree::new(3).unwrap();
+		assert_eq!(tree.depth(), 3);
+		assert_eq!(tree.root(), empty_root::<Blake2Hasher>(3));
+		let leaves = (0..8).map(value).collect::<Vec<_>>();
+		for (i, leaf) in leaves.iter().enumerate() {
+			tree.insert(&H256::from_low_u64_be(i as u64), *leaf);
+		}
+
+		let level = |nodes: &[H256]| -> Vec<H256> {
+			nodes
+				.chunks(2)
+				.map(|pair| Blake2Hasher::hash_node(&pair[0], &pair[1]))
+				.collect()
+		};
+		assert_eq!(tree.root(), level(&level(&level(&leaves)))[0]);
+
+		// Only the three least significant bits of a key select its leaf.
+		assert_eq!(tree.get(&H256::from_low_u64_be(13)), value(5));
+		let proof = tree.prove(&H256::from_low_u64_be(5));
+		assert_eq!(proof.non_empty, vec![0b111]);
+		for key in [5, 13] {
+			let key = H256::from_low_u64_be(key);
+			assert!(verify_proof::<Blake2Hasher>(
+				tree.empty_hashes(),
+				&tree.root(),
+				&key,
+				&value(5),
+				&proof,
+			));
+		}
+		// The proof does not verify for a tree of another depth.
+		let empty = empty_hashes::<Blake2Hasher>(4);
+		let key = H256::from_low_u64_be(5);
+		assert!(!verify_proof::<Blake2Hasher>(&empty, &tree.root(), &key, &value(5), &proof));
+		assert!(!verify_proof::<Blake2Hasher>(&[], &tree.root(), &key, &value(5), &proof));
+	}
+
+	#[test]
+	fn poseidon_trees_work() {
+		type Tree = SparseMerkleTree<FieldNodeHasher<Poseidon>>;
+
+		// The hashes of empty subtrees used by circuits hashing with `Poseidon(2)`.
+		let empty = empty_hashes::<FieldNodeHasher<Poseidon>>(2);
+		assert_eq!(
+			empty[1],
+			H256(array_bytes::hex2array_unchecked(
+				"2098f5fb9e239eab3ceac3f27b81e481dc3124d55ffed523a839ee8446b64864"
+			)),
+		);
+		assert_eq!(
+			empty[2],
+			H256(array_bytes::hex2array_unchecked(
+				"1069673dcdb12263df301a6ff584a7ec261a44cb9dc68df067a4774460b1f1e1"
+			)),
+		);
+
+		let mut tree = Tree::new(32).unwrap();
+		for i in 0..4 {
+			tree.insert(&H256::from_low_u64_be(i * 7), value(i as u8));
+		}
+		let root = tree.root();
+		let proof = tree.prove(&H256::from_low_u64_be(7));
+		assert!(verify_proof::<FieldNodeHasher<Poseidon>>(
+			tree.empty_hashes(),
+			&root,
+			&H256::from_low_u64_be(7),
+			&value(1),
+			&proof,
+		));
+		let proof = tree.prove(&H256::from_low_u64_be(8));
+		assert!(verify_proof::<FieldNodeHasher<Poseidon>>(
+			tree.empty_hashes(),
+			&root,
+			&H256::from_low_u64_be(8),
+			&H256::zero(),
+			&proof,
+		));
+
+		let modulus = H256(array_bytes::hex2array_unchecked(
+			"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+		));
+		assert!(FieldNodeHasher::<Poseidon>::is_canonical(&value(1)));
+		assert!(!FieldNodeHasher::<Poseidon>::is_canonical(&modulus));
+	}
+}